@@ -0,0 +1,12 @@
+fn main() {
+    // Event messages (and the gRPC service) are generated from proto/bsc_streamer.proto with a vendored protoc
+    #[cfg(feature = "proto")]
+    {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().expect("vendored protoc"));
+        tonic_build::configure()
+            .build_server(cfg!(feature = "grpc"))
+            .build_client(cfg!(feature = "grpc"))
+            .compile_protos(&["proto/bsc_streamer.proto"], &["proto"])
+            .expect("failed to compile proto/bsc_streamer.proto");
+    }
+}
@@ -0,0 +1,56 @@
+use bsc_streamer::display::formatter::{SwapContext, SwapDisplay, SwapFormatter};
+use bsc_streamer::{StreamerBuilder, SwapEvent, TradeType};
+
+/// One line per swap, with the session stats tracked by SwapFormatter
+struct CompactLayout;
+
+impl SwapDisplay for CompactLayout {
+    fn render(&self, swap: &SwapEvent, context: &SwapContext) {
+        let side = match swap.trade_type {
+            TradeType::Buy => "BUY ",
+            TradeType::Sell => "SELL",
+        };
+        let change = context
+            .price_stats
+            .price_change_percent
+            .map(|pct| format!("{:+.2}%", pct))
+            .unwrap_or_else(|| "-".to_string());
+        let volume_5m = context
+            .volume
+            .as_ref()
+            .and_then(|volume| volume.windows.first())
+            .map(|window| format!("{} trades/{}", window.trades(), window.label()))
+            .unwrap_or_default();
+
+        println!(
+            "{} {:>14} {:<8} @ {:.12} {} | {} | high {:.12} low {:.12} | {}",
+            side,
+            swap.token.amount,
+            swap.token.symbol,
+            context.price_stats.current_price,
+            context.currency,
+            change,
+            context.price_stats.high,
+            context.price_stats.low,
+            volume_5m
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let formatter = SwapFormatter::new().with_layout(CompactLayout);
+
+    let handle = StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+        .await?
+        .token_address("0x...")
+        .auto_detect()
+        .on_swap(move |swap| formatter.display_sync(&swap))
+        .start()
+        .await?;
+
+    tokio::signal::ctrl_c().await?;
+    handle.stop();
+
+    Ok(())
+}
@@ -26,6 +26,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("    ⚠️  Token not found on any supported platform");
     }
 
+    if let Some(ref honeypot) = location.honeypot {
+        match honeypot.reason {
+            Some(ref reason) => println!("  🍯 Honeypot risk: {}", reason),
+            None => match honeypot.round_trip_loss_pct {
+                Some(loss) => println!("  ✅ Sellable ({:.1}% round-trip loss, {} buys / {} sells recently)", loss, honeypot.recent_buys, honeypot.recent_sells),
+                None => println!("  ✅ Sellable ({} buys / {} sells recently)", honeypot.recent_buys, honeypot.recent_sells),
+            },
+        }
+    }
+
+    if let Some(ref risk) = location.risk {
+        println!("  🛡️  Risk score: {}/100", risk.score);
+        for flag in &risk.flags {
+            println!("    - {}: {}", flag.category.as_str(), flag.signature);
+        }
+    }
+
+    if let Some(ref contract) = location.contract {
+        println!(
+            "  📜 Contract: {} ({})",
+            if contract.verified { "verified" } else { "NOT verified" },
+            contract.contract_name.as_deref().unwrap_or("unknown")
+        );
+        if let Some(creator) = contract.creator {
+            println!("    - Creator: {:?}", creator);
+        }
+        if let Some(ref created_at) = contract.created_at {
+            println!("    - Created: {}", created_at);
+        }
+    }
+
+    for lock in &location.lp_locks {
+        println!(
+            "  LP {:?}: {:.2}% burned, {:.2}% locked",
+            lock.pair_address, lock.burned_pct, lock.locked_pct
+        );
+        for holding in &lock.lockers {
+            println!("    - {} holds {:.2}%", holding.locker, holding.pct);
+        }
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,60 @@
+-- Swaps, migrations and candles written by sinks::PostgresSink
+-- Addresses and hashes are 0x-prefixed lowercase hex; `data` holds the full serialized event
+
+CREATE TABLE IF NOT EXISTS swaps (
+    tx_hash             TEXT NOT NULL,
+    log_index           BIGINT NOT NULL,
+    block_number        BIGINT NOT NULL,
+    block_time          TIMESTAMPTZ,
+    platform            TEXT NOT NULL,
+    trade_type          TEXT NOT NULL,
+    token_address       TEXT NOT NULL,
+    token_symbol        TEXT NOT NULL,
+    token_amount        NUMERIC NOT NULL,
+    base_token_address  TEXT NOT NULL,
+    base_token_symbol   TEXT NOT NULL,
+    base_amount         NUMERIC NOT NULL,
+    price               DOUBLE PRECISION NOT NULL,
+    price_usd           DOUBLE PRECISION,
+    usd_value           DOUBLE PRECISION,
+    trader              TEXT,
+    market              TEXT,
+    data                JSONB NOT NULL,
+    PRIMARY KEY (tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS swaps_token_time_idx ON swaps (token_address, block_number);
+
+CREATE TABLE IF NOT EXISTS migrations (
+    tx_hash         TEXT PRIMARY KEY,
+    token_address   TEXT NOT NULL,
+    from_platform   TEXT NOT NULL,
+    to_platform     TEXT NOT NULL,
+    block_number    BIGINT NOT NULL,
+    block_time      TIMESTAMPTZ,
+    data            JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS migrations_token_idx ON migrations (token_address);
+
+CREATE TABLE IF NOT EXISTS candles (
+    market          TEXT NOT NULL,
+    interval        TEXT NOT NULL,
+    open_time       BIGINT NOT NULL,
+    close_time      BIGINT NOT NULL,
+    token_address   TEXT NOT NULL,
+    base_token      TEXT NOT NULL,
+    open            DOUBLE PRECISION NOT NULL,
+    high            DOUBLE PRECISION NOT NULL,
+    low             DOUBLE PRECISION NOT NULL,
+    close           DOUBLE PRECISION NOT NULL,
+    volume_token    DOUBLE PRECISION NOT NULL,
+    volume_base     DOUBLE PRECISION NOT NULL,
+    volume_usd      DOUBLE PRECISION,
+    trades          INTEGER NOT NULL,
+    buys            INTEGER NOT NULL,
+    sells           INTEGER NOT NULL,
+    PRIMARY KEY (market, interval, open_time)
+);
+
+CREATE INDEX IF NOT EXISTS candles_token_time_idx ON candles (token_address, interval, open_time);
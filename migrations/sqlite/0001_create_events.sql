@@ -0,0 +1,15 @@
+-- Events written by sinks::SqliteSink; `data` holds the serialized StreamEvent
+
+CREATE TABLE IF NOT EXISTS events (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- swap:<tx>:<log_index>, migration:<tx> or candle:<market>:<interval>:<open_time>
+    event_key       TEXT NOT NULL UNIQUE,
+    kind            TEXT NOT NULL,
+    token_address   TEXT NOT NULL,
+    -- Unix seconds (trade time, migration time or candle open)
+    time            INTEGER NOT NULL,
+    data            TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS events_time_idx ON events (time);
+CREATE INDEX IF NOT EXISTS events_token_time_idx ON events (token_address, time);
@@ -0,0 +1,289 @@
+#!/usr/bin/env python3
+"""Assemble contracts/round_trip_helper.easm and check it against src/core/simulation.rs.
+
+    python3 scripts/round_trip_helper.py            # check the embedded bytes, run the mock scenarios
+    python3 scripts/round_trip_helper.py --write    # rewrite ROUND_TRIP_HELPER from the source
+
+The scenarios run the assembled code on a minimal EVM interpreter (only the
+opcodes the helper uses) against a mock router and token: a plain token, one
+taxing 10% on buys and 20% on sells, and one whose sells revert.
+"""
+
+import copy
+import re
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+SOURCE = ROOT / "contracts" / "round_trip_helper.easm"
+RUST = ROOT / "src" / "core" / "simulation.rs"
+CONSTANT = re.compile(r'(const ROUND_TRIP_HELPER: &str = ")0x([0-9a-f]*)(";)')
+
+OPCODES = {
+    "STOP": 0x00, "ADD": 0x01, "MUL": 0x02, "SUB": 0x03, "LT": 0x10, "GT": 0x11, "ISZERO": 0x15,
+    "SHL": 0x1B, "ADDRESS": 0x30, "CALLDATALOAD": 0x35, "CALLDATASIZE": 0x36, "CALLDATACOPY": 0x37,
+    "RETURNDATASIZE": 0x3D, "RETURNDATACOPY": 0x3E, "SELFBALANCE": 0x47, "POP": 0x50, "MLOAD": 0x51,
+    "MSTORE": 0x52, "JUMPI": 0x57, "GAS": 0x5A, "JUMPDEST": 0x5B, "PUSH0": 0x5F,
+    "DUP1": 0x80, "DUP2": 0x81, "DUP3": 0x82, "SWAP1": 0x90, "CALL": 0xF1, "RETURN": 0xF3, "STATICCALL": 0xFA,
+}
+
+
+def assemble(source):
+    """Bytecode of the source (two passes, jump targets are PUSH2)"""
+    items = []
+    for number, line in enumerate(source.splitlines(), 1):
+        line = line.split(";")[0].strip()
+        if not line:
+            continue
+        if line.endswith(":"):
+            items.append(("label", line[:-1]))
+        elif line.startswith("PUSH "):
+            arg = line[5:].strip()
+            items.append(("ref", arg[1:]) if arg.startswith("@") else ("push", int(arg, 0)))
+        elif line in OPCODES:
+            items.append(("op", OPCODES[line]))
+        else:
+            sys.exit(f"{SOURCE.name}:{number}: unknown instruction {line!r}")
+
+    def width(value):
+        return max(1, (value.bit_length() + 7) // 8)
+
+    labels, offset = {}, 0
+    for kind, arg in items:
+        if kind == "label":
+            labels[arg] = offset
+        offset += {"label": 1, "op": 1, "ref": 3}.get(kind) or 1 + width(arg)
+
+    code = bytearray()
+    for kind, arg in items:
+        if kind == "label":
+            code.append(OPCODES["JUMPDEST"])
+        elif kind == "op":
+            code.append(arg)
+        elif kind == "ref":
+            code += bytes([0x61]) + labels[arg].to_bytes(2, "big")
+        else:
+            code += bytes([0x5F + width(arg)]) + arg.to_bytes(width(arg), "big")
+    return bytes(code)
+
+
+# --- minimal EVM and mocks for the scenarios
+
+WORD = 2**256
+HELPER, ROUTER, TOKEN, WBNB, CALLER = 0xBEEF, 0x1111, 0x2222, 0x3333, 0xCAFE
+GET_AMOUNTS_OUT, BUY, SELL = "d06ca61f", "b6f9de95", "791ac947"
+BALANCE_OF, APPROVE = "70a08231", "095ea7b3"
+
+
+class Revert(Exception):
+    pass
+
+
+def word(data, offset):
+    return int.from_bytes(data[offset:offset + 32].ljust(32, b"\0"), "big")
+
+
+def words(*values):
+    return b"".join((v % WORD).to_bytes(32, "big") for v in values)
+
+
+class Chain:
+    """Helper, a 10,000 BNB / 1M token V2 pool behind the mock router, and the token"""
+
+    def __init__(self, code, buy_tax=0, sell_tax=0, sells_revert=False):
+        self.code = code
+        self.bnb = {HELPER: 10**18}
+        self.tokens = {HELPER: 0}
+        self.allowance = {}
+        self.buy_tax, self.sell_tax, self.sells_revert = buy_tax, sell_tax, sells_revert
+        self.token_reserve, self.bnb_reserve = 10**24, 10**20
+
+    def amount_out(self, amount, bnb_in):
+        reserve_in, reserve_out = (
+            (self.bnb_reserve, self.token_reserve) if bnb_in else (self.token_reserve, self.bnb_reserve)
+        )
+        amount = amount * 9975
+        return amount * reserve_out // (reserve_in * 10000 + amount)
+
+    def call(self, sender, to, value, data, static):
+        if value and static:
+            raise Revert()
+        self.bnb[sender] = self.bnb.get(sender, 0) - value
+        self.bnb[to] = self.bnb.get(to, 0) + value
+        if to == HELPER:
+            return run(self, data)
+        selector = data[:4].hex()
+        if to == ROUTER and selector == GET_AMOUNTS_OUT:
+            amount = word(data, 4)
+            return words(0x20, 2, amount, self.amount_out(amount, word(data, 4 + 3 * 32) == WBNB))
+        if to == ROUTER and selector == BUY:
+            out = self.amount_out(value, True)
+            self.bnb_reserve += value
+            self.token_reserve -= out
+            self.tokens[word(data, 4 + 2 * 32)] += out - out * self.buy_tax // 100
+            return b""
+        if to == ROUTER and selector == SELL and not static:
+            amount = word(data, 4)
+            if self.sells_revert or self.allowance.get(sender, 0) < amount:
+                raise Revert()
+            self.tokens[sender] -= amount
+            arrived = amount - amount * self.sell_tax // 100
+            out = self.amount_out(arrived, False)
+            self.token_reserve += arrived
+            self.bnb_reserve -= out
+            self.bnb[ROUTER] += out
+            self.call(ROUTER, sender, out, b"", False)
+            return b""
+        if to == TOKEN and selector == BALANCE_OF:
+            return words(self.tokens.get(word(data, 4) % 2**160, 0))
+        if to == TOKEN and selector == APPROVE and not static:
+            self.allowance[sender] = word(data, 36)
+            return words(1)
+        raise Revert()
+
+
+def run(chain, calldata):
+    code, pc, stack, memory, returned = chain.code, 0, [], bytearray(), b""
+
+    def touch(offset, size):
+        if size and offset + size > len(memory):
+            memory.extend(bytes(offset + size - len(memory)))
+
+    while True:
+        op = code[pc] if pc < len(code) else 0x00
+        pc += 1
+        if 0x5F <= op <= 0x7F:
+            size = op - 0x5F
+            stack.append(int.from_bytes(code[pc:pc + size], "big"))
+            pc += size
+        elif op == 0x00:
+            return b""
+        elif op in (0x01, 0x02, 0x03, 0x10, 0x11):
+            a, b = stack.pop(), stack.pop()
+            stack.append({0x01: a + b, 0x02: a * b, 0x03: a - b, 0x10: a < b, 0x11: a > b}[op] % WORD)
+        elif op == 0x15:
+            stack.append(int(stack.pop() == 0))
+        elif op == 0x1B:
+            shift, value = stack.pop(), stack.pop()
+            stack.append((value << shift) % WORD)
+        elif op == 0x30:
+            stack.append(HELPER)
+        elif op == 0x35:
+            stack.append(word(calldata, stack.pop()))
+        elif op == 0x36:
+            stack.append(len(calldata))
+        elif op == 0x37:
+            dest, offset, size = stack.pop(), stack.pop(), stack.pop()
+            touch(dest, size)
+            memory[dest:dest + size] = calldata[offset:offset + size].ljust(size, b"\0")
+        elif op == 0x3D:
+            stack.append(len(returned))
+        elif op == 0x3E:
+            dest, offset, size = stack.pop(), stack.pop(), stack.pop()
+            assert offset + size <= len(returned), "RETURNDATACOPY out of bounds"
+            touch(dest, size)
+            memory[dest:dest + size] = returned[offset:offset + size]
+        elif op == 0x47:
+            stack.append(chain.bnb[HELPER])
+        elif op == 0x50:
+            stack.pop()
+        elif op == 0x51:
+            offset = stack.pop()
+            touch(offset, 32)
+            stack.append(int.from_bytes(memory[offset:offset + 32], "big"))
+        elif op == 0x52:
+            offset, value = stack.pop(), stack.pop()
+            touch(offset, 32)
+            memory[offset:offset + 32] = value.to_bytes(32, "big")
+        elif op == 0x57:
+            dest, condition = stack.pop(), stack.pop()
+            if condition:
+                assert code[dest] == 0x5B, "jump to a non-JUMPDEST"
+                pc = dest
+        elif op == 0x5A:
+            stack.append(10**7)
+        elif op == 0x5B:
+            pass
+        elif 0x80 <= op <= 0x82:
+            stack.append(stack[-(op - 0x7F)])
+        elif op == 0x90:
+            stack[-1], stack[-2] = stack[-2], stack[-1]
+        elif op in (0xF1, 0xFA):
+            _gas, to = stack.pop(), stack.pop()
+            value = stack.pop() if op == 0xF1 else 0
+            args, args_size, ret, ret_size = stack.pop(), stack.pop(), stack.pop(), stack.pop()
+            touch(args, args_size)
+            snapshot = copy.deepcopy(chain.__dict__)
+            try:
+                returned = chain.call(HELPER, to, value, bytes(memory[args:args + args_size]), op == 0xFA)
+                success = 1
+            except Revert:
+                chain.__dict__.update(snapshot)
+                returned, success = b"", 0
+            touch(ret, ret_size)
+            memory[ret:ret + min(ret_size, len(returned))] = returned[:ret_size]
+            stack.append(success)
+        elif op == 0xF3:
+            offset, size = stack.pop(), stack.pop()
+            touch(offset, size)
+            return bytes(memory[offset:offset + size])
+        else:
+            raise AssertionError(f"opcode {op:#04x} at {pc - 1} is not modelled")
+
+
+def helper_calldata(bnb_in):
+    """Same layout RouterSimulator::round_trip encodes"""
+    buy_path, sell_path = words(2, WBNB, TOKEN), words(2, TOKEN, WBNB)
+    calls = [
+        bytes.fromhex(GET_AMOUNTS_OUT) + words(bnb_in, 0x40) + buy_path,
+        bytes.fromhex(BUY) + words(0, 0x80, HELPER, WORD - 1) + buy_path,
+        bytes.fromhex(GET_AMOUNTS_OUT) + words(0, 0x40) + sell_path,
+        bytes.fromhex(SELL) + words(0, 0, 0xA0, HELPER, WORD - 1) + sell_path,
+    ]
+    offsets, tails = [], b""
+    for call in calls:
+        offsets.append(7 * 32 + len(tails))
+        tails += words(len(call)) + call.ljust((len(call) + 31) // 32 * 32, b"\0")
+    return words(ROUTER, TOKEN, bnb_in, *offsets) + tails
+
+
+def scenarios(code):
+    bnb_in = 10**16
+
+    def round_trip(**token):
+        output = Chain(code, **token).call(CALLER, HELPER, 0, helper_calldata(bnb_in), False)
+        return [word(output, i * 32) for i in range(6)]
+
+    buy_ok, expected_tokens, received, sell_ok, expected_bnb, bnb_out = round_trip()
+    assert buy_ok and sell_ok and received == expected_tokens and bnb_out == expected_bnb
+    assert 0 < bnb_out < bnb_in, "pool fees are lost on the way"
+
+    buy_ok, expected_tokens, received, sell_ok, expected_bnb, bnb_out = round_trip(buy_tax=10, sell_tax=20)
+    assert buy_ok and sell_ok
+    assert abs(1 - received / expected_tokens - 0.10) < 1e-6, "buy tax"
+    assert abs(1 - bnb_out / expected_bnb - 0.20) < 1e-3, "sell tax"
+
+    buy_ok, _, received, sell_ok, _, bnb_out = round_trip(sells_revert=True)
+    assert buy_ok and received > 0 and not sell_ok and bnb_out == 0
+
+    assert run(Chain(code), b"") == b"", "plain BNB transfers are accepted"
+
+
+def main():
+    code = assemble(SOURCE.read_text())
+    scenarios(code)
+    rust = RUST.read_text()
+    match = CONSTANT.search(rust)
+    if not match:
+        sys.exit(f"ROUND_TRIP_HELPER not found in {RUST}")
+    if "--write" in sys.argv[1:]:
+        RUST.write_text(CONSTANT.sub(lambda m: m.group(1) + "0x" + code.hex() + m.group(3), rust))
+        print(f"Wrote {len(code)} bytes to {RUST.relative_to(ROOT)}")
+    elif match.group(2) != code.hex():
+        sys.exit(f"ROUND_TRIP_HELPER is out of date with {SOURCE.relative_to(ROOT)}; run with --write")
+    else:
+        print(f"ROUND_TRIP_HELPER matches {SOURCE.relative_to(ROOT)} ({len(code)} bytes), scenarios pass")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,5 +1,9 @@
-use ethers::types::Address;
+use ethers::types::{Address, H256};
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
+
+use crate::types::{KnownRouter, LabelKind};
 
 // PancakeSwap V2 Factory
 pub const PANCAKESWAP_V2_FACTORY: &str = "0xcA143Ce32Fe78f1f7019d7d551a6402fC5350c73";
@@ -7,9 +11,33 @@ pub const PANCAKESWAP_V2_FACTORY: &str = "0xcA143Ce32Fe78f1f7019d7d551a6402fC535
 // PancakeSwap V3 Factory (🔥 NEW: For V3 token pairs)
 pub const PANCAKESWAP_V3_FACTORY: &str = "0x0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865";
 
+// PancakeSwap V2 Router (getAmountsOut quotes)
+pub const PANCAKESWAP_V2_ROUTER: &str = "0x10ED43C718714eb63d5aA57B78B54704E256024E";
+
+// PancakeSwap V3 QuoterV2 (quoteExactInputSingle quotes)
+pub const PANCAKESWAP_V3_QUOTER: &str = "0xB048Bbc1Ee6b733FFfCFb9e9CeF7375518e25997";
+
+// PancakeSwap V3 SwapRouter (exactInputSingle, multicall + unwrapWETH9)
+pub const PANCAKESWAP_V3_ROUTER: &str = "0x1b81D678ffb9C0263b24A97847620C99d213eB14";
+
+// Wrapped BNB
+pub const WBNB: &str = "0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c";
+
 // Four.meme bonding curve contract
 pub const FOURMEME_BONDING_CURVE: &str = "0x5c952063c7fc8610FFDB798152D69F0B9550762b";
 
+// Four.meme TokenManagerHelper3 (getTokenInfo: curve price, funds raised, migration state)
+pub const FOURMEME_HELPER: &str = "0xF251F83e40a78868FcfA3FA4599Dad6494E46034";
+
+// Transfer(address indexed from, address indexed to, uint256 value)
+pub const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+// PairCreated(address indexed token0, address indexed token1, address pair, uint)
+pub const PAIR_CREATED_TOPIC: &str = "0x0d3648bd0f6ba80134a33ba9275ac585d9d315f0ad8355cddefde31afa28d0e9";
+
+// PoolCreated(address indexed token0, address indexed token1, uint24 indexed fee, int24 tickSpacing, address pool)
+pub const POOL_CREATED_TOPIC: &str = "0x783cca1c0412dd0d695e784568c96da2e9c22ff989357a2e8b1d9b2b4e6b7118";
+
 // Base tokens on BSC
 pub struct BaseToken {
     pub symbol: &'static str,
@@ -47,6 +75,78 @@ pub const BASE_TOKENS: &[BaseToken] = &[
     },
 ];
 
+// Base tokens registered at runtime with `add_base_token` (e.g. from a config file)
+static EXTRA_BASE_TOKENS: RwLock<Vec<(String, Address)>> = RwLock::new(Vec::new());
+
+// Chainlink USD price feeds on BSC (aggregator proxies)
+pub const CHAINLINK_BNB_USD_FEED: &str = "0x0567F2323251f0Aab15c8dFb1967E4e8A7D42aeE";
+pub const CHAINLINK_ETH_USD_FEED: &str = "0x9ef1B8c0E4F7dc8bF5719Ea496883DC6401d5b2e";
+pub const CHAINLINK_BTC_USD_FEED: &str = "0x264990fbd0A4796A3E3d8E37C4d5F87a3aCa5Ebf";
+
+// Base token -> Chainlink USD feed
+pub const USD_PRICE_FEEDS: &[(&str, &str)] = &[
+    ("0xbb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c", CHAINLINK_BNB_USD_FEED), // WBNB
+    ("0x2170Ed0880ac9A755fd29B2688956BD959F933F8", CHAINLINK_ETH_USD_FEED), // ETH
+    ("0x7130d2A12B9BCbFAe4f2634d864A1Ee1Ce3Ead9c", CHAINLINK_BTC_USD_FEED), // BTCB
+];
+
+// USD stablecoins valued at parity ($1)
+pub const USD_STABLECOINS: &[&str] = &[
+    "0xe9e7CEA3DedcA5984780Bafc599bD69ADd087D56", // BUSD
+    "0x55d398326f99059fF775485246999027B3197955", // USDT
+    "0x8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d", // USDC
+];
+
+// Routers and aggregators swaps are commonly routed through
+pub const KNOWN_ROUTERS: &[(&str, KnownRouter)] = &[
+    ("0x10ED43C718714eb63d5aA57B78B54704E256024E", KnownRouter::PancakeSwapV2Router),
+    ("0x1b81D678ffb9C0263b24A97847620C99d213eB14", KnownRouter::PancakeSwapV3Router),
+    ("0x13f4EA83D0bd40E75C8222255bc855a974568Dd4", KnownRouter::PancakeSwapSmartRouter),
+    ("0xd9C500DfF816a1Da21A48A732d3498Bf09dc9AEB", KnownRouter::PancakeSwapUniversalRouter),
+    ("0x1111111254EEB25477B68fb85Ed929f73A960582", KnownRouter::OneInch),
+    ("0x111111125421cA6dc452d289314280a0f8842A65", KnownRouter::OneInch),
+    ("0x9b9efa5Efa731EA9Bbb0369E91fA17Abf249CFD4", KnownRouter::OkxDex),
+    ("0x6131B5fae19EA4f9D964eAc0408E4408b66337b5", KnownRouter::KyberSwap),
+    ("0xDEF171Fe48CF0115B1d80b88dc8eAB59176FEe57", KnownRouter::ParaSwap),
+    ("0xDef1C0ded9bec7F1a1670819833240f027b25EfF", KnownRouter::ZeroEx),
+    ("0x1a1ec25DC08e98e5E93F1104B5e5cdD298707d31", KnownRouter::MetaMaskSwaps),
+];
+
+// LP token lockers (more can be registered at runtime via LpLockChecker::with_locker)
+pub const LP_LOCKERS: &[(&str, &str)] = &[
+    ("0x407993575c91ce7643a4d4cCACc9A98c36eE1BBE", "PinkLock"),
+    ("0xC765bddB93b0D1c1A88282BA0fa6B2d00E3e0c83", "UNCX"),
+    ("0xAe7e6CAbad8d80f0b4E1C4DDE2a5dB7201eF1252", "Mudra"),
+];
+
+// LP tokens sent here are permanently burned
+pub const BURN_ADDRESSES: &[&str] = &[
+    "0x000000000000000000000000000000000000dEaD",
+    "0x0000000000000000000000000000000000000000",
+];
+
+// Curated address labels (routers come from KNOWN_ROUTERS, burn addresses from BURN_ADDRESSES)
+pub const ADDRESS_LABELS: &[(&str, &str, LabelKind)] = &[
+    ("0x8894E0a0c962CB723c1976a4421c95949bE2D4E3", "Binance Hot Wallet 6", LabelKind::Exchange),
+    ("0xe2fc31F816A9b94326492132018C3aEcC4a93aE1", "Binance Hot Wallet 7", LabelKind::Exchange),
+    ("0xF977814e90dA44bFA03b6295A0616a897441aceC", "Binance 8", LabelKind::Exchange),
+    (FOURMEME_BONDING_CURVE, "Four.meme TokenManager", LabelKind::Protocol),
+    (PANCAKESWAP_V2_FACTORY, "PancakeSwap V2 Factory", LabelKind::Protocol),
+    (PANCAKESWAP_V3_FACTORY, "PancakeSwap V3 Factory", LabelKind::Protocol),
+];
+
+pub fn get_transfer_topic() -> H256 {
+    H256::from_str(TRANSFER_TOPIC).unwrap()
+}
+
+pub fn get_pair_created_topic() -> H256 {
+    H256::from_str(PAIR_CREATED_TOPIC).unwrap()
+}
+
+pub fn get_pool_created_topic() -> H256 {
+    H256::from_str(POOL_CREATED_TOPIC).unwrap()
+}
+
 pub fn get_factory_address() -> Address {
     Address::from_str(PANCAKESWAP_V2_FACTORY).unwrap()
 }
@@ -55,14 +155,91 @@ pub fn get_v3_factory_address() -> Address {
     Address::from_str(PANCAKESWAP_V3_FACTORY).unwrap()
 }
 
+pub fn get_v2_router_address() -> Address {
+    Address::from_str(PANCAKESWAP_V2_ROUTER).unwrap()
+}
+
+pub fn get_v3_router_address() -> Address {
+    Address::from_str(PANCAKESWAP_V3_ROUTER).unwrap()
+}
+
+pub fn get_v3_quoter_address() -> Address {
+    Address::from_str(PANCAKESWAP_V3_QUOTER).unwrap()
+}
+
+pub fn get_wbnb_address() -> Address {
+    Address::from_str(WBNB).unwrap()
+}
+
 pub fn get_bonding_curve_address() -> Address {
     Address::from_str(FOURMEME_BONDING_CURVE).unwrap()
 }
 
+pub fn get_fourmeme_helper_address() -> Address {
+    Address::from_str(FOURMEME_HELPER).unwrap()
+}
+
 pub fn get_base_tokens() -> Vec<(String, Address)> {
-    BASE_TOKENS
+    let mut tokens: Vec<(String, Address)> = BASE_TOKENS
         .iter()
         .map(|t| (t.symbol.to_string(), Address::from_str(t.address).unwrap()))
+        .collect();
+    for (symbol, address) in EXTRA_BASE_TOKENS.read().unwrap_or_else(|e| e.into_inner()).iter() {
+        if !tokens.iter().any(|(_, a)| a == address) {
+            tokens.push((symbol.clone(), *address));
+        }
+    }
+    tokens
+}
+
+/// Also search pairs against `address` (in addition to `BASE_TOKENS`), for every streamer in the process
+pub fn add_base_token(symbol: &str, address: Address) {
+    let mut extra = EXTRA_BASE_TOKENS.write().unwrap_or_else(|e| e.into_inner());
+    if !extra.iter().any(|(_, a)| *a == address) {
+        extra.push((symbol.to_string(), address));
+    }
+}
+
+pub fn get_usd_price_feeds() -> Vec<(Address, Address)> {
+    USD_PRICE_FEEDS
+        .iter()
+        .map(|(token, feed)| (Address::from_str(token).unwrap(), Address::from_str(feed).unwrap()))
         .collect()
 }
 
+pub fn get_usd_stablecoins() -> Vec<Address> {
+    USD_STABLECOINS
+        .iter()
+        .map(|t| Address::from_str(t).unwrap())
+        .collect()
+}
+
+pub fn get_known_routers() -> Vec<(Address, KnownRouter)> {
+    KNOWN_ROUTERS
+        .iter()
+        .map(|(address, router)| (Address::from_str(address).unwrap(), *router))
+        .collect()
+}
+
+// `KNOWN_ROUTERS` parsed once, for per-swap lookups
+static KNOWN_ROUTER_TABLE: LazyLock<HashMap<Address, KnownRouter>> =
+    LazyLock::new(|| get_known_routers().into_iter().collect());
+
+/// Known router or aggregator deployed at `address`
+pub fn get_known_router(address: Address) -> Option<KnownRouter> {
+    KNOWN_ROUTER_TABLE.get(&address).copied()
+}
+
+pub fn get_lp_lockers() -> Vec<(Address, String)> {
+    LP_LOCKERS
+        .iter()
+        .map(|(address, name)| (Address::from_str(address).unwrap(), name.to_string()))
+        .collect()
+}
+
+pub fn get_burn_addresses() -> Vec<Address> {
+    BURN_ADDRESSES
+        .iter()
+        .map(|a| Address::from_str(a).unwrap())
+        .collect()
+}
@@ -0,0 +1,90 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    providers::Middleware,
+    types::{Address, Filter, Log, H256},
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::{get_bonding_curve_address, get_transfer_topic};
+use crate::core::pair_finder::PairFinder;
+use crate::core::streamer::{SWAP_V2_TOPIC, SWAP_V3_TOPIC};
+use crate::core::swap_parser::SwapParser;
+use crate::types::SwapEvent;
+
+// Block range per eth_getLogs request (public RPCs reject large ranges)
+const LOG_SCAN_CHUNK_BLOCKS: u64 = 5_000;
+
+/// Fetch all logs matching `filter` between two blocks, in chunks
+pub(crate) async fn scan_logs<M: Middleware + 'static>(provider: &Arc<M>, filter: Filter, from_block: u64, to_block: u64) -> Result<Vec<Log>> {
+    let mut logs = Vec::new();
+    let mut from = from_block;
+    while from <= to_block {
+        let to = (from + LOG_SCAN_CHUNK_BLOCKS - 1).min(to_block);
+        let chunk = provider
+            .get_logs(&filter.clone().from_block(from).to_block(to))
+            .await
+            .map_err(|e| anyhow!("Failed to fetch logs {}..{}: {}", from, to, e))?;
+        logs.extend(chunk);
+        from = to + 1;
+    }
+    Ok(logs)
+}
+
+/// Historical swaps of a token on the Four.meme bonding curve and its DEX pairs, oldest first
+///
+/// `to_block` defaults to the latest block. Swaps are parsed like streamed ones,
+/// but USD values use current reference prices.
+pub async fn fetch_swaps<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: Address,
+    from_block: u64,
+    to_block: Option<u64>,
+) -> Result<Vec<SwapEvent>> {
+    let to_block = match to_block {
+        Some(block) => block,
+        None => provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64(),
+    };
+    if from_block > to_block {
+        return Err(anyhow!("from_block {} is after to_block {}", from_block, to_block));
+    }
+
+    let parser = SwapParser::new(provider.clone());
+    let mut swaps = Vec::new();
+
+    // Bonding curve trades are token Transfers from (buys) or to (sells) the curve
+    let bonding_curve = get_bonding_curve_address();
+    let transfer_topic = get_transfer_topic();
+    let curve_topic = H256::from(bonding_curve);
+    let transfers = Filter::new().address(token_address).topic0(transfer_topic);
+    for filter in [transfers.clone().topic1(curve_topic), transfers.topic2(curve_topic)] {
+        for log in scan_logs(&provider, filter, from_block, to_block).await? {
+            match parser.parse_bonding_curve_event(&log, token_address, bonding_curve).await {
+                Ok(Some(swap)) => swaps.push(swap),
+                Ok(None) => {}
+                Err(e) => log::warn!("⚠️ [BACKFILL] Failed to parse bonding curve trade {:?}: {}", log.transaction_hash, e),
+            }
+        }
+    }
+
+    for pair_info in PairFinder::new(provider.clone()).find_pairs(token_address).await? {
+        let topic = H256::from_str(if pair_info.is_v3 { SWAP_V3_TOPIC } else { SWAP_V2_TOPIC })?;
+        let filter = Filter::new().address(pair_info.pair_address).topic0(topic);
+        let logs = scan_logs(&provider, filter, from_block, to_block).await?;
+        log::debug!("📜 [BACKFILL] {} swap log(s) on pair {:?}", logs.len(), pair_info.pair_address);
+        for log in logs {
+            match parser.parse_swap_event(&log, &pair_info).await {
+                Ok(swap) => swaps.push(swap),
+                Err(e) => log::warn!("⚠️ [BACKFILL] Failed to parse swap {:?}: {}", log.transaction_hash, e),
+            }
+        }
+    }
+
+    swaps.sort_by_key(|swap| (swap.block_number, swap.log_index));
+    log::info!("📜 [BACKFILL] Fetched {} swap(s) of {:?} in blocks {}..{}", swaps.len(), token_address, from_block, to_block);
+    Ok(swaps)
+}
@@ -0,0 +1,89 @@
+use ethers::providers::{Middleware, PubsubClient, StreamExt};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::health::HealthMonitor;
+use crate::core::options::BlockCallback;
+use crate::types::BlockEvent;
+
+/// Swaps of the monitored tokens seen per block, for `BlockEvent::previous_block_swaps`
+#[derive(Clone, Default)]
+pub struct BlockSwapCounter {
+    counts: Arc<Mutex<BTreeMap<u64, u64>>>,
+}
+
+impl BlockSwapCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, block_number: u64) {
+        *self.counts.lock().unwrap().entry(block_number).or_default() += 1;
+    }
+
+    /// Swaps seen in `block_number`, forgetting it and every older block
+    fn take(&self, block_number: u64) -> u64 {
+        let mut counts = self.counts.lock().unwrap();
+        let count = counts.get(&block_number).copied().unwrap_or_default();
+        *counts = counts.split_off(&(block_number + 1));
+        count
+    }
+}
+
+/// Report every new head with the swap count of the block before it
+pub(crate) fn spawn_block_watcher<M>(
+    provider: Arc<M>,
+    counter: BlockSwapCounter,
+    callback: BlockCallback,
+    health: HealthMonitor,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let probe = health.subscription("New heads".to_string());
+        let mut stream = match provider.subscribe_blocks().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [BLOCKS] Failed to subscribe to new blocks: {}", e);
+                probe.failed(e);
+                return;
+            }
+        };
+        probe.live();
+
+        loop {
+            let block = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [BLOCKS] Block watcher cancelled");
+                    probe.stopped();
+                    break;
+                }
+                block = stream.next() => match block {
+                    Some(block) => block,
+                    None => {
+                        log::warn!("⚠️ [BLOCKS] Block stream ended");
+                        probe.ended();
+                        break;
+                    }
+                },
+            };
+            probe.event();
+            let Some(block_number) = block.number.map(|n| n.as_u64()) else {
+                continue;
+            };
+
+            callback(BlockEvent {
+                block_number,
+                block_hash: block.hash,
+                timestamp: block.timestamp.as_u64(),
+                base_fee_per_gas: block.base_fee_per_gas,
+                gas_used: block.gas_used,
+                gas_limit: block.gas_limit,
+                previous_block_swaps: counter.take(block_number.saturating_sub(1)),
+            });
+        }
+    });
+}
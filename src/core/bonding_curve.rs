@@ -0,0 +1,121 @@
+use anyhow::Result;
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::Middleware,
+    types::{Address, U256},
+    utils::format_units,
+};
+use std::sync::Arc;
+
+use crate::config::{get_fourmeme_helper_address, get_wbnb_address};
+use crate::core::creator::{find_token_launch, CREATOR_LOOKBACK_BLOCKS};
+use crate::core::price_oracle::PriceOracle;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::BondingCurveInfo;
+
+pub(crate) const HELPER_ABI: &str = r#"[
+    {"inputs":[{"name":"token","type":"address"}],"name":"getTokenInfo","outputs":[
+        {"name":"version","type":"uint256"},
+        {"name":"tokenManager","type":"address"},
+        {"name":"quote","type":"address"},
+        {"name":"lastPrice","type":"uint256"},
+        {"name":"tradingFeeRate","type":"uint256"},
+        {"name":"minTradingFee","type":"uint256"},
+        {"name":"launchTime","type":"uint256"},
+        {"name":"offers","type":"uint256"},
+        {"name":"maxOffers","type":"uint256"},
+        {"name":"funds","type":"uint256"},
+        {"name":"maxFunds","type":"uint256"},
+        {"name":"liquidityAdded","type":"bool"}
+    ],"stateMutability":"view","type":"function"}
+]"#;
+
+pub(crate) type HelperTokenInfo = (U256, Address, Address, U256, U256, U256, U256, U256, U256, U256, U256, bool);
+
+/// Reads a token's Four.meme curve state through the TokenManagerHelper3 contract
+pub struct BondingCurveReader<M> {
+    provider: Arc<M>,
+    helper: Address,
+}
+
+impl<M: Middleware + 'static> BondingCurveReader<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            provider,
+            helper: get_fourmeme_helper_address(),
+        }
+    }
+
+    /// Curve progress, price, funds raised and creator, or None if the token was not launched on Four.meme
+    pub async fn read(&self, token_address: Address) -> Result<Option<BondingCurveInfo>> {
+        let abi: Abi = serde_json::from_str(HELPER_ABI)?;
+        let contract = Contract::new(self.helper, abi, self.provider.clone());
+        let (version, token_manager, quote, last_price, _, _, launch_time, offers, _, funds, max_funds, liquidity_added): HelperTokenInfo =
+            contract.method("getTokenInfo", token_address)?.call().await?;
+        if version.is_zero() || token_manager.is_zero() {
+            return Ok(None);
+        }
+
+        let token_cache = TokenInfoCache::new(self.provider.clone());
+        let token_decimals = token_cache.get_token_info(token_address).await.map(|t| t.decimals).unwrap_or(18);
+        let (quote_token, quote_symbol, quote_decimals) = if quote.is_zero() {
+            (None, "BNB".to_string(), 18)
+        } else {
+            let info = token_cache.get_token_info(quote).await?;
+            (Some(quote), info.symbol, info.decimals)
+        };
+        let amount = |value: U256, decimals: u8| -> f64 {
+            format_units(value, decimals as u32).ok().and_then(|s| s.parse().ok()).unwrap_or(0.0)
+        };
+
+        // lastPrice is quote wei per whole token, always 18 decimals
+        let price = amount(last_price, 18);
+        let funds_raised = amount(funds, quote_decimals);
+        let funds_target = amount(max_funds, quote_decimals);
+        let progress_pct = if liquidity_added {
+            100.0
+        } else if funds_target > 0.0 {
+            (funds_raised / funds_target * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+
+        let oracle = PriceOracle::new(self.provider.clone());
+        let quote_usd = oracle.usd_price(quote_token.unwrap_or_else(get_wbnb_address)).await;
+
+        let creator = match find_token_launch(self.provider.clone(), token_address, CREATOR_LOOKBACK_BLOCKS).await {
+            Ok(launch) => launch.map(|l| l.creator),
+            Err(e) => {
+                log::debug!("⚠️ [BONDING_CURVE] Failed to resolve creator of {:?}: {}", token_address, e);
+                None
+            }
+        };
+
+        log::debug!(
+            "📈 [BONDING_CURVE] {:?}: {:.2}% of {} {} raised, price {:.10} {}",
+            token_address,
+            progress_pct,
+            funds_target,
+            quote_symbol,
+            price,
+            quote_symbol
+        );
+
+        Ok(Some(BondingCurveInfo {
+            address: token_manager,
+            token: token_address,
+            quote_token,
+            quote_symbol,
+            progress_pct,
+            price,
+            price_usd: quote_usd.map(|usd| price * usd),
+            funds_raised,
+            funds_target,
+            tokens_remaining: amount(offers, token_decimals),
+            launch_time: launch_time.low_u64(),
+            liquidity_added,
+            creator,
+        }))
+    }
+}
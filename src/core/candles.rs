@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use ethers::types::Address;
+
+use crate::core::options::CandleCallback;
+use crate::types::{Candle, CandleInterval, SwapEvent, TradeType};
+
+// Candles close this long after their interval ends, so swaps from the last block can land
+const CLOSE_GRACE_SECS: i64 = 3;
+
+/// Unix time of a swap, from its block timestamp (falls back to now)
+pub(crate) fn swap_time(swap: &SwapEvent) -> i64 {
+    swap.timestamp
+        .as_deref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|dt| dt.timestamp())
+        .unwrap_or_else(|| chrono::Utc::now().timestamp())
+}
+
+#[derive(Default)]
+struct CandleState {
+    open: HashMap<(Address, CandleInterval), Candle>,
+    // Open time of the last closed candle, so late swaps don't reopen it
+    last_closed: HashMap<(Address, CandleInterval), i64>,
+}
+
+/// Aggregates swaps into OHLCV candles per market (pair or bonding curve) and interval
+#[derive(Clone)]
+pub struct CandleAggregator {
+    intervals: Vec<CandleInterval>,
+    state: Arc<Mutex<CandleState>>,
+}
+
+impl CandleAggregator {
+    pub fn new(intervals: Vec<CandleInterval>) -> Self {
+        Self {
+            intervals,
+            state: Arc::new(Mutex::new(CandleState::default())),
+        }
+    }
+
+    /// Add a swap; returns candles closed by it (a swap in a later interval closes the current one)
+    pub fn push(&self, swap: &SwapEvent) -> Vec<Candle> {
+        let Some(market) = swap.pair_address.or(swap.bonding_curve_address) else {
+            return Vec::new();
+        };
+        let price = swap.price.value;
+        if !price.is_finite() || price <= 0.0 {
+            return Vec::new();
+        }
+        let time = swap_time(swap);
+        let token_volume: f64 = swap.token.amount.parse().unwrap_or_default();
+        let base_volume: f64 = swap.base_token.amount.parse().unwrap_or_default();
+
+        let mut state = self.state.lock().unwrap();
+        let mut closed = Vec::new();
+
+        for &interval in &self.intervals {
+            let key = (market, interval);
+            let open_time = time - time.rem_euclid(interval.as_secs() as i64);
+
+            if state.last_closed.get(&key).is_some_and(|last| open_time <= *last) {
+                log::debug!("⏱️ [CANDLES] Dropping late swap {:?} for closed {} candle", swap.transaction_hash, interval.as_str());
+                continue;
+            }
+
+            if state.open.get(&key).is_some_and(|candle| candle.open_time < open_time) {
+                if let Some(candle) = state.open.remove(&key) {
+                    state.last_closed.insert(key, candle.open_time);
+                    closed.push(candle);
+                }
+            }
+
+            let candle = state.open.entry(key).or_insert_with(|| Candle {
+                market,
+                token_address: swap.token.address,
+                base_token: swap.price.base_token.clone(),
+                interval,
+                open_time,
+                close_time: open_time + interval.as_secs() as i64,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume_token: 0.0,
+                volume_base: 0.0,
+                volume_usd: None,
+                trades: 0,
+                buys: 0,
+                sells: 0,
+            });
+
+            candle.high = candle.high.max(price);
+            candle.low = candle.low.min(price);
+            candle.close = price;
+            candle.volume_token += token_volume;
+            candle.volume_base += base_volume;
+            if let Some(usd) = swap.usd_value {
+                candle.volume_usd = Some(candle.volume_usd.unwrap_or_default() + usd);
+            }
+            candle.trades += 1;
+            match swap.trade_type {
+                TradeType::Buy => candle.buys += 1,
+                TradeType::Sell => candle.sells += 1,
+            }
+        }
+
+        closed
+    }
+
+    /// Close every candle whose interval ended before `now` (unix seconds)
+    pub fn flush(&self, now: i64) -> Vec<Candle> {
+        let mut state = self.state.lock().unwrap();
+        let expired: Vec<_> = state
+            .open
+            .iter()
+            .filter(|(_, candle)| candle.close_time + CLOSE_GRACE_SECS <= now)
+            .map(|(key, _)| *key)
+            .collect();
+
+        let mut closed = Vec::new();
+        for key in expired {
+            if let Some(candle) = state.open.remove(&key) {
+                state.last_closed.insert(key, candle.open_time);
+                closed.push(candle);
+            }
+        }
+        closed.sort_by_key(|c| (c.open_time, c.interval.as_secs()));
+        closed
+    }
+
+    /// Candles still accumulating
+    pub fn open_candles(&self) -> Vec<Candle> {
+        self.state.lock().unwrap().open.values().cloned().collect()
+    }
+}
+
+/// Close candles on wall-clock time so quiet markets still emit bars
+///
+/// Intervals without any swap produce no candle.
+pub(crate) fn spawn_candle_timer(aggregator: CandleAggregator, callback: CandleCallback, cancel_token: CancellationToken) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [CANDLES] Candle timer cancelled");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            for candle in aggregator.flush(chrono::Utc::now().timestamp()) {
+                callback(candle);
+            }
+        }
+    });
+}
@@ -0,0 +1,136 @@
+use ethers::{
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::H256,
+};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::health::HealthMonitor;
+
+// An event's transaction, log index and the closure delivering it
+type Held = (H256, u64, Box<dyn FnOnce() + Send>);
+
+/// Events held back until their block is `confirmations` blocks deep, keyed by block number
+#[derive(Clone)]
+pub struct ConfirmationBuffer {
+    confirmations: u64,
+    held: Arc<Mutex<BTreeMap<u64, Vec<Held>>>>,
+}
+
+impl ConfirmationBuffer {
+    pub fn new(confirmations: u64) -> Self {
+        Self {
+            confirmations,
+            held: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Hold `deliver` until the block of `transaction_hash` is deep enough
+    ///
+    /// A log re-sent for the same block (reorg onto a sibling block) is only held once.
+    pub fn hold(&self, block_number: u64, transaction_hash: H256, log_index: u64, deliver: impl FnOnce() + Send + 'static) {
+        let mut held = self.held.lock().unwrap();
+        let events = held.entry(block_number).or_default();
+        if events.iter().any(|(hash, index, _)| *hash == transaction_hash && *index == log_index) {
+            return;
+        }
+        events.push((transaction_hash, log_index, Box::new(deliver)));
+    }
+
+    /// Number of events waiting for confirmations
+    pub fn len(&self) -> usize {
+        self.held.lock().unwrap().values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove the blocks that are `confirmations` deep at `head`, oldest first
+    fn take_confirmed(&self, head: u64) -> Vec<(u64, Vec<Held>)> {
+        let mut held = self.held.lock().unwrap();
+        let Some(newest) = head.checked_sub(self.confirmations) else {
+            return Vec::new();
+        };
+        let pending = held.split_off(&(newest + 1));
+        std::mem::replace(&mut *held, pending).into_iter().collect()
+    }
+
+    fn put_back(&self, block_number: u64, events: Vec<Held>) {
+        self.held.lock().unwrap().entry(block_number).or_default().extend(events);
+    }
+}
+
+/// Deliver held events on each new head once they are deep enough
+///
+/// The block is re-read before delivery; events whose transaction is no longer
+/// in the canonical block (reorged out) are dropped.
+pub(crate) fn spawn_confirmation_release<M>(
+    provider: Arc<M>,
+    buffer: ConfirmationBuffer,
+    health: HealthMonitor,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let probe = health.subscription(format!("New heads ({} confirmations)", buffer.confirmations));
+        let mut stream = match provider.subscribe_blocks().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [CONFIRMATIONS] Failed to subscribe to new blocks: {}", e);
+                probe.failed(e);
+                return;
+            }
+        };
+        probe.live();
+
+        loop {
+            let head = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [CONFIRMATIONS] Cancelled with {} event(s) still unconfirmed", buffer.len());
+                    probe.stopped();
+                    break;
+                }
+                block = stream.next() => match block {
+                    Some(block) => block,
+                    None => {
+                        log::warn!("⚠️ [CONFIRMATIONS] Block stream ended with {} event(s) unconfirmed", buffer.len());
+                        probe.ended();
+                        break;
+                    }
+                },
+            };
+            probe.event();
+            let Some(head) = head.number else {
+                continue;
+            };
+
+            for (block_number, events) in buffer.take_confirmed(head.as_u64()) {
+                let canonical = match provider.get_block(block_number).await {
+                    Ok(Some(block)) => block.transactions.into_iter().collect::<HashSet<_>>(),
+                    other => {
+                        if let Err(e) = other {
+                            log::warn!("⚠️ [CONFIRMATIONS] Failed to fetch block {}: {} - retrying on the next head", block_number, e);
+                        }
+                        buffer.put_back(block_number, events);
+                        continue;
+                    }
+                };
+                for (transaction_hash, _, deliver) in events {
+                    if canonical.contains(&transaction_hash) {
+                        deliver();
+                    } else {
+                        log::warn!(
+                            "🔀 [CONFIRMATIONS] Dropping event of {:?}: no longer in block {} (reorg)",
+                            transaction_hash,
+                            block_number
+                        );
+                    }
+                }
+            }
+        }
+    });
+}
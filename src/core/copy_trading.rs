@@ -0,0 +1,298 @@
+use anyhow::Result;
+use ethers::{providers::Middleware, types::Address};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::core::paper::bnb_price;
+use crate::core::risk::RiskScanner;
+#[cfg(feature = "trader")]
+use crate::trader::Trader;
+use crate::types::{CopyConfig, CopySignal, SwapEvent, TradeType};
+
+/// Tokens held through mirrored trades and the BNB they cost
+#[derive(Debug, Clone, Copy, Default)]
+struct Position {
+    tokens: f64,
+    cost_bnb: f64,
+}
+
+#[derive(Default)]
+struct State {
+    positions: HashMap<Address, Position>,
+    /// Tokens each watched wallet was seen buying, by (wallet, token)
+    leader_bought: HashMap<(Address, Address), f64>,
+    /// Risk score per token (None when the scan failed)
+    risk_scores: HashMap<Address, Option<u8>>,
+}
+
+/// Turns the swaps of watched wallets into sized mirror trades
+///
+/// Buys are scaled by `CopyConfig::scale` and capped per trade and per token;
+/// tokens above the risk score limit are skipped. Sells exit the same share
+/// of the mirrored position as the leader sold of the tokens it was seen
+/// buying (everything, if it was never seen buying). With the `trader`
+/// feature, `with_trader` also sends each signal as a real order.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::{CopyConfig, CopyTrader, StreamerBuilder};
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Arc::new(Provider::<Ws>::connect("wss://bsc.publicnode.com").await?);
+/// let config = CopyConfig {
+///     wallets: vec!["0x...".parse()?],
+///     scale: 0.05,
+///     max_position_bnb: 0.5,
+///     ..CopyConfig::default()
+/// };
+/// let copier = CopyTrader::new(provider.clone(), config.clone());
+///
+/// StreamerBuilder::new(provider)
+///     .token_address("0x...")
+///     .auto_detect()
+///     .allow_senders(&config.wallets)
+///     .on_swap(move |swap| {
+///         // Risk scans are async: handle the swap off the callback
+///         let copier = copier.clone();
+///         tokio::spawn(async move {
+///             if let Ok(Some(signal)) = copier.on_swap(&swap).await {
+///                 println!("Copy {:?} of {} for {:.4} BNB", signal.trade_type, signal.token_symbol, signal.size_bnb);
+///             }
+///         });
+///     })
+///     .start()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CopyTrader<M> {
+    provider: Arc<M>,
+    config: CopyConfig,
+    state: Arc<Mutex<State>>,
+    #[cfg(feature = "trader")]
+    trader: Option<Arc<Trader<M>>>,
+}
+
+impl<M> Clone for CopyTrader<M> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            config: self.config.clone(),
+            state: self.state.clone(),
+            #[cfg(feature = "trader")]
+            trader: self.trader.clone(),
+        }
+    }
+}
+
+impl<M: Middleware + 'static> CopyTrader<M> {
+    /// `provider` is used for token risk scans
+    pub fn new(provider: Arc<M>, config: CopyConfig) -> Self {
+        Self {
+            provider,
+            config,
+            state: Arc::new(Mutex::new(State::default())),
+            #[cfg(feature = "trader")]
+            trader: None,
+        }
+    }
+
+    /// Send every signal as a real order through `trader` (requires the `trader` feature)
+    #[cfg(feature = "trader")]
+    pub fn with_trader(mut self, trader: Arc<Trader<M>>) -> Self {
+        self.trader = Some(trader);
+        self
+    }
+
+    /// Mirror trade for a streamed swap, if a watched wallet made it and it passes the guardrails
+    ///
+    /// The signal is counted against the position limits right away. With a
+    /// trader attached the order is sent before returning; a failed order is
+    /// taken back out of the position and returned as the error.
+    pub async fn on_swap(&self, swap: &SwapEvent) -> Result<Option<CopySignal>> {
+        let wallets = [swap.trader, Some(swap.sender), Some(swap.recipient)];
+        let Some(wallet) = wallets.into_iter().flatten().find(|w| self.config.wallets.contains(w)) else {
+            return Ok(None);
+        };
+        let Some(price_bnb) = bnb_price(swap) else {
+            log::debug!("👥 [COPY] No BNB price for {} swap {:?} - not copied", swap.token.symbol, swap.transaction_hash);
+            return Ok(None);
+        };
+        let token = swap.token.address;
+        let leader_tokens = swap.token.amount.parse::<f64>().unwrap_or_default();
+
+        let (size_bnb, token_amount, risk_score) = match swap.trade_type {
+            TradeType::Buy => {
+                *self.state.lock().unwrap().leader_bought.entry((wallet, token)).or_default() += leader_tokens;
+                let risk_score = self.risk_score(token).await;
+                if let Some(max) = self.config.max_risk_score {
+                    if risk_score.is_none_or(|score| score > max) {
+                        log::info!("👥 [COPY] Not copying buy of {}: risk score {:?} above {}", swap.token.symbol, risk_score, max);
+                        return Ok(None);
+                    }
+                }
+                let Some(size_bnb) = self.reserve_buy(token, leader_tokens * price_bnb * self.config.scale, price_bnb) else {
+                    return Ok(None);
+                };
+                (size_bnb, None, risk_score)
+            }
+            TradeType::Sell => {
+                if !self.config.mirror_sells {
+                    return Ok(None);
+                }
+                let Some((tokens, cost_bnb)) = self.reserve_sell(wallet, token, leader_tokens) else {
+                    return Ok(None);
+                };
+                let risk_score = self.state.lock().unwrap().risk_scores.get(&token).copied().flatten();
+                (cost_bnb, Some(tokens), risk_score)
+            }
+        };
+
+        let signal = CopySignal {
+            wallet,
+            token_address: token,
+            token_symbol: swap.token.symbol.clone(),
+            trade_type: swap.trade_type,
+            platform: swap.platform,
+            pool_address: swap.pair_address.or(swap.bonding_curve_address),
+            transaction_hash: swap.transaction_hash,
+            block_number: swap.block_number,
+            leader_bnb: leader_tokens * price_bnb,
+            price_bnb,
+            size_bnb,
+            token_amount,
+            risk_score,
+            execution: None,
+        };
+        log::info!(
+            "👥 [COPY] {:?} copies {:?} of {} for {:.4} BNB (leader {:.4} BNB)",
+            wallet,
+            signal.trade_type,
+            signal.token_symbol,
+            signal.size_bnb,
+            signal.leader_bnb
+        );
+
+        #[cfg(feature = "trader")]
+        let signal = self.execute(signal).await?;
+        Ok(Some(signal))
+    }
+
+    /// Send the signal as an order through the attached trader, if any
+    #[cfg(feature = "trader")]
+    async fn execute(&self, mut signal: CopySignal) -> Result<CopySignal> {
+        let Some(trader) = &self.trader else {
+            return Ok(signal);
+        };
+        let sent = match signal.trade_type {
+            TradeType::Buy => trader.buy(signal.token_address, signal.size_bnb, self.config.slippage_pct).await,
+            TradeType::Sell => {
+                trader
+                    .sell(signal.token_address, signal.token_amount.unwrap_or_default(), self.config.slippage_pct)
+                    .await
+            }
+        };
+        match sent {
+            Ok(execution) => {
+                if signal.trade_type == TradeType::Buy {
+                    // Track the guaranteed output rather than the price estimate, so sells don't exceed the balance
+                    let mut state = self.state.lock().unwrap();
+                    let position = state.positions.entry(signal.token_address).or_default();
+                    position.tokens += execution.min_out - signal.size_bnb / signal.price_bnb;
+                }
+                signal.execution = Some(execution);
+                Ok(signal)
+            }
+            Err(e) => {
+                self.release(&signal);
+                Err(e.context(format!("Mirror {:?} of {} failed", signal.trade_type, signal.token_symbol)))
+            }
+        }
+    }
+
+    /// BNB currently committed to a token through mirrored buys
+    pub fn exposure_bnb(&self, token: Address) -> f64 {
+        self.state.lock().unwrap().positions.get(&token).map(|p| p.cost_bnb).unwrap_or_default()
+    }
+
+    /// Tokens held through mirrored buys, as estimated from the signals
+    pub fn position_tokens(&self, token: Address) -> f64 {
+        self.state.lock().unwrap().positions.get(&token).map(|p| p.tokens).unwrap_or_default()
+    }
+
+    /// Cached risk score of a token, scanning it the first time (only with `max_risk_score` set)
+    async fn risk_score(&self, token: Address) -> Option<u8> {
+        self.config.max_risk_score?;
+        if let Some(score) = self.state.lock().unwrap().risk_scores.get(&token) {
+            return *score;
+        }
+        let score = match RiskScanner::new(self.provider.clone()).scan(token).await {
+            Ok(report) => Some(report.score),
+            Err(e) => {
+                log::warn!("⚠️ [COPY] Risk scan of {:?} failed: {}", token, e);
+                None
+            }
+        };
+        self.state.lock().unwrap().risk_scores.insert(token, score);
+        score
+    }
+
+    /// Size a buy within the per-trade and per-token limits and add it to the position
+    fn reserve_buy(&self, token: Address, wanted_bnb: f64, price_bnb: f64) -> Option<f64> {
+        let mut state = self.state.lock().unwrap();
+        let position = state.positions.entry(token).or_default();
+        let room = (self.config.max_position_bnb - position.cost_bnb).max(0.0);
+        let size_bnb = wanted_bnb.min(self.config.max_trade_bnb.unwrap_or(f64::INFINITY)).min(room);
+        if size_bnb < self.config.min_trade_bnb {
+            log::debug!("👥 [COPY] Buy of {:?} below the minimum or over the position limit - not copied", token);
+            return None;
+        }
+        position.cost_bnb += size_bnb;
+        position.tokens += size_bnb / price_bnb;
+        Some(size_bnb)
+    }
+
+    /// Take the leader's sold share out of the position, returning (tokens, cost basis)
+    fn reserve_sell(&self, wallet: Address, token: Address, sold: f64) -> Option<(f64, f64)> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        let share = match state.leader_bought.get_mut(&(wallet, token)) {
+            Some(bought) if *bought > sold => {
+                let share = sold / *bought;
+                *bought -= sold;
+                share
+            }
+            _ => {
+                state.leader_bought.remove(&(wallet, token));
+                1.0
+            }
+        };
+        let position = state.positions.get_mut(&token).filter(|p| p.tokens > 0.0)?;
+        let taken = Position {
+            tokens: position.tokens * share,
+            cost_bnb: position.cost_bnb * share,
+        };
+        position.tokens -= taken.tokens;
+        position.cost_bnb -= taken.cost_bnb;
+        Some((taken.tokens, taken.cost_bnb))
+    }
+
+    /// Undo what a signal did to the position (its order failed)
+    #[cfg(feature = "trader")]
+    fn release(&self, signal: &CopySignal) {
+        let mut state = self.state.lock().unwrap();
+        let position = state.positions.entry(signal.token_address).or_default();
+        match signal.trade_type {
+            TradeType::Buy => {
+                position.cost_bnb -= signal.size_bnb;
+                position.tokens -= signal.size_bnb / signal.price_bnb;
+            }
+            TradeType::Sell => {
+                position.cost_bnb += signal.size_bnb;
+                position.tokens += signal.token_amount.unwrap_or_default();
+            }
+        }
+    }
+}
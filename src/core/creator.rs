@@ -0,0 +1,239 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::{self, ParamType, Token},
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{Address, Filter, Log, H256, U256},
+    utils::format_units,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{get_bonding_curve_address, get_transfer_topic};
+use crate::core::liquidity::{BURN_V2_TOPIC, BURN_V3_TOPIC, MINT_V2_TOPIC, MINT_V3_TOPIC};
+use crate::core::options::DevActionCallback;
+use crate::core::streamer::{SWAP_V2_TOPIC, SWAP_V3_TOPIC};
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{DevActionEvent, DevActionKind, TokenLaunch};
+
+// Four.meme TokenCreate(address creator, address token, uint256 requestId, string name, string symbol, uint256 totalSupply, uint256 launchTime, uint256 launchFee)
+pub const TOKEN_CREATE_TOPIC: &str = "0x396d5e902b675b032348d3d2e9517ee8f0c4a926603fbc075d3d282ff00cad20";
+
+// How far back launches are searched (TokenCreate has no indexed fields, so logs are scanned)
+pub const CREATOR_LOOKBACK_BLOCKS: u64 = 200_000;
+const LOG_SCAN_CHUNK_BLOCKS: u64 = 5_000;
+
+/// Decode a TokenCreate log
+pub(crate) fn decode_token_create(log: &Log) -> Option<TokenLaunch> {
+    let tokens = abi::decode(
+        &[
+            ParamType::Address,
+            ParamType::Address,
+            ParamType::Uint(256),
+            ParamType::String,
+            ParamType::String,
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+        ],
+        &log.data,
+    )
+    .ok()?;
+
+    match tokens.as_slice() {
+        [Token::Address(creator), Token::Address(token), _, Token::String(name), Token::String(symbol), Token::Uint(total_supply), Token::Uint(launch_time), _] => {
+            Some(TokenLaunch {
+                token_address: *token,
+                creator: *creator,
+                name: name.clone(),
+                symbol: symbol.clone(),
+                total_supply: *total_supply,
+                launch_time: launch_time.low_u64(),
+                block_number: log.block_number?.as_u64(),
+                transaction_hash: log.transaction_hash?,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Find a Four.meme token's launch (and creator), newest blocks first
+///
+/// Returns None if the token was not launched on Four.meme within `lookback_blocks`.
+pub async fn find_token_launch<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: Address,
+    lookback_blocks: u64,
+) -> Result<Option<TokenLaunch>> {
+    let latest = provider
+        .get_block_number()
+        .await
+        .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+        .as_u64();
+    let earliest = latest.saturating_sub(lookback_blocks);
+    let topic = H256::from_str(TOKEN_CREATE_TOPIC)?;
+
+    let mut to_block = latest;
+    while to_block >= earliest {
+        let from_block = to_block.saturating_sub(LOG_SCAN_CHUNK_BLOCKS - 1).max(earliest);
+        let filter = Filter::new()
+            .address(get_bonding_curve_address())
+            .topic0(topic)
+            .from_block(from_block)
+            .to_block(to_block);
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch TokenCreate logs {}..{}: {}", from_block, to_block, e))?;
+
+        if let Some(launch) = logs.iter().filter_map(decode_token_create).find(|l| l.token_address == token_address) {
+            return Ok(Some(launch));
+        }
+
+        if from_block == 0 || from_block == earliest {
+            break;
+        }
+        to_block = from_block - 1;
+    }
+
+    Ok(None)
+}
+
+/// Four.meme launches of the last `lookback_blocks` blocks, oldest first
+pub async fn recent_launches<M: Middleware + 'static>(provider: Arc<M>, lookback_blocks: u64) -> Result<Vec<TokenLaunch>> {
+    let latest = provider
+        .get_block_number()
+        .await
+        .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+        .as_u64();
+    let topic = H256::from_str(TOKEN_CREATE_TOPIC)?;
+
+    let mut launches = Vec::new();
+    let mut from_block = latest.saturating_sub(lookback_blocks);
+    while from_block <= latest {
+        let to_block = (from_block + LOG_SCAN_CHUNK_BLOCKS - 1).min(latest);
+        let filter = Filter::new()
+            .address(get_bonding_curve_address())
+            .topic0(topic)
+            .from_block(from_block)
+            .to_block(to_block);
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch TokenCreate logs {}..{}: {}", from_block, to_block, e))?;
+        launches.extend(logs.iter().filter_map(decode_token_create));
+        from_block = to_block + 1;
+    }
+
+    Ok(launches)
+}
+
+/// Classify a creator Transfer from the other logs in its transaction
+async fn classify<M: Middleware + 'static>(provider: &Arc<M>, log: &Log, outgoing: bool, counterparty: Address) -> Option<DevActionKind> {
+    let receipt = provider.get_transaction_receipt(log.transaction_hash?).await.ok()??;
+    let has_topic = |topics: &[&str]| {
+        receipt.logs.iter().any(|l| {
+            l.topics.first().is_some_and(|t| topics.iter().any(|topic| H256::from_str(topic).ok() == Some(*t)))
+        })
+    };
+
+    if outgoing {
+        if has_topic(&[MINT_V2_TOPIC, MINT_V3_TOPIC]) {
+            Some(DevActionKind::AddLiquidity)
+        } else if counterparty == get_bonding_curve_address() || has_topic(&[SWAP_V2_TOPIC, SWAP_V3_TOPIC]) {
+            Some(DevActionKind::Sell)
+        } else {
+            Some(DevActionKind::Transfer)
+        }
+    } else if has_topic(&[BURN_V2_TOPIC, BURN_V3_TOPIC]) {
+        Some(DevActionKind::RemoveLiquidity)
+    } else {
+        // Buys and incoming transfers are not dev actions
+        None
+    }
+}
+
+/// Resolve the creator of `token_address` and report its sells, transfers and liquidity changes
+pub(crate) fn spawn_creator_watcher<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    callback: DevActionCallback,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let creator = match find_token_launch(provider.clone(), token_address, CREATOR_LOOKBACK_BLOCKS).await {
+            Ok(Some(launch)) => launch.creator,
+            Ok(None) => {
+                log::warn!("⚠️ [DEV_WATCH] No Four.meme launch found for {:?} - creator unknown", token_address);
+                return;
+            }
+            Err(e) => {
+                log::warn!("⚠️ [DEV_WATCH] Failed to resolve creator of {:?}: {}", token_address, e);
+                return;
+            }
+        };
+        log::info!("👤 [DEV_WATCH] Watching creator {:?} of {:?}", creator, token_address);
+
+        let decimals = TokenInfoCache::new(provider.clone())
+            .get_token_info(token_address)
+            .await
+            .map(|t| t.decimals)
+            .unwrap_or(18);
+
+        let transfer_topic = get_transfer_topic();
+        let creator_topic = H256::from(creator);
+        let outgoing_filter = Filter::new().address(token_address).topic0(transfer_topic).topic1(creator_topic);
+        let incoming_filter = Filter::new().address(token_address).topic0(transfer_topic).topic2(creator_topic);
+
+        let (mut outgoing, mut incoming) = match (
+            provider.subscribe_logs(&outgoing_filter).await,
+            provider.subscribe_logs(&incoming_filter).await,
+        ) {
+            (Ok(outgoing), Ok(incoming)) => (outgoing, incoming),
+            (Err(e), _) | (_, Err(e)) => {
+                log::error!("❌ [DEV_WATCH] Failed to subscribe to creator transfers: {}", e);
+                return;
+            }
+        };
+
+        loop {
+            let (log, is_outgoing) = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [DEV_WATCH] Creator watcher cancelled for {:?}", token_address);
+                    break;
+                }
+                Some(log) = outgoing.next() => (log, true),
+                Some(log) = incoming.next() => (log, false),
+                else => {
+                    log::warn!("⚠️ [DEV_WATCH] Creator transfer streams ended for {:?}", token_address);
+                    break;
+                }
+            };
+
+            if log.topics.len() < 3 || log.data.len() < 32 {
+                continue;
+            }
+            let counterparty = Address::from(if is_outgoing { log.topics[2] } else { log.topics[1] });
+            let amount_raw = U256::from_big_endian(&log.data[0..32]);
+
+            let Some(kind) = classify(&provider, &log, is_outgoing, counterparty).await else {
+                continue;
+            };
+
+            log::info!("👤 [DEV_WATCH] {} on {:?} - tx: {:?}", kind.as_str(), token_address, log.transaction_hash);
+            callback(DevActionEvent {
+                kind,
+                token_address,
+                creator,
+                amount: format_units(amount_raw, decimals as u32).unwrap_or_default(),
+                amount_raw,
+                counterparty,
+                transaction_hash: log.transaction_hash.unwrap_or_default(),
+                block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+            });
+        }
+    });
+}
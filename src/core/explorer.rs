@@ -0,0 +1,109 @@
+use anyhow::{anyhow, Result};
+use ethers::types::{Address, H256};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::types::ContractVerification;
+
+// Etherscan V2 multichain endpoint (the same key works for BscScan)
+pub const ETHERSCAN_V2_API: &str = "https://api.etherscan.io/v2/api";
+pub const BSC_CHAIN_ID: u64 = 56;
+
+/// Minimal BscScan / Etherscan V2 API client for contract due diligence
+#[derive(Clone)]
+pub struct BscScanClient {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    chain_id: u64,
+}
+
+impl BscScanClient {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: api_key.to_string(),
+            base_url: ETHERSCAN_V2_API.to_string(),
+            chain_id: BSC_CHAIN_ID,
+        }
+    }
+
+    /// Client from `BSCSCAN_API_KEY` (or `ETHERSCAN_API_KEY`), if set
+    pub fn from_env() -> Option<Self> {
+        std::env::var("BSCSCAN_API_KEY")
+            .or_else(|_| std::env::var("ETHERSCAN_API_KEY"))
+            .ok()
+            .filter(|key| !key.is_empty())
+            .map(|key| Self::new(&key))
+    }
+
+    /// Use a different API endpoint (e.g. a self-hosted proxy)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Verification status, creator and creation time of a contract
+    pub async fn contract_info(&self, address: Address) -> Result<ContractVerification> {
+        let address_str = format!("{:?}", address);
+
+        let source = self
+            .query(&[("module", "contract"), ("action", "getsourcecode"), ("address", &address_str)])
+            .await?;
+        let source = source.first().cloned().unwrap_or(Value::Null);
+        let source_code = source["SourceCode"].as_str().unwrap_or_default();
+        let non_empty = |v: &Value| v.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+
+        // Creation lookup fails for EOAs and some precompiles - treat as unknown
+        let creation = self
+            .query(&[("module", "contract"), ("action", "getcontractcreation"), ("contractaddresses", &address_str)])
+            .await
+            .ok()
+            .and_then(|r| r.first().cloned())
+            .unwrap_or(Value::Null);
+
+        let created_at = creation["timestamp"]
+            .as_str()
+            .and_then(|t| t.parse::<i64>().ok())
+            .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+            .map(|dt| dt.to_rfc3339());
+
+        Ok(ContractVerification {
+            address,
+            verified: !source_code.is_empty(),
+            contract_name: non_empty(&source["ContractName"]),
+            compiler_version: non_empty(&source["CompilerVersion"]),
+            is_proxy: source["Proxy"].as_str() == Some("1"),
+            creator: creation["contractCreator"].as_str().and_then(|a| a.parse().ok()),
+            creation_tx: creation["txHash"].as_str().and_then(|h| h.parse::<H256>().ok()),
+            created_at,
+        })
+    }
+
+    /// Run an API query and return its `result` array
+    async fn query(&self, params: &[(&str, &str)]) -> Result<Vec<Value>> {
+        let chain_id = self.chain_id.to_string();
+        let response: Value = self
+            .client
+            .get(&self.base_url)
+            .query(&[("chainid", chain_id.as_str()), ("apikey", self.api_key.as_str())])
+            .query(params)
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| anyhow!("BscScan request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse BscScan response: {}", e))?;
+
+        if response["status"].as_str() != Some("1") {
+            return Err(anyhow!(
+                "BscScan error: {} ({})",
+                response["message"].as_str().unwrap_or("unknown"),
+                response["result"].as_str().unwrap_or_default()
+            ));
+        }
+
+        Ok(response["result"].as_array().cloned().unwrap_or_default())
+    }
+}
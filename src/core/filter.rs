@@ -0,0 +1,86 @@
+use ethers::types::Address;
+
+use crate::types::{PairInfo, Platform, PoolVersion, SwapEvent, TradeThreshold, TradeType};
+
+/// Declarative swap filter applied before any callback sees a swap
+#[derive(Debug, Clone, Default)]
+pub struct SwapFilter {
+    pub min_trade: Option<TradeThreshold>,
+    pub trade_type: Option<TradeType>,
+    /// Only swaps on these pairs / bonding curves (empty = all)
+    pub pairs: Vec<Address>,
+    /// Only swaps by these wallets (empty = all)
+    pub allow_senders: Vec<Address>,
+    /// Never swaps by these wallets
+    pub deny_senders: Vec<Address>,
+}
+
+impl SwapFilter {
+    /// Whether a swap passes every configured filter
+    ///
+    /// Wallet lists are matched against the trader (tx.from) as well as the
+    /// swap's sender and recipient, since those are often router contracts.
+    pub fn matches(&self, swap: &SwapEvent) -> bool {
+        if self.min_trade.is_some_and(|threshold| !threshold.is_met(swap)) {
+            return false;
+        }
+
+        if self.trade_type.is_some_and(|t| t != swap.trade_type) {
+            return false;
+        }
+
+        if !self.pairs.is_empty() {
+            let pool = swap.pair_address.or(swap.bonding_curve_address);
+            if !pool.is_some_and(|p| self.pairs.contains(&p)) {
+                return false;
+            }
+        }
+
+        let wallets = [swap.trader, Some(swap.sender), Some(swap.recipient)];
+        let involves = |list: &[Address]| wallets.iter().flatten().any(|w| list.contains(w));
+
+        if !self.allow_senders.is_empty() && !involves(&self.allow_senders) {
+            return false;
+        }
+
+        !involves(&self.deny_senders)
+    }
+}
+
+/// Filter for the chain-wide swap firehose (`Firehose::on_swap`)
+///
+/// Empty lists accept everything. Pair-level criteria are checked before a
+/// swap is parsed, so narrow filters also save RPC calls.
+#[derive(Debug, Clone, Default)]
+pub struct FirehoseFilter {
+    pub min_trade: Option<TradeThreshold>,
+    pub trade_type: Option<TradeType>,
+    /// Only swaps priced in these base tokens (WBNB, USDT, ...)
+    pub base_tokens: Vec<Address>,
+    /// Only swaps on these platforms
+    pub platforms: Vec<Platform>,
+    /// Only DEX swaps on these pool versions (bonding curve trades are unaffected)
+    pub versions: Vec<PoolVersion>,
+}
+
+impl FirehoseFilter {
+    /// Whether swaps on `platform` are wanted at all
+    pub fn accepts_platform(&self, platform: Platform) -> bool {
+        self.platforms.is_empty() || self.platforms.contains(&platform)
+    }
+
+    /// Whether swaps on a DEX pair can match, before parsing them
+    pub fn accepts_pair(&self, pair: &PairInfo) -> bool {
+        (self.versions.is_empty() || self.versions.contains(&pair.version()))
+            && (self.base_tokens.is_empty() || self.base_tokens.contains(&pair.base_token))
+    }
+
+    /// Whether a parsed swap passes every configured filter
+    pub fn matches(&self, swap: &SwapEvent) -> bool {
+        self.accepts_platform(swap.platform)
+            && self.min_trade.is_none_or(|threshold| threshold.is_met(swap))
+            && self.trade_type.is_none_or(|t| t == swap.trade_type)
+            && (self.base_tokens.is_empty() || self.base_tokens.contains(&swap.base_token.address))
+            && (self.versions.is_empty() || swap.pool.as_ref().is_none_or(|pool| self.versions.contains(&pool.version)))
+    }
+}
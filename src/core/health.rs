@@ -0,0 +1,154 @@
+use anyhow::{anyhow, Result};
+use ethers::providers::Middleware;
+use futures::future::BoxFuture;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::core::latency::LatencyTracker;
+use crate::types::{HealthReport, ProviderHealth, SubscriptionHealth, SubscriptionState};
+
+// Round-trips slower than this count as a disconnected provider
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(5);
+
+type BlockNumberProbe = Arc<dyn Fn() -> BoxFuture<'static, Result<u64>> + Send + Sync>;
+
+struct Subscription {
+    name: String,
+    state: SubscriptionState,
+    events: u64,
+    last_event: Option<Instant>,
+    error: Option<String>,
+}
+
+/// Provider connectivity, liveness of a streamer's swap/migration subscriptions and swap latency
+#[derive(Clone)]
+pub struct HealthMonitor {
+    block_number: BlockNumberProbe,
+    started_at: Instant,
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+    latency: LatencyTracker,
+}
+
+impl HealthMonitor {
+    pub fn new<M: Middleware + 'static>(provider: Arc<M>) -> Self {
+        let block_number: BlockNumberProbe = Arc::new(move || {
+            let provider = provider.clone();
+            Box::pin(async move {
+                let block = provider.get_block_number().await.map_err(|e| anyhow!("{}", e))?;
+                Ok(block.as_u64())
+            })
+        });
+        Self {
+            block_number,
+            started_at: Instant::now(),
+            subscriptions: Arc::new(Mutex::new(Vec::new())),
+            latency: LatencyTracker::new(),
+        }
+    }
+
+    /// Where delivered swaps record their latency
+    pub fn latency(&self) -> LatencyTracker {
+        self.latency.clone()
+    }
+
+    /// Register a subscription in the `Connecting` state
+    pub(crate) fn subscription(&self, name: String) -> SubscriptionProbe {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.push(Subscription {
+            name,
+            state: SubscriptionState::Connecting,
+            events: 0,
+            last_event: None,
+            error: None,
+        });
+        SubscriptionProbe {
+            subscriptions: self.subscriptions.clone(),
+            index: subscriptions.len() - 1,
+        }
+    }
+
+    /// Query the provider and collect the state of every subscription
+    pub async fn report(&self, stopped: bool) -> HealthReport {
+        let started = Instant::now();
+        let provider = match tokio::time::timeout(PROVIDER_TIMEOUT, (self.block_number)()).await {
+            Ok(Ok(block_number)) => ProviderHealth {
+                connected: true,
+                block_number: Some(block_number),
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                error: None,
+            },
+            Ok(Err(e)) => ProviderHealth {
+                connected: false,
+                block_number: None,
+                latency_ms: None,
+                error: Some(e.to_string()),
+            },
+            Err(_) => ProviderHealth {
+                connected: false,
+                block_number: None,
+                latency_ms: None,
+                error: Some(format!("No response within {}s", PROVIDER_TIMEOUT.as_secs())),
+            },
+        };
+
+        let subscriptions = self.subscriptions.lock().unwrap();
+        let last_event = subscriptions.iter().filter_map(|s| s.last_event).max();
+        HealthReport {
+            provider,
+            subscriptions: subscriptions
+                .iter()
+                .map(|s| SubscriptionHealth {
+                    name: s.name.clone(),
+                    state: s.state,
+                    events: s.events,
+                    last_event_age_secs: s.last_event.map(|t| t.elapsed().as_secs_f64()),
+                    error: s.error.clone(),
+                })
+                .collect(),
+            last_event_age_secs: last_event.map(|t| t.elapsed().as_secs_f64()),
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            stopped,
+            latency: self.latency.stats(),
+        }
+    }
+}
+
+/// Reports the lifecycle of one subscription to its `HealthMonitor`
+pub(crate) struct SubscriptionProbe {
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+    index: usize,
+}
+
+impl SubscriptionProbe {
+    fn update(&self, f: impl FnOnce(&mut Subscription)) {
+        if let Some(subscription) = self.subscriptions.lock().unwrap().get_mut(self.index) {
+            f(subscription);
+        }
+    }
+
+    pub(crate) fn live(&self) {
+        self.update(|s| s.state = SubscriptionState::Live);
+    }
+
+    pub(crate) fn event(&self) {
+        self.update(|s| {
+            s.events += 1;
+            s.last_event = Some(Instant::now());
+        });
+    }
+
+    pub(crate) fn stopped(&self) {
+        self.update(|s| s.state = SubscriptionState::Stopped);
+    }
+
+    pub(crate) fn ended(&self) {
+        self.update(|s| s.state = SubscriptionState::Ended);
+    }
+
+    pub(crate) fn failed(&self, error: impl ToString) {
+        self.update(|s| {
+            s.state = SubscriptionState::Failed;
+            s.error = Some(error.to_string());
+        });
+    }
+}
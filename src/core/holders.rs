@@ -0,0 +1,281 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{Address, Filter, Log, U256},
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{get_burn_addresses, get_transfer_topic};
+use crate::core::options::HolderStatsCallback;
+use crate::types::{HolderBalance, HolderSnapshot, HolderStats, HolderStatsConfig};
+
+// Max block range per eth_getLogs request when replaying history
+const HISTORY_CHUNK_BLOCKS: u64 = 5_000;
+
+// Transfer history scanned to find holder candidates for a snapshot
+pub const SNAPSHOT_LOOKBACK_BLOCKS: u64 = 100_000;
+
+// Candidates re-checked with balanceOf per requested top holder
+const SNAPSHOT_CANDIDATE_FACTOR: usize = 3;
+
+const ERC20_SUPPLY_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"totalSupply","outputs":[{"name":"","type":"uint256"}],"type":"function"},
+    {"constant":true,"inputs":[{"name":"account","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"type":"function"}
+]"#;
+
+#[derive(Default)]
+struct HolderState {
+    /// Only positive balances are kept
+    balances: HashMap<Address, U256>,
+    /// When live transfers created a new holder, oldest first
+    new_holders: VecDeque<Instant>,
+    transfers_seen: u64,
+}
+
+/// Approximate holder set for one token, maintained from Transfer events
+#[derive(Clone)]
+pub struct HolderTracker {
+    state: Arc<RwLock<HolderState>>,
+    excluded: Arc<Vec<Address>>,
+}
+
+impl Default for HolderTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HolderTracker {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::default(),
+            excluded: Arc::new(get_burn_addresses()),
+        }
+    }
+
+    /// Number of addresses currently holding the token
+    pub async fn holder_count(&self) -> usize {
+        self.state.read().await.balances.len()
+    }
+
+    /// Current stats, with the new-holder rate measured over `rate_window`
+    pub async fn stats(&self, token_address: Address, rate_window: std::time::Duration) -> HolderStats {
+        let mut state = self.state.write().await;
+        while state.new_holders.front().is_some_and(|t| t.elapsed() > rate_window) {
+            state.new_holders.pop_front();
+        }
+
+        let new_holders = state.new_holders.len();
+        let minutes = rate_window.as_secs_f64() / 60.0;
+
+        HolderStats {
+            token_address,
+            holder_count: state.balances.len(),
+            new_holders,
+            new_holders_per_min: if minutes > 0.0 { new_holders as f64 / minutes } else { 0.0 },
+            transfers_seen: state.transfers_seen,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Largest reconstructed balances, descending
+    pub async fn top_balances(&self, n: usize) -> Vec<(Address, U256)> {
+        let state = self.state.read().await;
+        let mut balances: Vec<(Address, U256)> = state.balances.iter().map(|(a, b)| (*a, *b)).collect();
+        balances.sort_by_key(|(_, balance)| std::cmp::Reverse(*balance));
+        balances.truncate(n);
+        balances
+    }
+
+    /// Apply a Transfer log; `live` transfers count towards the new-holder rate
+    pub(crate) async fn apply_transfer(&self, log: &Log, live: bool) -> Result<()> {
+        if log.topics.len() < 3 || log.data.len() < 32 {
+            return Err(anyhow!("Malformed Transfer log"));
+        }
+        let from = Address::from(log.topics[1]);
+        let to = Address::from(log.topics[2]);
+        let amount = U256::from_big_endian(&log.data[0..32]);
+
+        let mut state = self.state.write().await;
+        state.transfers_seen += 1;
+
+        if amount.is_zero() || from == to {
+            return Ok(());
+        }
+
+        // Senders holding from before the replayed window have unknown balances - clamp at zero
+        if let Some(balance) = state.balances.get_mut(&from) {
+            *balance = balance.saturating_sub(amount);
+            if balance.is_zero() {
+                state.balances.remove(&from);
+            }
+        }
+
+        if !to.is_zero() && !self.excluded.contains(&to) {
+            let balance = state.balances.entry(to).or_insert_with(U256::zero);
+            let is_new = balance.is_zero();
+            *balance = balance.saturating_add(amount);
+            if is_new && live {
+                state.new_holders.push_back(Instant::now());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replay recent Transfer history to seed balances, returning the last replayed block
+    pub(crate) async fn seed<M: Middleware + 'static>(
+        &self,
+        provider: Arc<M>,
+        token_address: Address,
+        lookback_blocks: u64,
+    ) -> Result<u64> {
+        let latest = provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64();
+        let topic = get_transfer_topic();
+
+        let mut from_block = latest.saturating_sub(lookback_blocks);
+        while from_block <= latest {
+            let to_block = (from_block + HISTORY_CHUNK_BLOCKS - 1).min(latest);
+            let filter = Filter::new()
+                .address(token_address)
+                .topic0(topic)
+                .from_block(from_block)
+                .to_block(to_block);
+            let logs = provider
+                .get_logs(&filter)
+                .await
+                .map_err(|e| anyhow!("Failed to fetch Transfer logs {}..{}: {}", from_block, to_block, e))?;
+            for log in &logs {
+                let _ = self.apply_transfer(log, false).await;
+            }
+            from_block = to_block + 1;
+        }
+
+        log::debug!("👥 [HOLDERS] Seeded {} holders for {:?} from {} blocks of history",
+            self.holder_count().await, token_address, lookback_blocks);
+        Ok(latest)
+    }
+}
+
+/// Share held by the top `top_n` wallets, excluding `excluded` addresses
+///
+/// Candidates come from replayed Transfer history; their balances are then
+/// re-read with balanceOf so holdings from before the window are counted.
+pub(crate) async fn snapshot<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: Address,
+    top_n: usize,
+    mut excluded: Vec<Address>,
+) -> Result<HolderSnapshot> {
+    excluded.extend(get_burn_addresses());
+
+    let tracker = HolderTracker::new();
+    let block_number = tracker.seed(provider.clone(), token_address, SNAPSHOT_LOOKBACK_BLOCKS).await?;
+
+    let abi: Abi = serde_json::from_str(ERC20_SUPPLY_ABI)?;
+    let contract = Contract::new(token_address, abi, provider);
+    let total_supply: U256 = contract.method("totalSupply", ())?.call().await?;
+    let pct = |amount: U256| -> f64 {
+        if total_supply.is_zero() {
+            0.0
+        } else {
+            (amount * U256::from(10_000) / total_supply).as_u64() as f64 / 100.0
+        }
+    };
+
+    let candidates = tracker.top_balances((top_n + excluded.len()) * SNAPSHOT_CANDIDATE_FACTOR).await;
+    let mut top_holders = Vec::new();
+    for (address, _) in candidates.into_iter().filter(|(a, _)| !excluded.contains(a)) {
+        let balance: U256 = contract.method("balanceOf", address)?.call().await?;
+        if !balance.is_zero() {
+            top_holders.push(HolderBalance { address, balance, pct: pct(balance) });
+        }
+    }
+    top_holders.sort_by_key(|h| std::cmp::Reverse(h.balance));
+    top_holders.truncate(top_n);
+
+    let top_total = top_holders.iter().fold(U256::zero(), |acc, h| acc + h.balance);
+
+    Ok(HolderSnapshot {
+        token_address,
+        block_number,
+        total_supply,
+        top_holders_pct: pct(top_total),
+        top_holders,
+        excluded,
+    })
+}
+
+/// Track holders of `token_address` and report stats on `config.interval`
+pub(crate) fn spawn_holder_tracker<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    config: HolderStatsConfig,
+    callback: HolderStatsCallback,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let tracker = HolderTracker::new();
+
+        let filter = Filter::new()
+            .address(token_address)
+            .topic0(get_transfer_topic());
+        // Subscribe before replaying history so no transfer falls in between
+        let mut stream = match provider.subscribe_logs(&filter).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [HOLDERS] Failed to subscribe to Transfer events for {:?}: {}", token_address, e);
+                return;
+            }
+        };
+
+        let replayed_to = match tracker.seed(provider.clone(), token_address, config.lookback_blocks).await {
+            Ok(block) => block,
+            Err(e) => {
+                log::warn!("⚠️ [HOLDERS] Failed to replay Transfer history for {:?}: {}", token_address, e);
+                0
+            }
+        };
+
+        let mut ticker = tokio::time::interval(config.interval);
+        ticker.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [HOLDERS] Holder tracking cancelled for {:?}", token_address);
+                    break;
+                }
+                _ = ticker.tick() => {
+                    callback(tracker.stats(token_address, config.rate_window).await);
+                }
+                log_option = stream.next() => {
+                    let Some(log) = log_option else {
+                        log::warn!("⚠️ [HOLDERS] Transfer stream ended for {:?}", token_address);
+                        break;
+                    };
+                    // Already applied during the history replay
+                    if log.block_number.is_some_and(|b| b.as_u64() <= replayed_to) {
+                        continue;
+                    }
+                    if let Err(e) = tracker.apply_transfer(&log, true).await {
+                        log::debug!("⚠️ [HOLDERS] Skipping Transfer {:?}: {}", log.transaction_hash, e);
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,149 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    providers::Middleware,
+    types::{Filter, H256, I256, U256},
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::core::simulation::{RoundTrip, RouterSimulator, ROUND_TRIP_BNB};
+use crate::core::streamer::{SWAP_V2_TOPIC, SWAP_V3_TOPIC};
+use crate::types::{HoneypotReport, PairInfo};
+
+// Recent pair history inspected for real buys/sells
+const HISTORY_BLOCKS: u64 = 2_000;
+
+// Buys seen without a single sell before the pair is flagged (only when the round trip can't be simulated)
+const MIN_BUYS_WITHOUT_SELLS: usize = 10;
+
+// Share of the BNB lost buying and selling straight back before the token is flagged
+const MAX_ROUND_TRIP_LOSS_PCT: f64 = 50.0;
+
+/// Checks whether a token can actually be sold into its pool
+pub struct HoneypotChecker<M> {
+    provider: Arc<M>,
+}
+
+impl<M: Middleware + 'static> HoneypotChecker<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self { provider }
+    }
+
+    /// Simulate a buy and an immediate sell through the PancakeSwap V2 router and
+    /// inspect recent trades on the pool
+    ///
+    /// The round trip runs in a single eth_call with state overrides, so it requires an
+    /// RPC node that supports them. The trade history only decides when the round trip
+    /// could not be simulated (V3 pools, no override support); otherwise it is reported
+    /// as supporting evidence.
+    pub async fn check(&self, pair_info: &PairInfo) -> Result<HoneypotReport> {
+        // The V2 router can't trade through V3 pools
+        let round_trip = if pair_info.is_v3 {
+            None
+        } else {
+            RouterSimulator::new(self.provider.clone())
+                .round_trip(pair_info.token, pair_info.base_token, U256::from(ROUND_TRIP_BNB))
+                .await
+                .map_err(|e| log::debug!("⚠️ [HONEYPOT] Round trip not simulated for {:?}: {}", pair_info.token, e))
+                .ok()
+        };
+
+        let (recent_buys, recent_sells) = self.recent_trades(pair_info).await.unwrap_or_else(|e| {
+            log::debug!("⚠️ [HONEYPOT] Could not read trade history for {:?}: {}", pair_info.pair_address, e);
+            (0, 0)
+        });
+
+        let buy_simulated = round_trip.as_ref().is_some_and(|trip| trip.buy_succeeded);
+        // Whether the tokens can be sold is only known once a buy went through
+        let sell_simulated = round_trip
+            .as_ref()
+            .filter(|trip| trip.buy_succeeded)
+            .map(|trip| trip.sell_succeeded);
+        let round_trip_loss_pct = round_trip.as_ref().and_then(RoundTrip::loss_pct);
+        let no_sells = recent_sells == 0 && recent_buys >= MIN_BUYS_WITHOUT_SELLS;
+
+        let mut reason = match (&round_trip, sell_simulated) {
+            (Some(trip), Some(false)) if trip.tokens_received.is_zero() => {
+                Some("Simulated buy delivered no tokens".to_string())
+            }
+            (_, Some(false)) => Some("Simulated sell through the router reverted".to_string()),
+            _ => match round_trip_loss_pct {
+                Some(loss) if loss >= MAX_ROUND_TRIP_LOSS_PCT => {
+                    Some(format!("Simulated round trip lost {:.1}% of the BNB spent", loss))
+                }
+                _ if sell_simulated.is_none() && no_sells => {
+                    Some(format!("{} recent buys and no sells (round trip not simulated)", recent_buys))
+                }
+                _ => None,
+            },
+        };
+        if let Some(reason) = reason.as_mut().filter(|_| no_sells && sell_simulated.is_some()) {
+            reason.push_str(&format!("; {} recent buys and no sells", recent_buys));
+        }
+
+        Ok(HoneypotReport {
+            token_address: pair_info.token,
+            pair_address: pair_info.pair_address,
+            is_honeypot: reason.is_some(),
+            reason,
+            buy_simulated,
+            sell_simulated,
+            round_trip_loss_pct,
+            recent_buys,
+            recent_sells,
+        })
+    }
+
+    /// Count (buys, sells) over the pool's recent Swap events
+    async fn recent_trades(&self, pair_info: &PairInfo) -> Result<(usize, usize)> {
+        let latest = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64();
+        let topic = H256::from_str(if pair_info.is_v3 { SWAP_V3_TOPIC } else { SWAP_V2_TOPIC })?;
+        let filter = Filter::new()
+            .address(pair_info.pair_address)
+            .topic0(topic)
+            .from_block(latest.saturating_sub(HISTORY_BLOCKS))
+            .to_block(latest);
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch Swap logs: {}", e))?;
+
+        // Pools order tokens by address
+        let token_is_token0 = pair_info.token < pair_info.base_token;
+        let word = |data: &[u8], i: usize| data.get(i * 32..(i + 1) * 32).map(U256::from_big_endian);
+
+        let mut buys = 0;
+        let mut sells = 0;
+        for log in &logs {
+            let is_sell = if pair_info.is_v3 {
+                // amount0/amount1 are signed; positive = paid into the pool
+                let index = if token_is_token0 { 0 } else { 1 };
+                match word(&log.data, index) {
+                    Some(raw) => I256::from_raw(raw).is_positive(),
+                    None => continue,
+                }
+            } else {
+                // amount0In, amount1In, amount0Out, amount1Out
+                let index = if token_is_token0 { 0 } else { 1 };
+                match word(&log.data, index) {
+                    Some(amount_in) => !amount_in.is_zero(),
+                    None => continue,
+                }
+            };
+
+            if is_sell {
+                sells += 1;
+            } else {
+                buys += 1;
+            }
+        }
+
+        Ok((buys, sells))
+    }
+}
@@ -0,0 +1,92 @@
+use ethers::types::Address;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crate::config::{get_burn_addresses, get_known_routers, get_lp_lockers, ADDRESS_LABELS};
+use crate::types::{AddressLabel, LabelKind, SwapEvent};
+
+/// Address -> label lookup, seeded with the curated BSC dataset
+///
+/// Clones share the same table, so labels added at runtime (e.g. through
+/// `StreamerHandle::labels()`) apply to a running streamer immediately.
+#[derive(Clone)]
+pub struct LabelRegistry {
+    labels: Arc<RwLock<HashMap<Address, AddressLabel>>>,
+}
+
+impl Default for LabelRegistry {
+    fn default() -> Self {
+        let mut labels = HashMap::new();
+
+        for (address, name, kind) in ADDRESS_LABELS {
+            labels.insert(Address::from_str(address).unwrap(), AddressLabel { name: name.to_string(), kind: *kind });
+        }
+        for (address, router) in get_known_routers() {
+            let kind = if router.is_aggregator() { LabelKind::Aggregator } else { LabelKind::Router };
+            labels.insert(address, AddressLabel { name: router.as_str().to_string(), kind });
+        }
+        for (address, name) in get_lp_lockers() {
+            labels.insert(address, AddressLabel { name: format!("{} LP Locker", name), kind: LabelKind::Protocol });
+        }
+        for address in get_burn_addresses() {
+            labels.insert(address, AddressLabel { name: "Burn Address".to_string(), kind: LabelKind::Burn });
+        }
+
+        Self {
+            labels: Arc::new(RwLock::new(labels)),
+        }
+    }
+}
+
+impl LabelRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with no labels at all
+    pub fn empty() -> Self {
+        Self {
+            labels: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Add or replace a label
+    pub fn insert(&self, address: Address, name: &str, kind: LabelKind) {
+        self.labels
+            .write()
+            .unwrap()
+            .insert(address, AddressLabel { name: name.to_string(), kind });
+    }
+
+    pub fn remove(&self, address: Address) -> Option<AddressLabel> {
+        self.labels.write().unwrap().remove(&address)
+    }
+
+    pub fn get(&self, address: Address) -> Option<AddressLabel> {
+        self.labels.read().unwrap().get(&address).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fill the sender/recipient/trader labels of a swap
+    pub fn label_swap(&self, swap: &mut SwapEvent) {
+        let labels = self.labels.read().unwrap();
+        swap.sender_label = labels.get(&swap.sender).cloned();
+        swap.recipient_label = labels.get(&swap.recipient).cloned();
+        swap.trader_label = swap.trader.and_then(|t| labels.get(&t).cloned());
+    }
+}
+
+impl fmt::Debug for LabelRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LabelRegistry").field("labels", &self.len()).finish()
+    }
+}
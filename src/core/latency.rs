@@ -0,0 +1,96 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::types::{EventLatency, LatencyStats, LatencySummary, SwapEvent};
+
+// Live swaps kept for the latency summaries
+const LATENCY_WINDOW: usize = 1_000;
+
+// (block -> receive, receive -> dispatch, block -> dispatch) of one swap
+type Sample = (Option<i64>, i64, Option<i64>);
+
+/// Unix time in milliseconds
+pub(crate) fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Block timestamp of a swap in unix milliseconds
+fn block_ms(swap: &SwapEvent) -> Option<i64> {
+    let timestamp = chrono::DateTime::parse_from_rfc3339(swap.timestamp.as_deref()?).ok()?;
+    Some(timestamp.timestamp_millis())
+}
+
+/// Stamp a parsed live swap with the time its log arrived
+pub(crate) fn mark_received(swap: &mut SwapEvent, received_at_ms: u64) {
+    swap.latency = Some(EventLatency {
+        received_at_ms,
+        dispatched_at_ms: None,
+        block_to_receive_ms: block_ms(swap).map(|block| received_at_ms as i64 - block),
+        receive_to_dispatch_ms: None,
+        block_to_dispatch_ms: None,
+    });
+}
+
+/// Latencies of the most recent live swaps, for `HealthReport::latency`
+#[derive(Clone, Default)]
+pub struct LatencyTracker {
+    samples: Arc<Mutex<VecDeque<Sample>>>,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stamp a swap as handed to the callbacks now and record its latencies
+    ///
+    /// Swaps that were not received live (replayed history) are left alone.
+    pub(crate) fn mark_dispatched(&self, swap: &mut SwapEvent) {
+        let block = block_ms(swap);
+        let Some(ref mut latency) = swap.latency else {
+            return;
+        };
+        let now = now_ms();
+        latency.dispatched_at_ms = Some(now);
+        latency.receive_to_dispatch_ms = Some(now.saturating_sub(latency.received_at_ms));
+        latency.block_to_dispatch_ms = block.map(|block| now as i64 - block);
+
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() >= LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back((
+            latency.block_to_receive_ms,
+            latency.receive_to_dispatch_ms.unwrap_or_default() as i64,
+            latency.block_to_dispatch_ms,
+        ));
+    }
+
+    /// Summaries over the recorded swaps (None before the first one)
+    pub fn stats(&self) -> Option<LatencyStats> {
+        let samples = self.samples.lock().unwrap();
+        if samples.is_empty() {
+            return None;
+        }
+        Some(LatencyStats {
+            samples: samples.len(),
+            block_to_receive: summarize(samples.iter().filter_map(|s| s.0).collect()),
+            receive_to_dispatch: summarize(samples.iter().map(|s| s.1).collect()),
+            block_to_dispatch: summarize(samples.iter().filter_map(|s| s.2).collect()),
+        })
+    }
+}
+
+fn summarize(mut values: Vec<i64>) -> Option<LatencySummary> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let percentile = |p: f64| values[((values.len() - 1) as f64 * p).round() as usize];
+    Some(LatencySummary {
+        mean_ms: values.iter().sum::<i64>() as f64 / values.len() as f64,
+        p50_ms: percentile(0.5),
+        p95_ms: percentile(0.95),
+        max_ms: values[values.len() - 1],
+    })
+}
@@ -0,0 +1,247 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::Middleware,
+    types::{Address, Log, H256, I256, U256},
+    utils::format_units,
+};
+use std::sync::Arc;
+
+use crate::core::price_oracle::PriceOracle;
+use crate::core::swap_parser::SwapParser;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{DrainAlertConfig, DrainReason, LiquidityEvent, LiquidityEventKind, PairInfo, TokenInfo};
+
+// V2: Mint(address indexed sender, uint amount0, uint amount1)
+pub const MINT_V2_TOPIC: &str = "0x4c209b5fc8ad50758f13e2e1088ba56a560dff690a1c6fef26394f4c03821c4f";
+// V2: Burn(address indexed sender, uint amount0, uint amount1, address indexed to)
+pub const BURN_V2_TOPIC: &str = "0xdccd412f0b1252819cb1fd330b93224ca42612892bb3f4f789976e6d81936496";
+// V3: Mint(address sender, address indexed owner, int24 indexed tickLower, int24 indexed tickUpper, uint128 amount, uint256 amount0, uint256 amount1)
+pub const MINT_V3_TOPIC: &str = "0x7a53080ba414158be7ec69b987b5fb7d07dee101fe85488f0853ae16239d0bde";
+// V3: Burn(address indexed owner, int24 indexed tickLower, int24 indexed tickUpper, uint128 amount, uint256 amount0, uint256 amount1)
+pub const BURN_V3_TOPIC: &str = "0x0c396cd989a39f4459b5fa1aed6a9a8dcdbc45908acfd67e028cd568da98982c";
+// V3: Collect(address indexed owner, address recipient, int24 indexed tickLower, int24 indexed tickUpper, uint128 amount0, uint128 amount1)
+pub const COLLECT_V3_TOPIC: &str = "0x70935338e69775456a85ddef226c395fb668b63fa0115f5f20610b388e6ca9c0";
+
+const ERC20_BALANCE_ABI: &str = r#"[
+    {"constant":true,"inputs":[{"name":"account","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"type":"function"}
+]"#;
+
+/// Liquidity event topics for a pool version
+pub fn liquidity_topics(is_v3: bool) -> Vec<H256> {
+    let topics: &[&str] = if is_v3 {
+        &[MINT_V3_TOPIC, BURN_V3_TOPIC, COLLECT_V3_TOPIC]
+    } else {
+        &[MINT_V2_TOPIC, BURN_V2_TOPIC]
+    };
+    topics.iter().map(|t| t.parse().unwrap()).collect()
+}
+
+/// Balance of `token` held by a pool, in whole tokens
+pub async fn pool_balance<M: Middleware + 'static>(provider: &Arc<M>, token_cache: &TokenInfoCache<M>, pool: Address, token: Address) -> Option<f64> {
+    let abi: Abi = serde_json::from_str(ERC20_BALANCE_ABI).ok()?;
+    let contract = Contract::new(token, abi, provider.clone());
+    let raw: U256 = contract.method("balanceOf", pool).ok()?.call().await.ok()?;
+    let decimals = token_cache.get_token_info(token).await.ok()?.decimals;
+    format_units(raw, decimals as u32).ok()?.parse().ok()
+}
+
+/// USD value of a pool from its base-token balance (both sides, so twice the base side)
+///
+/// V3 balances cover every position, in and out of range. None when the base
+/// token has no USD price or the balance cannot be read.
+pub async fn pool_liquidity_usd<M: Middleware + 'static>(
+    provider: &Arc<M>,
+    price_oracle: &PriceOracle<M>,
+    token_cache: &TokenInfoCache<M>,
+    pool: Address,
+    base_token: Address,
+) -> Option<f64> {
+    let balance = pool_balance(provider, token_cache, pool, base_token).await?;
+    Some(balance * price_oracle.usd_price(base_token).await? * 2.0)
+}
+
+/// Check a liquidity event against the drain thresholds
+///
+/// Returns the reason and the share of the base reserve removed, for Burns only.
+pub fn check_drain(config: &DrainAlertConfig, event: &LiquidityEvent) -> Option<(DrainReason, f64)> {
+    if event.kind != LiquidityEventKind::Burn {
+        return None;
+    }
+
+    let removed: f64 = event.base_token.amount.parse().ok()?;
+    let remaining = event.base_reserve?;
+    let before = remaining + removed;
+    let removed_pct = if before > 0.0 { removed / before * 100.0 } else { 0.0 };
+
+    if removed_pct > config.max_removal_pct {
+        Some((DrainReason::LargeRemoval, removed_pct))
+    } else if config.min_base_reserve.is_some_and(|floor| remaining < floor) {
+        Some((DrainReason::BelowFloor, removed_pct))
+    } else {
+        None
+    }
+}
+
+/// 32-byte data word `index` as U256
+fn word(log: &Log, index: usize) -> Result<U256> {
+    let start = index * 32;
+    log.data
+        .get(start..start + 32)
+        .map(U256::from_big_endian)
+        .ok_or_else(|| anyhow!("Event data too short: {} bytes", log.data.len()))
+}
+
+/// Indexed topic `index` as an address
+fn topic_address(log: &Log, index: usize) -> Result<Address> {
+    log.topics
+        .get(index)
+        .map(|t| Address::from(*t))
+        .ok_or_else(|| anyhow!("Missing topic {}", index))
+}
+
+/// Indexed int24 topic `index`
+fn topic_tick(log: &Log, index: usize) -> Result<i32> {
+    log.topics
+        .get(index)
+        .map(|t| I256::from_raw(U256::from_big_endian(t.as_bytes())).as_i32())
+        .ok_or_else(|| anyhow!("Missing topic {}", index))
+}
+
+impl<M: Middleware + 'static> SwapParser<M> {
+    /// Parse a Mint/Burn/Collect log from a monitored pool
+    ///
+    /// V3 events carry the pool's post-change token balances; for V2 pairs the
+    /// caller fills reserves from Sync-tracked state.
+    pub async fn parse_liquidity_event(&self, log: &Log, pair_info: &PairInfo) -> Result<LiquidityEvent> {
+        let topic0 = *log.topics.first().ok_or_else(|| anyhow!("Log has no topics"))?;
+        let topic = format!("{:#x}", topic0);
+
+        // (kind, owner, recipient, tick_lower, tick_upper, liquidity, amount0, amount1)
+        let (kind, owner, recipient, tick_lower, tick_upper, liquidity, amount0, amount1) = match topic.as_str() {
+            MINT_V2_TOPIC => (
+                LiquidityEventKind::Mint,
+                topic_address(log, 1)?,
+                None,
+                None,
+                None,
+                None,
+                word(log, 0)?,
+                word(log, 1)?,
+            ),
+            BURN_V2_TOPIC => (
+                LiquidityEventKind::Burn,
+                topic_address(log, 1)?,
+                Some(topic_address(log, 2)?),
+                None,
+                None,
+                None,
+                word(log, 0)?,
+                word(log, 1)?,
+            ),
+            MINT_V3_TOPIC => (
+                LiquidityEventKind::Mint,
+                topic_address(log, 1)?,
+                None,
+                Some(topic_tick(log, 2)?),
+                Some(topic_tick(log, 3)?),
+                Some(word(log, 1)?.as_u128()),
+                word(log, 2)?,
+                word(log, 3)?,
+            ),
+            BURN_V3_TOPIC => (
+                LiquidityEventKind::Burn,
+                topic_address(log, 1)?,
+                None,
+                Some(topic_tick(log, 2)?),
+                Some(topic_tick(log, 3)?),
+                Some(word(log, 0)?.as_u128()),
+                word(log, 1)?,
+                word(log, 2)?,
+            ),
+            COLLECT_V3_TOPIC => {
+                let recipient_word = word(log, 0)?;
+                let mut bytes = [0u8; 32];
+                recipient_word.to_big_endian(&mut bytes);
+                (
+                    LiquidityEventKind::Collect,
+                    topic_address(log, 1)?,
+                    Some(Address::from_slice(&bytes[12..])),
+                    Some(topic_tick(log, 2)?),
+                    Some(topic_tick(log, 3)?),
+                    None,
+                    word(log, 1)?,
+                    word(log, 2)?,
+                )
+            }
+            _ => return Err(anyhow!("Not a liquidity event: {}", topic)),
+        };
+
+        let abi: Abi = serde_json::from_str(ERC20_BALANCE_ABI)?;
+        let contract = Contract::new(pair_info.pair_address, abi.clone(), self.provider.clone());
+        let (token0, token1) = self.get_pair_tokens(&contract).await?;
+        let token0_info = self.token_cache.get_token_info(token0).await?;
+        let token1_info = self.token_cache.get_token_info(token1).await?;
+
+        let is_token0_target = token0 == pair_info.token;
+        let (token_amount, base_amount, token_meta, base_meta) = if is_token0_target {
+            (amount0, amount1, token0_info, token1_info)
+        } else {
+            (amount1, amount0, token1_info, token0_info)
+        };
+
+        // V3 pools have no reserves - use their token balances instead
+        let (token_reserve, base_reserve) = if pair_info.is_v3 {
+            let balance = |token: Address| {
+                let contract = Contract::new(token, abi.clone(), self.provider.clone());
+                async move {
+                    let raw: U256 = contract.method("balanceOf", pair_info.pair_address)?.call().await?;
+                    Ok::<U256, anyhow::Error>(raw)
+                }
+            };
+            let token_balance = balance(pair_info.token).await.ok();
+            let base_balance = balance(pair_info.base_token).await.ok();
+            (
+                token_balance.and_then(|b| format_units(b, token_meta.decimals as u32).ok()?.parse().ok()),
+                base_balance.and_then(|b| format_units(b, base_meta.decimals as u32).ok()?.parse().ok()),
+            )
+        } else {
+            (None, None)
+        };
+
+        let block_number = log.block_number.ok_or_else(|| anyhow!("Log has no block number"))?;
+        let timestamp = self.get_block_timestamp(block_number).await?;
+
+        Ok(LiquidityEvent {
+            transaction_hash: log.transaction_hash.ok_or_else(|| anyhow!("Log has no transaction hash"))?,
+            block_number: block_number.as_u64(),
+            log_index: log.log_index.map(|i| i.as_u64()).unwrap_or_default(),
+            timestamp,
+            kind,
+            pair_address: pair_info.pair_address,
+            pool: pair_info.pool_metadata(),
+            token: TokenInfo {
+                address: pair_info.token,
+                symbol: token_meta.symbol,
+                amount: format_units(token_amount, token_meta.decimals as u32)?,
+                amount_raw: token_amount,
+                decimals: token_meta.decimals,
+            },
+            base_token: TokenInfo {
+                address: pair_info.base_token,
+                symbol: pair_info.base_token_symbol.clone(),
+                amount: format_units(base_amount, base_meta.decimals as u32)?,
+                amount_raw: base_amount,
+                decimals: base_meta.decimals,
+            },
+            owner,
+            recipient,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            token_reserve,
+            base_reserve,
+        })
+    }
+}
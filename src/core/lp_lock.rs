@@ -0,0 +1,84 @@
+use anyhow::Result;
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::Middleware,
+    types::{Address, U256},
+};
+use std::sync::Arc;
+
+use crate::config::{get_burn_addresses, get_lp_lockers};
+use crate::types::{LpLockHolding, LpLockStatus};
+
+const LP_TOKEN_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"totalSupply","outputs":[{"name":"","type":"uint256"}],"type":"function"},
+    {"constant":true,"inputs":[{"name":"account","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"type":"function"}
+]"#;
+
+/// Checks whether a V2 pair's LP tokens are burned or held by known lockers
+pub struct LpLockChecker<M> {
+    provider: Arc<M>,
+    lockers: Vec<(Address, String)>,
+}
+
+impl<M: Middleware + 'static> LpLockChecker<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            provider,
+            lockers: get_lp_lockers(),
+        }
+    }
+
+    /// Register an additional locker contract (e.g. a team vesting contract)
+    pub fn with_locker(mut self, address: Address, name: &str) -> Self {
+        self.lockers.push((address, name.to_string()));
+        self
+    }
+
+    /// Read the LP supply and the balances held by burn addresses and lockers
+    pub async fn check(&self, pair_address: Address) -> Result<LpLockStatus> {
+        let abi: Abi = serde_json::from_str(LP_TOKEN_ABI)?;
+        let contract = Contract::new(pair_address, abi, self.provider.clone());
+
+        let total_supply: U256 = contract.method("totalSupply", ())?.call().await?;
+        let pct = |amount: U256| -> f64 {
+            if total_supply.is_zero() {
+                0.0
+            } else {
+                // Scale to basis points in integer math, LP supplies overflow f64 precision
+                (amount * U256::from(10_000) / total_supply).as_u64() as f64 / 100.0
+            }
+        };
+
+        let mut burned = U256::zero();
+        for address in get_burn_addresses() {
+            let balance: U256 = contract.method("balanceOf", address)?.call().await?;
+            burned += balance;
+        }
+
+        let mut lockers = Vec::new();
+        let mut locked = U256::zero();
+        for (address, name) in &self.lockers {
+            let balance: U256 = contract.method("balanceOf", *address)?.call().await?;
+            if !balance.is_zero() {
+                locked += balance;
+                lockers.push(LpLockHolding {
+                    locker: name.clone(),
+                    address: *address,
+                    amount: balance,
+                    pct: pct(balance),
+                });
+            }
+        }
+
+        log::debug!("🔒 [LP_LOCK] Pair {:?}: {:.2}% burned, {:.2}% locked", pair_address, pct(burned), pct(locked));
+
+        Ok(LpLockStatus {
+            pair_address,
+            total_supply,
+            burned_pct: pct(burned),
+            locked_pct: pct(locked),
+            lockers,
+        })
+    }
+}
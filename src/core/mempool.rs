@@ -0,0 +1,237 @@
+use ethers::{
+    abi::{self, ParamType, Token},
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{Address, Transaction},
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::core::options::PendingSwapCallback;
+use crate::core::pending::PendingTracker;
+use crate::types::{KnownRouter, PendingSwapEvent, TradeType};
+
+// Pending transactions fetched concurrently from a newPendingTransactions subscription
+const PENDING_FETCH_CONCURRENCY: usize = 32;
+
+// txpool_content polling interval when pending subscriptions are unavailable
+const TXPOOL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Seen-hash set is reset past this size
+const MAX_SEEN_HASHES: usize = 50_000;
+
+// Calldata layout of a router swap method
+#[derive(Debug, Clone, Copy)]
+enum SwapLayout {
+    // (amountOutMin, path, to, deadline), BNB in tx value
+    ExactEthIn,
+    // (amountOut, path, to, deadline), max BNB in tx value
+    ExactEthOut,
+    // (amountIn, amountOutMin, path, to, deadline)
+    ExactTokensIn,
+    // (amountOut, amountInMax, path, to, deadline)
+    ExactTokensOut,
+    // V3 ((tokenIn, tokenOut, fee, recipient, amountIn, amountOutMinimum, sqrtPriceLimitX96))
+    V3ExactInputSingle,
+}
+
+// PancakeSwap V2 router (and forks) / V3 SmartRouter swap selectors
+const SWAP_METHODS: &[([u8; 4], &str, SwapLayout)] = &[
+    ([0x7f, 0xf3, 0x6a, 0xb5], "swapExactETHForTokens", SwapLayout::ExactEthIn),
+    ([0xb6, 0xf9, 0xde, 0x95], "swapExactETHForTokensSupportingFeeOnTransferTokens", SwapLayout::ExactEthIn),
+    ([0xfb, 0x3b, 0xdb, 0x41], "swapETHForExactTokens", SwapLayout::ExactEthOut),
+    ([0x18, 0xcb, 0xaf, 0xe5], "swapExactTokensForETH", SwapLayout::ExactTokensIn),
+    ([0x79, 0x1a, 0xc9, 0x47], "swapExactTokensForETHSupportingFeeOnTransferTokens", SwapLayout::ExactTokensIn),
+    ([0x38, 0xed, 0x17, 0x39], "swapExactTokensForTokens", SwapLayout::ExactTokensIn),
+    ([0x5c, 0x11, 0xd7, 0x95], "swapExactTokensForTokensSupportingFeeOnTransferTokens", SwapLayout::ExactTokensIn),
+    ([0x4a, 0x25, 0xd9, 0x4a], "swapTokensForExactETH", SwapLayout::ExactTokensOut),
+    ([0x88, 0x03, 0xdb, 0xee], "swapTokensForExactTokens", SwapLayout::ExactTokensOut),
+    ([0x04, 0xe4, 0x5a, 0xaf], "exactInputSingle", SwapLayout::V3ExactInputSingle),
+];
+
+/// Decode a router swap transaction touching `token_address`
+pub fn decode_pending_swap(tx: &Transaction, token_address: Address) -> Option<PendingSwapEvent> {
+    let router = tx.to?;
+    if tx.input.len() < 4 {
+        return None;
+    }
+    let (_, method, layout) = SWAP_METHODS.iter().find(|(selector, _, _)| tx.input[..4] == selector[..])?;
+    let data = &tx.input[4..];
+
+    let address_path = ParamType::Array(Box::new(ParamType::Address));
+    let (path, amount_in, amount_out, exact_input) = match layout {
+        SwapLayout::ExactEthIn | SwapLayout::ExactEthOut => {
+            let tokens = abi::decode(&[ParamType::Uint(256), address_path, ParamType::Address, ParamType::Uint(256)], data).ok()?;
+            let amount = tokens[0].clone().into_uint()?;
+            let exact_input = matches!(layout, SwapLayout::ExactEthIn);
+            (decode_path(&tokens[1])?, tx.value, amount, exact_input)
+        }
+        SwapLayout::ExactTokensIn | SwapLayout::ExactTokensOut => {
+            let tokens = abi::decode(
+                &[ParamType::Uint(256), ParamType::Uint(256), address_path, ParamType::Address, ParamType::Uint(256)],
+                data,
+            )
+            .ok()?;
+            let first = tokens[0].clone().into_uint()?;
+            let second = tokens[1].clone().into_uint()?;
+            match layout {
+                SwapLayout::ExactTokensIn => (decode_path(&tokens[2])?, first, second, true),
+                _ => (decode_path(&tokens[2])?, second, first, false),
+            }
+        }
+        SwapLayout::V3ExactInputSingle => {
+            let params = ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::Address,
+                ParamType::Uint(24),
+                ParamType::Address,
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(160),
+            ]);
+            let tokens = abi::decode(&[params], data).ok()?.pop()?.into_tuple()?;
+            let path = vec![tokens[0].clone().into_address()?, tokens[1].clone().into_address()?];
+            (path, tokens[4].clone().into_uint()?, tokens[5].clone().into_uint()?, true)
+        }
+    };
+
+    let trade_type = if path.last() == Some(&token_address) {
+        TradeType::Buy
+    } else if path.first() == Some(&token_address) {
+        TradeType::Sell
+    } else {
+        return None;
+    };
+
+    Some(PendingSwapEvent {
+        transaction_hash: tx.hash,
+        from: tx.from,
+        router,
+        route_via: KnownRouter::from_address(router),
+        method: method.to_string(),
+        trade_type,
+        token_address,
+        path,
+        amount_in,
+        amount_out,
+        exact_input,
+        value: tx.value,
+        gas_price: tx.gas_price,
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+        nonce: tx.nonce,
+        detected_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+fn decode_path(token: &Token) -> Option<Vec<Address>> {
+    token.clone().into_array()?.into_iter().map(|t| t.into_address()).collect()
+}
+
+/// Stream pending router swaps of a token from the mempool
+///
+/// Uses a newPendingTransactions subscription and falls back to polling
+/// txpool_content when the node does not support it.
+pub(crate) fn spawn_mempool_watcher<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    callback: Option<PendingSwapCallback>,
+    tracker: Option<PendingTracker>,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let stream = match provider.subscribe_pending_txs().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::warn!("⚠️ [MEMPOOL] Pending tx subscription unavailable ({}), polling txpool", e);
+                poll_txpool(provider.clone(), token_address, callback, tracker, cancel_token).await;
+                return;
+            }
+        };
+        log::info!("⏳ [MEMPOOL] Watching pending swaps of {:?}", token_address);
+
+        let fetch_provider = provider.clone();
+        let mut transactions = stream
+            .map(move |hash| {
+                let provider = fetch_provider.clone();
+                async move { provider.get_transaction(hash).await.ok().flatten() }
+            })
+            .buffer_unordered(PENDING_FETCH_CONCURRENCY);
+
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [MEMPOOL] Mempool watcher cancelled for {:?}", token_address);
+                    break;
+                }
+                tx = transactions.next() => match tx {
+                    Some(Some(tx)) => {
+                        if let Some(event) = decode_pending_swap(&tx, token_address) {
+                            emit(event, &callback, &tracker);
+                        }
+                    }
+                    // Already mined or dropped before it could be fetched
+                    Some(None) => {}
+                    None => {
+                        log::warn!("⚠️ [MEMPOOL] Pending tx stream ended for {:?}", token_address);
+                        break;
+                    }
+                },
+            }
+        }
+    });
+}
+
+fn emit(event: PendingSwapEvent, callback: &Option<PendingSwapCallback>, tracker: &Option<PendingTracker>) {
+    if let Some(tracker) = tracker {
+        tracker.insert(event.clone());
+    }
+    if let Some(callback) = callback {
+        callback(event);
+    }
+}
+
+async fn poll_txpool<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: Address,
+    callback: Option<PendingSwapCallback>,
+    tracker: Option<PendingTracker>,
+    cancel_token: CancellationToken,
+) {
+    let mut seen = HashSet::new();
+    let mut interval = tokio::time::interval(TXPOOL_POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                log::debug!("🛑 [MEMPOOL] Txpool poller cancelled for {:?}", token_address);
+                break;
+            }
+            _ = interval.tick() => {}
+        }
+
+        let content = match provider.txpool_content().await {
+            Ok(content) => content,
+            Err(e) => {
+                log::error!("❌ [MEMPOOL] txpool_content not supported by this node: {}", e);
+                break;
+            }
+        };
+
+        if seen.len() > MAX_SEEN_HASHES {
+            seen.clear();
+        }
+        for tx in content.pending.values().flat_map(|txs| txs.values()) {
+            if !seen.insert(tx.hash) {
+                continue;
+            }
+            if let Some(event) = decode_pending_swap(tx, token_address) {
+                emit(event, &callback, &tracker);
+            }
+        }
+    }
+}
+
@@ -1,6 +1,44 @@
+pub mod backfill;
+pub mod backtest;
+pub mod blocks;
+pub mod bonding_curve;
+pub mod candles;
+pub mod confirmations;
+pub mod copy_trading;
+pub mod creator;
+pub mod explorer;
+pub mod filter;
+pub mod health;
+pub mod holders;
+pub mod honeypot;
+pub mod labels;
+pub mod latency;
+pub mod liquidity;
+pub mod lp_lock;
+pub mod mempool;
+pub mod new_pairs;
+pub mod options;
+pub mod ownership;
 pub mod pair_finder;
+pub mod paper;
+pub mod pending;
+pub mod pipeline;
+pub mod price_oracle;
 pub mod price_tracker;
+pub mod profile;
+pub mod proxy;
+pub mod quoter;
+pub mod reserves;
+pub mod risk;
+pub mod search;
+pub mod simulation;
 pub mod streamer;
+pub mod stats;
+pub mod supply;
+pub mod swap_firehose;
 pub mod swap_parser;
+pub mod tax;
 pub mod token_info;
+pub mod trending;
+pub mod venues;
 
@@ -0,0 +1,292 @@
+use ethers::{
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{Address, Filter, Log, H256, I256, U256},
+    utils::format_units,
+};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{get_base_tokens, get_factory_address, get_pair_created_topic, get_pool_created_topic, get_v3_factory_address};
+use crate::core::health::HealthMonitor;
+use crate::core::liquidity::{MINT_V2_TOPIC, MINT_V3_TOPIC};
+use crate::core::options::{NewPairCallback, RawLogCallback};
+use crate::core::pair_finder::V2_FEE_TIER;
+use crate::core::price_oracle::PriceOracle;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{NewPairEvent, PoolMetadata, PoolVersion};
+
+/// Pair or pool decoded from a factory creation log
+struct CreatedLog {
+    token0: Address,
+    token1: Address,
+    pair_address: Address,
+    pool: PoolMetadata,
+}
+
+/// Decode a V2 PairCreated or V3 PoolCreated log
+fn decode_created(log: &Log) -> Option<CreatedLog> {
+    let topic0 = *log.topics.first()?;
+    let is_v3 = topic0 == get_pool_created_topic();
+    if (!is_v3 && topic0 != get_pair_created_topic()) || log.topics.len() < 3 {
+        return None;
+    }
+
+    // V2: data = pair, uint | V3: topic3 = fee, data = tickSpacing, pool
+    let pair_offset = if is_v3 { 32 } else { 0 };
+    if log.data.len() < pair_offset + 32 || (is_v3 && log.topics.len() < 4) {
+        return None;
+    }
+    let pool = if is_v3 {
+        PoolMetadata {
+            version: PoolVersion::V3,
+            fee_tier: U256::from_big_endian(log.topics[3].as_bytes()).low_u32(),
+            tick_spacing: Some(I256::from_raw(U256::from_big_endian(&log.data[0..32])).as_i32()),
+        }
+    } else {
+        PoolMetadata {
+            version: PoolVersion::V2,
+            fee_tier: V2_FEE_TIER,
+            tick_spacing: None,
+        }
+    };
+
+    Some(CreatedLog {
+        token0: Address::from(log.topics[1]),
+        token1: Address::from(log.topics[2]),
+        pair_address: Address::from_slice(&log.data[pair_offset + 12..pair_offset + 32]),
+        pool,
+    })
+}
+
+/// Whole-token amount of a raw value
+fn whole(raw: U256, decimals: u8) -> Option<f64> {
+    format_units(raw, decimals as u32).ok()?.parse().ok()
+}
+
+/// Split a pair into its traded token and base side (None when neither token is a base token)
+pub(crate) fn split_pair(token0: Address, token1: Address, base_tokens: &[(String, Address)]) -> (Address, Option<(String, Address)>) {
+    let rank = |address: Address| base_tokens.iter().position(|(_, base)| *base == address);
+    match (rank(token0), rank(token1)) {
+        // Against a base token, the other side is the traded token
+        (Some(base), None) => (token1, Some(base_tokens[base].clone())),
+        (None, Some(base)) => (token0, Some(base_tokens[base].clone())),
+        // Base/base pools: the first listed base token is the quote (WBNB before stablecoins)
+        (Some(base0), Some(base1)) if base0 <= base1 => (token1, Some(base_tokens[base0].clone())),
+        (Some(_), Some(base1)) => (token0, Some(base_tokens[base1].clone())),
+        (None, None) => (token0, None),
+    }
+}
+
+/// Resolves tokens, base side and initial liquidity of newly created pairs
+struct NewPairEnricher<M> {
+    provider: Arc<M>,
+    token_cache: TokenInfoCache<M>,
+    price_oracle: PriceOracle<M>,
+    base_tokens: Vec<(String, Address)>,
+}
+
+impl<M: Middleware + 'static> NewPairEnricher<M> {
+    fn new(provider: Arc<M>) -> Self {
+        Self {
+            token_cache: TokenInfoCache::new(provider.clone()),
+            price_oracle: PriceOracle::new(provider.clone()),
+            base_tokens: get_base_tokens(),
+            provider,
+        }
+    }
+
+    async fn enrich(&self, log: &Log, created: CreatedLog) -> NewPairEvent {
+        let (token, base) = split_pair(created.token0, created.token1, &self.base_tokens);
+
+        let (token_name, token_symbol, token_decimals) = match self.token_cache.get_token_info(token).await {
+            Ok(info) => (info.name, info.symbol, info.decimals),
+            Err(_) => ("Unknown".to_string(), "UNKNOWN".to_string(), 18),
+        };
+
+        // Routers usually create the pair and add liquidity in one transaction
+        let receipt = match log.transaction_hash {
+            Some(hash) => self.provider.get_transaction_receipt(hash).await.ok().flatten(),
+            None => None,
+        };
+        let mint = receipt.as_ref().and_then(|receipt| {
+            let mint_topic = if created.pool.version == PoolVersion::V3 { MINT_V3_TOPIC } else { MINT_V2_TOPIC };
+            receipt.logs.iter().find(|l| {
+                l.address == created.pair_address && l.topics.first().is_some_and(|t| format!("{:#x}", t) == mint_topic)
+            })
+        });
+        // V2 Mint data = amount0, amount1 | V3 Mint data = sender, amount, amount0, amount1
+        let amounts = mint.and_then(|mint| {
+            let offset = if created.pool.version == PoolVersion::V3 { 64 } else { 0 };
+            (mint.data.len() >= offset + 64).then(|| {
+                (
+                    U256::from_big_endian(&mint.data[offset..offset + 32]),
+                    U256::from_big_endian(&mint.data[offset + 32..offset + 64]),
+                )
+            })
+        });
+
+        let (mut token_liquidity, mut base_liquidity, mut liquidity_usd) = (None, None, None);
+        if let Some((amount0, amount1)) = amounts {
+            let (token_raw, base_raw) = if token == created.token0 { (amount0, amount1) } else { (amount1, amount0) };
+            token_liquidity = whole(token_raw, token_decimals);
+            if let Some((_, base_address)) = &base {
+                let base_decimals = match self.token_cache.get_token_info(*base_address).await {
+                    Ok(info) => info.decimals,
+                    Err(_) => 18,
+                };
+                base_liquidity = whole(base_raw, base_decimals);
+                if let (Some(amount), Some(price)) = (base_liquidity, self.price_oracle.usd_price(*base_address).await) {
+                    liquidity_usd = Some(amount * price * 2.0);
+                }
+            }
+        }
+
+        NewPairEvent {
+            pair_address: created.pair_address,
+            pool: created.pool,
+            token0: created.token0,
+            token1: created.token1,
+            token,
+            token_name,
+            token_symbol,
+            token_decimals,
+            base_token: base.as_ref().map(|(_, address)| *address),
+            base_token_symbol: base.map(|(symbol, _)| symbol),
+            token_liquidity,
+            base_liquidity,
+            liquidity_usd,
+            creator: receipt.map(|receipt| receipt.from),
+            block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+            log_index: log.log_index.map(|i| i.as_u64()).unwrap_or_default(),
+            transaction_hash: log.transaction_hash.unwrap_or_default(),
+        }
+    }
+}
+
+/// Report every pair and pool created on the PancakeSwap V2 and V3 factories
+///
+/// Pairs are enriched one at a time, so events arrive in creation order.
+pub(crate) fn spawn_new_pair_watcher<M>(provider: Arc<M>, callback: NewPairCallback, health: HealthMonitor, cancel_token: CancellationToken)
+where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    let probe = health.subscription("PancakeSwap V2/V3 factory pair creations".to_string());
+    tokio::spawn(async move {
+        let filter = Filter::new()
+            .address(vec![get_factory_address(), get_v3_factory_address()])
+            .topic0(vec![
+                get_pair_created_topic(),
+                get_pool_created_topic(),
+            ]);
+        let mut stream = match provider.subscribe_logs(&filter).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [NEW_PAIRS] Failed to subscribe to factory logs: {}", e);
+                probe.failed(e);
+                return;
+            }
+        };
+        probe.live();
+        log::info!("🆕 [NEW_PAIRS] Watching PancakeSwap factories for new pairs");
+
+        let enricher = NewPairEnricher::new(provider.clone());
+        loop {
+            let log = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [NEW_PAIRS] New-pair watcher cancelled");
+                    probe.stopped();
+                    break;
+                }
+                log = stream.next() => match log {
+                    Some(log) => log,
+                    None => {
+                        log::warn!("⚠️ [NEW_PAIRS] Factory log stream ended");
+                        probe.ended();
+                        break;
+                    }
+                },
+            };
+            probe.event();
+
+            let Some(created) = decode_created(&log) else {
+                continue;
+            };
+            let event = enricher.enrich(&log, created).await;
+            log::info!(
+                "🆕 [NEW_PAIRS] {} {}/{} pair {:?}",
+                if event.pool.version == PoolVersion::V3 { "V3" } else { "V2" },
+                event.token_symbol,
+                event.base_token_symbol.as_deref().unwrap_or("?"),
+                event.pair_address
+            );
+            callback(event);
+        }
+    });
+}
+
+/// Report pairs and pools created for `token_address` (either side) while it is being streamed
+pub(crate) fn spawn_token_pair_watcher<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    callback: NewPairCallback,
+    raw_log: Option<RawLogCallback>,
+    health: HealthMonitor,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    let probe = health.subscription(format!("New pairs of {:?}", token_address));
+    tokio::spawn(async move {
+        let created = Filter::new()
+            .address(vec![get_factory_address(), get_v3_factory_address()])
+            .topic0(vec![
+                get_pair_created_topic(),
+                get_pool_created_topic(),
+            ]);
+        // The token is indexed as token0 or token1 depending on address order
+        let token_topic = H256::from(token_address);
+        let (as_token0, as_token1) = match (
+            provider.subscribe_logs(&created.clone().topic1(token_topic)).await,
+            provider.subscribe_logs(&created.topic2(token_topic)).await,
+        ) {
+            (Ok(as_token0), Ok(as_token1)) => (as_token0, as_token1),
+            (Err(e), _) | (_, Err(e)) => {
+                log::error!("❌ [NEW_PAIRS] Failed to subscribe to new pairs of {:?}: {}", token_address, e);
+                probe.failed(e);
+                return;
+            }
+        };
+        probe.live();
+        log::debug!("🆕 [NEW_PAIRS] Watching factories for new pairs of {:?}", token_address);
+
+        let mut stream = futures::stream::select(as_token0, as_token1);
+        let enricher = NewPairEnricher::new(provider.clone());
+        loop {
+            let log = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [NEW_PAIRS] Pair watcher for {:?} cancelled", token_address);
+                    probe.stopped();
+                    break;
+                }
+                log = stream.next() => match log {
+                    Some(log) => log,
+                    None => {
+                        log::warn!("⚠️ [NEW_PAIRS] Factory log stream for {:?} ended", token_address);
+                        probe.ended();
+                        break;
+                    }
+                },
+            };
+            probe.event();
+            if let Some(ref raw_log) = raw_log {
+                raw_log(log.clone());
+            }
+
+            if let Some(created) = decode_created(&log) {
+                callback(enricher.enrich(&log, created).await);
+            }
+        }
+    });
+}
@@ -0,0 +1,149 @@
+use ethers::types::{Address, Log};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::filter::SwapFilter;
+use crate::core::labels::LabelRegistry;
+use crate::sinks::SinkSet;
+use crate::types::{
+    BlockEvent, BurnAlertConfig, BurnEvent, Candle, CandleInterval, DevActionEvent, DrainAlertConfig, HolderStats, HolderStatsConfig, ImplementationChangedEvent, Indicator, IndicatorCross, LiquidityDrainedEvent, LiquidityEvent, NewPairEvent, OwnershipChangeEvent,
+    PendingSwapEvent, PendingSwapResolution, PoolMigrationConfig, PoolMigrationEvent, PriceAlert, PriceAlertRule, QuoteCurrency, SwapEvent, TradeThreshold, TrendingMetrics,
+};
+
+pub type LiquidityCallback = Arc<dyn Fn(LiquidityEvent) + Send + Sync>;
+pub type LiquidityDrainedCallback = Arc<dyn Fn(LiquidityDrainedEvent) + Send + Sync>;
+pub type HolderStatsCallback = Arc<dyn Fn(HolderStats) + Send + Sync>;
+pub type DevActionCallback = Arc<dyn Fn(DevActionEvent) + Send + Sync>;
+pub type NewPairCallback = Arc<dyn Fn(NewPairEvent) + Send + Sync>;
+pub type PoolMigrationCallback = Arc<dyn Fn(PoolMigrationEvent) + Send + Sync>;
+pub type OwnershipCallback = Arc<dyn Fn(OwnershipChangeEvent) + Send + Sync>;
+pub type ImplementationChangedCallback = Arc<dyn Fn(ImplementationChangedEvent) + Send + Sync>;
+pub type PendingSwapCallback = Arc<dyn Fn(PendingSwapEvent) + Send + Sync>;
+pub type PendingResolvedCallback = Arc<dyn Fn(PendingSwapResolution) + Send + Sync>;
+pub type CandleCallback = Arc<dyn Fn(Candle) + Send + Sync>;
+pub type BurnCallback = Arc<dyn Fn(BurnEvent) + Send + Sync>;
+pub type PriceAlertCallback = Arc<dyn Fn(PriceAlert) + Send + Sync>;
+pub type IndicatorCrossCallback = Arc<dyn Fn(IndicatorCross) + Send + Sync>;
+pub type SwapCallback = Arc<dyn Fn(SwapEvent) + Send + Sync>;
+pub type BlockCallback = Arc<dyn Fn(BlockEvent) + Send + Sync>;
+pub type RawLogCallback = Arc<dyn Fn(Log) + Send + Sync>;
+pub type TrendingCallback = Arc<dyn Fn(Address, TrendingMetrics) + Send + Sync>;
+
+/// Options applied to every subscription a streamer creates
+#[derive(Debug, Clone, Default)]
+pub struct StreamOptions {
+    /// Currency swap prices are normalized to (see `SwapEvent::normalized_price`)
+    pub quote_currency: QuoteCurrency,
+    /// Swaps not matching the filter are dropped before any callback
+    pub filter: SwapFilter,
+    /// Known-address labels attached to swaps
+    pub labels: LabelRegistry,
+    /// Trades kept per token for `StreamerHandle::price_history` (tracker default when unset)
+    pub price_history_limit: Option<usize>,
+    /// Indicators computed over streamed prices (see `StreamerHandle::get_stats`)
+    pub indicators: Vec<Indicator>,
+    /// Minimum verified USD liquidity of monitored DEX pairs (pair finder default when unset)
+    pub min_liquidity_usd: Option<f64>,
+    /// File token metadata is persisted to, and how long entries stay valid
+    pub token_cache_file: Option<(PathBuf, Duration)>,
+    /// Block the initial DEX pairs are backfilled from before their live subscriptions
+    pub start_block: Option<u64>,
+    /// Monitor the bonding curve and DEX pairs at the same time instead of either/or
+    pub monitor_all: bool,
+    /// Bonding curve contract per token (other launchpads, newer Four.meme deployments); the default curve otherwise
+    pub bonding_curves: HashMap<Address, Address>,
+    /// Blocks that must be built on top of an event's block before it is delivered (0 = immediately)
+    pub confirmations: u64,
+    /// Swaps parsed at once per subscription, emitted in log order (pipeline default when unset; 1 = one at a time)
+    pub parse_concurrency: Option<usize>,
+    /// Outputs receiving every swap (after the filter), migration and candle
+    pub sinks: SinkSet,
+    pub hooks: StreamHooks,
+}
+
+/// Optional callbacks for events other than swaps and migrations
+#[derive(Clone, Default)]
+pub struct StreamHooks {
+    /// Mint/Burn/Collect on monitored DEX pairs
+    pub liquidity: Option<LiquidityCallback>,
+    /// Rug-pull alert on large liquidity removal or drained reserves
+    pub liquidity_drained: Option<(DrainAlertConfig, LiquidityDrainedCallback)>,
+    /// Periodic holder count / new-holder rate of the monitored token
+    pub holder_stats: Option<(HolderStatsConfig, HolderStatsCallback)>,
+    /// Sells, transfers and liquidity changes by the token creator
+    pub dev_action: Option<DevActionCallback>,
+    /// OwnershipTransferred on the token contract, including renounces
+    pub ownership_change: Option<OwnershipCallback>,
+    /// Upgrade of a proxy token to a new implementation
+    pub implementation_changed: Option<ImplementationChangedCallback>,
+    /// Router swaps of the token seen in the mempool
+    pub pending_swap: Option<PendingSwapCallback>,
+    /// Confirmation, revert, replacement or drop of pending swaps
+    pub pending_resolved: Option<PendingResolvedCallback>,
+    /// Swaps at or above a size threshold
+    pub whale_trade: Option<(TradeThreshold, SwapCallback)>,
+    /// Large burns to the dead/zero address (also keeps circulating supply live)
+    pub burn: Option<(BurnAlertConfig, BurnCallback)>,
+    /// Price level crosses, fast moves and new session highs/lows
+    pub price_alert: Option<(Vec<PriceAlertRule>, PriceAlertCallback)>,
+    /// Price crossing SMA/EMA indicators
+    pub indicator_cross: Option<(Vec<Indicator>, IndicatorCrossCallback)>,
+    /// OHLCV candles emitted when each interval closes
+    pub candles: Option<(Vec<CandleInterval>, CandleCallback)>,
+    /// DEX pairs of the token created while streaming (they are monitored automatically)
+    pub new_pair: Option<NewPairCallback>,
+    /// Liquidity moving from the primary pool to another pool of the token (e.g. V2 to V3)
+    pub pool_migration: Option<(PoolMigrationConfig, PoolMigrationCallback)>,
+    /// Every undecoded log of the pair, bonding curve and factory subscriptions
+    pub raw_log: Option<RawLogCallback>,
+    /// Every new chain head, with the swap count of the block before it
+    pub block: Option<BlockCallback>,
+}
+
+impl StreamHooks {
+    /// Whether Mint/Burn/Collect logs need to be subscribed to
+    pub fn wants_liquidity(&self) -> bool {
+        self.liquidity.is_some() || self.liquidity_drained.is_some()
+    }
+
+    /// Whether the mempool needs to be watched
+    pub fn wants_mempool(&self) -> bool {
+        self.pending_swap.is_some() || self.pending_resolved.is_some()
+    }
+
+    /// Run swap-level hooks for a swap about to be delivered to `on_swap`
+    pub fn dispatch_swap(&self, swap: &SwapEvent) {
+        if let Some((threshold, ref callback)) = self.whale_trade {
+            if threshold.is_met(swap) {
+                callback(swap.clone());
+            }
+        }
+    }
+}
+
+impl fmt::Debug for StreamHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamHooks")
+            .field("liquidity", &self.liquidity.is_some())
+            .field("liquidity_drained", &self.liquidity_drained.as_ref().map(|(config, _)| config))
+            .field("holder_stats", &self.holder_stats.as_ref().map(|(config, _)| config))
+            .field("dev_action", &self.dev_action.is_some())
+            .field("ownership_change", &self.ownership_change.is_some())
+            .field("implementation_changed", &self.implementation_changed.is_some())
+            .field("pending_swap", &self.pending_swap.is_some())
+            .field("pending_resolved", &self.pending_resolved.is_some())
+            .field("whale_trade", &self.whale_trade.as_ref().map(|(threshold, _)| threshold))
+            .field("burn", &self.burn.as_ref().map(|(config, _)| config))
+            .field("price_alert", &self.price_alert.as_ref().map(|(rules, _)| rules))
+            .field("indicator_cross", &self.indicator_cross.as_ref().map(|(indicators, _)| indicators))
+            .field("candles", &self.candles.as_ref().map(|(intervals, _)| intervals))
+            .field("new_pair", &self.new_pair.is_some())
+            .field("pool_migration", &self.pool_migration.as_ref().map(|(config, _)| config))
+            .field("raw_log", &self.raw_log.is_some())
+            .field("block", &self.block.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,80 @@
+use ethers::{
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{Address, Filter, H256},
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::get_burn_addresses;
+use crate::core::options::OwnershipCallback;
+use crate::types::OwnershipChangeEvent;
+
+// OwnershipTransferred(address indexed previousOwner, address indexed newOwner)
+pub const OWNERSHIP_TRANSFERRED_TOPIC: &str = "0x8be0079c531659141344cd1fd0a4f28419497f9722a3daafe3b4186f6b6457e0";
+
+/// Subscribe to OwnershipTransferred events emitted by the token contract
+pub(crate) fn spawn_ownership_watcher<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    callback: OwnershipCallback,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let filter = Filter::new()
+            .address(token_address)
+            .topic0(H256::from_str(OWNERSHIP_TRANSFERRED_TOPIC).unwrap());
+
+        let mut stream = match provider.subscribe_logs(&filter).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [OWNERSHIP] Failed to subscribe to ownership changes: {}", e);
+                return;
+            }
+        };
+        log::debug!("👑 [OWNERSHIP] Watching ownership of {:?}", token_address);
+
+        let burn_addresses = get_burn_addresses();
+
+        loop {
+            let log = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [OWNERSHIP] Ownership watcher cancelled for {:?}", token_address);
+                    break;
+                }
+                log = stream.next() => match log {
+                    Some(log) => log,
+                    None => {
+                        log::warn!("⚠️ [OWNERSHIP] Ownership stream ended for {:?}", token_address);
+                        break;
+                    }
+                },
+            };
+
+            if log.topics.len() < 3 {
+                continue;
+            }
+            let previous_owner = Address::from(log.topics[1]);
+            let new_owner = Address::from(log.topics[2]);
+            let renounced = burn_addresses.contains(&new_owner);
+
+            if renounced {
+                log::info!("👑 [OWNERSHIP] Ownership of {:?} renounced by {:?}", token_address, previous_owner);
+            } else {
+                log::info!("👑 [OWNERSHIP] Ownership of {:?} moved {:?} -> {:?}", token_address, previous_owner, new_owner);
+            }
+
+            callback(OwnershipChangeEvent {
+                token_address,
+                previous_owner,
+                new_owner,
+                renounced,
+                transaction_hash: log.transaction_hash.unwrap_or_default(),
+                block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+            });
+        }
+    });
+}
@@ -1,17 +1,27 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use ethers::{
     abi::Abi,
     contract::Contract,
     providers::Middleware,
-    types::Address,
+    types::{Address, Filter, H256, I256, U256, U64},
 };
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
 
-use crate::config::{get_base_tokens, get_factory_address, get_v3_factory_address};
-use crate::types::PairInfo;
+use crate::config::{get_base_tokens, get_factory_address, get_pair_created_topic, get_pool_created_topic, get_v3_factory_address};
+use crate::core::new_pairs::split_pair;
+use crate::core::streamer::{SWAP_V2_TOPIC, SWAP_V3_TOPIC};
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{PairInfo, QuoteRoute};
 
-// Minimum liquidity threshold in USD
-const MIN_LIQUIDITY_USD: f64 = 5000.0;
+// Default minimum liquidity threshold in USD
+pub const MIN_LIQUIDITY_USD: f64 = 5000.0;
+
+// How far back to scan factory creation logs when a token has no base-token pairs
+const COUNTER_PAIR_LOOKBACK_BLOCKS: u64 = 28_800;
+// Block range per eth_getLogs request (public RPCs reject large ranges)
+const LOG_SCAN_CHUNK_BLOCKS: u64 = 5_000;
 
 const FACTORY_V2_ABI: &str = r#"[
     {"constant":true,"inputs":[{"name":"tokenA","type":"address"},{"name":"tokenB","type":"address"}],"name":"getPair","outputs":[{"name":"pair","type":"address"}],"type":"function"}
@@ -21,7 +31,13 @@ const FACTORY_V3_ABI: &str = r#"[
     {"constant":true,"inputs":[{"name":"tokenA","type":"address"},{"name":"tokenB","type":"address"},{"name":"fee","type":"uint24"}],"name":"getPool","outputs":[{"name":"pool","type":"address"}],"type":"function"}
 ]"#;
 
-// PancakeSwap V3 fee tiers (in basis points)
+const POOL_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"token1","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"fee","outputs":[{"name":"","type":"uint24"}],"type":"function"}
+]"#;
+
+// PancakeSwap V3 fee tiers (in hundredths of a bip)
 const V3_FEE_TIERS: [u32; 4] = [
     100,   // 0.01%
     500,   // 0.05%
@@ -29,16 +45,64 @@ const V3_FEE_TIERS: [u32; 4] = [
     10000, // 1.00%
 ];
 
+// PancakeSwap V2 charges a flat 0.25% on every pair
+pub const V2_FEE_TIER: u32 = 2500;
+
+/// Tick spacing the PancakeSwap V3 factory assigns to each fee tier
+pub fn v3_tick_spacing(fee: u32) -> Option<i32> {
+    match fee {
+        100 => Some(1),
+        500 => Some(10),
+        2500 => Some(50),
+        10000 => Some(200),
+        _ => None,
+    }
+}
+
+/// Pair or pool found in factory creation logs
+struct CreatedPair {
+    counter_token: Address,
+    pair_address: Address,
+    is_v3: bool,
+    fee_tier: u32,
+    tick_spacing: Option<i32>,
+}
+
 pub struct PairFinder<M> {
     provider: Arc<M>,
+    min_liquidity_usd: f64,
 }
 
 impl<M: Middleware + 'static> PairFinder<M> {
     pub fn new(provider: Arc<M>) -> Self {
-        Self { provider }
+        Self {
+            provider,
+            min_liquidity_usd: MIN_LIQUIDITY_USD,
+        }
     }
 
+    /// Drop pairs with less verified liquidity than `usd` (default $5000)
+    pub fn with_min_liquidity(mut self, usd: f64) -> Self {
+        self.min_liquidity_usd = usd;
+        self
+    }
+
+    /// Pairs and pools of the token with enough liquidity to stream
     pub async fn find_pairs(&self, token_address: Address) -> Result<Vec<PairInfo>> {
+        let pairs = self.find_all_pairs(token_address).await?;
+
+        // Filter pairs by liquidity (minimum $5000 USD by default)
+        let token_str = format!("{:?}", token_address);
+        let pairs_with_liquidity = self.filter_by_liquidity(pairs, &token_str).await;
+
+        // Don't log "no pairs found" here - let the caller (streamer.rs) decide
+        // This prevents misleading messages for Four.meme tokens that are on bonding curve
+
+        Ok(pairs_with_liquidity)
+    }
+
+    /// Every pair and pool the factories know for the token, however little liquidity it has
+    pub async fn find_all_pairs(&self, token_address: Address) -> Result<Vec<PairInfo>> {
         let base_tokens = get_base_tokens();
         let mut pairs = Vec::new();
 
@@ -52,14 +116,103 @@ impl<M: Middleware + 'static> PairFinder<M> {
             pairs.extend(v3_pairs);
         }
 
-        // Filter pairs by liquidity (minimum $5000 USD)
-        let token_str = format!("{:?}", token_address);
-        let pairs_with_liquidity = self.filter_by_liquidity(pairs, &token_str).await;
+        // A pool is reported once even if several lookups return it
+        let mut seen = HashSet::new();
+        pairs.retain(|pair| seen.insert(pair.pair_address));
 
-        // Don't log "no pairs found" here - let the caller (streamer.rs) decide
-        // This prevents misleading messages for Four.meme tokens that are on bonding curve
+        // Fallback: token may only trade against another (non-base) token
+        if pairs.is_empty() {
+            match self.find_counter_token_pairs(token_address, &base_tokens).await {
+                Ok(counter_pairs) => pairs.extend(counter_pairs),
+                Err(e) => log::warn!("⚠️  Counter-token pair scan failed: {}", e),
+            }
+        }
 
-        Ok(pairs_with_liquidity)
+        Ok(pairs)
+    }
+
+    /// Pair info of a known V2 pair or V3 pool, read from the pool itself
+    ///
+    /// Skips factory lookups and the liquidity filter. `token` selects the
+    /// streamed side; without it, the side that is not a base token is used.
+    pub async fn pair_info(&self, pair_address: Address, token: Option<Address>) -> Result<PairInfo> {
+        let abi: Abi = serde_json::from_str(POOL_ABI)?;
+        let pool = Contract::new(pair_address, abi, self.provider.clone());
+        let (token0, token1, fee) = (
+            pool.method::<_, Address>("token0", ())?,
+            pool.method::<_, Address>("token1", ())?,
+            pool.method::<_, u32>("fee", ())?,
+        );
+        let (token0, token1, fee) = tokio::join!(token0.call(), token1.call(), fee.call());
+        let (token0, token1) = match (token0, token1) {
+            (Ok(token0), Ok(token1)) => (token0, token1),
+            (Err(e), _) | (_, Err(e)) => return Err(anyhow!("{:?} is not a DEX pair: {}", pair_address, e)),
+        };
+        // Only V3 pools have fee()
+        let (is_v3, fee_tier) = match fee {
+            Ok(fee) => (true, fee),
+            Err(_) => (false, V2_FEE_TIER),
+        };
+
+        let base_tokens = get_base_tokens();
+        let (token, base) = match token {
+            Some(token) if token == token0 || token == token1 => {
+                let counter_token = if token == token0 { token1 } else { token0 };
+                (token, base_tokens.iter().find(|(_, base)| *base == counter_token).cloned())
+            }
+            Some(token) => bail!("Pair {:?} ({:?}/{:?}) does not trade {:?}", pair_address, token0, token1, token),
+            None => split_pair(token0, token1, &base_tokens),
+        };
+
+        let (base_token, base_token_symbol, route) = match base {
+            Some((symbol, base_token)) => (base_token, symbol, None),
+            None => {
+                let counter_token = if token == token0 { token1 } else { token0 };
+                let route = self
+                    .find_route_hop(counter_token, &base_tokens)
+                    .await
+                    .ok_or_else(|| anyhow!("No base-token route for {:?}, the counter-token of pair {:?}", counter_token, pair_address))?;
+                let symbol = TokenInfoCache::new(self.provider.clone())
+                    .get_token_info(counter_token)
+                    .await
+                    .map(|info| info.symbol)
+                    .unwrap_or_else(|_| "UNKNOWN".to_string());
+                (counter_token, symbol, Some(route))
+            }
+        };
+
+        log::info!("✅ Using {} {} pair {:?} for token {:?}", if is_v3 { "V3" } else { "V2" }, base_token_symbol, pair_address, token);
+        Ok(PairInfo {
+            pair_address,
+            token,
+            base_token,
+            base_token_symbol,
+            is_v3,
+            fee_tier,
+            tick_spacing: if is_v3 { v3_tick_spacing(fee_tier) } else { None },
+            route,
+        })
+    }
+
+    /// Number of Swap events each pair emitted over the last `blocks` blocks
+    pub async fn recent_swaps(&self, pairs: &[PairInfo], blocks: u64) -> Result<HashMap<Address, usize>> {
+        let mut counts: HashMap<Address, usize> = pairs.iter().map(|p| (p.pair_address, 0)).collect();
+        if pairs.is_empty() {
+            return Ok(counts);
+        }
+
+        let latest = self.provider.get_block_number().await?.as_u64();
+        let filter = Filter::new()
+            .address(pairs.iter().map(|p| p.pair_address).collect::<Vec<_>>())
+            .topic0(vec![H256::from_str(SWAP_V2_TOPIC)?, H256::from_str(SWAP_V3_TOPIC)?])
+            .from_block(latest.saturating_sub(blocks))
+            .to_block(latest);
+        for log in self.provider.get_logs(&filter).await? {
+            if let Some(count) = counts.get_mut(&log.address) {
+                *count += 1;
+            }
+        }
+        Ok(counts)
     }
     
     /// Filter pairs by liquidity using DexScreener API
@@ -119,7 +272,7 @@ impl<M: Middleware + 'static> PairFinder<M> {
             let pair_addr_str = format!("{:?}", pair.pair_address).to_lowercase();
             
             if let Some(&liquidity_usd) = liquidity_map.get(&pair_addr_str) {
-                if liquidity_usd >= MIN_LIQUIDITY_USD {
+                if liquidity_usd >= self.min_liquidity_usd {
                     let pool_type = if pair.is_v3 { "V3" } else { "V2" };
                     log::info!("✅ {} pair {} with {} has sufficient liquidity: ${:.0} USD", 
                         pool_type, &pair_addr_str[..10], pair.base_token_symbol, liquidity_usd);
@@ -127,7 +280,7 @@ impl<M: Middleware + 'static> PairFinder<M> {
                 } else {
                     let pool_type = if pair.is_v3 { "V3" } else { "V2" };
                     log::warn!("❌ Filtered out {} pair {} with {} - insufficient liquidity: ${:.2} USD (min: ${:.0})", 
-                        pool_type, &pair_addr_str[..10], pair.base_token_symbol, liquidity_usd, MIN_LIQUIDITY_USD);
+                        pool_type, &pair_addr_str[..10], pair.base_token_symbol, liquidity_usd, self.min_liquidity_usd);
                     // Don't add to any list - skip insufficient liquidity pairs
                 }
             } else {
@@ -181,6 +334,9 @@ impl<M: Middleware + 'static> PairFinder<M> {
                         base_token: *base_token_address,
                         base_token_symbol: symbol.clone(),
                         is_v3: false,
+                        fee_tier: V2_FEE_TIER,
+                        tick_spacing: None,
+                        route: None,
                     });
                 }
                 Ok(pair_address) => {
@@ -203,7 +359,7 @@ impl<M: Middleware + 'static> PairFinder<M> {
         log::debug!("🔍 Checking V3 pairs for token {:?} against {} base tokens", token_address, base_tokens.len());
 
         for (symbol, base_token_address) in base_tokens {
-            // Try each fee tier
+            // Every fee tier is a separate pool, so all of them are queried
             for fee in V3_FEE_TIERS {
                 tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
 
@@ -220,8 +376,10 @@ impl<M: Middleware + 'static> PairFinder<M> {
                             base_token: *base_token_address,
                             base_token_symbol: symbol.clone(),
                             is_v3: true,
+                            fee_tier: fee,
+                            tick_spacing: v3_tick_spacing(fee),
+                            route: None,
                         });
-                        break; // Found a pool for this base token, no need to check other fees
                     }
                     Ok(_) => {
                         log::debug!("  ⚪ No V3 pool with {} (fee: {})", symbol, fee);
@@ -235,5 +393,171 @@ impl<M: Middleware + 'static> PairFinder<M> {
 
         Ok(pairs)
     }
-}
+    /// Find pairs against arbitrary counter-tokens by scanning recent factory creation logs
+    ///
+    /// Each pair found is given a routing hop through the counter-token's own
+    /// base-token pair so swaps can still be priced in a base token.
+    async fn find_counter_token_pairs(&self, token_address: Address, base_tokens: &[(String, Address)]) -> Result<Vec<PairInfo>> {
+        log::debug!("🔍 No base-token pairs - scanning factory logs for counter-token pairs of {:?}", token_address);
+
+        let token_cache = TokenInfoCache::new(self.provider.clone());
+        let mut pairs = Vec::new();
 
+        for created in self.scan_created_pairs(token_address).await? {
+            let CreatedPair { counter_token, pair_address, is_v3, fee_tier, tick_spacing } = created;
+
+            if base_tokens.iter().any(|(_, base)| *base == counter_token) {
+                continue; // Already covered by the factory lookups
+            }
+
+            let Some(route) = self.find_route_hop(counter_token, base_tokens).await else {
+                log::warn!("⚠️  Skipping pair {:?} - no base-token route for counter-token {:?}", pair_address, counter_token);
+                continue;
+            };
+
+            let counter_symbol = token_cache
+                .get_token_info(counter_token)
+                .await
+                .map(|info| info.symbol)
+                .unwrap_or_else(|_| "UNKNOWN".to_string());
+
+            log::info!("✅ Found {} pair with counter-token {} ({:?}), priced via {}: {:?}",
+                if is_v3 { "V3" } else { "V2" }, counter_symbol, counter_token, route.base_token_symbol, pair_address);
+
+            pairs.push(PairInfo {
+                pair_address,
+                token: token_address,
+                base_token: counter_token,
+                base_token_symbol: counter_symbol,
+                is_v3,
+                fee_tier,
+                tick_spacing,
+                route: Some(route),
+            });
+        }
+
+        Ok(pairs)
+    }
+
+    /// Scan V2 PairCreated and V3 PoolCreated logs involving the token
+    async fn scan_created_pairs(&self, token_address: Address) -> Result<Vec<CreatedPair>> {
+        let pair_created_topic = get_pair_created_topic();
+        let pool_created_topic = get_pool_created_topic();
+        let token_topic = H256::from(token_address);
+
+        let current_block = self.provider.get_block_number().await
+            .map_err(|e| anyhow::anyhow!("Failed to get block number: {}", e))?;
+        let start_block = current_block.saturating_sub(U64::from(COUNTER_PAIR_LOOKBACK_BLOCKS));
+
+        let mut found = Vec::new();
+        let mut from_block = start_block;
+
+        while from_block <= current_block {
+            let to_block = (from_block + U64::from(LOG_SCAN_CHUNK_BLOCKS - 1)).min(current_block);
+
+            for (factory, topic, is_v3) in [
+                (get_factory_address(), pair_created_topic, false),
+                (get_v3_factory_address(), pool_created_topic, true),
+            ] {
+                // The token can be either token0 (topic1) or token1 (topic2)
+                let as_token0 = Filter::new().address(factory).topic0(topic).topic1(token_topic)
+                    .from_block(from_block).to_block(to_block);
+                let as_token1 = Filter::new().address(factory).topic0(topic).topic2(token_topic)
+                    .from_block(from_block).to_block(to_block);
+
+                for filter in [as_token0, as_token1] {
+                    let logs = match self.provider.get_logs(&filter).await {
+                        Ok(logs) => logs,
+                        Err(e) => {
+                            log::warn!("⚠️  Failed to fetch creation logs ({} to {}): {}", from_block, to_block, e);
+                            continue;
+                        }
+                    };
+
+                    for log in logs {
+                        if log.topics.len() < 3 {
+                            continue;
+                        }
+                        let token0 = Address::from(log.topics[1]);
+                        let token1 = Address::from(log.topics[2]);
+                        let counter_token = if token0 == token_address { token1 } else { token0 };
+
+                        // V2: data = pair, uint | V3: topic3 = fee, data = tickSpacing, pool
+                        let pair_offset = if is_v3 { 32 } else { 0 };
+                        if log.data.len() < pair_offset + 32 || (is_v3 && log.topics.len() < 4) {
+                            continue;
+                        }
+                        let pair_address = Address::from_slice(&log.data[pair_offset + 12..pair_offset + 32]);
+                        let (fee_tier, tick_spacing) = if is_v3 {
+                            let fee = U256::from_big_endian(log.topics[3].as_bytes()).low_u32();
+                            let tick_spacing = I256::from_raw(
+                                U256::from_big_endian(&log.data[0..32]),
+                            ).as_i32();
+                            (fee, Some(tick_spacing))
+                        } else {
+                            (V2_FEE_TIER, None)
+                        };
+
+                        if !found.iter().any(|c: &CreatedPair| c.pair_address == pair_address) {
+                            found.push(CreatedPair { counter_token, pair_address, is_v3, fee_tier, tick_spacing });
+                        }
+                    }
+                }
+            }
+
+            from_block = to_block + 1;
+        }
+
+        log::debug!("🔍 Found {} creation log(s) for token {:?} in last {} blocks", found.len(), token_address, COUNTER_PAIR_LOOKBACK_BLOCKS);
+        Ok(found)
+    }
+
+    /// Find a pair quoting the counter-token against a base token (V2 first, then V3)
+    async fn find_route_hop(&self, counter_token: Address, base_tokens: &[(String, Address)]) -> Option<QuoteRoute> {
+        let v2_abi: Abi = serde_json::from_str(FACTORY_V2_ABI).ok()?;
+        let v2_factory = Contract::new(get_factory_address(), v2_abi, self.provider.clone());
+
+        for (symbol, base_token_address) in base_tokens {
+            let pair_address: Address = v2_factory
+                .method::<_, Address>("getPair", (counter_token, *base_token_address))
+                .ok()?
+                .call()
+                .await
+                .unwrap_or_default();
+
+            if !pair_address.is_zero() {
+                return Some(QuoteRoute {
+                    hop_pair: pair_address,
+                    hop_is_v3: false,
+                    base_token: *base_token_address,
+                    base_token_symbol: symbol.clone(),
+                });
+            }
+        }
+
+        let v3_abi: Abi = serde_json::from_str(FACTORY_V3_ABI).ok()?;
+        let v3_factory = Contract::new(get_v3_factory_address(), v3_abi, self.provider.clone());
+
+        for (symbol, base_token_address) in base_tokens {
+            for fee in V3_FEE_TIERS {
+                let pool_address: Address = v3_factory
+                    .method::<_, Address>("getPool", (counter_token, *base_token_address, fee))
+                    .ok()?
+                    .call()
+                    .await
+                    .unwrap_or_default();
+
+                if !pool_address.is_zero() {
+                    return Some(QuoteRoute {
+                        hop_pair: pool_address,
+                        hop_is_v3: true,
+                        base_token: *base_token_address,
+                        base_token_symbol: symbol.clone(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,351 @@
+use anyhow::{anyhow, Result};
+use ethers::types::Address;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::core::candles::swap_time;
+use crate::types::{PaperConfig, PaperFill, PaperPortfolio, PaperPosition, QuoteCurrency, SwapEvent, TradeType};
+
+// Fee assumed for pools whose fee tier is unknown (PancakeSwap V2)
+const DEFAULT_POOL_FEE: f64 = 0.0025;
+
+/// Latest state of a token's market, from the stream
+#[derive(Debug, Clone, Copy)]
+struct Market {
+    price: f64,
+    /// (token, BNB) reserves and pool fee, for WBNB pairs with tracked reserves
+    pool: Option<(f64, f64, f64)>,
+}
+
+impl Market {
+    /// Tokens for `bnb` (buys) or BNB for `tokens` (sells), with pool price impact but no extra slippage
+    fn fill(&self, trade_type: TradeType, amount_in: f64) -> f64 {
+        match (self.pool, trade_type) {
+            (Some((token_reserve, bnb_reserve, fee)), TradeType::Buy) => {
+                let in_after_fee = amount_in * (1.0 - fee);
+                token_reserve * in_after_fee / (bnb_reserve + in_after_fee)
+            }
+            (Some((token_reserve, bnb_reserve, fee)), TradeType::Sell) => {
+                let in_after_fee = amount_in * (1.0 - fee);
+                bnb_reserve * in_after_fee / (token_reserve + in_after_fee)
+            }
+            // Bonding curve and non-BNB pairs fill at the last price
+            (None, TradeType::Buy) => amount_in / self.price,
+            (None, TradeType::Sell) => amount_in * self.price,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Order {
+    id: u64,
+    token_address: Address,
+    trade_type: TradeType,
+    amount_in: f64,
+    quoted_out: f64,
+    /// Cost basis of the tokens a sell takes out of the position
+    cost_bnb: f64,
+    submitted_at: f64,
+}
+
+#[derive(Default)]
+struct State {
+    clock: f64,
+    cash_bnb: f64,
+    gas_spent_bnb: f64,
+    next_order_id: u64,
+    markets: HashMap<Address, Market>,
+    positions: HashMap<Address, PaperPosition>,
+    pending: Vec<Order>,
+}
+
+/// Paper-trading engine fed by the live (or replayed) swap stream
+///
+/// Orders fill `latency` after they are placed, against the market as it
+/// stands then: swaps streamed in between move the price first. WBNB pairs
+/// fill along the pool's constant-product curve from the reserves reported
+/// with each swap (`SwapEvent::depth`); bonding curve and other pairs fill at
+/// the last BNB price. Simulated orders do not move the tracked market.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::{PaperConfig, PaperTrader, StreamerBuilder, TradeType};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let paper = PaperTrader::new(PaperConfig::default());
+/// let engine = paper.clone();
+/// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+///     .await?
+///     .token_address("0x...")
+///     .auto_detect()
+///     .on_swap(move |swap| {
+///         for fill in engine.on_swap(&swap) {
+///             println!("Filled {:?}: {} -> {}", fill.trade_type, fill.amount_in, fill.amount_out);
+///         }
+///         if swap.trade_type == TradeType::Buy && engine.position(swap.token.address).is_none() && engine.portfolio().pending_orders == 0 {
+///             let _ = engine.buy(swap.token.address, 0.5);
+///         }
+///     })
+///     .start()
+///     .await?;
+///
+/// let portfolio = paper.portfolio();
+/// println!("Equity {:.4} BNB", portfolio.equity_bnb);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PaperTrader {
+    config: PaperConfig,
+    state: Arc<Mutex<State>>,
+}
+
+impl PaperTrader {
+    pub fn new(config: PaperConfig) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                cash_bnb: config.starting_bnb,
+                ..State::default()
+            })),
+            config,
+        }
+    }
+
+    /// Update the market with a streamed swap, returning the orders that filled before it
+    pub fn on_swap(&self, swap: &SwapEvent) -> Vec<PaperFill> {
+        let Some(market) = market_of(swap) else {
+            return Vec::new();
+        };
+        let mut state = self.state.lock().unwrap();
+        let time = swap_time(swap) as f64;
+        let fills = self.fill_due(&mut state, time, Some(swap.token.address));
+        state.clock = state.clock.max(time);
+        state.markets.insert(swap.token.address, market);
+        if let Some(position) = state.positions.get_mut(&swap.token.address) {
+            position.last_price = Some(market.price);
+        }
+        fills
+    }
+
+    /// Fill every order due at `now` (unix seconds) against the current markets
+    ///
+    /// Quiet tokens may not stream a swap for a while; call this from a timer
+    /// so their orders still fill.
+    pub fn poll(&self, now: f64) -> Vec<PaperFill> {
+        let mut state = self.state.lock().unwrap();
+        let fills = self.fill_due(&mut state, now, None);
+        state.clock = state.clock.max(now);
+        fills
+    }
+
+    /// Place a buy spending `bnb_amount`, returning the order id
+    ///
+    /// The BNB is reserved immediately and returned if the order is rejected.
+    pub fn buy(&self, token: Address, bnb_amount: f64) -> Result<u64> {
+        let mut state = self.state.lock().unwrap();
+        if bnb_amount <= 0.0 || bnb_amount + self.config.gas_fee_bnb > state.cash_bnb {
+            return Err(anyhow!("Not enough paper BNB: {:.6} available, {:.6} needed", state.cash_bnb, bnb_amount + self.config.gas_fee_bnb));
+        }
+        let market = *state.markets.get(&token).ok_or_else(|| anyhow!("No streamed price for {:?} yet", token))?;
+        state.cash_bnb -= bnb_amount;
+        let quoted_out = market.fill(TradeType::Buy, bnb_amount);
+        Ok(self.place(&mut state, token, TradeType::Buy, bnb_amount, quoted_out, 0.0))
+    }
+
+    /// Place a sell of `token_amount` held tokens, returning the order id
+    pub fn sell(&self, token: Address, token_amount: f64) -> Result<u64> {
+        let mut state = self.state.lock().unwrap();
+        let market = *state.markets.get(&token).ok_or_else(|| anyhow!("No streamed price for {:?} yet", token))?;
+        let held = state.positions.get(&token).map(|p| p.amount).unwrap_or_default();
+        if token_amount <= 0.0 || token_amount > held {
+            return Err(anyhow!("Not enough paper tokens: {} held, {} to sell", held, token_amount));
+        }
+        if self.config.gas_fee_bnb > state.cash_bnb {
+            return Err(anyhow!("Not enough paper BNB for gas"));
+        }
+        let cost_bnb = take_tokens(&mut state, token, token_amount);
+        let quoted_out = market.fill(TradeType::Sell, token_amount);
+        Ok(self.place(&mut state, token, TradeType::Sell, token_amount, quoted_out, cost_bnb))
+    }
+
+    /// Sell the whole position in `token`
+    pub fn sell_all(&self, token: Address) -> Result<u64> {
+        let held = self.position(token).map(|p| p.amount).unwrap_or_default();
+        self.sell(token, held)
+    }
+
+    pub fn position(&self, token: Address) -> Option<PaperPosition> {
+        self.state.lock().unwrap().positions.get(&token).filter(|p| p.amount > 0.0).cloned()
+    }
+
+    pub fn cash(&self) -> f64 {
+        self.state.lock().unwrap().cash_bnb
+    }
+
+    pub fn portfolio(&self) -> PaperPortfolio {
+        let state = self.state.lock().unwrap();
+        let positions: Vec<PaperPosition> = state.positions.values().cloned().collect();
+        let held_value: f64 = positions.iter().map(|p| p.amount * p.last_price.unwrap_or_default()).sum();
+        // Pending orders still own their reserved BNB or tokens
+        let pending_value: f64 = state
+            .pending
+            .iter()
+            .map(|o| match o.trade_type {
+                TradeType::Buy => o.amount_in,
+                TradeType::Sell => o.amount_in * state.markets.get(&o.token_address).map(|m| m.price).unwrap_or_default(),
+            })
+            .sum();
+        PaperPortfolio {
+            cash_bnb: state.cash_bnb,
+            equity_bnb: state.cash_bnb + pending_value + held_value,
+            realized_pnl_bnb: positions.iter().map(|p| p.realized_pnl_bnb).sum(),
+            unrealized_pnl_bnb: positions.iter().map(PaperPosition::unrealized_pnl_bnb).sum(),
+            gas_spent_bnb: state.gas_spent_bnb,
+            pending_orders: state.pending.len(),
+            positions: positions.into_iter().filter(|p| p.amount > 0.0 || p.realized_pnl_bnb != 0.0).collect(),
+        }
+    }
+
+    fn place(&self, state: &mut State, token: Address, trade_type: TradeType, amount_in: f64, quoted_out: f64, cost_bnb: f64) -> u64 {
+        state.next_order_id += 1;
+        let id = state.next_order_id;
+        state.pending.push(Order {
+            id,
+            token_address: token,
+            trade_type,
+            amount_in,
+            quoted_out,
+            cost_bnb,
+            submitted_at: state.clock,
+        });
+        log::debug!("📝 [PAPER] Order #{} {} {} of {:?}", id, trade_type.as_str(), amount_in, token);
+        id
+    }
+
+    /// Fill orders (of `token`, or all) whose latency has elapsed at `time`
+    fn fill_due(&self, state: &mut State, time: f64, token: Option<Address>) -> Vec<PaperFill> {
+        let latency = self.config.latency.as_secs_f64();
+        let (due, pending): (Vec<Order>, Vec<Order>) = std::mem::take(&mut state.pending)
+            .into_iter()
+            .partition(|o| token.is_none_or(|t| t == o.token_address) && o.submitted_at + latency <= time);
+        state.pending = pending;
+        due.into_iter().map(|order| self.fill(state, order, time)).collect()
+    }
+
+    fn fill(&self, state: &mut State, order: Order, time: f64) -> PaperFill {
+        let market = state.markets[&order.token_address];
+        let amount_out = market.fill(order.trade_type, order.amount_in) * (1.0 - self.config.slippage_pct / 100.0);
+        let price = |out: f64| match order.trade_type {
+            TradeType::Buy if out > 0.0 => order.amount_in / out,
+            TradeType::Sell if order.amount_in > 0.0 => out / order.amount_in,
+            _ => 0.0,
+        };
+        let (quoted_price, fill_price) = (price(order.quoted_out), price(amount_out));
+        // Paying more on buys / receiving less on sells than quoted
+        let slippage_pct = match order.trade_type {
+            TradeType::Buy => (fill_price - quoted_price) / quoted_price * 100.0,
+            TradeType::Sell => (quoted_price - fill_price) / quoted_price * 100.0,
+        };
+
+        let rejected = self
+            .config
+            .max_slippage_pct
+            .filter(|max| slippage_pct > *max)
+            .map(|max| format!("slippage {:.2}% above the {:.2}% tolerance", slippage_pct, max));
+        let mut fill = PaperFill {
+            order_id: order.id,
+            token_address: order.token_address,
+            trade_type: order.trade_type,
+            amount_in: order.amount_in,
+            amount_out: 0.0,
+            quoted_price,
+            fill_price,
+            submitted_at: order.submitted_at,
+            filled_at: time,
+            rejected,
+        };
+        if fill.rejected.is_some() {
+            log::info!("🚫 [PAPER] Order #{} rejected: {}", order.id, fill.rejected.as_deref().unwrap_or_default());
+            match order.trade_type {
+                TradeType::Buy => state.cash_bnb += order.amount_in,
+                TradeType::Sell => add_tokens(state, order.token_address, order.amount_in, order.cost_bnb, market.price),
+            }
+            return fill;
+        }
+
+        fill.amount_out = amount_out;
+        state.cash_bnb -= self.config.gas_fee_bnb;
+        state.gas_spent_bnb += self.config.gas_fee_bnb;
+        match order.trade_type {
+            TradeType::Buy => add_tokens(state, order.token_address, amount_out, order.amount_in, market.price),
+            TradeType::Sell => {
+                state.cash_bnb += amount_out;
+                if let Some(position) = state.positions.get_mut(&order.token_address) {
+                    position.realized_pnl_bnb += amount_out - order.cost_bnb;
+                }
+            }
+        }
+        log::info!(
+            "✅ [PAPER] Order #{} filled: {} {} -> {} at {:.10} BNB ({:+.2}% vs quote)",
+            order.id,
+            order.trade_type.as_str(),
+            order.amount_in,
+            amount_out,
+            fill_price,
+            slippage_pct
+        );
+        fill
+    }
+}
+
+/// BNB price and pool state of the swap's token, if it can be expressed in BNB
+/// BNB price per token of a swap, from its normalized price or a WBNB base
+pub(crate) fn bnb_price(swap: &SwapEvent) -> Option<f64> {
+    let price = match swap.normalized_price {
+        Some(ref normalized) if normalized.currency == QuoteCurrency::Bnb => normalized.value,
+        _ if is_bnb(swap) => swap.price.value,
+        _ => return None,
+    };
+    (price > 0.0).then_some(price)
+}
+
+fn is_bnb(swap: &SwapEvent) -> bool {
+    matches!(swap.base_token.symbol.as_str(), "WBNB" | "BNB")
+}
+
+fn market_of(swap: &SwapEvent) -> Option<Market> {
+    let price = bnb_price(swap)?;
+    let is_bnb = is_bnb(swap);
+    let pool = match swap.depth {
+        Some(ref depth) if is_bnb && depth.token_reserve > 0.0 && depth.base_reserve > 0.0 => {
+            let fee = swap.pool.as_ref().map(|p| p.fee_tier as f64 / 1_000_000.0).unwrap_or(DEFAULT_POOL_FEE);
+            Some((depth.token_reserve, depth.base_reserve, fee))
+        }
+        _ => None,
+    };
+    Some(Market { price, pool })
+}
+
+fn add_tokens(state: &mut State, token: Address, amount: f64, cost_bnb: f64, price: f64) {
+    let position = state.positions.entry(token).or_insert_with(|| PaperPosition {
+        token_address: token,
+        amount: 0.0,
+        cost_bnb: 0.0,
+        realized_pnl_bnb: 0.0,
+        last_price: None,
+    });
+    position.amount += amount;
+    position.cost_bnb += cost_bnb;
+    position.last_price = Some(price);
+}
+
+/// Reserve tokens for a pending sell, returning their share of the cost basis
+fn take_tokens(state: &mut State, token: Address, amount: f64) -> f64 {
+    let Some(position) = state.positions.get_mut(&token) else {
+        return 0.0;
+    };
+    let cost = if position.amount > 0.0 { position.cost_bnb * amount / position.amount } else { 0.0 };
+    position.amount -= amount;
+    position.cost_bnb -= cost;
+    cost
+}
@@ -0,0 +1,138 @@
+use ethers::{
+    providers::Middleware,
+    types::{H256, U64},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::options::PendingResolvedCallback;
+use crate::types::{PendingSwapEvent, PendingSwapResolution, PendingSwapStatus, SwapEvent};
+
+// How often unconfirmed pending swaps are re-checked
+const SWEEP_INTERVAL: Duration = Duration::from_secs(3);
+
+// Time a pending swap gets to show up as a streamed SwapEvent before its receipt is checked
+const CONFIRMATION_GRACE: Duration = Duration::from_secs(15);
+
+// Pending swaps neither mined nor replaced after this long are reported as dropped
+const PENDING_TTL: Duration = Duration::from_secs(300);
+
+/// Pending swaps awaiting their confirmed SwapEvent, keyed by transaction hash
+#[derive(Clone, Default)]
+pub struct PendingTracker {
+    pending: Arc<Mutex<HashMap<H256, (PendingSwapEvent, Instant)>>>,
+}
+
+impl PendingTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a pending swap (ignored if its hash is already tracked)
+    pub fn insert(&self, event: PendingSwapEvent) {
+        self.pending
+            .lock()
+            .unwrap()
+            .entry(event.transaction_hash)
+            .or_insert_with(|| (event, Instant::now()));
+    }
+
+    /// Match a confirmed swap to its pending event, if one was seen
+    pub fn confirm(&self, swap: &SwapEvent) -> Option<PendingSwapResolution> {
+        let (pending, seen_at) = self.pending.lock().unwrap().remove(&swap.transaction_hash)?;
+        Some(PendingSwapResolution {
+            pending,
+            status: PendingSwapStatus::Confirmed,
+            swap: Some(swap.clone()),
+            block_number: Some(swap.block_number),
+            pending_ms: seen_at.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Number of pending swaps not yet resolved
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tracked swaps pending for at least `age`
+    fn older_than(&self, age: Duration) -> Vec<(PendingSwapEvent, Instant)> {
+        self.pending
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|(_, seen_at)| seen_at.elapsed() >= age)
+            .cloned()
+            .collect()
+    }
+
+    fn remove(&self, hash: &H256) -> bool {
+        self.pending.lock().unwrap().remove(hash).is_some()
+    }
+}
+
+/// Periodically resolve pending swaps that never produced a SwapEvent
+///
+/// A mined receipt resolves to Confirmed (swap on an unmonitored pool) or
+/// Reverted; otherwise a sender nonce past the pending one means the
+/// transaction was replaced, and anything older than the TTL is dropped.
+pub(crate) fn spawn_pending_sweeper<M: Middleware + 'static>(
+    provider: Arc<M>,
+    tracker: PendingTracker,
+    callback: PendingResolvedCallback,
+    cancel_token: CancellationToken,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [MEMPOOL] Pending sweeper cancelled");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+
+            for (pending, seen_at) in tracker.older_than(CONFIRMATION_GRACE) {
+                let hash = pending.transaction_hash;
+                let (status, block_number) = match provider.get_transaction_receipt(hash).await {
+                    Ok(Some(receipt)) => {
+                        let status = if receipt.status == Some(U64::one()) {
+                            PendingSwapStatus::Confirmed
+                        } else {
+                            PendingSwapStatus::Reverted
+                        };
+                        (status, receipt.block_number.map(|b| b.as_u64()))
+                    }
+                    Ok(None) => match provider.get_transaction_count(pending.from, None).await {
+                        Ok(nonce) if nonce > pending.nonce => (PendingSwapStatus::Replaced, None),
+                        _ if seen_at.elapsed() >= PENDING_TTL => (PendingSwapStatus::Dropped, None),
+                        _ => continue,
+                    },
+                    Err(e) => {
+                        log::debug!("⚠️ [MEMPOOL] Receipt lookup failed for {:?}: {}", hash, e);
+                        continue;
+                    }
+                };
+
+                // A streamed SwapEvent may have resolved it in the meantime
+                if !tracker.remove(&hash) {
+                    continue;
+                }
+                log::debug!("⏳ [MEMPOOL] Pending swap {:?} resolved as {}", hash, status.as_str());
+                callback(PendingSwapResolution {
+                    pending,
+                    status,
+                    swap: None,
+                    block_number,
+                    pending_ms: seen_at.elapsed().as_millis() as u64,
+                });
+            }
+        }
+    });
+}
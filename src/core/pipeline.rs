@@ -0,0 +1,77 @@
+use futures::FutureExt;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use tokio::sync::mpsc;
+
+/// Parses in flight per subscription when `StreamOptions::parse_concurrency` is unset
+pub const DEFAULT_PARSE_CONCURRENCY: usize = 8;
+
+/// Runs parses concurrently on the runtime's workers and hands results back in submission order
+///
+/// Each submitted future gets a sequence number; results that finish early
+/// wait in a reorder buffer until every earlier one has been emitted, so a
+/// subscription's swaps keep their on-chain order however long each parse
+/// takes. With a concurrency of 1 this is the old one-at-a-time behaviour.
+pub(crate) struct OrderedPipeline<T> {
+    concurrency: usize,
+    next_seq: u64,
+    next_emit: u64,
+    /// Finished results waiting for earlier ones (None if the work panicked)
+    done: BTreeMap<u64, Option<T>>,
+    tx: mpsc::UnboundedSender<(u64, Option<T>)>,
+    rx: mpsc::UnboundedReceiver<(u64, Option<T>)>,
+}
+
+impl<T: Send + 'static> OrderedPipeline<T> {
+    pub(crate) fn new(concurrency: usize) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            concurrency: concurrency.max(1),
+            next_seq: 0,
+            next_emit: 0,
+            done: BTreeMap::new(),
+            tx,
+            rx,
+        }
+    }
+
+    /// Submitted but not yet emitted
+    pub(crate) fn in_flight(&self) -> usize {
+        (self.next_seq - self.next_emit) as usize
+    }
+
+    /// Whether to stop reading the subscription until a result is emitted
+    pub(crate) fn is_full(&self) -> bool {
+        self.in_flight() >= self.concurrency
+    }
+
+    pub(crate) fn submit(&mut self, work: impl Future<Output = T> + Send + 'static) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            // A panicking parse must still release its sequence number
+            let result = AssertUnwindSafe(work).catch_unwind().await.ok();
+            let _ = tx.send((seq, result));
+        });
+    }
+
+    /// Next result in submission order, waiting for it to finish (None if it panicked)
+    ///
+    /// Pends forever with nothing in flight, so it can sit in a `select!`
+    /// (guarded by `in_flight() > 0`). Cancel-safe: a result received before
+    /// cancellation stays buffered.
+    pub(crate) async fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(result) = self.done.remove(&self.next_emit) {
+                self.next_emit += 1;
+                return result;
+            }
+            // The pipeline holds a sender, so the channel never closes
+            if let Some((seq, result)) = self.rx.recv().await {
+                self.done.insert(seq, result);
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::Middleware,
+    types::{Address, I256, U256},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::config::{get_usd_price_feeds, get_usd_stablecoins};
+
+const AGGREGATOR_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"decimals","outputs":[{"name":"","type":"uint8"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"latestRoundData","outputs":[{"name":"roundId","type":"uint80"},{"name":"answer","type":"int256"},{"name":"startedAt","type":"uint256"},{"name":"updatedAt","type":"uint256"},{"name":"answeredInRound","type":"uint80"}],"type":"function"}
+]"#;
+
+// How long a feed answer is reused before querying the aggregator again
+const PRICE_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// USD price oracle for base tokens
+///
+/// Stablecoins are valued at parity; WBNB, ETH and BTCB are read from their
+/// Chainlink feeds and cached for a short time so the hot path stays cheap.
+pub struct PriceOracle<M> {
+    provider: Arc<M>,
+    feeds: Arc<HashMap<Address, Address>>,
+    stablecoins: Arc<Vec<Address>>,
+    cache: Arc<RwLock<HashMap<Address, (f64, Instant)>>>,
+}
+
+impl<M: Middleware + 'static> PriceOracle<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            provider,
+            feeds: Arc::new(get_usd_price_feeds().into_iter().collect()),
+            stablecoins: Arc::new(get_usd_stablecoins()),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// USD price of one whole base token, or None if the token has no known USD source
+    pub async fn usd_price(&self, base_token: Address) -> Option<f64> {
+        if self.stablecoins.contains(&base_token) {
+            return Some(1.0);
+        }
+
+        let feed = *self.feeds.get(&base_token)?;
+
+        {
+            let cache = self.cache.read().await;
+            if let Some((price, fetched_at)) = cache.get(&base_token) {
+                if fetched_at.elapsed() < PRICE_CACHE_TTL {
+                    return Some(*price);
+                }
+            }
+        }
+
+        match self.read_feed(feed).await {
+            Ok(price) => {
+                let mut cache = self.cache.write().await;
+                cache.insert(base_token, (price, Instant::now()));
+                Some(price)
+            }
+            Err(e) => {
+                log::warn!("⚠️  [PRICE_ORACLE] Failed to read USD feed {:?}: {}", feed, e);
+                // Serve the stale value rather than nothing
+                let cache = self.cache.read().await;
+                cache.get(&base_token).map(|(price, _)| *price)
+            }
+        }
+    }
+
+    /// USD price of one BNB
+    pub async fn bnb_usd(&self) -> Option<f64> {
+        self.usd_price(crate::config::get_base_tokens()[0].1).await
+    }
+
+    async fn read_feed(&self, feed: Address) -> Result<f64> {
+        let abi: Abi = serde_json::from_str(AGGREGATOR_ABI)?;
+        let contract = Contract::new(feed, abi, self.provider.clone());
+
+        let decimals: u8 = contract.method("decimals", ())?.call().await?;
+        let (_, answer, _, _, _): (u128, I256, U256, U256, u128) =
+            contract.method("latestRoundData", ())?.call().await?;
+
+        if answer <= I256::zero() {
+            return Err(anyhow!("Feed returned non-positive answer"));
+        }
+
+        let answer: f64 = answer.to_string().parse().unwrap_or(0.0);
+        Ok(answer / 10f64.powi(decimals as i32))
+    }
+}
+
+impl<M> Clone for PriceOracle<M> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            feeds: self.feeds.clone(),
+            stablecoins: self.stablecoins.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
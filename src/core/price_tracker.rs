@@ -1,40 +1,431 @@
-use crate::types::PriceStats;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-#[derive(Debug, Clone)]
+use crate::types::{AveragePrice, CrossDirection, Indicator, IndicatorCross, IndicatorValue, PriceAlert, PriceAlertRule, PriceSession, PriceStats};
+
+// Bumped when the snapshot layout changes incompatibly
+const SNAPSHOT_VERSION: u32 = 1;
+
+// Default number of trades kept per pair for `price_history`
+const DEFAULT_HISTORY_LIMIT: usize = 1_000;
+
+// Default VWAP/TWAP windows
+const DEFAULT_AVERAGE_WINDOWS: &[Duration] = &[Duration::from_secs(300), Duration::from_secs(3600)];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct TradePoint {
+    // Unix time in seconds (fractional for millisecond block times)
+    time: f64,
+    price: f64,
+    volume: f64,
+}
+
+// Running state of one indicator for one pair
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct IndicatorState {
+    // Prices (EMA) or price changes (RSI) seen so far
+    samples: usize,
+    ema: f64,
+    avg_gain: f64,
+    avg_loss: f64,
+    value: Option<f64>,
+}
+
+impl IndicatorState {
+    // Advance with a new trade; `series` already ends with `price`
+    fn update(&mut self, indicator: Indicator, series: &VecDeque<TradePoint>, price: f64, previous: Option<f64>) {
+        let period = indicator.period().max(1);
+        self.value = match indicator {
+            Indicator::Sma(_) => (series.len() >= period)
+                .then(|| series.iter().rev().take(period).map(|t| t.price).sum::<f64>() / period as f64),
+            Indicator::Ema(_) => {
+                self.samples += 1;
+                if self.samples == 1 {
+                    self.ema = price;
+                } else {
+                    self.ema += (price - self.ema) * 2.0 / (period as f64 + 1.0);
+                }
+                (self.samples >= period).then_some(self.ema)
+            }
+            Indicator::Rsi(_) => {
+                let Some(previous) = previous else { return };
+                let (gain, loss) = ((price - previous).max(0.0), (previous - price).max(0.0));
+                self.samples += 1;
+                // Plain average until the first full period, Wilder smoothing after
+                let n = self.samples.min(period) as f64;
+                self.avg_gain += (gain - self.avg_gain) / n;
+                self.avg_loss += (loss - self.avg_loss) / n;
+                (self.samples >= period).then(|| {
+                    if self.avg_loss == 0.0 {
+                        100.0
+                    } else {
+                        100.0 - 100.0 / (1.0 + self.avg_gain / self.avg_loss)
+                    }
+                })
+            }
+        };
+    }
+}
+
+// One named session of one pair
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SessionState {
+    started_at: f64,
+    daily: bool,
+    first_price: f64,
+    high: f64,
+    low: f64,
+    last_price: f64,
+    swap_count: u64,
+}
+
+impl SessionState {
+    // A session whose baseline is `price` (the last known price when started)
+    fn starting_at(price: f64, time: f64, daily: bool) -> Self {
+        Self {
+            started_at: time,
+            daily,
+            first_price: price,
+            high: price,
+            low: price,
+            last_price: price,
+            swap_count: 0,
+        }
+    }
+
+    fn record(&mut self, price: f64, time: f64) {
+        if self.daily && utc_day(time) != utc_day(self.started_at) {
+            *self = Self::starting_at(price, utc_day(time) * 86_400.0, true);
+        }
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.last_price = price;
+        self.swap_count += 1;
+    }
+
+    fn to_session(self, name: &str) -> PriceSession {
+        PriceSession {
+            name: name.to_string(),
+            started_at: self.started_at,
+            daily: self.daily,
+            first_price: self.first_price,
+            high: self.high,
+            low: self.low,
+            last_price: self.last_price,
+            change_pct: if self.first_price > 0.0 {
+                (self.last_price - self.first_price) / self.first_price * 100.0
+            } else {
+                0.0
+            },
+            swap_count: self.swap_count,
+        }
+    }
+}
+
+fn utc_day(time: f64) -> f64 {
+    (time / 86_400.0).floor()
+}
+
+fn now() -> f64 {
+    chrono::Utc::now().timestamp_millis() as f64 / 1000.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PriceHistory {
-    prices: Vec<f64>,
+    token: String,
+    base_token: String,
+    // Every recorded trade, oldest first, capped at the tracker's history limit
+    series: VecDeque<TradePoint>,
     high: f64,
     low: f64,
     first_price: f64,
     last_price: Option<f64>,
     swap_count: u64,
+    // Trades within the longest average/alert window, plus the last one before it
+    trades: VecDeque<TradePoint>,
+    #[serde(default)]
+    indicators: Vec<(Indicator, IndicatorState)>,
+    #[serde(default)]
+    sessions: HashMap<String, SessionState>,
+    // Last time each move-within-window rule fired (index into the rule list)
+    #[serde(skip)]
+    move_alerts_fired: HashMap<usize, f64>,
 }
 
+/// Serializable copy of a PriceTracker's per-token state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceTrackerSnapshot {
+    version: u32,
+    /// When the snapshot was taken (RFC3339)
+    pub saved_at: String,
+    entries: HashMap<String, PriceHistory>,
+}
+
+impl PriceTrackerSnapshot {
+    /// Number of token/base pairs in the snapshot
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Per-token price statistics; clones share the same state
+#[derive(Clone)]
 pub struct PriceTracker {
-    history: Arc<RwLock<HashMap<String, PriceHistory>>>,
+    history: Arc<Mutex<HashMap<String, PriceHistory>>>,
+    // Running named sessions (name -> restarts daily)
+    sessions: Arc<Mutex<HashMap<String, bool>>>,
+    windows: Vec<Duration>,
+    alert_rules: Vec<PriceAlertRule>,
+    indicators: Vec<Indicator>,
+    history_limit: usize,
 }
 
 impl PriceTracker {
     pub fn new() -> Self {
+        Self::with_windows(DEFAULT_AVERAGE_WINDOWS)
+    }
+
+    /// Tracker computing VWAP/TWAP over the given windows
+    pub fn with_windows(windows: &[Duration]) -> Self {
         Self {
-            history: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(Mutex::new(HashMap::new())),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            windows: windows.to_vec(),
+            alert_rules: Vec::new(),
+            indicators: Vec::new(),
+            history_limit: DEFAULT_HISTORY_LIMIT,
+        }
+    }
+
+    /// Evaluate these alert rules on every update (results in `PriceStats::alerts`)
+    pub fn with_alerts(mut self, rules: &[PriceAlertRule]) -> Self {
+        self.alert_rules = rules.to_vec();
+        self
+    }
+
+    /// Compute these indicators on every update (values in `PriceStats::indicators`)
+    pub fn with_indicators(mut self, indicators: &[Indicator]) -> Self {
+        for indicator in indicators {
+            if !self.indicators.contains(indicator) {
+                self.indicators.push(*indicator);
+            }
+        }
+        self
+    }
+
+    /// Restart the session high/low/first price of every pair of `token` at its last price
+    ///
+    /// Trade history, averages and indicators are kept. Returns false if the token is unknown.
+    pub fn reset(&self, token: &str) -> bool {
+        let mut history_map = self.history.lock().unwrap();
+        let mut found = false;
+        for history in history_map.values_mut().filter(|h| h.token == token) {
+            let price = history.last_price.unwrap_or(history.first_price);
+            history.first_price = price;
+            history.high = price;
+            history.low = price;
+            history.swap_count = 0;
+            history.move_alerts_fired.clear();
+            found = true;
+        }
+        found
+    }
+
+    /// Start (or restart) a named session on every pair, measured from each pair's last price
+    pub fn start_session(&self, name: &str) {
+        self.begin_session(name, false);
+    }
+
+    /// Start a named session that restarts automatically at UTC midnight
+    pub fn start_daily_session(&self, name: &str) {
+        self.begin_session(name, true);
+    }
+
+    fn begin_session(&self, name: &str, daily: bool) {
+        let now = now();
+        let started_at = if daily { utc_day(now) * 86_400.0 } else { now };
+        self.sessions.lock().unwrap().insert(name.to_string(), daily);
+        for history in self.history.lock().unwrap().values_mut() {
+            if let Some(price) = history.last_price {
+                history.sessions.insert(name.to_string(), SessionState::starting_at(price, started_at, daily));
+            }
+        }
+    }
+
+    /// Stop tracking a named session
+    pub fn end_session(&self, name: &str) {
+        self.sessions.lock().unwrap().remove(name);
+        for history in self.history.lock().unwrap().values_mut() {
+            history.sessions.remove(name);
         }
     }
 
+    /// High/low/change of a token/base pair since the named session started
+    pub fn session_stats(&self, name: &str, token: &str, base_token: &str) -> Option<PriceSession> {
+        let key = format!("{}-{}", token, base_token);
+        let history_map = self.history.lock().unwrap();
+        history_map.get(&key)?.sessions.get(name).map(|session| session.to_session(name))
+    }
+
+    /// Keep at most `limit` trades per pair for `price_history` (default 1000)
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.history_limit = limit.max(1);
+        self
+    }
+
+    /// Recorded trades of a token/base pair at or after unix time `since` (seconds),
+    /// oldest first, as `(timestamp, price, volume)`
+    pub fn price_history(&self, token: &str, base_token: &str, since: f64) -> Vec<(f64, f64, f64)> {
+        let key = format!("{}-{}", token, base_token);
+        let history_map = self.history.lock().unwrap();
+        history_map
+            .get(&key)
+            .map(|history| {
+                history
+                    .series
+                    .iter()
+                    .filter(|t| t.time >= since)
+                    .map(|t| (t.time, t.price, t.volume))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Copy of the current highs/lows/first prices and trade history
+    pub fn snapshot(&self) -> PriceTrackerSnapshot {
+        PriceTrackerSnapshot {
+            version: SNAPSHOT_VERSION,
+            saved_at: chrono::Utc::now().to_rfc3339(),
+            entries: self.history.lock().unwrap().clone(),
+        }
+    }
+
+    /// Load state from a snapshot, replacing any tracked token/base pair it contains
+    pub fn restore(&self, snapshot: PriceTrackerSnapshot) -> Result<()> {
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(anyhow!(
+                "Unsupported price tracker snapshot version {} (expected {})",
+                snapshot.version,
+                SNAPSHOT_VERSION
+            ));
+        }
+        self.history.lock().unwrap().extend(snapshot.entries);
+        Ok(())
+    }
+
+    /// Write a snapshot to `path` as JSON (via a temp file, so a crash never leaves a partial file)
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec(&self.snapshot())?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Restore state saved with `save_to_file`; a missing file is not an error
+    pub fn load_from_file(&self, path: impl AsRef<Path>) -> Result<bool> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(false);
+        }
+        let snapshot: PriceTrackerSnapshot = serde_json::from_slice(&std::fs::read(path)?)?;
+        log::info!("💾 Restored price stats for {} pair(s) saved at {}", snapshot.len(), snapshot.saved_at);
+        self.restore(snapshot)?;
+        Ok(true)
+    }
+
+    /// Current statistics of a token/base pair, without recording a trade
+    ///
+    /// `alerts` is always empty; averages are computed up to now.
+    pub fn get_stats(&self, token: &str, base_token: &str) -> Option<PriceStats> {
+        let key = format!("{}-{}", token, base_token);
+        let history_map = self.history.lock().unwrap();
+        history_map.get(&key).map(|history| self.current_stats(history))
+    }
+
+    /// Current statistics of every tracked pair, keyed by (token, base token)
+    pub fn all_stats(&self) -> HashMap<(String, String), PriceStats> {
+        let history_map = self.history.lock().unwrap();
+        history_map
+            .values()
+            .map(|history| ((history.token.clone(), history.base_token.clone()), self.current_stats(history)))
+            .collect()
+    }
+
+    fn current_stats(&self, history: &PriceHistory) -> PriceStats {
+        let now = now();
+        let current_price = history.last_price.unwrap_or(history.first_price);
+        let last_price = history.series.len().checked_sub(2).map(|i| history.series[i].price);
+
+        PriceStats {
+            current_price,
+            last_price,
+            price_change: last_price.map(|lp| current_price - lp),
+            price_change_percent: last_price.map(|lp| if lp > 0.0 { (current_price - lp) / lp * 100.0 } else { 0.0 }),
+            high: history.high,
+            low: history.low,
+            first_price: history.first_price,
+            swap_count: history.swap_count as usize,
+            averages: self
+                .windows
+                .iter()
+                .map(|window| average_price(&history.trades, *window, now))
+                .collect(),
+            alerts: Vec::new(),
+            indicators: self.indicator_values(history),
+            crosses: Vec::new(),
+            sessions: session_list(history),
+        }
+    }
+
+    fn indicator_values(&self, history: &PriceHistory) -> Vec<IndicatorValue> {
+        self.indicators
+            .iter()
+            .map(|indicator| IndicatorValue {
+                indicator: *indicator,
+                value: history.indicators.iter().find(|(i, _)| i == indicator).and_then(|(_, state)| state.value),
+            })
+            .collect()
+    }
+
+    /// Record a price without volume, timestamped now (VWAP stays unavailable)
     pub async fn update_price(&self, token: &str, base_token: &str, price: f64) -> PriceStats {
+        self.record_trade(token, base_token, price, 0.0, now())
+    }
+
+    /// Record a trade of `volume` tokens at `price`, executed at unix time `time` (seconds)
+    pub async fn update_trade(&self, token: &str, base_token: &str, price: f64, volume: f64, time: f64) -> PriceStats {
+        self.record_trade(token, base_token, price, volume, time)
+    }
+
+    /// Non-async `update_trade`, for use from synchronous callbacks
+    pub fn record_trade(&self, token: &str, base_token: &str, price: f64, volume: f64, time: f64) -> PriceStats {
         let key = format!("{}-{}", token, base_token);
-        let mut history_map = self.history.write().await;
+        let sessions = self.sessions.lock().unwrap().clone();
+        let mut history_map = self.history.lock().unwrap();
 
         let history = history_map.entry(key).or_insert_with(|| PriceHistory {
-            prices: Vec::new(),
+            token: token.to_string(),
+            base_token: base_token.to_string(),
+            series: VecDeque::new(),
             high: price,
             low: price,
             first_price: price,
             last_price: None,
             swap_count: 0,
+            trades: VecDeque::new(),
+            indicators: Vec::new(),
+            sessions: HashMap::new(),
+            move_alerts_fired: HashMap::new(),
         });
 
         // Calculate changes
@@ -47,11 +438,12 @@ impl PriceTracker {
                 0.0
             }
         });
+        let (previous_high, previous_low) = (history.high, history.low);
 
         // Update history
-        history.prices.push(price);
-        if history.prices.len() > 100 {
-            history.prices.remove(0);
+        history.series.push_back(TradePoint { time, price, volume });
+        while history.series.len() > self.history_limit {
+            history.series.pop_front();
         }
 
         history.high = history.high.max(price);
@@ -59,6 +451,123 @@ impl PriceTracker {
         history.last_price = Some(price);
         history.swap_count += 1;
 
+        // Pairs first seen after a session started join it at this trade
+        for (name, daily) in sessions {
+            history
+                .sessions
+                .entry(name)
+                .or_insert_with(|| SessionState::starting_at(price, time, daily));
+        }
+        for session in history.sessions.values_mut() {
+            session.record(price, time);
+        }
+
+        history.trades.push_back(TradePoint { time, price, volume });
+        let longest = self
+            .windows
+            .iter()
+            .chain(self.alert_rules.iter().filter_map(|rule| match rule {
+                PriceAlertRule::MoveWithin { window, .. } => Some(window),
+                _ => None,
+            }))
+            .max()
+            .map(|w| w.as_secs_f64())
+            .unwrap_or_default();
+        // Keep one trade older than the window: it sets the price at the window start
+        while history.trades.len() > 1 && history.trades[1].time < time - longest {
+            history.trades.pop_front();
+        }
+
+        // SMA/EMA crossings compare the previous price against the previous indicator value
+        let mut crosses = Vec::new();
+        for indicator in &self.indicators {
+            let index = match history.indicators.iter().position(|(i, _)| i == indicator) {
+                Some(index) => index,
+                None => {
+                    history.indicators.push((*indicator, IndicatorState::default()));
+                    history.indicators.len() - 1
+                }
+            };
+            let state = &mut history.indicators[index].1;
+            let previous_value = state.value;
+            state.update(*indicator, &history.series, price, last_price);
+
+            if matches!(indicator, Indicator::Rsi(_)) {
+                continue;
+            }
+            if let (Some(lp), Some(pv), Some(value)) = (last_price, previous_value, state.value) {
+                let direction = if lp <= pv && price > value {
+                    Some(CrossDirection::Above)
+                } else if lp >= pv && price < value {
+                    Some(CrossDirection::Below)
+                } else {
+                    None
+                };
+                if let Some(direction) = direction {
+                    crosses.push(IndicatorCross {
+                        token: token.to_string(),
+                        currency: base_token.to_string(),
+                        indicator: *indicator,
+                        direction,
+                        price,
+                        value,
+                        time,
+                    });
+                }
+            }
+        }
+
+        let averages = self
+            .windows
+            .iter()
+            .map(|window| average_price(&history.trades, *window, time))
+            .collect();
+
+        let mut alerts = Vec::new();
+        for (index, rule) in self.alert_rules.iter().enumerate() {
+            let reference_price = match *rule {
+                PriceAlertRule::CrossesAbove(level) => last_price.filter(|lp| *lp < level && price >= level),
+                PriceAlertRule::CrossesBelow(level) => last_price.filter(|lp| *lp > level && price <= level),
+                PriceAlertRule::NewHigh => (last_price.is_some() && price > previous_high).then_some(previous_high),
+                PriceAlertRule::NewLow => (last_price.is_some() && price < previous_low).then_some(previous_low),
+                PriceAlertRule::MoveWithin { pct, window } => {
+                    let start = time - window.as_secs_f64();
+                    // Fire at most once per window
+                    if history.move_alerts_fired.get(&index).is_some_and(|fired| *fired > start) {
+                        None
+                    } else {
+                        let reference = history
+                            .trades
+                            .iter()
+                            .filter(|t| t.time >= start)
+                            .map(|t| t.price)
+                            .filter(|p| *p > 0.0)
+                            .max_by(|a, b| ((price - a) / a).abs().total_cmp(&((price - b) / b).abs()));
+                        reference.filter(|r| ((price - r) / r * 100.0).abs() >= pct)
+                    }
+                }
+            };
+
+            if let Some(reference_price) = reference_price {
+                if matches!(rule, PriceAlertRule::MoveWithin { .. }) {
+                    history.move_alerts_fired.insert(index, time);
+                }
+                alerts.push(PriceAlert {
+                    token: token.to_string(),
+                    currency: base_token.to_string(),
+                    rule: *rule,
+                    price,
+                    reference_price,
+                    change_pct: if reference_price > 0.0 {
+                        (price - reference_price) / reference_price * 100.0
+                    } else {
+                        0.0
+                    },
+                    time,
+                });
+            }
+        }
+
         PriceStats {
             current_price: price,
             last_price,
@@ -68,6 +577,11 @@ impl PriceTracker {
             low: history.low,
             first_price: history.first_price,
             swap_count: history.swap_count as usize,
+            averages,
+            alerts,
+            indicators: self.indicator_values(history),
+            crosses,
+            sessions: session_list(history),
         }
     }
 
@@ -83,5 +597,63 @@ impl PriceTracker {
             _ => "➡️",
         }
     }
+
+    /// ASCII counterpart of `get_trend_emoji`
+    pub fn get_trend_text(change_percent: Option<f64>) -> &'static str {
+        match change_percent {
+            None => "=",
+            Some(p) if p > 5.0 => "^^^",
+            Some(p) if p > 1.0 => "^^",
+            Some(p) if p > 0.0 => "^",
+            Some(p) if p < -5.0 => "vvv",
+            Some(p) if p < -1.0 => "vv",
+            Some(p) if p < 0.0 => "v",
+            _ => "=",
+        }
+    }
 }
+fn session_list(history: &PriceHistory) -> Vec<PriceSession> {
+    let mut sessions: Vec<PriceSession> = history
+        .sessions
+        .iter()
+        .map(|(name, session)| session.to_session(name))
+        .collect();
+    sessions.sort_by(|a, b| a.name.cmp(&b.name));
+    sessions
+}
+
+/// VWAP and TWAP of the trades in `[now - window, now]`
+fn average_price(trades: &VecDeque<TradePoint>, window: Duration, now: f64) -> AveragePrice {
+    let start = now - window.as_secs_f64();
 
+    let (notional, volume) = trades
+        .iter()
+        .filter(|t| t.time >= start)
+        .fold((0.0, 0.0), |(notional, volume), t| (notional + t.price * t.volume, volume + t.volume));
+    let vwap = (volume > 0.0).then(|| notional / volume);
+
+    // Each price holds until the next trade; the window starts at the price in effect then
+    let mut weighted = 0.0;
+    let mut elapsed = 0.0;
+    for (i, trade) in trades.iter().enumerate() {
+        let end = trades.get(i + 1).map(|next| next.time).unwrap_or(now);
+        let held = end.min(now) - trade.time.max(start);
+        if held > 0.0 {
+            weighted += trade.price * held;
+            elapsed += held;
+        }
+    }
+    let twap = if elapsed > 0.0 {
+        Some(weighted / elapsed)
+    } else {
+        trades.back().filter(|t| t.time >= start).map(|t| t.price)
+    };
+
+    AveragePrice { window, vwap, twap }
+}
+
+impl Default for PriceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
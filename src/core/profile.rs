@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Result};
+use ethers::{types::Address, utils::to_checksum};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::types::TokenProfile;
+
+// TrustWallet asset repository (only listed tokens have an entry)
+pub const TRUSTWALLET_ASSETS_URL: &str = "https://raw.githubusercontent.com/trustwallet/assets/master/blockchains/smartchain/assets";
+// Four.meme token detail endpoint (tokens launched on Four.meme)
+pub const FOURMEME_TOKEN_API: &str = "https://four.meme/meme-api/v1/private/token/get/v2";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Resolves a token's logo and project links
+#[derive(Clone)]
+pub struct TokenProfileClient {
+    client: reqwest::Client,
+}
+
+impl Default for TokenProfileClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenProfileClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// TrustWallet entry first, then the Four.meme API; None when neither knows the token
+    pub async fn profile(&self, token: Address) -> Option<TokenProfile> {
+        match self.trustwallet(token).await {
+            Ok(profile) => return Some(profile),
+            Err(e) => log::debug!("🖼️ [PROFILE] No TrustWallet entry for {:?}: {}", token, e),
+        }
+        match self.four_meme(token).await {
+            Ok(profile) => Some(profile),
+            Err(e) => {
+                log::debug!("🖼️ [PROFILE] No Four.meme profile for {:?}: {}", token, e);
+                None
+            }
+        }
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        let response = self.client.get(url).timeout(REQUEST_TIMEOUT).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("HTTP {}", response.status()));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn trustwallet(&self, token: Address) -> Result<TokenProfile> {
+        let base = format!("{}/{}", TRUSTWALLET_ASSETS_URL, to_checksum(&token, None));
+        let info = self.get_json(&format!("{}/info.json", base)).await?;
+        let link = |name: &str| {
+            info["links"]
+                .as_array()?
+                .iter()
+                .find(|l| l["name"].as_str() == Some(name))
+                .and_then(|l| l["url"].as_str())
+                .map(str::to_string)
+        };
+        Ok(TokenProfile {
+            logo_url: Some(format!("{}/logo.png", base)),
+            website: non_empty(&info["website"]),
+            twitter: link("twitter").or_else(|| link("x")),
+            telegram: link("telegram"),
+            description: non_empty(&info["description"]),
+        })
+    }
+
+    async fn four_meme(&self, token: Address) -> Result<TokenProfile> {
+        let body = self.get_json(&format!("{}?address={:?}", FOURMEME_TOKEN_API, token)).await?;
+        let data = &body["data"];
+        if !data.is_object() {
+            return Err(anyhow!("token not found"));
+        }
+        Ok(TokenProfile {
+            logo_url: non_empty(&data["image"]),
+            website: non_empty(&data["webUrl"]),
+            twitter: non_empty(&data["twitterUrl"]),
+            telegram: non_empty(&data["telegramUrl"]),
+            description: non_empty(&data["descr"]),
+        })
+    }
+}
+
+fn non_empty(value: &Value) -> Option<String> {
+    value.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
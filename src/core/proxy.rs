@@ -0,0 +1,181 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{transaction::eip2718::TypedTransaction, Address, Filter, TransactionRequest, H256},
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use crate::core::options::ImplementationChangedCallback;
+use crate::types::{ImplementationChangedEvent, ProxyInfo, ProxyKind};
+
+// EIP-1967 slots: keccak256("eip1967.proxy.<name>") - 1
+pub const EIP1967_IMPLEMENTATION_SLOT: &str = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+pub const EIP1967_BEACON_SLOT: &str = "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50";
+pub const EIP1967_ADMIN_SLOT: &str = "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103";
+
+// Upgraded(address indexed implementation), emitted by proxies and beacons
+pub const UPGRADED_TOPIC: &str = "0xbc7cd75a20ee27fd9adebab32041f755214dbc6bffa90cc0225b39da2e5c2d3b";
+// BeaconUpgraded(address indexed beacon)
+pub const BEACON_UPGRADED_TOPIC: &str = "0x1cf3b03a6cf19fa2baba4df148e9dcabedea7f8a5c07840e207e5c089be95d3e";
+
+// implementation() on an UpgradeableBeacon
+const BEACON_IMPLEMENTATION_SELECTOR: [u8; 4] = [0x5c, 0x60, 0xda, 0x1b];
+
+// EIP-1167 minimal proxy prefix, followed by the 20-byte implementation address
+const EIP1167_PREFIX: [u8; 10] = [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+
+/// Detect an EIP-1967 (transparent, UUPS, beacon) or EIP-1167 proxy and resolve its implementation
+pub async fn resolve_proxy<M: Middleware>(provider: &M, token: Address) -> Result<Option<ProxyInfo>> {
+    let code = provider
+        .get_code(token, None)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch bytecode: {}", e))?;
+    Ok(resolve_proxy_code(provider, token, &code).await)
+}
+
+/// Same as `resolve_proxy`, reusing bytecode that was already fetched
+pub(crate) async fn resolve_proxy_code<M: Middleware>(provider: &M, token: Address, code: &[u8]) -> Option<ProxyInfo> {
+    if code.len() >= 30 && code[..10] == EIP1167_PREFIX {
+        return Some(ProxyInfo {
+            kind: ProxyKind::Minimal,
+            implementation: Address::from_slice(&code[10..30]),
+            admin: None,
+            beacon: None,
+        });
+    }
+    if code.is_empty() {
+        return None;
+    }
+
+    if let Some(implementation) = read_address_slot(provider, token, EIP1967_IMPLEMENTATION_SLOT).await {
+        let admin = read_address_slot(provider, token, EIP1967_ADMIN_SLOT).await;
+        let kind = if admin.is_some() { ProxyKind::Transparent } else { ProxyKind::Uups };
+        return Some(ProxyInfo {
+            kind,
+            implementation,
+            admin,
+            beacon: None,
+        });
+    }
+
+    let beacon = read_address_slot(provider, token, EIP1967_BEACON_SLOT).await?;
+    Some(ProxyInfo {
+        kind: ProxyKind::Beacon,
+        implementation: beacon_implementation(provider, beacon).await?,
+        admin: None,
+        beacon: Some(beacon),
+    })
+}
+
+async fn read_address_slot<M: Middleware>(provider: &M, contract: Address, slot: &str) -> Option<Address> {
+    let value = provider.get_storage_at(contract, H256::from_str(slot).ok()?, None).await.ok()?;
+    let address = Address::from_slice(&value.as_bytes()[12..]);
+    (!address.is_zero()).then_some(address)
+}
+
+async fn beacon_implementation<M: Middleware>(provider: &M, beacon: Address) -> Option<Address> {
+    let tx: TypedTransaction = TransactionRequest::new().to(beacon).data(BEACON_IMPLEMENTATION_SELECTOR.to_vec()).into();
+    let data = provider.call(&tx, None).await.ok()?;
+    let address = Address::from_slice(data.get(12..32)?);
+    (!address.is_zero()).then_some(address)
+}
+
+/// Subscribe to upgrades of a proxy token (and of its beacon); non-proxies are not watched
+pub(crate) fn spawn_upgrade_watcher<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    callback: ImplementationChangedCallback,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let mut proxy = match resolve_proxy(provider.as_ref(), token_address).await {
+            Ok(Some(proxy)) if proxy.kind.is_upgradeable() => proxy,
+            Ok(_) => {
+                log::debug!("🧬 [PROXY] {:?} is not an upgradeable proxy, not watching upgrades", token_address);
+                return;
+            }
+            Err(e) => {
+                log::error!("❌ [PROXY] Failed to resolve proxy of {:?}: {}", token_address, e);
+                return;
+            }
+        };
+
+        let upgraded = H256::from_str(UPGRADED_TOPIC).unwrap();
+        let beacon_upgraded = H256::from_str(BEACON_UPGRADED_TOPIC).unwrap();
+        let filter = Filter::new()
+            .address(std::iter::once(token_address).chain(proxy.beacon).collect::<Vec<_>>())
+            .topic0(vec![upgraded, beacon_upgraded]);
+
+        let mut stream = match provider.subscribe_logs(&filter).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [PROXY] Failed to subscribe to upgrades: {}", e);
+                return;
+            }
+        };
+        log::debug!(
+            "🧬 [PROXY] Watching {} proxy {:?} (implementation {:?})",
+            proxy.kind.as_str(),
+            token_address,
+            proxy.implementation
+        );
+
+        loop {
+            let log = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [PROXY] Upgrade watcher cancelled for {:?}", token_address);
+                    break;
+                }
+                log = stream.next() => match log {
+                    Some(log) => log,
+                    None => {
+                        log::warn!("⚠️ [PROXY] Upgrade stream ended for {:?}", token_address);
+                        break;
+                    }
+                },
+            };
+
+            // A replaced beacon keeps emitting for its other proxies
+            if log.topics.len() < 2 || (log.address != token_address && Some(log.address) != proxy.beacon) {
+                continue;
+            }
+            let target = Address::from(log.topics[1]);
+            let new_implementation = if log.topics[0] == beacon_upgraded {
+                proxy.beacon = Some(target);
+                match beacon_implementation(provider.as_ref(), target).await {
+                    Some(implementation) => implementation,
+                    None => {
+                        log::warn!("⚠️ [PROXY] {:?} moved to beacon {:?} without an implementation", token_address, target);
+                        continue;
+                    }
+                }
+            } else {
+                target
+            };
+            if new_implementation == proxy.implementation {
+                continue;
+            }
+
+            log::warn!(
+                "🧬 [PROXY] {:?} upgraded: implementation {:?} -> {:?}",
+                token_address,
+                proxy.implementation,
+                new_implementation
+            );
+            callback(ImplementationChangedEvent {
+                token_address,
+                previous_implementation: proxy.implementation,
+                new_implementation,
+                beacon: proxy.beacon,
+                transaction_hash: log.transaction_hash.unwrap_or_default(),
+                block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+            });
+            proxy.implementation = new_implementation;
+        }
+    });
+}
@@ -0,0 +1,149 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::{Abi, Token},
+    contract::Contract,
+    providers::Middleware,
+    types::{Address, U256},
+    utils::{format_units, parse_units},
+};
+use std::sync::Arc;
+
+use crate::config::{get_v2_router_address, get_v3_quoter_address, get_wbnb_address};
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{PairInfo, TradeQuote, TradeType};
+
+const ROUTER_ABI: &str = r#"[
+    {"inputs":[{"name":"amountIn","type":"uint256"},{"name":"path","type":"address[]"}],"name":"getAmountsOut","outputs":[{"name":"amounts","type":"uint256[]"}],"stateMutability":"view","type":"function"}
+]"#;
+
+const QUOTER_V2_ABI: &str = r#"[
+    {"inputs":[{"components":[{"name":"tokenIn","type":"address"},{"name":"tokenOut","type":"address"},{"name":"amountIn","type":"uint256"},{"name":"fee","type":"uint24"},{"name":"sqrtPriceLimitX96","type":"uint160"}],"name":"params","type":"tuple"}],"name":"quoteExactInputSingle","outputs":[{"name":"amountOut","type":"uint256"},{"name":"sqrtPriceX96After","type":"uint160"},{"name":"initializedTicksCrossed","type":"uint32"},{"name":"gasEstimate","type":"uint256"}],"stateMutability":"nonpayable","type":"function"}
+]"#;
+
+/// Convert a whole-unit amount into raw token units
+pub(crate) fn to_raw_amount(amount: f64, decimals: u8) -> Result<U256> {
+    let formatted = format!("{:.*}", decimals as usize, amount);
+    Ok(parse_units(formatted, decimals as u32)?.into())
+}
+
+/// Convert raw token units into a whole-unit amount
+pub(crate) fn from_raw_amount(amount: U256, decimals: u8) -> f64 {
+    format_units(amount, decimals as u32)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Prices BNB <-> token orders against PancakeSwap's router and V3 quoter
+pub struct Quoter<M> {
+    provider: Arc<M>,
+    router: Contract<M>,
+    quoter_v3: Contract<M>,
+}
+
+impl<M: Middleware + 'static> Quoter<M> {
+    pub fn new(provider: Arc<M>) -> Result<Self> {
+        let router_abi: Abi = serde_json::from_str(ROUTER_ABI)?;
+        let quoter_abi: Abi = serde_json::from_str(QUOTER_V2_ABI)?;
+        Ok(Self {
+            router: Contract::new(get_v2_router_address(), router_abi, provider.clone()),
+            quoter_v3: Contract::new(get_v3_quoter_address(), quoter_abi, provider.clone()),
+            provider,
+        })
+    }
+
+    /// Quote spending `bnb_amount` BNB on the token, using the best of `pairs`
+    pub async fn quote_buy(&self, pairs: &[PairInfo], bnb_amount: f64) -> Result<TradeQuote> {
+        let amount_in = to_raw_amount(bnb_amount, 18)?;
+        self.best_quote(pairs, TradeType::Buy, amount_in).await
+    }
+
+    /// Quote selling `token_amount` tokens for BNB, using the best of `pairs`
+    pub async fn quote_sell(&self, pairs: &[PairInfo], token_amount: f64) -> Result<TradeQuote> {
+        let token = pairs.first().ok_or_else(|| anyhow!("No pairs to quote against"))?.token;
+        let decimals = TokenInfoCache::new(self.provider.clone()).get_token_info(token).await?.decimals;
+        let amount_in = to_raw_amount(token_amount, decimals)?;
+        self.best_quote(pairs, TradeType::Sell, amount_in).await
+    }
+
+    async fn best_quote(&self, pairs: &[PairInfo], trade_type: TradeType, amount_in: U256) -> Result<TradeQuote> {
+        let token = pairs.first().ok_or_else(|| anyhow!("No pairs to quote against"))?.token;
+        let decimals = TokenInfoCache::new(self.provider.clone()).get_token_info(token).await?.decimals;
+
+        let mut best: Option<(PairInfo, Vec<Address>, U256)> = None;
+        for pair in pairs {
+            match self.quote_pair(pair, trade_type, amount_in).await {
+                Ok(Some((path, amount_out))) => {
+                    if best.as_ref().is_none_or(|(_, _, out)| amount_out > *out) {
+                        best = Some((pair.clone(), path, amount_out));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::debug!("⚠️  Quote failed on pair {:?}: {}", pair.pair_address, e),
+            }
+        }
+
+        let (pair, path, amount_out) = best.ok_or_else(|| anyhow!("No quotable pair found for {:?}", token))?;
+        let (in_decimals, out_decimals) = match trade_type {
+            TradeType::Buy => (18, decimals),
+            TradeType::Sell => (decimals, 18),
+        };
+        let amount_in_f = from_raw_amount(amount_in, in_decimals);
+        let amount_out_f = from_raw_amount(amount_out, out_decimals);
+        let (bnb, tokens) = match trade_type {
+            TradeType::Buy => (amount_in_f, amount_out_f),
+            TradeType::Sell => (amount_out_f, amount_in_f),
+        };
+
+        Ok(TradeQuote {
+            token_address: token,
+            pair_address: pair.pair_address,
+            version: pair.version(),
+            trade_type,
+            path,
+            amount_in: amount_in_f,
+            amount_in_raw: amount_in,
+            amount_out: amount_out_f,
+            amount_out_raw: amount_out,
+            price_bnb: if tokens > 0.0 { bnb / tokens } else { 0.0 },
+        })
+    }
+
+    /// Output amount through a single pair, or None if it cannot be quoted from BNB
+    async fn quote_pair(&self, pair: &PairInfo, trade_type: TradeType, amount_in: U256) -> Result<Option<(Vec<Address>, U256)>> {
+        // Pairs priced through a non-base counter-token have no direct router path
+        if pair.route.is_some() {
+            return Ok(None);
+        }
+
+        let wbnb = get_wbnb_address();
+        let mut path = if pair.base_token == wbnb {
+            vec![wbnb, pair.token]
+        } else {
+            vec![wbnb, pair.base_token, pair.token]
+        };
+        if trade_type == TradeType::Sell {
+            path.reverse();
+        }
+
+        if !pair.is_v3 {
+            let amounts: Vec<U256> = self.router.method("getAmountsOut", (amount_in, path.clone()))?.call().await?;
+            return Ok(amounts.last().map(|out| (path, *out)));
+        }
+
+        // Multi-hop V3 paths need the hop pool's fee tier; only WBNB pools are quoted
+        if pair.base_token != wbnb {
+            return Ok(None);
+        }
+        let params = Token::Tuple(vec![
+            Token::Address(path[0]),
+            Token::Address(path[1]),
+            Token::Uint(amount_in),
+            Token::Uint(U256::from(pair.fee_tier)),
+            Token::Uint(U256::zero()),
+        ]);
+        let (amount_out, _, _, _): (U256, U256, u32, U256) =
+            self.quoter_v3.method("quoteExactInputSingle", (params,))?.call().await?;
+        Ok(Some((path, amount_out)))
+    }
+}
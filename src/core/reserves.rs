@@ -0,0 +1,253 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::Middleware,
+    types::{Address, Log, U256},
+    utils::format_units,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::core::pair_finder::V2_FEE_TIER;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{MarketDepth, PairInfo, PriceImpact, QuoteRoute, Reserves, TradeType};
+
+// Sync(uint112 reserve0, uint112 reserve1)
+pub const SYNC_TOPIC: &str = "0x1c411e9a96e071241c2f21f7726b17ae89e3cab4c78be50e062b03a9fffbbad1";
+
+// Price move used for the depth figure on MarketDepth
+const DEPTH_PRICE_MOVE: f64 = 0.02;
+
+const PAIR_RESERVES_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"getReserves","outputs":[{"name":"reserve0","type":"uint112"},{"name":"reserve1","type":"uint112"},{"name":"blockTimestampLast","type":"uint32"}],"type":"function"}
+]"#;
+
+/// How a pair's reserves map onto the monitored token and its base token
+#[derive(Debug, Clone, Copy)]
+struct PairLayout {
+    token_is_token0: bool,
+    token_decimals: u8,
+    base_decimals: u8,
+    // Swap fee as a fraction of the input amount
+    fee: f64,
+}
+
+/// In-memory reserve state for monitored V2 pairs, kept current by Sync events
+#[derive(Clone, Default)]
+pub struct ReserveTracker {
+    reserves: Arc<RwLock<HashMap<Address, Reserves>>>,
+    // State replaced by the most recent Sync, i.e. the reserves before the latest trade
+    previous: Arc<RwLock<HashMap<Address, Reserves>>>,
+    layouts: Arc<RwLock<HashMap<Address, PairLayout>>>,
+}
+
+impl ReserveTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest known reserves for a pair
+    pub async fn current_reserves(&self, pair: Address) -> Option<Reserves> {
+        self.reserves.read().await.get(&pair).copied()
+    }
+
+    /// Reserves of every tracked pair
+    pub async fn all_reserves(&self) -> HashMap<Address, Reserves> {
+        self.reserves.read().await.clone()
+    }
+
+    /// Post-swap pool price of the monitored token, in the pair's base token
+    ///
+    /// Unlike `SwapEvent::price` (the trade's execution price), this is the
+    /// marginal price implied by the reserves after the latest Sync.
+    pub async fn reserve_price(&self, pair: Address) -> Option<f64> {
+        let reserves = self.current_reserves(pair).await?;
+        let layout = *self.layouts.read().await.get(&pair)?;
+        layout.price(&reserves)
+    }
+
+    /// Marginal price before/after the latest Sync and current depth, computed without RPC
+    ///
+    /// Pairs emit Sync right before Swap, so when called for a freshly received
+    /// Swap this describes that trade.
+    pub async fn market_depth(&self, pair: Address) -> Option<MarketDepth> {
+        let layout = *self.layouts.read().await.get(&pair)?;
+        let reserves = self.current_reserves(pair).await?;
+        let previous = self.previous.read().await.get(&pair).copied();
+
+        let (token_reserve, base_reserve) = layout.amounts(&reserves)?;
+        let price_after = layout.price(&reserves)?;
+        let price_before = previous.and_then(|prev| layout.price(&prev));
+
+        // Constant product: moving the price by a factor f takes base_reserve * (sqrt(f) - 1) in (before fees)
+        let depth_2pct = base_reserve * ((1.0 + DEPTH_PRICE_MOVE).sqrt() - 1.0);
+
+        Some(MarketDepth {
+            price_before,
+            price_after,
+            token_reserve,
+            base_reserve,
+            depth_2pct,
+        })
+    }
+
+    /// Reserves of a pair as (token, base) in whole tokens
+    pub async fn reserve_amounts(&self, pair: Address) -> Option<(f64, f64)> {
+        let reserves = self.current_reserves(pair).await?;
+        let layout = *self.layouts.read().await.get(&pair)?;
+        layout.amounts(&reserves)
+    }
+
+    /// Expected slippage of a hypothetical trade against the tracked reserves
+    ///
+    /// `size` is the input amount: base token for buys, monitored token for sells.
+    pub async fn price_impact(&self, pair: Address, trade_type: TradeType, size: f64) -> Option<PriceImpact> {
+        let layout = *self.layouts.read().await.get(&pair)?;
+        let reserves = self.current_reserves(pair).await?;
+        let (token_reserve, base_reserve) = layout.amounts(&reserves)?;
+        if token_reserve <= 0.0 || base_reserve <= 0.0 || size <= 0.0 {
+            return None;
+        }
+
+        let spot_price = base_reserve / token_reserve;
+        let size_after_fee = size * (1.0 - layout.fee);
+        let (amount_out, execution_price, price_after) = match trade_type {
+            TradeType::Buy => {
+                let out = token_reserve * size_after_fee / (base_reserve + size_after_fee);
+                (out, size / out, (base_reserve + size) / (token_reserve - out))
+            }
+            TradeType::Sell => {
+                let out = base_reserve * size_after_fee / (token_reserve + size_after_fee);
+                (out, out / size, (base_reserve - out) / (token_reserve + size))
+            }
+        };
+
+        Some(PriceImpact {
+            pair_address: pair,
+            trade_type,
+            amount_in: size,
+            amount_out,
+            spot_price,
+            execution_price,
+            price_after,
+            impact_pct: (execution_price - spot_price).abs() / spot_price * 100.0,
+        })
+    }
+
+    /// Register a pair and read its current reserves so state is available before the first Sync
+    pub(crate) async fn seed<M: Middleware + 'static>(
+        &self,
+        provider: Arc<M>,
+        token_cache: &TokenInfoCache<M>,
+        pair_info: &PairInfo,
+    ) -> Result<()> {
+        self.seed_pool(provider, token_cache, pair_info.pair_address, pair_info.token, pair_info.base_token, pair_info.fee_tier)
+            .await
+    }
+
+    /// Register the V2 hop of a counter-token route, priced as `counter_token` in the route's base token
+    pub(crate) async fn seed_route<M: Middleware + 'static>(
+        &self,
+        provider: Arc<M>,
+        token_cache: &TokenInfoCache<M>,
+        counter_token: Address,
+        route: &QuoteRoute,
+    ) -> Result<()> {
+        self.seed_pool(provider, token_cache, route.hop_pair, counter_token, route.base_token, V2_FEE_TIER)
+            .await
+    }
+
+    async fn seed_pool<M: Middleware + 'static>(
+        &self,
+        provider: Arc<M>,
+        token_cache: &TokenInfoCache<M>,
+        pair: Address,
+        token: Address,
+        base_token: Address,
+        fee_tier: u32,
+    ) -> Result<()> {
+        let abi: Abi = serde_json::from_str(PAIR_RESERVES_ABI)?;
+        let contract = Contract::new(pair, abi, provider.clone());
+
+        let token0: Address = contract.method("token0", ())?.call().await?;
+        let (reserve0, reserve1, _): (u128, u128, u32) = contract.method("getReserves", ())?.call().await?;
+        let block_number = provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64();
+
+        let token_decimals = token_cache.get_token_info(token).await?.decimals;
+        let base_decimals = token_cache.get_token_info(base_token).await?.decimals;
+
+        self.layouts.write().await.insert(
+            pair,
+            PairLayout {
+                token_is_token0: token0 == token,
+                token_decimals,
+                base_decimals,
+                fee: fee_tier as f64 / 1_000_000.0,
+            },
+        );
+        self.reserves.write().await.insert(
+            pair,
+            Reserves {
+                reserve0: U256::from(reserve0),
+                reserve1: U256::from(reserve1),
+                block_number,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Apply a Sync log to the pair's reserve state
+    pub(crate) async fn apply_sync(&self, log: &Log) -> Result<Reserves> {
+        if log.data.len() < 64 {
+            return Err(anyhow!("Sync event data too short: {} bytes", log.data.len()));
+        }
+
+        let reserves = Reserves {
+            reserve0: U256::from_big_endian(&log.data[0..32]),
+            reserve1: U256::from_big_endian(&log.data[32..64]),
+            block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+        };
+
+        if let Some(prev) = self.reserves.write().await.insert(log.address, reserves) {
+            self.previous.write().await.insert(log.address, prev);
+        }
+        Ok(reserves)
+    }
+}
+
+impl PairLayout {
+    /// Split reserves into (token_reserve, base_reserve)
+    fn split(&self, reserves: &Reserves) -> (U256, U256) {
+        if self.token_is_token0 {
+            (reserves.reserve0, reserves.reserve1)
+        } else {
+            (reserves.reserve1, reserves.reserve0)
+        }
+    }
+
+    /// Reserves as (token, base) in whole tokens
+    fn amounts(&self, reserves: &Reserves) -> Option<(f64, f64)> {
+        let (token_reserve, base_reserve) = self.split(reserves);
+        let token_amount: f64 = format_units(token_reserve, self.token_decimals as u32).ok()?.parse().ok()?;
+        let base_amount: f64 = format_units(base_reserve, self.base_decimals as u32).ok()?.parse().ok()?;
+        Some((token_amount, base_amount))
+    }
+
+    /// Marginal price of the token in base token units
+    fn price(&self, reserves: &Reserves) -> Option<f64> {
+        let (token_amount, base_amount) = self.amounts(reserves)?;
+        if token_amount > 0.0 {
+            Some(base_amount / token_amount)
+        } else {
+            None
+        }
+    }
+}
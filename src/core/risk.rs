@@ -0,0 +1,113 @@
+use anyhow::{anyhow, Result};
+use ethers::{providers::Middleware, types::Address};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::core::proxy::resolve_proxy_code;
+use crate::types::{RiskCategory, RiskFlag, RiskReport};
+
+// Owner-only functions commonly used to rug or trap holders
+const RISKY_SELECTORS: &[(&str, &str, RiskCategory)] = &[
+    ("40c10f19", "mint(address,uint256)", RiskCategory::Mint),
+    ("a0712d68", "mint(uint256)", RiskCategory::Mint),
+    ("f9f92be4", "blacklist(address)", RiskCategory::Blacklist),
+    ("44337ea1", "addToBlacklist(address)", RiskCategory::Blacklist),
+    ("153b0d1e", "setBlacklist(address,bool)", RiskCategory::Blacklist),
+    ("455a4396", "blacklistAddress(address,bool)", RiskCategory::Blacklist),
+    ("9c0db5f3", "setBots(address[],bool)", RiskCategory::Blacklist),
+    ("d34628cc", "addBots(address[])", RiskCategory::Blacklist),
+    ("8456cb59", "pause()", RiskCategory::Pause),
+    ("3f4ba83a", "unpause()", RiskCategory::Pause),
+    ("16c38b3c", "setPaused(bool)", RiskCategory::Pause),
+    ("c2e5ec04", "setTradingEnabled(bool)", RiskCategory::Pause),
+    ("c4081a4c", "setTaxFee(uint256)", RiskCategory::TaxChange),
+    ("0b78f9c0", "setFees(uint256,uint256)", RiskCategory::TaxChange),
+    ("dc1052e2", "setBuyTax(uint256)", RiskCategory::TaxChange),
+    ("8cd09d50", "setSellTax(uint256)", RiskCategory::TaxChange),
+    ("c647b20e", "setTaxes(uint256,uint256)", RiskCategory::TaxChange),
+    ("6db79437", "updateFees(uint256,uint256)", RiskCategory::TaxChange),
+    ("3659cfe6", "upgradeTo(address)", RiskCategory::Proxy),
+    ("4f1ef286", "upgradeToAndCall(address,bytes)", RiskCategory::Proxy),
+];
+
+/// All 4-byte PUSH4 immediates in the bytecode (function dispatcher selectors)
+fn push4_selectors(code: &[u8]) -> HashSet<[u8; 4]> {
+    let mut selectors = HashSet::new();
+    let mut i = 0;
+    while i < code.len() {
+        let op = code[i];
+        if (0x60..=0x7f).contains(&op) {
+            let size = (op - 0x5f) as usize;
+            if op == 0x63 && i + 4 < code.len() {
+                selectors.insert([code[i + 1], code[i + 2], code[i + 3], code[i + 4]]);
+            }
+            i += size;
+        }
+        i += 1;
+    }
+    selectors
+}
+
+/// Scans token bytecode for dangerous owner functions and upgradeability
+pub struct RiskScanner<M> {
+    provider: Arc<M>,
+}
+
+impl<M: Middleware + 'static> RiskScanner<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self { provider }
+    }
+
+    /// Scan a token's bytecode (and its implementation, if it is a proxy)
+    pub async fn scan(&self, token: Address) -> Result<RiskReport> {
+        let code = self
+            .provider
+            .get_code(token, None)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch bytecode: {}", e))?;
+        if code.is_empty() {
+            return Err(anyhow!("No contract code at {:?}", token));
+        }
+
+        let implementation = resolve_proxy_code(self.provider.as_ref(), token, &code).await.map(|proxy| proxy.implementation);
+        let mut selectors = push4_selectors(&code);
+        if let Some(implementation) = implementation {
+            match self.provider.get_code(implementation, None).await {
+                Ok(impl_code) => selectors.extend(push4_selectors(&impl_code)),
+                Err(e) => log::debug!("⚠️  [RISK] Failed to fetch implementation code: {}", e),
+            }
+        }
+
+        let mut flags: Vec<RiskFlag> = RISKY_SELECTORS
+            .iter()
+            .filter_map(|(selector, signature, category)| {
+                let bytes: [u8; 4] = ethers::utils::hex::decode(selector).ok()?.try_into().ok()?;
+                selectors.contains(&bytes).then(|| RiskFlag {
+                    category: *category,
+                    selector: format!("0x{}", selector),
+                    signature: signature.to_string(),
+                })
+            })
+            .collect();
+
+        if implementation.is_some() && !flags.iter().any(|f| f.category == RiskCategory::Proxy) {
+            flags.push(RiskFlag {
+                category: RiskCategory::Proxy,
+                selector: String::new(),
+                signature: "proxy delegating to implementation".to_string(),
+            });
+        }
+
+        let mut categories: Vec<RiskCategory> = flags.iter().map(|f| f.category).collect();
+        categories.dedup();
+        let score = categories.iter().map(|c| c.weight()).sum::<u32>().min(100) as u8;
+
+        Ok(RiskReport {
+            token_address: token,
+            is_proxy: implementation.is_some(),
+            implementation,
+            flags,
+            score,
+        })
+    }
+}
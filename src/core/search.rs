@@ -0,0 +1,148 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    providers::Middleware,
+    types::{Address, Filter, H256},
+};
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::{get_base_tokens, get_bonding_curve_address, get_factory_address, get_pair_created_topic, get_pool_created_topic, get_v3_factory_address};
+use crate::core::backfill::scan_logs;
+use crate::core::creator::{decode_token_create, TOKEN_CREATE_TOPIC};
+use crate::core::liquidity::pool_liquidity_usd;
+use crate::core::new_pairs::split_pair;
+use crate::core::price_oracle::PriceOracle;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::TokenCandidate;
+
+// Default search window (about a day of blocks)
+pub const SEARCH_LOOKBACK_BLOCKS: u64 = 28_800;
+
+// Token metadata lookups run concurrently
+const METADATA_CONCURRENCY: usize = 32;
+
+#[derive(Default)]
+struct Seen {
+    name: Option<String>,
+    symbol: Option<String>,
+    // (pool, base token)
+    pairs: Vec<(Address, Address)>,
+    four_meme: bool,
+    first_seen_block: u64,
+}
+
+/// Tokens whose symbol equals `query` or whose name contains it (case-insensitive)
+///
+/// Only tokens that got a PancakeSwap base-token pair or a Four.meme launch in
+/// the last `lookback_blocks` are found. Exact symbol matches come first, then
+/// the most liquid.
+pub async fn search_tokens<M: Middleware + 'static>(provider: Arc<M>, query: &str, lookback_blocks: u64) -> Result<Vec<TokenCandidate>> {
+    let query = query.trim().trim_start_matches('$').to_lowercase();
+    if query.is_empty() {
+        return Err(anyhow!("Empty search query"));
+    }
+
+    let latest = provider
+        .get_block_number()
+        .await
+        .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+        .as_u64();
+    let from_block = latest.saturating_sub(lookback_blocks);
+    let base_tokens = get_base_tokens();
+    let mut seen: HashMap<Address, Seen> = HashMap::new();
+
+    let created = Filter::new()
+        .address(vec![get_factory_address(), get_v3_factory_address()])
+        .topic0(vec![get_pair_created_topic(), get_pool_created_topic()]);
+    for log in scan_logs(&provider, created, from_block, latest).await? {
+        if log.topics.len() < 3 {
+            continue;
+        }
+        let is_v3 = log.topics[0] == get_pool_created_topic();
+        // V2: data = pair, uint | V3: data = tickSpacing, pool
+        let offset = if is_v3 { 32 } else { 0 };
+        if log.data.len() < offset + 32 {
+            continue;
+        }
+        let pool = Address::from_slice(&log.data[offset + 12..offset + 32]);
+        let (token, Some((_, base_token))) = split_pair(Address::from(log.topics[1]), Address::from(log.topics[2]), &base_tokens) else {
+            continue;
+        };
+        let entry = seen.entry(token).or_default();
+        entry.pairs.push((pool, base_token));
+        let block = log.block_number.map(|b| b.as_u64()).unwrap_or(latest);
+        if entry.first_seen_block == 0 || block < entry.first_seen_block {
+            entry.first_seen_block = block;
+        }
+    }
+
+    // Four.meme launches carry their name and symbol
+    let launches = Filter::new().address(get_bonding_curve_address()).topic0(H256::from_str(TOKEN_CREATE_TOPIC)?);
+    for launch in scan_logs(&provider, launches, from_block, latest).await?.iter().filter_map(decode_token_create) {
+        let entry = seen.entry(launch.token_address).or_default();
+        entry.name = Some(launch.name);
+        entry.symbol = Some(launch.symbol);
+        entry.four_meme = true;
+        if entry.first_seen_block == 0 || launch.block_number < entry.first_seen_block {
+            entry.first_seen_block = launch.block_number;
+        }
+    }
+    log::debug!("🔎 [SEARCH] {} token(s) seen in blocks {}..{}", seen.len(), from_block, latest);
+
+    let token_cache = TokenInfoCache::new(provider.clone());
+    let unnamed: Vec<Address> = seen.iter().filter(|(_, s)| s.symbol.is_none()).map(|(address, _)| *address).collect();
+    let metadata: Vec<_> = stream::iter(unnamed)
+        .map(|address| {
+            let token_cache = &token_cache;
+            async move { (address, token_cache.get_token_info(address).await) }
+        })
+        .buffer_unordered(METADATA_CONCURRENCY)
+        .collect()
+        .await;
+    for (address, info) in metadata {
+        if let (Ok(info), Some(entry)) = (info, seen.get_mut(&address)) {
+            entry.name = Some(info.name);
+            entry.symbol = Some(info.symbol);
+        }
+    }
+
+    let price_oracle = PriceOracle::new(provider.clone());
+    let mut candidates = Vec::new();
+    for (address, entry) in seen {
+        let (Some(name), Some(symbol)) = (entry.name, entry.symbol) else {
+            continue;
+        };
+        let exact_symbol = symbol.to_lowercase() == query;
+        if !exact_symbol && !name.to_lowercase().contains(&query) {
+            continue;
+        }
+
+        let mut liquidity_usd = None;
+        for (pool, base_token) in &entry.pairs {
+            if let Some(usd) = pool_liquidity_usd(&provider, &price_oracle, &token_cache, *pool, *base_token).await {
+                *liquidity_usd.get_or_insert(0.0) += usd;
+            }
+        }
+
+        candidates.push(TokenCandidate {
+            address,
+            name,
+            symbol,
+            exact_symbol,
+            pairs: entry.pairs.into_iter().map(|(pool, _)| pool).collect(),
+            liquidity_usd,
+            four_meme: entry.four_meme,
+            first_seen_block: entry.first_seen_block,
+        });
+    }
+
+    candidates.sort_by(|a, b| {
+        b.exact_symbol
+            .cmp(&a.exact_symbol)
+            .then(b.liquidity_usd.unwrap_or(0.0).total_cmp(&a.liquidity_usd.unwrap_or(0.0)))
+    });
+    log::info!("🔎 [SEARCH] {} candidate(s) for \"{}\"", candidates.len(), query);
+    Ok(candidates)
+}
@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    abi::{self, Token},
+    providers::{call_raw::spoof, Middleware, RawCall},
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, U256},
+    utils::keccak256,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::{get_v2_router_address, get_wbnb_address};
+use crate::core::tax::pct;
+
+// BNB spent on the simulated buy (0.01 BNB)
+pub const ROUND_TRIP_BNB: u64 = 10_000_000_000_000_000;
+
+// Throwaway wallet the helper contract is injected at (no code, no history)
+const SIMULATION_WALLET: &str = "0x000000000000000000000000000000000000beef";
+
+// Sender of the simulation call
+const SIMULATION_CALLER: &str = "0x000000000000000000000000000000000000cafe";
+
+// Runtime code of the round-trip helper, injected at SIMULATION_WALLET by state override.
+// Assembled from contracts/round_trip_helper.easm; `python3 scripts/round_trip_helper.py`
+// checks these bytes against it and runs it on mock router scenarios (`--write` rebuilds them).
+//
+// Called with abi.encode(router, token, bnbIn, buyQuote, buy, sellQuote, sell), where the
+// four bytes arguments are complete router calldata. It staticcalls buyQuote
+// (getAmountsOut), calls buy with bnbIn as value, reads token.balanceOf(this), then
+// staticcalls sellQuote, approves the router and calls sell with the first argument of
+// both replaced by the balance received. Returns
+// (buyOk, expectedTokens, tokensReceived, sellOk, expectedBnb, bnbOut); empty calldata
+// (the router paying out BNB) just stops. It never reverts.
+const ROUND_TRIP_HELPER: &str = "0x3615610172576060358035906020018190610400375f5f826104005f355afa905015610054573d5f6108003e3d60601161005457610820516020026040013d1061005457610820516020026108200151610220525b6080358035906020018190610400375f5f826104006040355f355af1905080610200521561016b575f610800526370a0823160e01b610400523061040452602061080060246104006020355afa1561016b576108005180610240521561016b5760a03580359060200181906104003761024051610404525f5f826104005f355afa90501561010b573d5f6108003e3d60601161010b57610820516020026040013d1061010b57610820516020026108200151610280525b63095ea7b360e01b610400525f356104045261024051610424525f5f60446104005f6020355af150476102c05260c03580359060200181906104003761024051610404525f5f826104005f5f355af19050610260526102c05147036102a0525b60c0610200f35b00";
+
+/// Router calldata: 4-byte selector followed by the encoded arguments
+fn calldata(signature: &str, args: &[Token]) -> Bytes {
+    let mut data = keccak256(signature)[0..4].to_vec();
+    data.extend(abi::encode(args));
+    Bytes::from(data)
+}
+
+/// Outcome of buying a token with BNB and selling it straight back
+#[derive(Debug, Clone)]
+pub struct RoundTrip {
+    pub bnb_in: U256,
+    pub buy_succeeded: bool,
+    /// Tokens getAmountsOut promised for `bnb_in`
+    pub expected_tokens: U256,
+    /// Tokens that actually reached the wallet
+    pub tokens_received: U256,
+    pub sell_succeeded: bool,
+    /// BNB getAmountsOut promised for `tokens_received`
+    pub expected_bnb: U256,
+    /// BNB that actually came back from the sell
+    pub bnb_out: U256,
+}
+
+impl RoundTrip {
+    /// Share of the bought tokens withheld by the token contract (percent)
+    pub fn buy_tax_pct(&self) -> Option<f64> {
+        if !self.buy_succeeded {
+            return None;
+        }
+        pct(self.expected_tokens.saturating_sub(self.tokens_received), self.expected_tokens)
+    }
+
+    /// Share of the sell proceeds withheld by the token contract (percent)
+    pub fn sell_tax_pct(&self) -> Option<f64> {
+        if !self.sell_succeeded {
+            return None;
+        }
+        pct(self.expected_bnb.saturating_sub(self.bnb_out), self.expected_bnb)
+    }
+
+    /// BNB lost between buying and selling (percent), pool fees and price impact included
+    pub fn loss_pct(&self) -> Option<f64> {
+        if !self.buy_succeeded {
+            return None;
+        }
+        pct(self.bnb_in.saturating_sub(self.bnb_out), self.bnb_in)
+    }
+}
+
+/// Simulates PancakeSwap V2 router trades with eth_call state overrides
+///
+/// Requires an RPC node that supports eth_call state overrides.
+pub struct RouterSimulator<M> {
+    provider: Arc<M>,
+}
+
+impl<M: Middleware + 'static> RouterSimulator<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self { provider }
+    }
+
+    /// Buy `token` with `bnb_in` through the V2 router and sell everything received back,
+    /// in one eth_call. Non-WBNB base tokens are routed through their WBNB pair.
+    pub async fn round_trip(&self, token: Address, base_token: Address, bnb_in: U256) -> Result<RoundTrip> {
+        let router = get_v2_router_address();
+        let wbnb = get_wbnb_address();
+        let wallet = Address::from_str(SIMULATION_WALLET)?;
+
+        let mut buy_path = vec![wbnb];
+        if base_token != wbnb {
+            buy_path.push(base_token);
+        }
+        buy_path.push(token);
+        let sell_path: Vec<Address> = buy_path.iter().rev().copied().collect();
+        let path = |addresses: &[Address]| Token::Array(addresses.iter().map(|a| Token::Address(*a)).collect());
+
+        // Amounts left at zero are filled in by the helper
+        let buy_quote = calldata("getAmountsOut(uint256,address[])", &[Token::Uint(bnb_in), path(&buy_path)]);
+        let buy = calldata(
+            "swapExactETHForTokensSupportingFeeOnTransferTokens(uint256,address[],address,uint256)",
+            &[Token::Uint(U256::zero()), path(&buy_path), Token::Address(wallet), Token::Uint(U256::MAX)],
+        );
+        let sell_quote = calldata("getAmountsOut(uint256,address[])", &[Token::Uint(U256::zero()), path(&sell_path)]);
+        let sell = calldata(
+            "swapExactTokensForETHSupportingFeeOnTransferTokens(uint256,uint256,address[],address,uint256)",
+            &[
+                Token::Uint(U256::zero()),
+                Token::Uint(U256::zero()),
+                path(&sell_path),
+                Token::Address(wallet),
+                Token::Uint(U256::MAX),
+            ],
+        );
+
+        let data = abi::encode(&[
+            Token::Address(router),
+            Token::Address(token),
+            Token::Uint(bnb_in),
+            Token::Bytes(buy_quote.to_vec()),
+            Token::Bytes(buy.to_vec()),
+            Token::Bytes(sell_quote.to_vec()),
+            Token::Bytes(sell.to_vec()),
+        ]);
+        let tx: TypedTransaction = TransactionRequest::new()
+            .from(Address::from_str(SIMULATION_CALLER)?)
+            .to(wallet)
+            .data(Bytes::from(data))
+            .into();
+
+        let mut state = spoof::state();
+        state
+            .account(wallet)
+            .code(Bytes::from_str(ROUND_TRIP_HELPER)?)
+            .balance(bnb_in);
+
+        let output = self
+            .provider
+            .provider()
+            .call_raw(&tx)
+            .state(&state)
+            .await
+            .map_err(|e| anyhow!("Round-trip simulation failed: {}", e))?;
+        if output.len() < 6 * 32 {
+            return Err(anyhow!("Unexpected round-trip output ({} bytes)", output.len()));
+        }
+        let word = |i: usize| U256::from_big_endian(&output[i * 32..(i + 1) * 32]);
+
+        Ok(RoundTrip {
+            bnb_in,
+            buy_succeeded: !word(0).is_zero(),
+            expected_tokens: word(1),
+            tokens_received: word(2),
+            sell_succeeded: !word(3).is_zero(),
+            expected_bnb: word(4),
+            bnb_out: word(5),
+        })
+    }
+}
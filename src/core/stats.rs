@@ -0,0 +1,162 @@
+use ethers::types::Address;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::core::candles::swap_time;
+use crate::types::{SwapEvent, TradeType, TraderStats, TraderWindow, VolumeStats, VolumeWindow};
+
+// DexScreener-style rolling windows: 5m, 1h, 24h
+pub const DEFAULT_STATS_WINDOWS: &[Duration] = &[
+    Duration::from_secs(300),
+    Duration::from_secs(3600),
+    Duration::from_secs(86_400),
+];
+
+#[derive(Debug, Clone, Copy)]
+struct TradeRecord {
+    time: i64,
+    trade_type: TradeType,
+    tokens: f64,
+    usd: Option<f64>,
+    trader: Address,
+}
+
+/// Rolling per-token trade statistics
+#[derive(Clone)]
+pub struct TradeStats {
+    trades: Arc<Mutex<HashMap<Address, VecDeque<TradeRecord>>>>,
+    windows: Vec<Duration>,
+}
+
+impl TradeStats {
+    pub fn new() -> Self {
+        Self::with_windows(DEFAULT_STATS_WINDOWS)
+    }
+
+    pub fn with_windows(windows: &[Duration]) -> Self {
+        Self {
+            trades: Arc::new(Mutex::new(HashMap::new())),
+            windows: windows.to_vec(),
+        }
+    }
+
+    /// Add a swap to its token's windows
+    pub fn record(&self, swap: &SwapEvent) {
+        let record = TradeRecord {
+            time: swap_time(swap),
+            trade_type: swap.trade_type,
+            tokens: swap.token.amount.parse().unwrap_or_default(),
+            usd: swap.usd_value,
+            // Router swaps report the router as recipient; the tx sender is the real trader
+            trader: swap.trader.unwrap_or(swap.recipient),
+        };
+
+        let longest = self.windows.iter().max().map(|w| w.as_secs() as i64).unwrap_or_default();
+        let cutoff = chrono::Utc::now().timestamp().max(record.time) - longest;
+
+        let mut trades = self.trades.lock().unwrap();
+        let history = trades.entry(swap.token.address).or_default();
+        history.push_back(record);
+        while history.front().is_some_and(|t| t.time < cutoff) {
+            history.pop_front();
+        }
+    }
+
+    /// Buy/sell volume and trade counts of a token over each window, ending now
+    pub fn volume(&self, token: Address) -> Option<VolumeStats> {
+        let now = chrono::Utc::now().timestamp();
+        let trades = self.trades.lock().unwrap();
+        let history = trades.get(&token)?;
+
+        let windows = self
+            .windows
+            .iter()
+            .map(|window| {
+                let start = now - window.as_secs() as i64;
+                let mut stats = VolumeWindow {
+                    window: *window,
+                    buys: 0,
+                    sells: 0,
+                    buy_volume_tokens: 0.0,
+                    sell_volume_tokens: 0.0,
+                    buy_volume_usd: None,
+                    sell_volume_usd: None,
+                };
+                for trade in history.iter().filter(|t| t.time >= start) {
+                    let (count, tokens, usd) = match trade.trade_type {
+                        TradeType::Buy => (&mut stats.buys, &mut stats.buy_volume_tokens, &mut stats.buy_volume_usd),
+                        TradeType::Sell => (&mut stats.sells, &mut stats.sell_volume_tokens, &mut stats.sell_volume_usd),
+                    };
+                    *count += 1;
+                    *tokens += trade.tokens;
+                    if let Some(value) = trade.usd {
+                        *usd = Some(usd.unwrap_or_default() + value);
+                    }
+                }
+                stats
+            })
+            .collect();
+
+        Some(VolumeStats {
+            token_address: token,
+            windows,
+        })
+    }
+
+    /// Distinct traders, buy:sell ratio and trader concentration of a token over each window
+    pub fn traders(&self, token: Address) -> Option<TraderStats> {
+        let now = chrono::Utc::now().timestamp();
+        let trades = self.trades.lock().unwrap();
+        let history = trades.get(&token)?;
+
+        let windows = self
+            .windows
+            .iter()
+            .map(|window| {
+                let start = now - window.as_secs() as i64;
+                let mut buyers = HashSet::new();
+                let mut sellers = HashSet::new();
+                let mut volume_by_trader: HashMap<Address, f64> = HashMap::new();
+                let (mut buys, mut sells) = (0u32, 0u32);
+
+                for trade in history.iter().filter(|t| t.time >= start) {
+                    match trade.trade_type {
+                        TradeType::Buy => {
+                            buys += 1;
+                            buyers.insert(trade.trader);
+                        }
+                        TradeType::Sell => {
+                            sells += 1;
+                            sellers.insert(trade.trader);
+                        }
+                    }
+                    *volume_by_trader.entry(trade.trader).or_default() += trade.tokens;
+                }
+
+                let total_volume: f64 = volume_by_trader.values().sum();
+                let top_volume = volume_by_trader.values().copied().fold(0.0, f64::max);
+
+                TraderWindow {
+                    window: *window,
+                    unique_traders: buyers.union(&sellers).count() as u32,
+                    unique_buyers: buyers.len() as u32,
+                    unique_sellers: sellers.len() as u32,
+                    buy_sell_ratio: (sells > 0).then(|| buys as f64 / sells as f64),
+                    top_trader_volume_pct: (total_volume > 0.0).then(|| top_volume / total_volume * 100.0),
+                }
+            })
+            .collect();
+
+        Some(TraderStats {
+            token_address: token,
+            windows,
+        })
+    }
+}
+
+impl Default for TradeStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
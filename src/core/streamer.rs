@@ -1,42 +1,183 @@
 use anyhow::{anyhow, Result};
 use ethers::{
-    providers::{Middleware, StreamExt},
+    providers::{Middleware, PubsubClient, StreamExt},
     types::{Address, Filter, H256, U64},
 };
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
-use crate::config::{get_bonding_curve_address, get_factory_address};
-use crate::core::{pair_finder::PairFinder, swap_parser::SwapParser, token_info::TokenInfoCache};
-use crate::types::{MigrationEvent, Platform, SwapEvent};
+use crate::config::{get_bonding_curve_address, get_factory_address, get_pair_created_topic, get_transfer_topic};
+use crate::core::{
+    backfill::scan_logs,
+    blocks::{spawn_block_watcher, BlockSwapCounter},
+    candles::{spawn_candle_timer, CandleAggregator},
+    confirmations::{spawn_confirmation_release, ConfirmationBuffer},
+    creator::spawn_creator_watcher,
+    filter::SwapFilter,
+    health::HealthMonitor,
+    labels::LabelRegistry,
+    latency::{mark_received, now_ms},
+    holders::spawn_holder_tracker,
+    honeypot::HoneypotChecker,
+    liquidity::{check_drain, liquidity_topics},
+    lp_lock::LpLockChecker,
+    mempool::spawn_mempool_watcher,
+    new_pairs::spawn_token_pair_watcher,
+    options::{NewPairCallback, StreamHooks, StreamOptions},
+    ownership::spawn_ownership_watcher,
+    proxy::spawn_upgrade_watcher,
+    pending::{spawn_pending_sweeper, PendingTracker},
+    price_tracker::PriceTracker,
+    pair_finder::{PairFinder, MIN_LIQUIDITY_USD},
+    pipeline::{OrderedPipeline, DEFAULT_PARSE_CONCURRENCY},
+    reserves::{ReserveTracker, SYNC_TOPIC},
+    stats::TradeStats,
+    supply::{spawn_supply_watcher, SupplyCache},
+    risk::RiskScanner,
+    swap_parser::SwapParser,
+    token_info::TokenInfoCache,
+    venues::{spawn_venue_watcher, VenueTracker},
+};
+use crate::sinks::SinkSet;
+use crate::types::{Candle, DrainReason, EVENT_SCHEMA_VERSION, LiquidityDrainedEvent, LiquidityEvent, MigrationEvent, NewPairEvent, PairInfo, Platform, StreamEvent, SwapEvent};
 
-const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
-const SWAP_V2_TOPIC: &str = "0xd78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822";
+pub const SWAP_V2_TOPIC: &str = "0xd78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822";
 // 🔥 CRITICAL FIX: PancakeSwap V3 Swap event (9 params, NO indexed sender/recipient)
 // Swap(address,address,int256,int256,uint160,uint128,int24,uint128,uint128)
 // Parameters: sender, recipient, amount0, amount1, sqrtPriceX96, liquidity, tick, protocolFeesToken0, protocolFeesToken1
-const SWAP_V3_TOPIC: &str = "0x19b47279256b2a23a1665c810c8d55a1758940ee09377d4f8d26497a3577dc83";
-const PAIR_CREATED_TOPIC: &str = "0x0d3648bd0f6ba80134a33ba9275ac585d9d315f0ad8355cddefde31afa28d0e9";
+pub const SWAP_V3_TOPIC: &str = "0x19b47279256b2a23a1665c810c8d55a1758940ee09377d4f8d26497a3577dc83";
 
 pub struct SwapStreamer<M> {
     provider: Arc<M>,
     pair_finder: PairFinder<M>,
     swap_parser: SwapParser<M>,
+    reserves: ReserveTracker,
+    venues: VenueTracker,
+    filter: SwapFilter,
+    labels: LabelRegistry,
+    hooks: StreamHooks,
+    pending: PendingTracker,
+    candles: Option<CandleAggregator>,
+    prices: PriceTracker,
+    stats: TradeStats,
+    sinks: SinkSet,
+    health: HealthMonitor,
+    min_liquidity_usd: f64,
+    start_block: Option<u64>,
+    monitor_all: bool,
+    bonding_curves: HashMap<Address, Address>,
+    confirmations: Option<ConfirmationBuffer>,
+    block_swaps: BlockSwapCounter,
+    parse_concurrency: usize,
+    // Known pool to monitor instead of discovering pairs
+    pair: Option<PairInfo>,
     is_streaming: bool,
 }
 
 impl<M: Middleware + 'static> SwapStreamer<M> {
     pub fn new(provider: Arc<M>) -> Self {
+        Self::with_options(provider, StreamOptions::default())
+    }
+
+    pub fn with_options(provider: Arc<M>, options: StreamOptions) -> Self {
+        let mut swap_parser = SwapParser::new(provider.clone());
+        swap_parser.quote_currency = options.quote_currency;
+        if let Some((ref path, ttl)) = options.token_cache_file {
+            swap_parser.token_cache = TokenInfoCache::new(provider.clone()).with_persistence(path, ttl);
+        }
+
+        let min_liquidity_usd = options.min_liquidity_usd.unwrap_or(MIN_LIQUIDITY_USD);
+
         Self {
-            pair_finder: PairFinder::new(provider.clone()),
-            swap_parser: SwapParser::new(provider.clone()),
+            pair_finder: PairFinder::new(provider.clone()).with_min_liquidity(min_liquidity_usd),
+            // Shared with the parser, which prices route hops from it
+            reserves: swap_parser.reserves.clone(),
+            swap_parser,
+            venues: VenueTracker::new(),
+            filter: options.filter,
+            labels: options.labels,
+            candles: options.hooks.candles.as_ref().map(|(intervals, _)| CandleAggregator::new(intervals.clone())),
+            prices: {
+                let mut prices = PriceTracker::new();
+                if let Some((ref rules, _)) = options.hooks.price_alert {
+                    prices = prices.with_alerts(rules);
+                }
+                prices = prices.with_indicators(&options.indicators);
+                if let Some((ref indicators, _)) = options.hooks.indicator_cross {
+                    prices = prices.with_indicators(indicators);
+                }
+                if let Some(limit) = options.price_history_limit {
+                    prices = prices.with_history_limit(limit);
+                }
+                prices
+            },
+            hooks: options.hooks,
+            pending: PendingTracker::new(),
+            stats: TradeStats::new(),
+            sinks: options.sinks,
+            health: HealthMonitor::new(provider.clone()),
+            min_liquidity_usd,
+            start_block: options.start_block,
+            monitor_all: options.monitor_all,
+            bonding_curves: options.bonding_curves,
+            confirmations: (options.confirmations > 0).then(|| ConfirmationBuffer::new(options.confirmations)),
+            block_swaps: BlockSwapCounter::new(),
+            parse_concurrency: options.parse_concurrency.unwrap_or(DEFAULT_PARSE_CONCURRENCY),
+            pair: None,
             provider,
             is_streaming: false,
         }
     }
 
+    /// Monitor only this pair (see `PairFinder::pair_info`), skipping pair discovery
+    pub fn with_pair(mut self, pair: PairInfo) -> Self {
+        self.pair = Some(pair);
+        self
+    }
+
+    /// Sinks of this streamer (workers start with the stream)
+    pub fn sinks(&self) -> SinkSet {
+        self.sinks.clone()
+    }
+
+    /// Reserve state of the monitored V2 pairs, updated from Sync events
+    pub fn reserves(&self) -> ReserveTracker {
+        self.reserves.clone()
+    }
+
+    /// Token metadata cache shared by everything this streamer parses (see `TokenInfoCache::preload`)
+    pub fn token_cache(&self) -> TokenInfoCache<M> {
+        self.swap_parser.token_cache.clone()
+    }
+
+    /// Monitored pools of the streamed token and the current primary (deepest) pool
+    pub fn venues(&self) -> VenueTracker {
+        self.venues.clone()
+    }
+
+    /// Last known total/burned/circulating supply of tokens priced by this streamer
+    pub fn supply(&self) -> SupplyCache {
+        self.swap_parser.supply.cache()
+    }
+
+    /// Price high/low/change statistics of the streamed token (all swaps, before filtering)
+    pub fn prices(&self) -> PriceTracker {
+        self.prices.clone()
+    }
+
+    /// Rolling volume statistics of the streamed token (all swaps, before filtering)
+    pub fn stats(&self) -> TradeStats {
+        self.stats.clone()
+    }
+
+    /// Provider connectivity and subscription liveness
+    pub fn health(&self) -> HealthMonitor {
+        self.health.clone()
+    }
+
     pub async fn start<F>(&mut self, token_address_str: &str, callback: F) -> Result<()>
     where
         F: Fn(SwapEvent) + Send + Sync + 'static,
@@ -72,6 +213,26 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
         migration_callback: Option<G>,
         cancel_token: CancellationToken,
     ) -> Result<()>
+    where
+        F: Fn(SwapEvent) + Send + Sync + 'static,
+        G: Fn(MigrationEvent) + Send + Sync + 'static,
+        M::Provider: ethers::providers::PubsubClient,
+    {
+        let token_address = Address::from_str(token_address_str)?;
+        self.start_tokens(&[token_address], swap_callback, migration_callback, cancel_token).await
+    }
+
+    /// Stream several tokens through the same callbacks, sinks and statistics
+    ///
+    /// Each token is located like a single one. Tokens that cannot be streamed
+    /// are logged and skipped; this only fails if none can be streamed.
+    pub async fn start_tokens<F, G>(
+        &mut self,
+        tokens: &[Address],
+        swap_callback: F,
+        migration_callback: Option<G>,
+        cancel_token: CancellationToken,
+    ) -> Result<()>
     where
         F: Fn(SwapEvent) + Send + Sync + 'static,
         G: Fn(MigrationEvent) + Send + Sync + 'static,
@@ -82,145 +243,171 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
             return Ok(());
         }
 
-        let token_address = Address::from_str(token_address_str)?;
+        // Swaps are labelled, then filtered; swap-level hooks (whale alerts, ...) see
+        // every remaining swap before the user's callback
+        let sinks = self.sinks.spawn(&cancel_token);
+        if let (false, Some((_, callback))) = (sinks.is_empty(), &mut self.hooks.candles) {
+            // Closed candles (from swaps and the candle timer) also go to the sinks
+            let candle_sinks = sinks.clone();
+            let user_callback = callback.clone();
+            *callback = Arc::new(move |candle: Candle| {
+                candle_sinks.send(StreamEvent::Candle(candle.clone()));
+                user_callback(candle);
+            });
+        }
+        let labels = self.labels.clone();
+        let filter = self.filter.clone();
+        let hooks = self.hooks.clone();
+        let pending = self.pending.clone();
+        let candles = self.candles.clone();
+        let stats = self.stats.clone();
+        let prices = self.prices.clone();
+        let latency = self.health.latency();
+        let swap_sinks = sinks.clone();
+        // Migrations are only built when someone listens for them
+        let confirmations = self.confirmations.clone();
+        let migration_callback = (migration_callback.is_some() || !sinks.is_empty()).then(|| {
+            let deliver = Arc::new(move |migration: MigrationEvent| {
+                if !sinks.is_empty() {
+                    sinks.send(StreamEvent::Migration(Box::new(migration.clone())));
+                }
+                if let Some(ref callback) = migration_callback {
+                    callback(migration);
+                }
+            });
+            move |migration: MigrationEvent| match confirmations {
+                Some(ref buffer) => {
+                    let deliver = deliver.clone();
+                    // One migration per transaction
+                    buffer.hold(migration.block_number, migration.transaction_hash, u64::MAX, move || deliver(migration));
+                }
+                None => deliver(migration),
+            }
+        });
+        let deliver_swap = move |mut swap: SwapEvent| {
+            labels.label_swap(&mut swap);
+            stats.record(&swap);
+            {
+                // Normalized prices keep stats and alerts consistent across pairs with different base tokens
+                let (price, currency) = match swap.normalized_price {
+                    Some(ref normalized) => (normalized.value, normalized.currency.as_str().to_string()),
+                    None => (swap.price.value, swap.price.base_token.clone()),
+                };
+                let volume = swap.token.amount.parse().unwrap_or_default();
+                let time = crate::core::candles::swap_time(&swap) as f64;
+                let token = format!("{:?}", swap.token.address);
+                let price_stats = prices.record_trade(&token, &currency, price, volume, time);
+                if let Some((_, ref callback)) = hooks.price_alert {
+                    for alert in price_stats.alerts {
+                        callback(alert);
+                    }
+                }
+                if let Some((ref indicators, ref callback)) = hooks.indicator_cross {
+                    for cross in price_stats.crosses.into_iter().filter(|c| indicators.contains(&c.indicator)) {
+                        callback(cross);
+                    }
+                }
+            }
+            if let Some(ref callback) = hooks.pending_resolved {
+                if let Some(resolution) = pending.confirm(&swap) {
+                    callback(resolution);
+                }
+            }
+            if !filter.matches(&swap) {
+                return;
+            }
+            latency.mark_dispatched(&mut swap);
+            hooks.dispatch_swap(&swap);
+            if !swap_sinks.is_empty() {
+                swap_sinks.send(StreamEvent::Swap(Box::new(swap.clone())));
+            }
+            if let (Some(aggregator), Some((_, callback))) = (&candles, &hooks.candles) {
+                for candle in aggregator.push(&swap) {
+                    callback(candle);
+                }
+            }
+            swap_callback(swap);
+        };
+        // With confirmations, swaps enter the pipeline above only once their block is deep enough
+        let deliver_swap = Arc::new(deliver_swap);
+        let confirmations = self.confirmations.clone();
+        let block_swaps = self.hooks.block.is_some().then(|| self.block_swaps.clone());
+        let swap_callback = Arc::new(move |swap: SwapEvent| {
+            if let Some(ref counter) = block_swaps {
+                counter.record(swap.block_number);
+            }
+            match confirmations {
+                Some(ref buffer) => {
+                    let deliver = deliver_swap.clone();
+                    buffer.hold(swap.block_number, swap.transaction_hash, swap.log_index, move || deliver(swap));
+                }
+                None => deliver_swap(swap),
+            }
+        });
+        let migration_callback = migration_callback.map(Arc::new);
 
-        log::debug!("🚀 Starting swap event streamer for token: {}", token_address_str);
+        let mut last_error = None;
+        for &token_address in tokens {
+            if let Err(e) = self.start_token(token_address, swap_callback.clone(), migration_callback.clone(), &cancel_token).await {
+                if tokens.len() > 1 {
+                    log::error!("❌ [SWAP_STREAMER] Not streaming {:?}: {}", token_address, e);
+                }
+                last_error = Some(e);
+            }
+        }
+        if !self.is_streaming {
+            return Err(last_error.unwrap_or_else(|| anyhow!("No token to stream")));
+        }
+
+        if let (Some(aggregator), Some((_, callback))) = (&self.candles, &self.hooks.candles) {
+            spawn_candle_timer(aggregator.clone(), callback.clone(), cancel_token.clone());
+        }
+        if let Some(ref callback) = self.hooks.block {
+            spawn_block_watcher(self.provider.clone(), self.block_swaps.clone(), callback.clone(), self.health.clone(), cancel_token.clone());
+        }
+        if let Some(ref buffer) = self.confirmations {
+            spawn_confirmation_release(self.provider.clone(), buffer.clone(), self.health.clone(), cancel_token.clone());
+        }
+        if let Some(ref callback) = self.hooks.pending_resolved {
+            spawn_pending_sweeper(self.provider.clone(), self.pending.clone(), callback.clone(), cancel_token.clone());
+        }
+        Ok(())
+    }
+
+    /// Locate one token and subscribe to its DEX pairs or bonding curve
+    async fn start_token<F, G>(
+        &mut self,
+        token_address: Address,
+        swap_callback: Arc<F>,
+        migration_callback: Option<Arc<G>>,
+        cancel_token: &CancellationToken,
+    ) -> Result<()>
+    where
+        F: Fn(SwapEvent) + Send + Sync + 'static,
+        G: Fn(MigrationEvent) + Send + Sync + 'static,
+        M::Provider: ethers::providers::PubsubClient,
+    {
+        log::debug!("🚀 Starting swap event streamer for token: {:?}", token_address);
 
         // CRITICAL FIX: Check for DEX pairs FIRST before checking bonding curve
         // This prevents migrated tokens from being incorrectly detected as still on bonding curve
         // (The bonding curve check looks at historical transfers which may include pre-migration activity)
-        let pairs = self.pair_finder.find_pairs(token_address).await?;
+        let pairs = match self.pair {
+            Some(ref pair) if pair.token == token_address => vec![pair.clone()],
+            _ => self.pair_finder.find_pairs(token_address).await?,
+        };
+
+        if self.monitor_all {
+            return self.start_all(token_address, pairs, swap_callback, migration_callback, cancel_token).await;
+        }
 
         if !pairs.is_empty() {
             // Token has DEX pairs - monitor DEX (PancakeSwap V2/V3)
             log::info!("✅ Found {} DEX pair(s) - subscribing to PancakeSwap events", pairs.len());
 
         self.is_streaming = true;
-
-        // Wrap callback in Arc once
-        let callback = Arc::new(swap_callback);
-
-        // Monitor each pair
-        for pair_info in pairs {
-            // Use correct swap topic based on pool type
-            let swap_topic = if pair_info.is_v3 {
-                H256::from_str(SWAP_V3_TOPIC)?
-            } else {
-                H256::from_str(SWAP_V2_TOPIC)?
-            };
-            
-            let pool_type = if pair_info.is_v3 { "V3" } else { "V2" };
-            
-            // Watch for new events only (from latest block forward)
-            let filter = Filter::new()
-                .address(pair_info.pair_address)
-                .topic0(swap_topic);
-
-            let parser = self.swap_parser.clone();
-            let pair_info_clone = pair_info.clone();
-            let callback_clone = callback.clone();
-                let cancel_clone = cancel_token.clone();
-
-            tokio::spawn(async move {
-                    log::debug!("🔄 [SWAP_STREAMER] Starting {} subscription for pair {:?} with topic {:?}", pool_type, pair_info_clone.pair_address, swap_topic);
-                    
-                    // Use subscribe_logs for WebSocket providers (eth_subscribe instead of polling)
-                    match parser.provider.subscribe_logs(&filter).await {
-                        Ok(mut stream) => {
-                            log::debug!("✅ [SWAP_STREAMER] {} subscription created successfully for pair {:?} with swap topic {:?}", pool_type, pair_info_clone.pair_address, swap_topic);
-                            
-                            let mut events_received = 0;
-                            let mut events_parsed = 0;
-                            let mut events_failed = 0;
-                            let mut last_log_time = std::time::Instant::now();
-                            let start_time = std::time::Instant::now();
-                            
-                            loop {
-                                // Log heartbeat every 30 seconds to show subscription is alive
-                                if last_log_time.elapsed().as_secs() >= 30 {
-                                    let uptime = start_time.elapsed();
-                                    let rate = if uptime.as_secs() > 0 {
-                                        events_received as f64 / uptime.as_secs() as f64
-                                    } else {
-                                        0.0
-                                    };
-                                    
-                                    log::debug!("💓 [SWAP_STREAMER] {} pair {:?} - Received: {}, Parsed: {}, Failed: {}, Rate: {:.2}/s", 
-                                        pool_type, pair_info_clone.pair_address, events_received, events_parsed, events_failed, rate);
-                                    last_log_time = std::time::Instant::now();
-                                }
-                                
-                                tokio::select! {
-                                    // Listen for cancel signal
-                                    _ = cancel_clone.cancelled() => {
-                                        log::debug!("🛑 [SWAP_STREAMER] {} subscription cancelled - Received: {}, Parsed: {}, Failed: {}", 
-                                            pool_type, events_received, events_parsed, events_failed);
-                                        break;
-                                    }
-                                    // Process stream events
-                                    log_option = stream.next() => {
-                                        match log_option {
-                                            Some(log) => {
-                                                events_received += 1;
-                                                let receive_time = std::time::Instant::now();
-                                                
-                                                // Log block number to detect batching
-                                                if events_received == 1 || events_received % 100 == 0 {
-                                                    log::debug!("📊 [SWAP_STREAMER] Event #{}: block={:?}, tx={:?}", 
-                                                        events_received, log.block_number, log.transaction_hash);
-                                                }
-                                                
-                                                log::debug!("📥 [SWAP_STREAMER] Received {} log #{} for pair {:?} - tx: {:?}", 
-                                                    pool_type, events_received, pair_info_clone.pair_address, log.transaction_hash);
-                                                
-                                                let parse_start = std::time::Instant::now();
-                            match parser.parse_swap_event(&log, &pair_info_clone).await {
-                                Ok(swap) => {
-                                                        events_parsed += 1;
-                                                        let parse_duration = parse_start.elapsed();
-                                                        log::debug!("✅ [SWAP_STREAMER] Parsed {} event #{} in {:?}: {:?} {} @ {:.10} {}", 
-                                                            pool_type, events_received, parse_duration, swap.trade_type, swap.token.amount, 
-                                                            swap.price.value, swap.price.base_token);
-                                                        
-                                                        let callback_start = std::time::Instant::now();
-                                    callback_clone(swap);
-                                                        let callback_duration = callback_start.elapsed();
-                                                        
-                                                        let total_duration = receive_time.elapsed();
-                                                        if total_duration.as_millis() > 500 {
-                                                            log::warn!("⚠️  [SWAP_STREAMER] Slow event processing: parse={:?}, callback={:?}, total={:?}", 
-                                                                parse_duration, callback_duration, total_duration);
-                                                        }
-                                }
-                                Err(e) => {
-                                                        events_failed += 1;
-                                                        log::error!("❌ [SWAP_STREAMER] Failed to parse {} swap event from pair {:?}: {}", pool_type, pair_info_clone.pair_address, e);
-                                                        log::error!("   Event details - tx: {:?}, topics: {}, data_len: {}", log.transaction_hash, log.topics.len(), log.data.len());
-                                                        if events_failed <= 3 {
-                                                            log::error!("   First few failures - Topics: {:?}", log.topics);
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            None => {
-                                                log::warn!("⚠️ [SWAP_STREAMER] {} stream ended - Received: {}, Parsed: {}, Failed: {}", 
-                                                    pool_type, events_received, events_parsed, events_failed);
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                    }
-                    Err(e) => {
-                        log::error!("❌ [SWAP_STREAMER] Failed to create {} subscription for pair {:?}: {}", pool_type, pair_info_clone.pair_address, e);
-                        log::error!("   Error details: {:?}", e);
-                    }
-                }
-            });
-
-            log::debug!("  ✅ Listening to {} {} pair: {:?}", pool_type, pair_info.base_token_symbol, pair_info.pair_address);
-        }
+        self.start_dex(token_address, pairs, swap_callback, cancel_token);
+        self.spawn_token_watchers(token_address, cancel_token);
 
         log::debug!("✨ Streamer is now active. Waiting for swap events...");
 
@@ -239,15 +426,126 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
                     swap_callback,
                     migration_callback,
                     cancel_token.clone(),
+                    true,
                 )
                 .await?;
+                self.spawn_token_watchers(token_address, cancel_token);
                 return Ok(());
             }
         }
 
         // No DEX pairs and not on bonding curve
         log::warn!("⚠️ No pairs found with sufficient liquidity on DEX and no Four.meme bonding curve activity detected");
-        return Err(anyhow!("No trading pairs found on DEX and not on bonding curve"));
+        Err(anyhow!("No trading pairs found on DEX and not on bonding curve"))
+    }
+
+    /// Monitor the given pairs, and pairs created later unless a fixed pair was set
+    fn start_dex<F>(&self, token_address: Address, pairs: Vec<PairInfo>, swap_callback: Arc<F>, cancel_token: &CancellationToken)
+    where
+        F: Fn(SwapEvent) + Send + Sync + 'static,
+        M::Provider: PubsubClient,
+    {
+        let listeners = PairListeners {
+            parser: self.swap_parser.clone(),
+            callback: swap_callback,
+            reserves: self.reserves.clone(),
+            venues: self.venues.clone(),
+            hooks: self.hooks.clone(),
+            health: self.health.clone(),
+            parse_concurrency: self.parse_concurrency,
+            cancel_token: cancel_token.clone(),
+        };
+        if self.pair.is_none() {
+            listeners.watch_new_pairs(token_address, &pairs);
+        }
+        for pair_info in pairs {
+            listeners.attach(pair_info, self.start_block);
+        }
+        listeners.watch_venues(token_address);
+    }
+
+    /// `monitor_all` mode: bonding curve trades and DEX pairs (existing and new) at the same time
+    ///
+    /// Around graduation both venues can trade for a while; the graduation pair
+    /// is picked up by the new-pair watcher, and the migration event is still
+    /// reported when it is created.
+    async fn start_all<F, G>(
+        &mut self,
+        token_address: Address,
+        pairs: Vec<PairInfo>,
+        swap_callback: Arc<F>,
+        migration_callback: Option<Arc<G>>,
+        cancel_token: &CancellationToken,
+    ) -> Result<()>
+    where
+        F: Fn(SwapEvent) + Send + Sync + 'static,
+        G: Fn(MigrationEvent) + Send + Sync + 'static,
+        M::Provider: PubsubClient,
+    {
+        let on_curve = self.check_bonding_curve(&token_address).await.unwrap_or(false);
+        if pairs.is_empty() && !on_curve {
+            log::warn!("⚠️ No pairs found with sufficient liquidity on DEX and no Four.meme bonding curve activity detected");
+            return Err(anyhow!("No trading pairs found on DEX and not on bonding curve"));
+        }
+        log::info!(
+            "✅ Monitoring {} DEX pair(s){} for {:?}",
+            pairs.len(),
+            if on_curve { " and the Four.meme bonding curve" } else { "" },
+            token_address
+        );
+
+        self.is_streaming = true;
+        self.start_dex(token_address, pairs, swap_callback.clone(), cancel_token);
+        if on_curve {
+            self.start_bonding_curve_with_migration_detection_and_callback(
+                token_address,
+                swap_callback,
+                migration_callback,
+                cancel_token.clone(),
+                false,
+            )
+            .await?;
+        }
+        self.spawn_token_watchers(token_address, cancel_token);
+        Ok(())
+    }
+
+    /// Start token-level trackers (independent of where the token trades)
+    fn spawn_token_watchers(&self, token_address: Address, cancel_token: &CancellationToken)
+    where
+        M::Provider: PubsubClient,
+    {
+        if let Some((config, ref callback)) = self.hooks.holder_stats {
+            spawn_holder_tracker(self.provider.clone(), token_address, config, callback.clone(), cancel_token.clone());
+        }
+        if let Some(ref callback) = self.hooks.dev_action {
+            spawn_creator_watcher(self.provider.clone(), token_address, callback.clone(), cancel_token.clone());
+        }
+        if let Some(ref callback) = self.hooks.ownership_change {
+            spawn_ownership_watcher(self.provider.clone(), token_address, callback.clone(), cancel_token.clone());
+        }
+        if let Some(ref callback) = self.hooks.implementation_changed {
+            spawn_upgrade_watcher(self.provider.clone(), token_address, callback.clone(), cancel_token.clone());
+        }
+        if let Some(ref burn_alert) = self.hooks.burn {
+            spawn_supply_watcher(
+                self.provider.clone(),
+                token_address,
+                self.swap_parser.supply.clone(),
+                Some(burn_alert.clone()),
+                cancel_token.clone(),
+            );
+        }
+        if self.hooks.wants_mempool() {
+            let tracker = self.hooks.pending_resolved.is_some().then(|| self.pending.clone());
+            spawn_mempool_watcher(
+                self.provider.clone(),
+                token_address,
+                self.hooks.pending_swap.clone(),
+                tracker,
+                cancel_token.clone(),
+            );
+        }
     }
 
     /// Public method to check if a token is on the bonding curve (for library users)
@@ -255,8 +553,13 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
         self.check_bonding_curve(token_address).await
     }
 
+    /// Bonding curve contract the token trades on (per-token override or the Four.meme default)
+    fn bonding_curve(&self, token_address: Address) -> Address {
+        self.bonding_curves.get(&token_address).copied().unwrap_or_else(get_bonding_curve_address)
+    }
+
     async fn check_bonding_curve(&self, token_address: &Address) -> Result<bool> {
-        let bonding_curve = get_bonding_curve_address();
+        let bonding_curve = self.bonding_curve(*token_address);
         log::debug!("🔍 [BONDING_CURVE] Checking for Four.meme activity - Bonding Curve: {:?}", bonding_curve);
 
         // OPTIMIZED: Check only the last 100 blocks (much more efficient than 5000)
@@ -281,17 +584,17 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
         {
             Ok(balance) if balance > ethers::types::U256::zero() => {
                 log::debug!("✅ [BONDING_CURVE] Token has balance on bonding curve: {} tokens", balance);
-                return Ok(true);
+                Ok(true)
             }
             Ok(_) => {
                 log::info!("⚪ [BONDING_CURVE] Token has zero balance on bonding curve - likely migrated");
-                return Ok(false);
+                Ok(false)
             }
             Err(e) => {
                 log::warn!("⚠️ [BONDING_CURVE] Failed to check bonding curve balance: {}, falling back to Transfer scan", e);
                 
                 // Fallback: Check recent Transfer events (much faster with only 100 blocks)
-                let transfer_topic = H256::from_str(TRANSFER_TOPIC)?;
+                let transfer_topic = get_transfer_topic();
         let filter = Filter::new()
             .address(*token_address)
             .topic0(transfer_topic)
@@ -330,19 +633,20 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
     async fn start_bonding_curve_with_migration_detection_and_callback<F, G>(
         &self,
         token_address: Address,
-        swap_callback: F,
-        migration_callback: Option<G>,
+        swap_callback: Arc<F>,
+        migration_callback: Option<Arc<G>>,
         cancel_token: CancellationToken,
+        attach_dex: bool,
     ) -> Result<()>
     where
         F: Fn(SwapEvent) + Send + Sync + 'static,
         G: Fn(MigrationEvent) + Send + Sync + 'static,
         M::Provider: ethers::providers::PubsubClient,
     {
-        let bonding_curve = get_bonding_curve_address();
+        let bonding_curve = self.bonding_curve(token_address);
         let factory_address = get_factory_address();
-        let transfer_topic = H256::from_str(TRANSFER_TOPIC)?;
-        let pair_created_topic = H256::from_str(PAIR_CREATED_TOPIC)?;
+        let transfer_topic = get_transfer_topic();
+        let pair_created_topic = get_pair_created_topic();
 
         // Create channel for migration detection
         let (migration_tx, mut migration_rx) = mpsc::channel::<(H256, u64)>(1);
@@ -353,8 +657,6 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
             .topic0(transfer_topic);
 
         let parser = self.swap_parser.clone();
-        let swap_callback = Arc::new(swap_callback);
-        let migration_callback = migration_callback.map(Arc::new);
 
         log::debug!("  ✅ Listening to Four.meme bonding curve: {:?}", bonding_curve);
         log::debug!("  🔍 Watching PancakeSwap Factory for PairCreated event");
@@ -363,6 +665,9 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
         // Spawn bonding curve event listener
         let callback_clone = swap_callback.clone();
         let cancel_clone = cancel_token.clone();
+        let raw_log = self.hooks.raw_log.clone();
+        let parse_concurrency = self.parse_concurrency;
+        let transfer_probe = self.health.subscription(format!("Four.meme Transfer {:?}", token_address));
         tokio::spawn(async move {
             log::debug!("🔄 [BONDING_CURVE] Creating subscription for Transfer events on token {:?}", token_address);
             
@@ -370,14 +675,23 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
             match parser.provider.subscribe_logs(&transfer_filter).await {
                 Ok(mut stream) => {
                     log::debug!("✅ [BONDING_CURVE] Transfer subscription created successfully for token {:?}", token_address);
+                    transfer_probe.live();
                     
                     let mut events_received = 0;
                     let mut events_parsed = 0;
                     let mut events_filtered = 0;
                     let mut last_log_time = std::time::Instant::now();
                     let start_time = std::time::Instant::now();
+                    // Trades parse concurrently but reach the callback in log order
+                    let mut parsing = OrderedPipeline::<Result<Option<SwapEvent>>>::new(parse_concurrency);
+                    let mut stream_ended = false;
                     
                     loop {
+                        if stream_ended && parsing.in_flight() == 0 {
+                            transfer_probe.ended();
+                            break;
+                        }
+
                         // Log heartbeat every 30 seconds
                         if last_log_time.elapsed().as_secs() >= 30 {
                             let uptime = start_time.elapsed();
@@ -396,43 +710,63 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
                             _ = cancel_clone.cancelled() => {
                                 log::debug!("🛑 [BONDING_CURVE] Transfer listener cancelled - Received: {}, Bonding Curve: {}, Parsed: {}", 
                                     events_received, events_filtered, events_parsed);
+                                transfer_probe.stopped();
                                 break;
                             }
-                            log_option = stream.next() => {
+                            // Emit parsed trades in the order their logs arrived
+                            parsed = parsing.next(), if parsing.in_flight() > 0 => {
+                                match parsed {
+                                    Some(Ok(Some(swap))) => {
+                                        events_parsed += 1;
+                                        log::debug!("✅ [BONDING_CURVE] Parsed swap #{}: {} tokens at {} {}", 
+                                            events_parsed, swap.token.amount, swap.price.value, swap.price.base_token);
+                                        callback_clone(swap);
+                                    }
+                                    Some(Ok(None)) => {
+                                        log::debug!("⏭️ [BONDING_CURVE] Transfer not a valid swap event");
+                                    }
+                                    Some(Err(e)) => {
+                                        log::error!("❌ [BONDING_CURVE] Failed to parse event: {}", e);
+                                    }
+                                    None => {
+                                        log::error!("❌ [BONDING_CURVE] Parsing a Transfer of {:?} panicked", token_address);
+                                    }
+                                }
+                            }
+                            log_option = stream.next(), if !stream_ended && !parsing.is_full() => {
                                 match log_option {
                                     Some(log) => {
                                         events_received += 1;
-                                        
-                    if log.topics.len() >= 3 {
-                        let from = Address::from(log.topics[1]);
-                        let to = Address::from(log.topics[2]);
-
-                        if from == bonding_curve || to == bonding_curve {
-                                                events_filtered += 1;
-                                                log::debug!("📥 [BONDING_CURVE] Event #{}: Transfer involving bonding curve - tx: {:?}", 
-                                                    events_filtered, log.transaction_hash);
-                                                
-                                                match parser.parse_bonding_curve_event(&log, token_address, bonding_curve).await {
-                                                    Ok(Some(swap)) => {
-                                                        events_parsed += 1;
-                                                        log::debug!("✅ [BONDING_CURVE] Parsed swap #{}: {} tokens at {} {}", 
-                                                            events_parsed, swap.token.amount, swap.price.value, swap.price.base_token);
-                                callback_clone(swap);
-                                                    }
-                                                    Ok(None) => {
-                                                        log::debug!("⏭️ [BONDING_CURVE] Transfer not a valid swap event");
-                                                    }
-                                                    Err(e) => {
-                                                        log::error!("❌ [BONDING_CURVE] Failed to parse event: {}", e);
-                                                    }
-                                                }
-                                            }
+                                        let received_at_ms = now_ms();
+                                        transfer_probe.event();
+                                        if let Some(ref callback) = raw_log {
+                                            callback(log.clone());
+                                        }
+
+                                        let involves_curve = log.topics.len() >= 3
+                                            && (Address::from(log.topics[1]) == bonding_curve || Address::from(log.topics[2]) == bonding_curve);
+                                        if involves_curve {
+                                            events_filtered += 1;
+                                            log::debug!("📥 [BONDING_CURVE] Event #{}: Transfer involving bonding curve - tx: {:?}", 
+                                                events_filtered, log.transaction_hash);
+
+                                            let parser = parser.clone();
+                                            parsing.submit(async move {
+                                                let parsed = parser.parse_bonding_curve_event(&log, token_address, bonding_curve).await;
+                                                parsed.map(|swap| {
+                                                    swap.map(|mut swap| {
+                                                        mark_received(&mut swap, received_at_ms);
+                                                        swap
+                                                    })
+                                                })
+                                            });
                                         }
                                     }
                                     None => {
                                         log::warn!("⚠️ [BONDING_CURVE] Transfer stream ended - Received: {}, Parsed: {}", 
                                             events_received, events_parsed);
-                                        break;
+                                        // Trades still parsing are emitted before the listener stops
+                                        stream_ended = true;
                                     }
                                 }
                             }
@@ -442,14 +776,17 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
                 Err(e) => {
                     log::error!("❌ [BONDING_CURVE] Failed to create Transfer subscription for token {:?}: {}", token_address, e);
                     log::error!("   Error details: {:?}", e);
+                    transfer_probe.failed(e);
                 }
             }
         });
 
         // Spawn PairCreated event listener on Factory
         let provider_clone = self.provider.clone();
-        let pair_finder = PairFinder::new(provider_clone.clone());
+        let pair_finder = PairFinder::new(provider_clone.clone()).with_min_liquidity(self.min_liquidity_usd);
         let cancel_clone2 = cancel_token.clone();
+        let factory_raw_log = self.hooks.raw_log.clone();
+        let factory_probe = self.health.subscription("PancakeSwap Factory PairCreated".to_string());
         
         tokio::spawn(async move {
             // Watch for PairCreated events from the Factory
@@ -460,37 +797,52 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
                 .topic0(pair_created_topic);
             
             // Use subscribe_logs for WebSocket providers (eth_subscribe instead of polling)
-            if let Ok(mut stream) = provider_clone.subscribe_logs(&filter).await {
-                loop {
-                    tokio::select! {
-                        _ = cancel_clone2.cancelled() => {
-                            log::debug!("🛑 [BONDING_CURVE] PairCreated event listener cancelled");
-                            break;
-                        }
-                        log_option = stream.next() => {
-                            match log_option {
-                                Some(log) => {
-                    if log.topics.len() >= 3 {
-                        let token0 = Address::from(log.topics[1]);
-                        let token1 = Address::from(log.topics[2]);
+            let mut stream = match provider_clone.subscribe_logs(&filter).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::error!("❌ [BONDING_CURVE] Failed to create PairCreated subscription: {}", e);
+                    factory_probe.failed(e);
+                    return;
+                }
+            };
+            factory_probe.live();
+            loop {
+                tokio::select! {
+                    _ = cancel_clone2.cancelled() => {
+                        log::debug!("🛑 [BONDING_CURVE] PairCreated event listener cancelled");
+                        factory_probe.stopped();
+                        break;
+                    }
+                    log_option = stream.next() => {
+                        match log_option {
+                            Some(log) => {
+                                factory_probe.event();
+                                if let Some(ref callback) = factory_raw_log {
+                                    callback(log.clone());
+                                }
+                if log.topics.len() >= 3 {
+                    let token0 = Address::from(log.topics[1]);
+                    let token1 = Address::from(log.topics[2]);
+                    
+                    // Check if either token matches our target token
+                    if token0 == token_address || token1 == token_address {
+                        log::info!("🎉 MIGRATION DETECTED! PairCreated event received!");
+                        log::info!("🔄 Switching from bonding curve to DEX monitoring...");
                         
-                        // Check if either token matches our target token
-                        if token0 == token_address || token1 == token_address {
-                            log::info!("🎉 MIGRATION DETECTED! PairCreated event received!");
-                            log::info!("🔄 Switching from bonding curve to DEX monitoring...");
-                            
-                            // Send transaction hash and block number for migration event
-                            if let (Some(tx_hash), Some(block_num)) = (log.transaction_hash, log.block_number) {
-                                let _ = migration_tx.send((tx_hash, block_num.as_u64())).await;
-                                break;
-                                            }
+                        // Send transaction hash and block number for migration event
+                        if let (Some(tx_hash), Some(block_num)) = (log.transaction_hash, log.block_number) {
+                            let _ = migration_tx.send((tx_hash, block_num.as_u64())).await;
+                            // Swaps continue on the new DEX pair subscriptions
+                            factory_probe.stopped();
+                            break;
                                         }
                                     }
                                 }
-                                None => {
-                                    log::warn!("⚠️ [BONDING_CURVE] PairCreated stream ended");
-                                    break;
-                                }
+                            }
+                            None => {
+                                log::warn!("⚠️ [BONDING_CURVE] PairCreated stream ended");
+                                factory_probe.ended();
+                                break;
                             }
                         }
                     }
@@ -500,7 +852,12 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
 
         // Wait for migration event and start DEX monitoring
         let parser_for_dex = self.swap_parser.clone();
+        let reserves = self.reserves.clone();
+        let venues = self.venues.clone();
+        let hooks = self.hooks.clone();
         let provider_for_migration = self.provider.clone();
+        let health = self.health.clone();
+        let parse_concurrency = self.parse_concurrency;
         tokio::spawn(async move {
             if let Some((tx_hash, block_number)) = migration_rx.recv().await {
                 // Get full pair info
@@ -526,7 +883,39 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
                         None
                     };
 
+                    // Graduation creates a V2 pair - report whether its LP is burned/locked
+                    let lp_lock = match pairs.iter().find(|p| !p.is_v3) {
+                        Some(pair) => match LpLockChecker::new(provider_for_migration.clone()).check(pair.pair_address).await {
+                            Ok(status) => Some(status),
+                            Err(e) => {
+                                log::warn!("⚠️ [BONDING_CURVE] Failed to check LP lock for pair {:?}: {}", pair.pair_address, e);
+                                None
+                            }
+                        },
+                        None => None,
+                    };
+
+                    let honeypot = match pairs.iter().find(|p| !p.is_v3) {
+                        Some(pair) => match HoneypotChecker::new(provider_for_migration.clone()).check(pair).await {
+                            Ok(report) => Some(report),
+                            Err(e) => {
+                                log::warn!("⚠️ [BONDING_CURVE] Honeypot check failed for pair {:?}: {}", pair.pair_address, e);
+                                None
+                            }
+                        },
+                        None => None,
+                    };
+
+                    let risk = match RiskScanner::new(provider_for_migration.clone()).scan(token_address).await {
+                        Ok(report) => Some(report),
+                        Err(e) => {
+                            log::warn!("⚠️ [BONDING_CURVE] Risk scan failed for {:?}: {}", token_address, e);
+                            None
+                        }
+                    };
+
                     let migration_event = MigrationEvent {
+                        schema_version: EVENT_SCHEMA_VERSION,
                         token_address,
                         from_platform: Platform::FourMemeBondingCurve,
                         to_platform: Platform::PancakeSwap,
@@ -535,61 +924,37 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
                         timestamp,
                         pair_addresses: pair_addresses.clone(),
                         pair_count: pairs.len(),
+                        lp_lock,
+                        honeypot,
+                        risk,
                     };
                     
                     migration_cb(migration_event);
                 }
-                
+
+                // In monitor_all mode the new-pair watcher already attached the pairs
+                if !attach_dex {
+                    return;
+                }
+
                 // Start DEX monitoring
                 log::info!("📡 Now monitoring {} DEX pair(s)", pairs.len());
                 
+                let listeners = PairListeners {
+                    parser: parser_for_dex,
+                    callback: swap_callback,
+                    reserves,
+                    venues,
+                    hooks,
+                    health,
+                    parse_concurrency,
+                    cancel_token,
+                };
+                listeners.watch_new_pairs(token_address, &pairs);
                 for pair_info in pairs {
-                    let swap_topic = if pair_info.is_v3 {
-                        H256::from_str(SWAP_V3_TOPIC).unwrap()
-                    } else {
-                        H256::from_str(SWAP_V2_TOPIC).unwrap()
-                    };
-                    
-                    let pool_type = if pair_info.is_v3 { "V3" } else { "V2" };
-                    
-                    let filter = Filter::new()
-                        .address(pair_info.pair_address)
-                        .topic0(swap_topic);
-                    
-                    let parser_clone = parser_for_dex.clone();
-                    let pair_info_clone = pair_info.clone();
-                    let callback_clone = swap_callback.clone();
-                    let cancel_clone3 = cancel_token.clone();
-                    
-                    tokio::spawn(async move {
-                        // Use subscribe_logs for WebSocket providers (eth_subscribe instead of polling)
-                        if let Ok(mut stream) = parser_clone.provider.subscribe_logs(&filter).await {
-                            loop {
-                                tokio::select! {
-                                    _ = cancel_clone3.cancelled() => {
-                                        log::debug!("🛑 [MIGRATION_DEX] Swap event listener cancelled for pair {:?}", pair_info_clone.pair_address);
-                                        break;
-                                    }
-                                    log_option = stream.next() => {
-                                        match log_option {
-                                            Some(log) => {
-                                if let Ok(swap) = parser_clone.parse_swap_event(&log, &pair_info_clone).await {
-                                    callback_clone(swap);
-                                                }
-                                            }
-                                            None => {
-                                                log::warn!("⚠️ [MIGRATION_DEX] Stream ended for pair {:?}", pair_info_clone.pair_address);
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    });
-                    
-                    log::debug!("  ✅ Listening to {} {} pair: {:?}", pool_type, pair_info.base_token_symbol, pair_info.pair_address);
+                    listeners.attach(pair_info, None);
                 }
+                listeners.watch_venues(token_address);
                 
                 log::info!("✨ DEX monitoring is now active!");
             }
@@ -607,12 +972,473 @@ impl<M: Middleware + 'static> SwapStreamer<M> {
     }
 }
 
+/// Everything a pair listener needs, so pairs can be attached after the stream started
+struct PairListeners<M, F> {
+    parser: SwapParser<M>,
+    callback: Arc<F>,
+    reserves: ReserveTracker,
+    venues: VenueTracker,
+    hooks: StreamHooks,
+    health: HealthMonitor,
+    parse_concurrency: usize,
+    cancel_token: CancellationToken,
+}
+
+impl<M, F> Clone for PairListeners<M, F>
+where
+    M: Middleware + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
+            callback: self.callback.clone(),
+            reserves: self.reserves.clone(),
+            venues: self.venues.clone(),
+            hooks: self.hooks.clone(),
+            health: self.health.clone(),
+            parse_concurrency: self.parse_concurrency,
+            cancel_token: self.cancel_token.clone(),
+        }
+    }
+}
+
+impl<M, F> PairListeners<M, F>
+where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+    F: Fn(SwapEvent) + Send + Sync + 'static,
+{
+    /// Start monitoring a pair, first replaying its swaps since `start_block` if given
+    fn attach(&self, pair_info: PairInfo, start_block: Option<u64>) {
+        self.venues.add(pair_info.clone());
+        log::debug!("  ✅ Listening to {} {} pair: {:?}", pair_info.version().as_str(), pair_info.base_token_symbol, pair_info.pair_address);
+        spawn_pair_listener(self.clone(), pair_info, start_block);
+    }
+
+    /// Keep the token's primary pool current and report liquidity moving between its pools
+    fn watch_venues(&self, token_address: Address) {
+        let (config, callback) = match self.hooks.pool_migration {
+            Some((config, ref callback)) => (config, Some(callback.clone())),
+            None => (Default::default(), None),
+        };
+        spawn_venue_watcher(
+            self.parser.provider.clone(),
+            self.venues.clone(),
+            token_address,
+            config,
+            callback,
+            self.cancel_token.clone(),
+        );
+    }
+
+    /// Attach V2 pairs and V3 pools of the token created after `known` were found
+    ///
+    /// New pairs are attached regardless of the minimum liquidity, since they are
+    /// usually created empty. Pairs without a base token cannot be priced and are
+    /// only reported.
+    fn watch_new_pairs(&self, token_address: Address, known: &[PairInfo]) {
+        let attached = Mutex::new(known.iter().map(|p| p.pair_address).collect::<HashSet<_>>());
+        let listeners = self.clone();
+        let callback: NewPairCallback = Arc::new(move |event: NewPairEvent| {
+            if !attached.lock().unwrap().insert(event.pair_address) {
+                return;
+            }
+            match event.pair_info() {
+                Some(pair_info) => {
+                    log::info!(
+                        "🆕 [SWAP_STREAMER] New {} {} pair {:?} - now monitoring it",
+                        pair_info.version().as_str(),
+                        pair_info.base_token_symbol,
+                        pair_info.pair_address
+                    );
+                    listeners.attach(pair_info, None);
+                }
+                None => log::info!("🆕 [SWAP_STREAMER] New pair {:?} has no base token - not monitored", event.pair_address),
+            }
+            if let Some(ref callback) = listeners.hooks.new_pair {
+                callback(event);
+            }
+        });
+        spawn_token_pair_watcher(
+            self.parser.provider.clone(),
+            token_address,
+            callback,
+            self.hooks.raw_log.clone(),
+            self.health.clone(),
+            self.cancel_token.clone(),
+        );
+    }
+}
+
+// A swap log with its parse result, parse time and arrival
+type ParsedSwap = (ethers::types::Log, Result<SwapEvent>, std::time::Duration, std::time::Instant);
+
+/// A pair log handled once every earlier log of the pair has been
+///
+/// Reserve amounts are (token, base), read when the log arrived.
+enum PairLog {
+    Swap(Box<ParsedSwap>),
+    /// Sync of the pair, queued only for the drain alert's reserve floor
+    Sync(ethers::types::Log, Option<(f64, f64)>),
+    Liquidity(Result<LiquidityEvent>, Option<(f64, f64)>),
+}
+
+/// Subscribe to a DEX pair and forward parsed swaps to the callback
+///
+/// Up to `parse_concurrency` swaps are parsed at once; they, liquidity events
+/// and drain alerts reach their callbacks in the order their logs arrived.
+///
+/// V2 pairs are subscribed to Sync events on the same stream (Sync is emitted
+/// before Swap), so `reserves` already reflects the pool after each swap. The
+/// V2 hop of a counter-token route is followed the same way, for pricing.
+///
+/// With a `start_block`, swaps from that block up to the head at subscription
+/// time are delivered first; live swaps in those blocks are then skipped.
+fn spawn_pair_listener<M, F>(listeners: PairListeners<M, F>, pair_info: PairInfo, start_block: Option<u64>)
+where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+    F: Fn(SwapEvent) + Send + Sync + 'static,
+{
+    let PairListeners {
+        parser,
+        callback,
+        reserves,
+        hooks,
+        health,
+        parse_concurrency,
+        cancel_token,
+        ..
+    } = listeners;
+    // Use correct swap topic based on pool type
+    let swap_topic = H256::from_str(if pair_info.is_v3 { SWAP_V3_TOPIC } else { SWAP_V2_TOPIC }).unwrap();
+    let sync_topic = H256::from_str(SYNC_TOPIC).unwrap();
+    let pool_type = pair_info.version().as_str().to_string();
+
+    // Watch for new events only (from latest block forward)
+    let mut topics = if pair_info.is_v3 { vec![swap_topic] } else { vec![swap_topic, sync_topic] };
+    let liquidity_topics = liquidity_topics(pair_info.is_v3);
+    if hooks.wants_liquidity() {
+        topics.extend(liquidity_topics.iter().copied());
+    }
+    // Sync of a V2 route hop keeps the counter-token price current without RPC
+    let hop = pair_info.route.as_ref().filter(|route| !route.hop_is_v3).map(|route| route.hop_pair);
+    if hop.is_some() && !topics.contains(&sync_topic) {
+        topics.push(sync_topic);
+    }
+    let filter = Filter::new()
+        .address(std::iter::once(pair_info.pair_address).chain(hop).collect::<Vec<_>>())
+        .topic0(topics);
+    let probe = health.subscription(format!("{} {} pair {:?}", pool_type, pair_info.base_token_symbol, pair_info.pair_address));
+
+    tokio::spawn(async move {
+        if !pair_info.is_v3 {
+            if let Err(e) = reserves.seed(parser.provider.clone(), &parser.token_cache, &pair_info).await {
+                log::warn!("⚠️ [SWAP_STREAMER] Failed to read initial reserves for pair {:?}: {}", pair_info.pair_address, e);
+            }
+        }
+        if let (Some(route), Some(hop)) = (&pair_info.route, hop) {
+            if let Err(e) = reserves.seed_route(parser.provider.clone(), &parser.token_cache, pair_info.base_token, route).await {
+                log::warn!("⚠️ [SWAP_STREAMER] Failed to read initial reserves for route hop {:?}: {}", hop, e);
+            }
+        }
+
+        log::debug!("🔄 [SWAP_STREAMER] Starting {} subscription for pair {:?} with topic {:?}", pool_type, pair_info.pair_address, swap_topic);
+
+        // Use subscribe_logs for WebSocket providers (eth_subscribe instead of polling)
+        let mut stream = match parser.provider.subscribe_logs(&filter).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("❌ [SWAP_STREAMER] Failed to create {} subscription for pair {:?}: {}", pool_type, pair_info.pair_address, e);
+                log::error!("   Error details: {:?}", e);
+                probe.failed(e);
+                return;
+            }
+        };
+        log::debug!("✅ [SWAP_STREAMER] {} subscription created successfully for pair {:?} with swap topic {:?}", pool_type, pair_info.pair_address, swap_topic);
+        probe.live();
+
+        // Live logs buffer in the subscription while history is replayed
+        let backfilled_to = match start_block {
+            Some(from_block) => backfill_pair(&parser, &pair_info, swap_topic, from_block, callback.as_ref()).await,
+            None => None,
+        };
+
+        let mut events_received = 0;
+        let mut events_parsed = 0;
+        let mut events_failed = 0;
+        // Whether the pool is currently under the drain alert's reserve floor
+        let mut below_floor = false;
+        // Swaps parse concurrently; they, Syncs and liquidity events reach the callbacks in log order
+        let mut parsing = OrderedPipeline::<PairLog>::new(parse_concurrency);
+        let mut stream_ended = false;
+        let mut last_log_time = std::time::Instant::now();
+        let start_time = std::time::Instant::now();
+
+        loop {
+            if stream_ended && parsing.in_flight() == 0 {
+                probe.ended();
+                break;
+            }
+
+            // Log heartbeat every 30 seconds to show subscription is alive
+            if last_log_time.elapsed().as_secs() >= 30 {
+                let uptime = start_time.elapsed();
+                let rate = if uptime.as_secs() > 0 {
+                    events_received as f64 / uptime.as_secs() as f64
+                } else {
+                    0.0
+                };
+
+                log::debug!("💓 [SWAP_STREAMER] {} pair {:?} - Received: {}, Parsed: {}, Failed: {}, Rate: {:.2}/s",
+                    pool_type, pair_info.pair_address, events_received, events_parsed, events_failed, rate);
+                last_log_time = std::time::Instant::now();
+            }
+
+            tokio::select! {
+                // Listen for cancel signal
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [SWAP_STREAMER] {} subscription cancelled - Received: {}, Parsed: {}, Failed: {}",
+                        pool_type, events_received, events_parsed, events_failed);
+                    probe.stopped();
+                    break;
+                }
+                // Handle pair logs in the order they arrived
+                handled = parsing.next(), if parsing.in_flight() > 0 => {
+                    let Some(handled) = handled else {
+                        events_failed += 1;
+                        log::error!("❌ [SWAP_STREAMER] Handling a {} log from pair {:?} panicked", pool_type, pair_info.pair_address);
+                        continue;
+                    };
+                    match handled {
+                        PairLog::Swap(handled) => match *handled {
+                            (_, Ok(swap), parse_duration, receive_time) => {
+                                events_parsed += 1;
+                                log::debug!("✅ [SWAP_STREAMER] Parsed {} event #{} in {:?}: {:?} {} @ {:.10} {}",
+                                    pool_type, events_parsed, parse_duration, swap.trade_type, swap.token.amount,
+                                    swap.price.value, swap.price.base_token);
+
+                                let callback_start = std::time::Instant::now();
+                                callback(swap);
+                                let callback_duration = callback_start.elapsed();
+
+                                let total_duration = receive_time.elapsed();
+                                if total_duration.as_millis() > 500 {
+                                    log::warn!("⚠️  [SWAP_STREAMER] Slow event processing: parse={:?}, callback={:?}, total={:?}",
+                                        parse_duration, callback_duration, total_duration);
+                                }
+                            }
+                            (log, Err(e), _, _) => {
+                                events_failed += 1;
+                                log::error!("❌ [SWAP_STREAMER] Failed to parse {} swap event from pair {:?}: {}", pool_type, pair_info.pair_address, e);
+                                log::error!("   Event details - tx: {:?}, topics: {}, data_len: {}", log.transaction_hash, log.topics.len(), log.data.len());
+                                if events_failed <= 3 {
+                                    log::error!("   First few failures - Topics: {:?}", log.topics);
+                                }
+                            }
+                        },
+                        PairLog::Sync(log, amounts) => {
+                            // Reserve floor is checked on every Sync so sells that drain the pool also alert
+                            if let Some((config, ref drained_cb)) = hooks.liquidity_drained {
+                                if let (Some(floor), Some((_, base_reserve))) = (config.min_base_reserve, amounts) {
+                                    let is_below = base_reserve < floor;
+                                    if is_below && !below_floor {
+                                        log::warn!("🚨 [SWAP_STREAMER] Pair {:?} reserves below floor: {} {}", pair_info.pair_address, base_reserve, pair_info.base_token_symbol);
+                                        drained_cb(LiquidityDrainedEvent {
+                                            reason: DrainReason::BelowFloor,
+                                            pair_address: pair_info.pair_address,
+                                            token_address: pair_info.token,
+                                            base_token_symbol: pair_info.base_token_symbol.clone(),
+                                            block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+                                            transaction_hash: log.transaction_hash,
+                                            removed_pct: None,
+                                            base_reserve,
+                                            liquidity: None,
+                                        });
+                                    }
+                                    below_floor = is_below;
+                                }
+                            }
+                        }
+                        PairLog::Liquidity(parsed, amounts) => match parsed {
+                            Ok(mut event) => {
+                                if let Some((token_reserve, base_reserve)) = amounts {
+                                    event.token_reserve = Some(token_reserve);
+                                    event.base_reserve = Some(base_reserve);
+                                }
+
+                                if let Some((config, ref drained_cb)) = hooks.liquidity_drained {
+                                    if let Some((reason, removed_pct)) = check_drain(&config, &event) {
+                                        // Floor crossings already alerted (e.g. from the preceding Sync) are not repeated
+                                        if reason == DrainReason::LargeRemoval || !below_floor {
+                                            log::warn!("🚨 [SWAP_STREAMER] {} on pair {:?}: {:.1}% removed", reason.as_str(), pair_info.pair_address, removed_pct);
+                                            drained_cb(LiquidityDrainedEvent {
+                                                reason,
+                                                pair_address: pair_info.pair_address,
+                                                token_address: pair_info.token,
+                                                base_token_symbol: pair_info.base_token_symbol.clone(),
+                                                block_number: event.block_number,
+                                                transaction_hash: Some(event.transaction_hash),
+                                                removed_pct: Some(removed_pct),
+                                                base_reserve: event.base_reserve.unwrap_or_default(),
+                                                liquidity: Some(event.clone()),
+                                            });
+                                        }
+                                    }
+                                    if let (Some(floor), Some(base_reserve)) = (config.min_base_reserve, event.base_reserve) {
+                                        below_floor = base_reserve < floor;
+                                    }
+                                }
+
+                                if let Some(ref liquidity_cb) = hooks.liquidity {
+                                    liquidity_cb(event);
+                                }
+                            }
+                            Err(e) => {
+                                log::warn!("⚠️ [SWAP_STREAMER] Failed to parse liquidity event from pair {:?}: {}", pair_info.pair_address, e);
+                            }
+                        },
+                    }
+                }
+                // Process stream events (paused while the pipeline is full)
+                log_option = stream.next(), if !stream_ended && !parsing.is_full() => {
+                    let Some(log) = log_option else {
+                        log::warn!("⚠️ [SWAP_STREAMER] {} stream ended - Received: {}, Parsed: {}, Failed: {}",
+                            pool_type, events_received, events_parsed, events_failed);
+                        // Logs still in the pipeline are handled before the listener stops
+                        stream_ended = true;
+                        continue;
+                    };
+                    probe.event();
+                    if log.address != pair_info.pair_address {
+                        // Route hop: only its reserves are of interest
+                        if log.topics.first() == Some(&sync_topic) {
+                            if let Err(e) = reserves.apply_sync(&log).await {
+                                log::warn!("⚠️ [SWAP_STREAMER] Failed to apply Sync for route hop {:?}: {}", log.address, e);
+                            }
+                        }
+                        continue;
+                    }
+                    if let Some(ref callback) = hooks.raw_log {
+                        callback(log.clone());
+                    }
+
+                    // Reserves are applied on arrival so the snapshots below describe this log;
+                    // anything that reaches a callback waits its turn in the pipeline
+                    if log.topics.first() == Some(&sync_topic) {
+                        if let Err(e) = reserves.apply_sync(&log).await {
+                            log::warn!("⚠️ [SWAP_STREAMER] Failed to apply Sync for pair {:?}: {}", pair_info.pair_address, e);
+                            continue;
+                        }
+                        if hooks.liquidity_drained.is_some() {
+                            let amounts = reserves.reserve_amounts(pair_info.pair_address).await;
+                            parsing.submit(std::future::ready(PairLog::Sync(log, amounts)));
+                        }
+                        continue;
+                    }
+
+                    if log.topics.first().is_some_and(|t| liquidity_topics.contains(t)) {
+                        // Sync precedes Mint/Burn, so the reserves already include this change
+                        let amounts = if pair_info.is_v3 { None } else { reserves.reserve_amounts(pair_info.pair_address).await };
+                        let parser = parser.clone();
+                        let pair_info = pair_info.clone();
+                        parsing.submit(async move { PairLog::Liquidity(parser.parse_liquidity_event(&log, &pair_info).await, amounts) });
+                        continue;
+                    }
+
+                    if backfilled_to.is_some_and(|to| log.block_number.is_some_and(|block| block.as_u64() <= to)) {
+                        continue;
+                    }
+
+                    events_received += 1;
+                    let receive_time = std::time::Instant::now();
+                    let received_at_ms = now_ms();
+
+                    // Log block number to detect batching
+                    if events_received == 1 || events_received % 100 == 0 {
+                        log::debug!("📊 [SWAP_STREAMER] Event #{}: block={:?}, tx={:?}",
+                            events_received, log.block_number, log.transaction_hash);
+                    }
+
+                    log::debug!("📥 [SWAP_STREAMER] Received {} log #{} for pair {:?} - tx: {:?}",
+                        pool_type, events_received, pair_info.pair_address, log.transaction_hash);
+
+                    // Reserves already reflect this trade (Sync precedes Swap); read them before
+                    // parsing so a later Sync cannot slip in. Same for the route hop's price.
+                    let depth = if pair_info.is_v3 { None } else { reserves.market_depth(pair_info.pair_address).await };
+                    let hop_price = match hop {
+                        Some(hop) => reserves.reserve_price(hop).await,
+                        None => None,
+                    };
+
+                    let parser = parser.clone();
+                    let pair_info = pair_info.clone();
+                    parsing.submit(async move {
+                        let parse_start = std::time::Instant::now();
+                        let parsed = parser.parse_swap_event_at(&log, &pair_info, hop_price).await.map(|mut swap| {
+                            swap.depth = depth;
+                            mark_received(&mut swap, received_at_ms);
+                            swap
+                        });
+                        PairLog::Swap(Box::new((log, parsed, parse_start.elapsed(), receive_time)))
+                    });
+                }
+            }
+        }
+    });
+}
+
+/// Deliver a pair's swaps from `from_block` to the current head; returns the last block covered
+async fn backfill_pair<M, F>(parser: &SwapParser<M>, pair_info: &PairInfo, swap_topic: H256, from_block: u64, callback: &F) -> Option<u64>
+where
+    M: Middleware + 'static,
+    F: Fn(SwapEvent),
+{
+    let to_block = match parser.provider.get_block_number().await {
+        Ok(block) => block.as_u64(),
+        Err(e) => {
+            log::warn!("⚠️ [SWAP_STREAMER] Not backfilling pair {:?}, failed to get block number: {}", pair_info.pair_address, e);
+            return None;
+        }
+    };
+    let filter = Filter::new().address(pair_info.pair_address).topic0(swap_topic);
+    let logs = match scan_logs(&parser.provider, filter, from_block, to_block).await {
+        Ok(logs) => logs,
+        Err(e) => {
+            log::warn!("⚠️ [SWAP_STREAMER] Failed to backfill pair {:?}: {}", pair_info.pair_address, e);
+            return None;
+        }
+    };
+    log::info!(
+        "📜 [SWAP_STREAMER] Replaying {} swap(s) of pair {:?} from blocks {}..{}",
+        logs.len(),
+        pair_info.pair_address,
+        from_block,
+        to_block
+    );
+    for log in logs {
+        match parser.parse_swap_event(&log, pair_info).await {
+            Ok(swap) => callback(swap),
+            Err(e) => log::warn!("⚠️ [SWAP_STREAMER] Failed to parse backfilled swap {:?}: {}", log.transaction_hash, e),
+        }
+    }
+    Some(to_block)
+}
+
 // Add Clone for SwapParser
 impl<M: Middleware + 'static> Clone for SwapParser<M> {
     fn clone(&self) -> Self {
         Self {
             provider: self.provider.clone(),
-            token_cache: TokenInfoCache::new(self.provider.clone()),
+            token_cache: self.token_cache.clone(),
+            price_oracle: self.price_oracle.clone(),
+            supply: self.supply.clone(),
+            quote_currency: self.quote_currency,
+            reserves: self.reserves.clone(),
+            tx_cache: self.tx_cache.clone(),
+            receipt_cache: self.receipt_cache.clone(),
+            pair_tokens: self.pair_tokens.clone(),
+            block_times: self.block_times.clone(),
         }
     }
 }
@@ -0,0 +1,233 @@
+use anyhow::Result;
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::{Middleware, PubsubClient, StreamExt},
+    types::{Address, Filter, H256, U256},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{get_burn_addresses, get_transfer_topic};
+use crate::core::options::BurnCallback;
+use crate::core::quoter::from_raw_amount;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{BurnAlertConfig, BurnEvent, MarketCap, TokenSupply};
+
+// Supply figures are re-read at most this often per token
+const SUPPLY_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+const SUPPLY_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"totalSupply","outputs":[{"name":"","type":"uint256"}],"type":"function"},
+    {"constant":true,"inputs":[{"name":"account","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"type":"function"}
+]"#;
+
+/// Last known supply per token, shared with the streamer handle
+#[derive(Clone, Default)]
+pub struct SupplyCache {
+    entries: Arc<RwLock<HashMap<Address, (TokenSupply, Instant)>>>,
+}
+
+impl SupplyCache {
+    /// Latest supply figure of a token, without any RPC
+    pub async fn get(&self, token: Address) -> Option<TokenSupply> {
+        self.entries.read().await.get(&token).map(|(supply, _)| supply.clone())
+    }
+
+    async fn fresh(&self, token: Address) -> Option<TokenSupply> {
+        let entries = self.entries.read().await;
+        let (supply, fetched_at) = entries.get(&token)?;
+        (fetched_at.elapsed() < SUPPLY_REFRESH_INTERVAL).then(|| supply.clone())
+    }
+
+    async fn insert(&self, supply: TokenSupply) {
+        self.entries.write().await.insert(supply.token_address, (supply, Instant::now()));
+    }
+}
+
+/// Total, burned and circulating supply per token, cached between refreshes
+pub struct SupplyTracker<M> {
+    provider: Arc<M>,
+    token_cache: TokenInfoCache<M>,
+    cache: SupplyCache,
+}
+
+impl<M: Middleware + 'static> SupplyTracker<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            token_cache: TokenInfoCache::new(provider.clone()),
+            provider,
+            cache: SupplyCache::default(),
+        }
+    }
+
+    /// Cached supply figures
+    pub fn cache(&self) -> SupplyCache {
+        self.cache.clone()
+    }
+
+    /// Supply of a token (refreshed when the cached figure is older than a minute)
+    pub async fn supply(&self, token: Address) -> Result<TokenSupply> {
+        if let Some(supply) = self.cache.fresh(token).await {
+            return Ok(supply);
+        }
+        self.refresh(token).await
+    }
+
+    /// Re-read a token's supply, bypassing the cache
+    pub async fn refresh(&self, token: Address) -> Result<TokenSupply> {
+        let supply = self.fetch(token).await?;
+        self.cache.insert(supply.clone()).await;
+        Ok(supply)
+    }
+
+    /// Market cap (circulating supply) and FDV (total supply) at a USD price
+    pub async fn market_cap(&self, token: Address, price_usd: f64) -> Option<MarketCap> {
+        let supply = match self.supply(token).await {
+            Ok(supply) => supply,
+            Err(e) => {
+                log::debug!("⚠️  Failed to read supply of {:?}: {}", token, e);
+                return None;
+            }
+        };
+
+        Some(MarketCap {
+            price_usd,
+            market_cap_usd: price_usd * supply.circulating_supply,
+            fdv_usd: price_usd * supply.total_supply,
+            circulating_supply: supply.circulating_supply,
+            total_supply: supply.total_supply,
+        })
+    }
+
+    async fn fetch(&self, token: Address) -> Result<TokenSupply> {
+        let abi: Abi = serde_json::from_str(SUPPLY_ABI)?;
+        let contract = Contract::new(token, abi, self.provider.clone());
+        let decimals = self.token_cache.get_token_info(token).await?.decimals;
+
+        let total_supply_raw: U256 = contract.method("totalSupply", ())?.call().await?;
+        let mut burned_raw = U256::zero();
+        for burn_address in get_burn_addresses() {
+            let balance: U256 = contract.method("balanceOf", burn_address)?.call().await.unwrap_or_default();
+            burned_raw = burned_raw.saturating_add(balance);
+        }
+        let burned_raw = burned_raw.min(total_supply_raw);
+
+        Ok(TokenSupply {
+            token_address: token,
+            decimals,
+            total_supply: from_raw_amount(total_supply_raw, decimals),
+            burned: from_raw_amount(burned_raw, decimals),
+            circulating_supply: from_raw_amount(total_supply_raw - burned_raw, decimals),
+            total_supply_raw,
+            burned_raw,
+        })
+    }
+}
+
+impl<M: Middleware + 'static> Clone for SupplyTracker<M> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            token_cache: TokenInfoCache::new(self.provider.clone()),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+/// Keep a token's supply live from burn (to dead/zero) and mint (from zero) transfers
+///
+/// Supply is re-read after each such transfer, since burning to the zero address
+/// may or may not reduce totalSupply depending on the token.
+pub(crate) fn spawn_supply_watcher<M>(
+    provider: Arc<M>,
+    token_address: Address,
+    tracker: SupplyTracker<M>,
+    burn_alert: Option<(BurnAlertConfig, BurnCallback)>,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    tokio::spawn(async move {
+        let transfer_topic = get_transfer_topic();
+        let burn_topics: Vec<H256> = get_burn_addresses().into_iter().map(H256::from).collect();
+        let burn_filter = Filter::new().address(token_address).topic0(transfer_topic).topic2(burn_topics);
+        let mint_filter = Filter::new()
+            .address(token_address)
+            .topic0(transfer_topic)
+            .topic1(H256::from(Address::zero()));
+
+        let (mut burns, mut mints) = match (
+            provider.subscribe_logs(&burn_filter).await,
+            provider.subscribe_logs(&mint_filter).await,
+        ) {
+            (Ok(burns), Ok(mints)) => (burns, mints),
+            (Err(e), _) | (_, Err(e)) => {
+                log::error!("❌ [SUPPLY] Failed to subscribe to burns/mints: {}", e);
+                return;
+            }
+        };
+        log::debug!("🔥 [SUPPLY] Tracking circulating supply of {:?}", token_address);
+
+        loop {
+            let (log, is_burn) = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [SUPPLY] Supply watcher cancelled for {:?}", token_address);
+                    break;
+                }
+                Some(log) = burns.next() => (log, true),
+                Some(log) = mints.next() => (log, false),
+                else => {
+                    log::warn!("⚠️ [SUPPLY] Burn/mint streams ended for {:?}", token_address);
+                    break;
+                }
+            };
+
+            if log.topics.len() < 3 || log.data.len() < 32 {
+                continue;
+            }
+            let amount_raw = U256::from_big_endian(&log.data[0..32]);
+            // Minting straight to a burn address changes nothing
+            if amount_raw.is_zero() || (!is_burn && log.topics[2] == log.topics[1]) {
+                continue;
+            }
+
+            let supply = match tracker.refresh(token_address).await {
+                Ok(supply) => supply,
+                Err(e) => {
+                    log::warn!("⚠️ [SUPPLY] Failed to refresh supply of {:?}: {}", token_address, e);
+                    continue;
+                }
+            };
+
+            if !is_burn {
+                log::info!("🪙 [SUPPLY] Mint on {:?} - total supply now {:.2}", token_address, supply.total_supply);
+                continue;
+            }
+
+            let amount = from_raw_amount(amount_raw, supply.decimals);
+            let pct_of_supply = if supply.total_supply > 0.0 { amount / supply.total_supply * 100.0 } else { 0.0 };
+            log::info!("🔥 [SUPPLY] {:.2} tokens ({:.4}%) burned on {:?}", amount, pct_of_supply, token_address);
+
+            if let Some((config, ref callback)) = burn_alert {
+                if pct_of_supply >= config.min_supply_pct {
+                    callback(BurnEvent {
+                        token_address,
+                        from: Address::from(log.topics[1]),
+                        burn_address: Address::from(log.topics[2]),
+                        amount,
+                        amount_raw,
+                        pct_of_supply,
+                        circulating_supply: supply.circulating_supply,
+                        transaction_hash: log.transaction_hash.unwrap_or_default(),
+                        block_number: log.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+                    });
+                }
+            }
+        }
+    });
+}
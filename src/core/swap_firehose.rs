@@ -0,0 +1,213 @@
+use ethers::{
+    abi::Abi,
+    contract::Contract,
+    providers::{Middleware, PubsubClient},
+    types::{Address, Filter, Log, H256},
+};
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{get_base_tokens, get_bonding_curve_address, get_factory_address, get_transfer_topic, get_v3_factory_address};
+use crate::core::filter::FirehoseFilter;
+use crate::core::health::HealthMonitor;
+use crate::core::latency::{mark_received, now_ms};
+use crate::core::new_pairs::split_pair;
+use crate::core::options::SwapCallback;
+use crate::core::pair_finder::{v3_tick_spacing, V2_FEE_TIER};
+use crate::core::streamer::{SWAP_V2_TOPIC, SWAP_V3_TOPIC};
+use crate::core::swap_parser::SwapParser;
+use crate::types::{PairInfo, Platform, SwapEvent};
+
+const POOL_ABI: &str = r#"[
+    {"constant":true,"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"token1","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"factory","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"fee","outputs":[{"name":"","type":"uint24"}],"type":"function"}
+]"#;
+
+// Swaps parsed concurrently; results are still delivered in log order
+const SWAP_PARSE_CONCURRENCY: usize = 16;
+
+// Resolved pool cache is reset past this size
+const MAX_CACHED_POOLS: usize = 50_000;
+
+/// Chain-wide swap source: resolves each emitting pool once and parses its swaps
+struct SwapFirehose<M> {
+    provider: Arc<M>,
+    parser: SwapParser<M>,
+    filter: FirehoseFilter,
+    base_tokens: Vec<(String, Address)>,
+    bonding_curve: Address,
+    transfer_topic: H256,
+    v3_topic: H256,
+    // None = not a PancakeSwap pool, or not priced in a base token
+    pools: RwLock<HashMap<Address, Option<PairInfo>>>,
+}
+
+impl<M: Middleware + 'static> SwapFirehose<M> {
+    fn new(provider: Arc<M>, filter: FirehoseFilter) -> Self {
+        Self {
+            parser: SwapParser::new(provider.clone()),
+            filter,
+            base_tokens: get_base_tokens(),
+            bonding_curve: get_bonding_curve_address(),
+            transfer_topic: get_transfer_topic(),
+            v3_topic: H256::from_str(SWAP_V3_TOPIC).unwrap(),
+            pools: RwLock::new(HashMap::new()),
+            provider,
+        }
+    }
+
+    /// PancakeSwap pair info of the pool emitting a swap, or None to skip it
+    ///
+    /// Forks (Biswap, ApeSwap, ...) share the Swap event and are told apart by
+    /// their factory. Pools without a base token cannot be priced and are skipped.
+    async fn resolve_pool(&self, pool: Address, is_v3: bool) -> Option<PairInfo> {
+        if let Some(info) = self.pools.read().await.get(&pool) {
+            return info.clone();
+        }
+
+        let abi: Abi = serde_json::from_str(POOL_ABI).ok()?;
+        let contract = Contract::new(pool, abi, self.provider.clone());
+        // RPC failures are not cached, so the pool is retried on its next swap
+        let factory: Address = contract.method("factory", ()).ok()?.call().await.ok()?;
+        let expected_factory = if is_v3 { get_v3_factory_address() } else { get_factory_address() };
+
+        let info = if factory == expected_factory {
+            let token0: Address = contract.method("token0", ()).ok()?.call().await.ok()?;
+            let token1: Address = contract.method("token1", ()).ok()?.call().await.ok()?;
+            let fee_tier = if is_v3 {
+                contract.method::<_, u32>("fee", ()).ok()?.call().await.ok()?
+            } else {
+                V2_FEE_TIER
+            };
+            match split_pair(token0, token1, &self.base_tokens) {
+                (token, Some((base_token_symbol, base_token))) => Some(PairInfo {
+                    pair_address: pool,
+                    token,
+                    base_token,
+                    base_token_symbol,
+                    is_v3,
+                    fee_tier,
+                    tick_spacing: if is_v3 { v3_tick_spacing(fee_tier) } else { None },
+                    route: None,
+                }),
+                (_, None) => None,
+            }
+        } else {
+            None
+        };
+
+        let mut pools = self.pools.write().await;
+        if pools.len() >= MAX_CACHED_POOLS {
+            pools.clear();
+        }
+        pools.insert(pool, info.clone());
+        info
+    }
+
+    /// Parse a firehose log received at `received_at_ms`, returning swaps that pass the filter
+    async fn parse(&self, log: Log, received_at_ms: u64) -> Option<SwapEvent> {
+        let topic0 = *log.topics.first()?;
+        let mut swap = if topic0 == self.transfer_topic {
+            // Transfers to or from the bonding curve, on any token
+            match self.parser.parse_bonding_curve_event(&log, log.address, self.bonding_curve).await {
+                Ok(swap) => swap?,
+                Err(e) => {
+                    log::debug!("⚠️ [FIREHOSE] Failed to parse bonding curve trade {:?}: {}", log.transaction_hash, e);
+                    return None;
+                }
+            }
+        } else {
+            let pair_info = self.resolve_pool(log.address, topic0 == self.v3_topic).await?;
+            if !self.filter.accepts_pair(&pair_info) {
+                return None;
+            }
+            match self.parser.parse_swap_event(&log, &pair_info).await {
+                Ok(swap) => swap,
+                Err(e) => {
+                    log::debug!("⚠️ [FIREHOSE] Failed to parse swap {:?}: {}", log.transaction_hash, e);
+                    return None;
+                }
+            }
+        };
+        mark_received(&mut swap, received_at_ms);
+        self.filter.matches(&swap).then_some(swap)
+    }
+}
+
+/// Report swaps on every PancakeSwap pool and the Four.meme bonding curve that pass `filter`
+pub(crate) fn spawn_swap_firehose<M>(
+    provider: Arc<M>,
+    filter: FirehoseFilter,
+    callback: SwapCallback,
+    health: HealthMonitor,
+    cancel_token: CancellationToken,
+) where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    let probe = health.subscription("Chain-wide swaps".to_string());
+    let latency = health.latency();
+    tokio::spawn(async move {
+        let firehose = SwapFirehose::new(provider.clone(), filter);
+
+        let mut filters = Vec::new();
+        if firehose.filter.accepts_platform(Platform::PancakeSwap) {
+            filters.push(Filter::new().topic0(vec![H256::from_str(SWAP_V2_TOPIC).unwrap(), firehose.v3_topic]));
+        }
+        if firehose.filter.accepts_platform(Platform::FourMemeBondingCurve) {
+            let curve_topic = H256::from(firehose.bonding_curve);
+            let transfers = Filter::new().topic0(firehose.transfer_topic);
+            filters.push(transfers.clone().topic1(curve_topic));
+            filters.push(transfers.topic2(curve_topic));
+        }
+
+        let mut subscriptions = Vec::new();
+        for filter in &filters {
+            match provider.subscribe_logs(filter).await {
+                Ok(subscription) => subscriptions.push(subscription),
+                Err(e) => {
+                    log::error!("❌ [FIREHOSE] Failed to subscribe to chain-wide swaps: {}", e);
+                    probe.failed(e);
+                    return;
+                }
+            }
+        }
+        probe.live();
+        log::info!("🌊 [FIREHOSE] Streaming chain-wide swaps ({} subscription(s))", subscriptions.len());
+
+        let firehose = &firehose;
+        let mut swaps = stream::select_all(subscriptions)
+            .map(|log| {
+                probe.event();
+                firehose.parse(log, now_ms())
+            })
+            .buffered(SWAP_PARSE_CONCURRENCY);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [FIREHOSE] Swap firehose cancelled");
+                    probe.stopped();
+                    break;
+                }
+                swap = swaps.next() => match swap {
+                    Some(Some(mut swap)) => {
+                        latency.mark_dispatched(&mut swap);
+                        callback(swap);
+                    }
+                    Some(None) => {}
+                    None => {
+                        log::warn!("⚠️ [FIREHOSE] Swap streams ended");
+                        probe.ended();
+                        break;
+                    }
+                },
+            }
+        }
+    });
+}
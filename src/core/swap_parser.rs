@@ -3,36 +3,211 @@ use ethers::{
     abi::Abi,
     contract::Contract,
     providers::Middleware,
-    types::{Address, Log, I256, U256},
+    types::{Address, Log, Transaction, TransactionReceipt, H256, I256, U256, U64},
     utils::format_units,
 };
-use std::sync::Arc;
-
+use lru::LruCache;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+
+use crate::core::liquidity::pool_balance;
+use crate::core::price_oracle::PriceOracle;
+use crate::core::reserves::ReserveTracker;
+use crate::core::supply::SupplyTracker;
+use crate::core::tax::tax_from_receipt;
 use crate::core::token_info::TokenInfoCache;
-use crate::types::{PairInfo, Platform, PriceInfo, SwapEvent, TokenInfo, TradeType};
+use crate::config::get_base_tokens;
+use crate::types::{
+    GasInfo, KnownRouter, MarketCap, NormalizedPrice, PairDetails, PairInfo, Platform, PriceInfo, QuoteCurrency, QuoteRoute, RationalPrice, SwapEvent,
+    TokenInfo, TradeType, V3SwapState, EVENT_SCHEMA_VERSION,
+};
 
 const PAIR_V2_ABI: &str = r#"[
     {"constant":true,"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"type":"function"},
     {"constant":true,"inputs":[],"name":"token1","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"getReserves","outputs":[{"name":"reserve0","type":"uint112"},{"name":"reserve1","type":"uint112"},{"name":"blockTimestampLast","type":"uint32"}],"type":"function"},
     {"anonymous":false,"inputs":[{"indexed":true,"name":"sender","type":"address"},{"indexed":false,"name":"amount0In","type":"uint256"},{"indexed":false,"name":"amount1In","type":"uint256"},{"indexed":false,"name":"amount0Out","type":"uint256"},{"indexed":false,"name":"amount1Out","type":"uint256"},{"indexed":true,"name":"to","type":"address"}],"name":"Swap","type":"event"}
 ]"#;
 
 const POOL_V3_ABI: &str = r#"[
     {"constant":true,"inputs":[],"name":"token0","outputs":[{"name":"","type":"address"}],"type":"function"},
     {"constant":true,"inputs":[],"name":"token1","outputs":[{"name":"","type":"address"}],"type":"function"},
+    {"constant":true,"inputs":[],"name":"slot0","outputs":[{"name":"sqrtPriceX96","type":"uint160"},{"name":"tick","type":"int24"},{"name":"observationIndex","type":"uint16"},{"name":"observationCardinality","type":"uint16"},{"name":"observationCardinalityNext","type":"uint16"},{"name":"feeProtocol","type":"uint32"},{"name":"unlocked","type":"bool"}],"type":"function"},
     {"anonymous":false,"inputs":[{"indexed":true,"name":"sender","type":"address"},{"indexed":true,"name":"recipient","type":"address"},{"indexed":false,"name":"amount0","type":"int256"},{"indexed":false,"name":"amount1","type":"int256"},{"indexed":false,"name":"sqrtPriceX96","type":"uint160"},{"indexed":false,"name":"liquidity","type":"uint128"},{"indexed":false,"name":"tick","type":"int24"},{"indexed":false,"name":"protocolFeesToken0","type":"uint128"},{"indexed":false,"name":"protocolFeesToken1","type":"uint128"}],"name":"Swap","type":"event"}
 ]"#;
 
+// Transactions kept per parser so several swaps in one tx share a single lookup
+const TX_CACHE_CAPACITY: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+type Lru<K, V> = Arc<Mutex<LruCache<K, V>>>;
+
+fn lru<K: std::hash::Hash + Eq, V>() -> Lru<K, V> {
+    Arc::new(Mutex::new(LruCache::new(TX_CACHE_CAPACITY)))
+}
+
 pub struct SwapParser<M> {
     pub provider: Arc<M>,
     pub token_cache: TokenInfoCache<M>,
+    pub price_oracle: PriceOracle<M>,
+    pub supply: SupplyTracker<M>,
+    pub quote_currency: QuoteCurrency,
+    /// Sync-driven reserves; route hops tracked here are priced without RPC
+    pub reserves: ReserveTracker,
+    pub(crate) tx_cache: Lru<H256, Transaction>,
+    pub(crate) receipt_cache: Lru<H256, TransactionReceipt>,
+    /// (token0, token1) per pool - immutable, so looked up once per pool
+    pub(crate) pair_tokens: Arc<RwLock<HashMap<Address, (Address, Address)>>>,
+    /// RFC3339 timestamps per block number
+    pub(crate) block_times: Lru<u64, Option<String>>,
 }
 
 impl<M: Middleware + 'static> SwapParser<M> {
     pub fn new(provider: Arc<M>) -> Self {
         Self {
             token_cache: TokenInfoCache::new(provider.clone()),
+            price_oracle: PriceOracle::new(provider.clone()),
+            supply: SupplyTracker::new(provider.clone()),
+            quote_currency: QuoteCurrency::default(),
+            reserves: ReserveTracker::new(),
             provider,
+            tx_cache: lru(),
+            receipt_cache: lru(),
+            pair_tokens: Arc::new(RwLock::new(HashMap::new())),
+            block_times: lru(),
+        }
+    }
+
+    /// token0/token1 of a pool, cached after the first lookup
+    pub(crate) async fn get_pair_tokens(&self, contract: &Contract<M>) -> Result<(Address, Address)> {
+        let pool = contract.address();
+        if let Some(tokens) = self.pair_tokens.read().await.get(&pool) {
+            return Ok(*tokens);
+        }
+
+        let token0: Address = contract.method("token0", ())?.call().await?;
+        let token1: Address = contract.method("token1", ())?.call().await?;
+        self.pair_tokens.write().await.insert(pool, (token0, token1));
+
+        Ok((token0, token1))
+    }
+
+    /// Block timestamp as RFC3339, fetched once per block
+    pub(crate) async fn get_block_timestamp(&self, block_number: U64) -> Result<Option<String>> {
+        let key = block_number.as_u64();
+        if let Some(timestamp) = self.block_times.lock().unwrap().get(&key) {
+            return Ok(timestamp.clone());
+        }
+
+        let block = self.provider.get_block(block_number).await
+            .map_err(|e| anyhow!("Failed to fetch block {}: {}", block_number, e))?;
+        let timestamp = block.and_then(|b| {
+            b.timestamp
+                .as_u64()
+                .checked_mul(1000)
+                .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
+                .map(|dt| dt.to_rfc3339())
+        });
+
+        self.block_times.lock().unwrap().put(key, timestamp.clone());
+
+        Ok(timestamp)
+    }
+
+    /// Fetch a transaction, reusing the cached copy when available
+    async fn get_transaction(&self, tx_hash: H256) -> Result<Option<Transaction>> {
+        if let Some(tx) = self.tx_cache.lock().unwrap().get(&tx_hash) {
+            return Ok(Some(tx.clone()));
+        }
+
+        let tx = self
+            .provider
+            .get_transaction(tx_hash)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch transaction {:?}: {}", tx_hash, e))?;
+
+        if let Some(ref tx) = tx {
+            self.tx_cache.lock().unwrap().put(tx_hash, tx.clone());
+        }
+
+        Ok(tx)
+    }
+
+    /// Fetch a transaction receipt, reusing the cached copy when available
+    async fn get_receipt(&self, tx_hash: H256) -> Result<Option<TransactionReceipt>> {
+        if let Some(receipt) = self.receipt_cache.lock().unwrap().get(&tx_hash) {
+            return Ok(Some(receipt.clone()));
+        }
+
+        let receipt = self
+            .provider
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch receipt {:?}: {}", tx_hash, e))?;
+
+        if let Some(ref receipt) = receipt {
+            self.receipt_cache.lock().unwrap().put(tx_hash, receipt.clone());
+        }
+
+        Ok(receipt)
+    }
+
+    /// Gas usage and fee for a log's transaction, from its receipt
+    async fn get_gas_info(&self, log: &Log) -> Option<GasInfo> {
+        let tx_hash = log.transaction_hash?;
+        let receipt = match self.get_receipt(tx_hash).await {
+            Ok(Some(receipt)) => receipt,
+            Ok(None) => return None,
+            Err(e) => {
+                log::debug!("⚠️  Could not fetch gas info for {:?}: {}", tx_hash, e);
+                return None;
+            }
+        };
+
+        let gas_used = receipt.gas_used?;
+        // Older nodes omit effectiveGasPrice - fall back to the tx gas price
+        let effective_gas_price = match receipt.effective_gas_price {
+            Some(price) => price,
+            None => self.get_transaction(tx_hash).await.ok()??.gas_price?,
+        };
+        let fee_bnb = format_units(gas_used * effective_gas_price, 18u32).ok()?;
+
+        Some(GasInfo {
+            gas_used,
+            effective_gas_price,
+            fee_bnb,
+        })
+    }
+
+    /// Transfer tax taken on a DEX swap, from its (cached) receipt
+    async fn effective_tax(&self, log: &Log, pair_info: &PairInfo, trade_type: TradeType, token_amount: U256) -> Option<f64> {
+        let receipt = self.get_receipt(log.transaction_hash?).await.ok()??;
+        tax_from_receipt(&receipt, pair_info.token, pair_info.pair_address, trade_type, token_amount)
+    }
+
+    /// Detect a known router/aggregator from the tx entry point, then the swap's sender/recipient
+    async fn detect_router(&self, log: &Log, sender: Address, recipient: Address) -> Option<KnownRouter> {
+        let tx_to = match log.transaction_hash {
+            Some(tx_hash) => self.get_transaction(tx_hash).await.ok().flatten().and_then(|tx| tx.to),
+            None => None,
+        };
+
+        tx_to
+            .and_then(KnownRouter::from_address)
+            .or_else(|| KnownRouter::from_address(sender))
+            .or_else(|| KnownRouter::from_address(recipient))
+    }
+
+    /// Resolve the originating wallet (tx.from) of a log's transaction
+    async fn get_trader(&self, log: &Log) -> Option<Address> {
+        let tx_hash = log.transaction_hash?;
+        match self.get_transaction(tx_hash).await {
+            Ok(tx) => tx.map(|t| t.from),
+            Err(e) => {
+                log::debug!("⚠️  Could not resolve trader for {:?}: {}", tx_hash, e);
+                None
+            }
         }
     }
 
@@ -40,11 +215,22 @@ impl<M: Middleware + 'static> SwapParser<M> {
         &self,
         log: &Log,
         pair_info: &PairInfo,
+    ) -> Result<SwapEvent> {
+        self.parse_swap_event_at(log, pair_info, None).await
+    }
+
+    /// Parse with the route hop priced at `hop_price` (counter-token in the route's
+    /// base token) as read when the log arrived, so later hop Syncs don't reprice it
+    pub(crate) async fn parse_swap_event_at(
+        &self,
+        log: &Log,
+        pair_info: &PairInfo,
+        hop_price: Option<f64>,
     ) -> Result<SwapEvent> {
         if pair_info.is_v3 {
-            self.parse_v3_swap_event(log, pair_info).await
+            self.parse_v3_swap_event(log, pair_info, hop_price).await
         } else {
-            self.parse_v2_swap_event(log, pair_info).await
+            self.parse_v2_swap_event(log, pair_info, hop_price).await
         }
     }
 
@@ -52,13 +238,13 @@ impl<M: Middleware + 'static> SwapParser<M> {
         &self,
         log: &Log,
         pair_info: &PairInfo,
+        hop_price: Option<f64>,
     ) -> Result<SwapEvent> {
         let abi: Abi = serde_json::from_str(PAIR_V2_ABI)?;
         let contract = Contract::new(pair_info.pair_address, abi.clone(), self.provider.clone());
 
         // Get token addresses
-        let token0: Address = contract.method("token0", ())?.call().await?;
-        let token1: Address = contract.method("token1", ())?.call().await?;
+        let (token0, token1) = self.get_pair_tokens(&contract).await?;
 
         // Get token info
         let token0_info = self.token_cache.get_token_info(token0).await?;
@@ -148,19 +334,26 @@ impl<M: Middleware + 'static> SwapParser<M> {
             0.0
         };
 
+        // Convert counter-token pricing into a base token via the routing hop
+        let (price, price_symbol, price_token) = self.apply_route(price, pair_info, hop_price).await?;
+        let (price_usd, usd_value) = self.usd_valuation(price_token, price, token_amount_f64).await;
+        let normalized_price = self.normalize_price(price_token, price, price_usd).await;
+        let market_cap = self.market_cap(pair_info.token, price_usd).await;
+
+        let trader = self.get_trader(log).await;
+        let gas = self.get_gas_info(log).await;
+        let effective_tax_pct = self.effective_tax(log, pair_info, trade_type, token_amount).await;
+        let route_via = self.detect_router(log, sender, to).await;
+
         // Get block info
-        let block = self.provider.get_block(log.block_number.unwrap()).await?;
-        let timestamp = block.and_then(|b| {
-            b.timestamp
-                .as_u64()
-                .checked_mul(1000)
-                .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
-                .map(|dt| dt.to_rfc3339())
-        });
+        let timestamp = self.get_block_timestamp(log.block_number.unwrap()).await?;
 
         Ok(SwapEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             transaction_hash: log.transaction_hash.unwrap(),
             block_number: log.block_number.unwrap().as_u64(),
+            transaction_index: log.transaction_index.map(|i| i.as_u64()).unwrap_or_default(),
+            log_index: log.log_index.map(|i| i.as_u64()).unwrap_or_default(),
             timestamp,
             platform: Platform::PancakeSwap,
             trade_type,
@@ -172,23 +365,45 @@ impl<M: Middleware + 'static> SwapParser<M> {
                     token1_info.symbol
                 },
                 amount: token_amount_str,
+                amount_raw: token_amount,
                 decimals: token_decimals,
             },
             base_token: TokenInfo {
                 address: pair_info.base_token,
                 symbol: pair_info.base_token_symbol.clone(),
                 amount: base_amount_str,
+                amount_raw: base_amount,
                 decimals: base_decimals,
             },
             price: PriceInfo {
                 value: price,
-                display: format!("{:.12} {}", price, pair_info.base_token_symbol),
-                base_token: pair_info.base_token_symbol.clone(),
+                display: format!("{:.12} {}", price, price_symbol),
+                base_token: price_symbol,
+                quote_token: Some(price_token),
+                raw: RationalPrice {
+                    numerator: base_amount,
+                    denominator: token_amount,
+                },
             },
+            price_usd,
+            usd_value,
+            normalized_price,
             sender,
             recipient: to,
+            trader,
+            route_via,
+            sender_label: None,
+            recipient_label: None,
+            trader_label: None,
             pair_address: Some(pair_info.pair_address),
             bonding_curve_address: None,
+            pool: Some(pair_info.pool_metadata()),
+            v3_state: None,
+            effective_tax_pct,
+            depth: None,
+            market_cap,
+            gas,
+            latency: None,
         })
     }
 
@@ -196,13 +411,13 @@ impl<M: Middleware + 'static> SwapParser<M> {
         &self,
         log: &Log,
         pair_info: &PairInfo,
+        hop_price: Option<f64>,
     ) -> Result<SwapEvent> {
         let abi: Abi = serde_json::from_str(POOL_V3_ABI)?;
         let contract = Contract::new(pair_info.pair_address, abi.clone(), self.provider.clone());
 
         // Get token addresses
-        let token0: Address = contract.method("token0", ())?.call().await?;
-        let token1: Address = contract.method("token1", ())?.call().await?;
+        let (token0, token1) = self.get_pair_tokens(&contract).await?;
 
         // Get token info
         let token0_info = self.token_cache.get_token_info(token0).await?;
@@ -234,6 +449,23 @@ impl<M: Middleware + 'static> SwapParser<M> {
             .ok_or_else(|| anyhow!("Failed to parse amount1 as int"))?;
         let amount1_raw = I256::from_raw(amount1_u256);
 
+        // Post-swap pool state
+        let find_uint = |name: &str| -> Result<U256> {
+            find_param(name)?
+                .into_uint()
+                .ok_or_else(|| anyhow!("Failed to parse {} as uint", name))
+        };
+        let tick_u256: U256 = find_param("tick")?
+            .into_int()
+            .ok_or_else(|| anyhow!("Failed to parse tick as int"))?;
+        let v3_state = V3SwapState {
+            sqrt_price_x96: find_uint("sqrtPriceX96")?,
+            liquidity: find_uint("liquidity")?.as_u128(),
+            tick: I256::from_raw(tick_u256).as_i32(),
+            protocol_fees_token0: find_uint("protocolFeesToken0")?.as_u128(),
+            protocol_fees_token1: find_uint("protocolFeesToken1")?.as_u128(),
+        };
+
         // Convert to absolute values for calculation
         let amount0 = if amount0_raw.is_negative() {
             amount0_raw.wrapping_neg().into_raw()
@@ -307,19 +539,26 @@ impl<M: Middleware + 'static> SwapParser<M> {
             0.0
         };
 
+        // Convert counter-token pricing into a base token via the routing hop
+        let (price, price_symbol, price_token) = self.apply_route(price, pair_info, hop_price).await?;
+        let (price_usd, usd_value) = self.usd_valuation(price_token, price, token_amount_f64).await;
+        let normalized_price = self.normalize_price(price_token, price, price_usd).await;
+        let market_cap = self.market_cap(pair_info.token, price_usd).await;
+
+        let trader = self.get_trader(log).await;
+        let gas = self.get_gas_info(log).await;
+        let effective_tax_pct = self.effective_tax(log, pair_info, trade_type, token_amount).await;
+        let route_via = self.detect_router(log, sender, to).await;
+
         // Get block info
-        let block = self.provider.get_block(log.block_number.unwrap()).await?;
-        let timestamp = block.and_then(|b| {
-            b.timestamp
-                .as_u64()
-                .checked_mul(1000)
-                .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
-                .map(|dt| dt.to_rfc3339())
-        });
+        let timestamp = self.get_block_timestamp(log.block_number.unwrap()).await?;
 
         Ok(SwapEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             transaction_hash: log.transaction_hash.unwrap(),
             block_number: log.block_number.unwrap().as_u64(),
+            transaction_index: log.transaction_index.map(|i| i.as_u64()).unwrap_or_default(),
+            log_index: log.log_index.map(|i| i.as_u64()).unwrap_or_default(),
             timestamp,
             platform: Platform::PancakeSwap,
             trade_type,
@@ -331,23 +570,45 @@ impl<M: Middleware + 'static> SwapParser<M> {
                     token1_info.symbol
                 },
                 amount: token_amount_str,
+                amount_raw: token_amount,
                 decimals: token_decimals,
             },
             base_token: TokenInfo {
                 address: pair_info.base_token,
                 symbol: pair_info.base_token_symbol.clone(),
                 amount: base_amount_str,
+                amount_raw: base_amount,
                 decimals: base_decimals,
             },
             price: PriceInfo {
                 value: price,
-                display: format!("{:.12} {}", price, pair_info.base_token_symbol),
-                base_token: pair_info.base_token_symbol.clone(),
+                display: format!("{:.12} {}", price, price_symbol),
+                base_token: price_symbol,
+                quote_token: Some(price_token),
+                raw: RationalPrice {
+                    numerator: base_amount,
+                    denominator: token_amount,
+                },
             },
+            price_usd,
+            usd_value,
+            normalized_price,
             sender,
             recipient: to,
+            trader,
+            route_via,
+            sender_label: None,
+            recipient_label: None,
+            trader_label: None,
             pair_address: Some(pair_info.pair_address),
             bonding_curve_address: None,
+            pool: Some(pair_info.pool_metadata()),
+            v3_state: Some(v3_state),
+            effective_tax_pct,
+            depth: None,
+            market_cap,
+            gas,
+            latency: None,
         })
     }
 
@@ -382,7 +643,6 @@ impl<M: Middleware + 'static> SwapParser<M> {
         let bnb_amount = if trade_type == TradeType::Buy {
             // For buys, check tx.value first
             let tx = self
-                .provider
                 .get_transaction(log.transaction_hash.unwrap())
                 .await?;
             let tx_value = tx.map(|t| t.value).unwrap_or_default();
@@ -392,8 +652,7 @@ impl<M: Middleware + 'static> SwapParser<M> {
             // In that case, also check the receipt for the BNB amount
             if tx_value == U256::zero() {
                 if let Some(receipt) = self
-                    .provider
-                    .get_transaction_receipt(log.transaction_hash.unwrap())
+                    .get_receipt(log.transaction_hash.unwrap())
                     .await?
                 {
                     let mut found_bnb = U256::zero();
@@ -430,8 +689,7 @@ impl<M: Middleware + 'static> SwapParser<M> {
             // For sells, check the transaction receipt for bonding curve events
             // The bonding curve contract should emit events with the BNB amount
             if let Some(receipt) = self
-                .provider
-                .get_transaction_receipt(log.transaction_hash.unwrap())
+                .get_receipt(log.transaction_hash.unwrap())
                 .await?
             {
                 // Look through all logs for events from the bonding curve
@@ -482,6 +740,10 @@ impl<M: Middleware + 'static> SwapParser<M> {
         let token_amount_str = format_units(token_amount, token_info.decimals as u32)?;
         let bnb_amount_str = format_units(bnb_amount, 18u32)?;
 
+        let trader = self.get_trader(log).await;
+        let gas = self.get_gas_info(log).await;
+        let route_via = self.detect_router(log, from, to).await;
+
         // Calculate price
         let token_amount_f64: f64 = token_amount_str.parse().unwrap_or(0.0);
         let bnb_amount_f64: f64 = bnb_amount_str.parse().unwrap_or(0.0);
@@ -490,20 +752,19 @@ impl<M: Middleware + 'static> SwapParser<M> {
         } else {
             0.0
         };
+        let (price_usd, usd_value) = self.usd_valuation(quote_token_address, price, token_amount_f64).await;
+        let normalized_price = self.normalize_price(quote_token_address, price, price_usd).await;
+        let market_cap = self.market_cap(token_address, price_usd).await;
 
         // Get block info
-        let block = self.provider.get_block(log.block_number.unwrap()).await?;
-        let timestamp = block.and_then(|b| {
-            b.timestamp
-                .as_u64()
-                .checked_mul(1000)
-                .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
-                .map(|dt| dt.to_rfc3339())
-        });
+        let timestamp = self.get_block_timestamp(log.block_number.unwrap()).await?;
 
         Ok(Some(SwapEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
             transaction_hash: log.transaction_hash.unwrap(),
             block_number: log.block_number.unwrap().as_u64(),
+            transaction_index: log.transaction_index.map(|i| i.as_u64()).unwrap_or_default(),
+            log_index: log.log_index.map(|i| i.as_u64()).unwrap_or_default(),
             timestamp,
             platform: Platform::FourMemeBondingCurve,
             trade_type,
@@ -511,26 +772,175 @@ impl<M: Middleware + 'static> SwapParser<M> {
                 address: token_address,
                 symbol: token_info.symbol,
                 amount: token_amount_str,
+                amount_raw: token_amount,
                 decimals: token_info.decimals,
             },
             base_token: TokenInfo {
                 address: quote_token_address,
                 symbol: quote_token_symbol.clone(),
                 amount: bnb_amount_str,
+                amount_raw: bnb_amount,
                 decimals: 18,
             },
             price: PriceInfo {
                 value: price,
                 display: format!("{:.12} {}", price, quote_token_symbol),
                 base_token: quote_token_symbol,
+                quote_token: Some(quote_token_address),
+                raw: RationalPrice {
+                    numerator: bnb_amount,
+                    denominator: token_amount,
+                },
             },
+            price_usd,
+            usd_value,
+            normalized_price,
             sender: from,
             recipient: to,
+            trader,
+            route_via,
+            sender_label: None,
+            recipient_label: None,
+            trader_label: None,
             pair_address: None,
             bonding_curve_address: Some(bonding_curve_address),
+            pool: None,
+            v3_state: None,
+            effective_tax_pct: None,
+            depth: None,
+            market_cap,
+            gas,
+            latency: None,
         }))
     }
     
+    /// Convert a price quoted in `price_token` into the configured quote currency
+    async fn normalize_price(&self, price_token: Address, price: f64, price_usd: Option<f64>) -> Option<NormalizedPrice> {
+        let value = match self.quote_currency {
+            QuoteCurrency::Usd => price_usd?,
+            QuoteCurrency::Bnb => {
+                let wbnb = get_base_tokens()[0].1;
+                if price_token == wbnb {
+                    price
+                } else {
+                    price_usd? / self.price_oracle.bnb_usd().await?
+                }
+            }
+        };
+
+        Some(NormalizedPrice {
+            value,
+            currency: self.quote_currency,
+        })
+    }
+
+    /// Convert a price quoted in the pair's counter-token into the route's base token
+    /// Returns (price, price_symbol, price_token); pairs without a route are returned unchanged
+    async fn apply_route(&self, price: f64, pair_info: &PairInfo, hop_price: Option<f64>) -> Result<(f64, String, Address)> {
+        match &pair_info.route {
+            Some(route) => {
+                let counter_price = match hop_price {
+                    Some(counter_price) => counter_price,
+                    None => self.counter_token_price(pair_info.base_token, route).await?,
+                };
+                Ok((price * counter_price, route.base_token_symbol.clone(), route.base_token))
+            }
+            None => Ok((price, pair_info.base_token_symbol.clone(), pair_info.base_token)),
+        }
+    }
+
+    /// Market cap/FDV at the trade's USD price
+    async fn market_cap(&self, token: Address, price_usd: Option<f64>) -> Option<MarketCap> {
+        self.supply.market_cap(token, price_usd?).await
+    }
+
+    /// USD price per token and USD trade value, given a price quoted in `price_token`
+    async fn usd_valuation(&self, price_token: Address, price: f64, token_amount: f64) -> (Option<f64>, Option<f64>) {
+        match self.price_oracle.usd_price(price_token).await {
+            Some(quote_usd) => {
+                let price_usd = price * quote_usd;
+                (Some(price_usd), Some(price_usd * token_amount))
+            }
+            None => (None, None),
+        }
+    }
+
+    /// Spot price of the counter-token in the route's base token
+    ///
+    /// V2 hops seeded into `reserves` are priced from their latest Sync; other
+    /// hops are read from the pool.
+    async fn counter_token_price(&self, counter_token: Address, route: &QuoteRoute) -> Result<f64> {
+        if let Some(price) = self.reserves.reserve_price(route.hop_pair).await {
+            return Ok(price);
+        }
+        self.spot_price(counter_token, route.hop_pair, route.hop_is_v3, route.base_token).await
+    }
+
+    /// Current price and liquidity of a pair, with USD values when its quote has a USD price
+    pub async fn pair_details(&self, pair_info: &PairInfo) -> PairDetails {
+        let price = self
+            .spot_price(pair_info.token, pair_info.pair_address, pair_info.is_v3, pair_info.base_token)
+            .await
+            .ok();
+        // USD value of one pair base token (or counter-token, priced through its route)
+        let base_usd = match self.apply_route(1.0, pair_info, None).await {
+            Ok((base_price, _, price_token)) => self.price_oracle.usd_price(price_token).await.map(|usd| base_price * usd),
+            Err(_) => None,
+        };
+        let base_balance = pool_balance(&self.provider, &self.token_cache, pair_info.pair_address, pair_info.base_token).await;
+
+        PairDetails {
+            info: pair_info.clone(),
+            liquidity_usd: base_balance.zip(base_usd).map(|(balance, usd)| balance * usd * 2.0),
+            price,
+            price_usd: price.zip(base_usd).map(|(price, usd)| price * usd),
+            recent_swaps: None,
+        }
+    }
+
+    /// Spot price of `token` in `base_token` on a V2 pair or V3 pool
+    pub async fn spot_price(&self, token: Address, pool: Address, is_v3: bool, base_token: Address) -> Result<f64> {
+        let counter_decimals = self.token_cache.get_token_info(token).await?.decimals as i32;
+        let base_decimals = self.token_cache.get_token_info(base_token).await?.decimals as i32;
+
+        let abi: Abi = serde_json::from_str(if is_v3 { POOL_V3_ABI } else { PAIR_V2_ABI })?;
+        let contract = Contract::new(pool, abi, self.provider.clone());
+        let (token0, _) = self.get_pair_tokens(&contract).await?;
+        let counter_is_token0 = token0 == token;
+
+        let price = if is_v3 {
+            let (sqrt_price_x96, _, _, _, _, _, _): (U256, i32, u16, u16, u16, u32, bool) =
+                contract.method("slot0", ())?.call().await?;
+            let sqrt_price = sqrt_price_x96.to_string().parse::<f64>().unwrap_or(0.0) / 2f64.powi(96);
+            // Raw price of token0 in token1 units
+            let raw_price = sqrt_price * sqrt_price;
+            if counter_is_token0 {
+                raw_price * 10f64.powi(counter_decimals - base_decimals)
+            } else if raw_price > 0.0 {
+                (1.0 / raw_price) * 10f64.powi(counter_decimals - base_decimals)
+            } else {
+                0.0
+            }
+        } else {
+            let (reserve0, reserve1, _): (u128, u128, u32) =
+                contract.method("getReserves", ())?.call().await?;
+            let (counter_reserve, base_reserve) = if counter_is_token0 {
+                (reserve0, reserve1)
+            } else {
+                (reserve1, reserve0)
+            };
+            let counter_amount = counter_reserve as f64 / 10f64.powi(counter_decimals);
+            let base_amount = base_reserve as f64 / 10f64.powi(base_decimals);
+            if counter_amount > 0.0 {
+                base_amount / counter_amount
+            } else {
+                0.0
+            }
+        };
+
+        Ok(price)
+    }
+
     /// Detect the quote token (BNB or stablecoin) for a Four.meme token
     /// Returns (address, symbol)
     async fn detect_fourmeme_quote_token(&self, token_address: Address) -> (Address, String) {
@@ -538,24 +948,21 @@ impl<M: Middleware + 'static> SwapParser<M> {
         let token_addr_str = format!("{:?}", token_address);
         let url = format!("https://api.dexscreener.com/latest/dex/tokens/{}", token_addr_str);
         
-        match reqwest::get(&url).await {
-            Ok(response) => {
-                if let Ok(data) = response.json::<serde_json::Value>().await {
-                    if let Some(pairs) = data["pairs"].as_array() {
-                        if let Some(first_pair) = pairs.first() {
-                            if let Some(quote_addr) = first_pair["quoteToken"]["address"].as_str() {
-                                if let Some(quote_symbol) = first_pair["quoteToken"]["symbol"].as_str() {
-                                    // Parse the quote token address
-                                    if let Ok(addr) = quote_addr.parse::<Address>() {
-                                        return (addr, quote_symbol.to_string());
-                                    }
+        if let Ok(response) = reqwest::get(&url).await {
+            if let Ok(data) = response.json::<serde_json::Value>().await {
+                if let Some(pairs) = data["pairs"].as_array() {
+                    if let Some(first_pair) = pairs.first() {
+                        if let Some(quote_addr) = first_pair["quoteToken"]["address"].as_str() {
+                            if let Some(quote_symbol) = first_pair["quoteToken"]["symbol"].as_str() {
+                                // Parse the quote token address
+                                if let Ok(addr) = quote_addr.parse::<Address>() {
+                                    return (addr, quote_symbol.to_string());
                                 }
                             }
                         }
                     }
                 }
             }
-            Err(_) => {}
         }
         
         // Default to WBNB if detection fails
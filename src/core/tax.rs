@@ -0,0 +1,203 @@
+use anyhow::{anyhow, Result};
+use ethers::{
+    providers::Middleware,
+    types::{Address, Filter, TransactionReceipt, H256, I256, U256},
+};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::config::get_transfer_topic;
+use crate::core::simulation::{RouterSimulator, ROUND_TRIP_BNB};
+use crate::core::streamer::{SWAP_V2_TOPIC, SWAP_V3_TOPIC};
+use crate::types::{PairInfo, TaxEstimate, TradeType};
+
+// Recent pair history sampled for tax estimation
+const HISTORY_BLOCKS: u64 = 2_000;
+
+// Receipts fetched per trade direction
+const MAX_SAMPLES: usize = 10;
+
+/// Token Transfer logs of a receipt as (from, to, amount)
+fn token_transfers(receipt: &TransactionReceipt, token: Address) -> Vec<(Address, Address, U256)> {
+    let transfer_topic = get_transfer_topic();
+    receipt
+        .logs
+        .iter()
+        .filter(|l| l.address == token && l.topics.len() >= 3 && l.topics[0] == transfer_topic && l.data.len() >= 32)
+        .map(|l| (Address::from(l.topics[1]), Address::from(l.topics[2]), U256::from_big_endian(&l.data[0..32])))
+        .collect()
+}
+
+/// Ratio of two token amounts as a percentage
+pub(crate) fn pct(part: U256, whole: U256) -> Option<f64> {
+    if whole.is_zero() || part > whole {
+        return None;
+    }
+    Some((part * U256::from(1_000_000) / whole).as_u64() as f64 / 10_000.0)
+}
+
+/// Infer the transfer tax taken on a swap from the token's Transfer logs in its receipt
+///
+/// Buys: the pool sends `token_amount`; whatever does not reach the largest
+/// recipient was taxed. Sells: the pool receives `token_amount`; other transfers
+/// by the same seller in the tx are the tax. Returns None when the logs don't fit.
+pub fn tax_from_receipt(
+    receipt: &TransactionReceipt,
+    token: Address,
+    pool: Address,
+    trade_type: TradeType,
+    token_amount: U256,
+) -> Option<f64> {
+    let transfers = token_transfers(receipt, token);
+
+    match trade_type {
+        TradeType::Buy => {
+            let received = transfers
+                .iter()
+                .filter(|(from, to, _)| *from == pool && *to != pool)
+                .map(|(_, _, amount)| *amount)
+                .max()?;
+            pct(token_amount.saturating_sub(received), token_amount)
+        }
+        TradeType::Sell => {
+            // The token contract's own swap-back of collected tax also goes to the pool - skip it
+            let (seller, _, _) = transfers
+                .iter()
+                .filter(|(from, to, _)| *to == pool && *from != token)
+                .max_by_key(|(_, _, amount)| *amount)?;
+            let taxed = transfers
+                .iter()
+                .filter(|(from, to, _)| from == seller && *to != pool)
+                .fold(U256::zero(), |acc, (_, _, amount)| acc + *amount);
+            pct(taxed, token_amount + taxed)
+        }
+    }
+}
+
+/// Median of the samples
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    Some(values[values.len() / 2])
+}
+
+/// Estimates buy/sell tax by simulating a router round trip, or from the receipts of
+/// recent trades on a pool when the round trip can't be simulated
+pub struct TaxEstimator<M> {
+    provider: Arc<M>,
+}
+
+impl<M: Middleware + 'static> TaxEstimator<M> {
+    pub fn new(provider: Arc<M>) -> Self {
+        Self { provider }
+    }
+
+    /// Compare what getAmountsOut promises with what a simulated buy and sell actually
+    /// deliver; fall back to the median tax of recent receipts for V3 pools, nodes
+    /// without eth_call state overrides and tokens whose buy reverts
+    pub async fn estimate(&self, pair_info: &PairInfo) -> Result<TaxEstimate> {
+        // The V2 router can't trade through V3 pools
+        if !pair_info.is_v3 {
+            match RouterSimulator::new(self.provider.clone())
+                .round_trip(pair_info.token, pair_info.base_token, U256::from(ROUND_TRIP_BNB))
+                .await
+            {
+                Ok(trip) if trip.buy_succeeded => {
+                    return Ok(TaxEstimate {
+                        token_address: pair_info.token,
+                        pair_address: pair_info.pair_address,
+                        buy_tax_pct: trip.buy_tax_pct(),
+                        sell_tax_pct: trip.sell_tax_pct(),
+                        simulated: true,
+                        buys_sampled: 0,
+                        sells_sampled: 0,
+                    });
+                }
+                Ok(_) => log::debug!("⚠️ [TAX] Simulated buy of {:?} reverted - sampling receipts", pair_info.token),
+                Err(e) => log::debug!("⚠️ [TAX] Round trip not simulated for {:?}: {} - sampling receipts", pair_info.token, e),
+            }
+        }
+
+        self.estimate_from_receipts(pair_info).await
+    }
+
+    /// Median tax of recent buys and sells on the pool, read from their receipts
+    async fn estimate_from_receipts(&self, pair_info: &PairInfo) -> Result<TaxEstimate> {
+        let latest = self
+            .provider
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64();
+        let topic = H256::from_str(if pair_info.is_v3 { SWAP_V3_TOPIC } else { SWAP_V2_TOPIC })?;
+        let filter = Filter::new()
+            .address(pair_info.pair_address)
+            .topic0(topic)
+            .from_block(latest.saturating_sub(HISTORY_BLOCKS))
+            .to_block(latest);
+        let logs = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch Swap logs: {}", e))?;
+
+        let token_is_token0 = pair_info.token < pair_info.base_token;
+        let word = |data: &[u8], i: usize| data.get(i * 32..(i + 1) * 32).map(U256::from_big_endian);
+
+        let mut buy_taxes = Vec::new();
+        let mut sell_taxes = Vec::new();
+
+        // Newest trades first
+        for log in logs.iter().rev() {
+            if buy_taxes.len() >= MAX_SAMPLES && sell_taxes.len() >= MAX_SAMPLES {
+                break;
+            }
+
+            let (trade_type, token_amount) = if pair_info.is_v3 {
+                let Some(raw) = word(&log.data, if token_is_token0 { 0 } else { 1 }) else { continue };
+                let amount = I256::from_raw(raw);
+                if amount.is_positive() {
+                    (TradeType::Sell, amount.into_raw())
+                } else {
+                    (TradeType::Buy, amount.wrapping_neg().into_raw())
+                }
+            } else {
+                let (in_index, out_index) = if token_is_token0 { (0, 2) } else { (1, 3) };
+                let (Some(amount_in), Some(amount_out)) = (word(&log.data, in_index), word(&log.data, out_index)) else {
+                    continue;
+                };
+                if amount_in.is_zero() {
+                    (TradeType::Buy, amount_out)
+                } else {
+                    (TradeType::Sell, amount_in)
+                }
+            };
+
+            let samples = match trade_type {
+                TradeType::Buy => &mut buy_taxes,
+                TradeType::Sell => &mut sell_taxes,
+            };
+            if samples.len() >= MAX_SAMPLES {
+                continue;
+            }
+
+            let Some(tx_hash) = log.transaction_hash else { continue };
+            let Ok(Some(receipt)) = self.provider.get_transaction_receipt(tx_hash).await else { continue };
+            if let Some(tax) = tax_from_receipt(&receipt, pair_info.token, pair_info.pair_address, trade_type, token_amount) {
+                samples.push(tax);
+            }
+        }
+
+        Ok(TaxEstimate {
+            token_address: pair_info.token,
+            pair_address: pair_info.pair_address,
+            buys_sampled: buy_taxes.len(),
+            sells_sampled: sell_taxes.len(),
+            buy_tax_pct: median(buy_taxes),
+            sell_tax_pct: median(sell_taxes),
+            simulated: false,
+        })
+    }
+}
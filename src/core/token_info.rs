@@ -1,30 +1,126 @@
 use anyhow::Result;
 use ethers::{
-    abi::Abi,
-    contract::Contract,
+    abi::{self, ParamType, Token},
     providers::Middleware,
-    types::Address,
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, Filter, TransactionRequest, U256},
 };
+use futures::stream::{self, StreamExt};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 
-const ERC20_ABI: &str = r#"[
-    {"constant":true,"inputs":[],"name":"name","outputs":[{"name":"","type":"string"}],"type":"function"},
-    {"constant":true,"inputs":[],"name":"symbol","outputs":[{"name":"","type":"string"}],"type":"function"},
-    {"constant":true,"inputs":[],"name":"decimals","outputs":[{"name":"","type":"uint8"}],"type":"function"}
-]"#;
+use crate::config::get_transfer_topic;
+use crate::core::profile::TokenProfileClient;
+use crate::core::proxy::resolve_proxy;
+use crate::types::{ProxyInfo, TokenProfile};
 
-#[derive(Debug, Clone)]
+// ERC20 selectors, called raw so non-standard return types can be decoded
+const NAME_SELECTOR: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+const SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+const TOTAL_SUPPLY_SELECTOR: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+
+// Recent transfers sampled to guess decimals when decimals() reverts
+const DECIMALS_SAMPLE_BLOCKS: u64 = 2_000;
+
+/// ABI string, or a null-padded bytes32 (MKR-style tokens)
+fn decode_text(data: &[u8]) -> Option<String> {
+    let text = match abi::decode(&[ParamType::String], data) {
+        Ok(tokens) => match tokens.into_iter().next() {
+            Some(Token::String(text)) => text,
+            _ => return None,
+        },
+        Err(_) if data.len() == 32 => {
+            let end = data.iter().position(|b| *b == 0).unwrap_or(32);
+            String::from_utf8(data[..end].to_vec()).ok()?
+        }
+        Err(_) => return None,
+    };
+    let text = text.trim_matches(char::from(0)).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Guess decimals from the median raw transfer amount
+///
+/// Amounts are usually between 1 and 10^7 whole tokens, so the median's digit
+/// count lands near the real decimals; the result snaps to a common value.
+fn guess_decimals(amounts: &mut [U256]) -> Option<u8> {
+    if amounts.is_empty() {
+        return None;
+    }
+    amounts.sort();
+    let digits = amounts[amounts.len() / 2].to_string().len() as i32;
+    [18u8, 9, 8, 6, 0].into_iter().find(|d| digits > *d as i32 - 3)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TokenMetadata {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
+    /// Raw total supply at first lookup (None when the call fails)
+    pub total_supply: Option<U256>,
+    /// Logo and links, only looked up with `TokenInfoCache::with_profiles`
+    pub profile: Option<TokenProfile>,
+    /// False when name or symbol could not be read, or decimals were guessed from transfer amounts
+    #[serde(default = "reliable_by_default")]
+    pub metadata_reliable: bool,
+    /// Implementation behind the token, when the token is a proxy (as of the lookup)
+    #[serde(default)]
+    pub proxy: Option<ProxyInfo>,
+}
+
+fn reliable_by_default() -> bool {
+    true
+}
+
+// Concurrent lookups made by `preload`
+const PRELOAD_CONCURRENCY: usize = 16;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct CachedToken {
+    metadata: TokenMetadata,
+    /// Unix timestamp of the lookup
+    fetched_at: u64,
+}
+
+/// File the cache is persisted to, and how long entries stay valid
+#[derive(Debug, Clone)]
+struct Persistence {
+    path: PathBuf,
+    ttl: Duration,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
 }
 
+fn read_cache_file(path: &Path) -> Result<HashMap<Address, CachedToken>> {
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
+
+/// Token name/symbol/decimals lookups, cached per token
+///
+/// Clones share the same cache. With `with_persistence`, entries survive
+/// restarts and are refetched once older than the TTL.
 pub struct TokenInfoCache<M> {
     provider: Arc<M>,
-    cache: Arc<RwLock<HashMap<Address, TokenMetadata>>>,
+    cache: Arc<RwLock<HashMap<Address, CachedToken>>>,
+    profiles: Option<TokenProfileClient>,
+    persistence: Option<Persistence>,
+}
+
+impl<M> Clone for TokenInfoCache<M> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            cache: self.cache.clone(),
+            profiles: self.profiles.clone(),
+            persistence: self.persistence.clone(),
+        }
+    }
 }
 
 impl<M: Middleware + 'static> TokenInfoCache<M> {
@@ -32,53 +128,178 @@ impl<M: Middleware + 'static> TokenInfoCache<M> {
         Self {
             provider,
             cache: Arc::new(RwLock::new(HashMap::new())),
+            profiles: None,
+            persistence: None,
+        }
+    }
+
+    /// Load entries younger than `ttl` from `path` and write new lookups back to it
+    ///
+    /// A missing or unreadable file starts an empty cache. Several caches may
+    /// share a file; writes merge with what is already on disk.
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        let path = path.into();
+        match read_cache_file(&path) {
+            Ok(entries) => {
+                let now = unix_now();
+                let fresh: HashMap<_, _> = entries
+                    .into_iter()
+                    .filter(|(_, entry)| now.saturating_sub(entry.fetched_at) < ttl.as_secs())
+                    .collect();
+                log::debug!("💾 [TOKEN_CACHE] Loaded {} token(s) from {}", fresh.len(), path.display());
+                self.cache = Arc::new(RwLock::new(fresh));
+            }
+            Err(e) if path.exists() => log::warn!("⚠️ [TOKEN_CACHE] Ignoring unreadable cache file {}: {}", path.display(), e),
+            Err(_) => {}
         }
+        self.persistence = Some(Persistence { path, ttl });
+        self
+    }
+
+    /// Also resolve logo and social links (TrustWallet assets, then the Four.meme API)
+    pub fn with_profiles(mut self) -> Self {
+        self.profiles = Some(TokenProfileClient::new());
+        self
+    }
+
+    /// Look up tokens ahead of time (e.g. a known watch list); returns how many are cached
+    pub async fn preload(&self, tokens: &[Address]) -> usize {
+        let loaded = stream::iter(tokens.iter().copied())
+            .map(|token| self.get_token_info(token))
+            .buffer_unordered(PRELOAD_CONCURRENCY)
+            .filter(|result| futures::future::ready(result.is_ok()))
+            .count()
+            .await;
+        log::debug!("💾 [TOKEN_CACHE] Preloaded {}/{} token(s)", loaded, tokens.len());
+        loaded
     }
 
     pub async fn get_token_info(&self, address: Address) -> Result<TokenMetadata> {
         // Check cache first
         {
             let cache = self.cache.read().await;
-            if let Some(info) = cache.get(&address) {
-                return Ok(info.clone());
+            if let Some(entry) = cache.get(&address) {
+                let expired = self
+                    .persistence
+                    .as_ref()
+                    .is_some_and(|p| unix_now().saturating_sub(entry.fetched_at) >= p.ttl.as_secs());
+                if !expired {
+                    return Ok(entry.metadata.clone());
+                }
             }
         }
 
         // Fetch from contract
-        let abi: Abi = serde_json::from_str(ERC20_ABI)?;
-        let contract = Contract::new(address, abi, self.provider.clone());
-
-        let name: String = contract
-            .method::<_, String>("name", ())?
-            .call()
+        let name = self.call(address, NAME_SELECTOR).await.and_then(|data| decode_text(&data));
+        let symbol = self.call(address, SYMBOL_SELECTOR).await.and_then(|data| decode_text(&data));
+        // Some tokens return uint256 (or garbage) - only values that fit a u8 are trusted
+        let decimals = self
+            .call(address, DECIMALS_SELECTOR)
             .await
-            .unwrap_or_else(|_| "Unknown".to_string());
-
-        let symbol: String = contract
-            .method::<_, String>("symbol", ())?
-            .call()
+            .filter(|data| data.len() >= 32)
+            .map(|data| U256::from_big_endian(&data[..32]))
+            .filter(|value| *value <= U256::from(u8::MAX))
+            .map(|value| value.as_u32() as u8);
+        let total_supply = self
+            .call(address, TOTAL_SUPPLY_SELECTOR)
             .await
-            .unwrap_or_else(|_| "UNKNOWN".to_string());
+            .filter(|data| data.len() >= 32)
+            .map(|data| U256::from_big_endian(&data[..32]));
 
-        let decimals: u8 = contract
-            .method::<_, u8>("decimals", ())?
-            .call()
-            .await
-            .unwrap_or(18);
+        let mut metadata_reliable = name.is_some() && symbol.is_some() && decimals.is_some();
+        let decimals = match decimals {
+            Some(decimals) => decimals,
+            None => {
+                let guessed = self.guess_decimals(address).await;
+                log::warn!("⚠️ [TOKEN_CACHE] decimals() unavailable on {:?}, using {:?} from transfer amounts", address, guessed);
+                metadata_reliable = false;
+                guessed.unwrap_or(18)
+            }
+        };
+        if !metadata_reliable {
+            log::debug!("⚠️ [TOKEN_CACHE] Non-standard metadata on {:?}: name={:?} symbol={:?}", address, name, symbol);
+        }
+
+        let proxy = match resolve_proxy(self.provider.as_ref(), address).await {
+            Ok(proxy) => proxy,
+            Err(e) => {
+                log::debug!("⚠️ [TOKEN_CACHE] Proxy check failed for {:?}: {}", address, e);
+                None
+            }
+        };
+        if let Some(ref proxy) = proxy {
+            log::debug!("🧬 [TOKEN_CACHE] {:?} is a {} proxy of {:?}", address, proxy.kind.as_str(), proxy.implementation);
+        }
+
+        let profile = match self.profiles {
+            Some(ref profiles) => profiles.profile(address).await,
+            None => None,
+        };
 
         let metadata = TokenMetadata {
-            name,
-            symbol,
+            name: name.unwrap_or_else(|| "Unknown".to_string()),
+            symbol: symbol.unwrap_or_else(|| "UNKNOWN".to_string()),
             decimals,
+            total_supply,
+            profile,
+            metadata_reliable,
+            proxy,
         };
 
         // Store in cache
-        {
-            let mut cache = self.cache.write().await;
-            cache.insert(address, metadata.clone());
+        let entry = CachedToken {
+            metadata: metadata.clone(),
+            fetched_at: unix_now(),
+        };
+        self.cache.write().await.insert(address, entry);
+
+        if let Some(ref persistence) = self.persistence {
+            if let Err(e) = self.save(&persistence.path).await {
+                log::warn!("⚠️ [TOKEN_CACHE] Failed to write {}: {}", persistence.path.display(), e);
+            }
         }
 
         Ok(metadata)
     }
+
+    /// Raw eth_call of a no-argument view function (None on revert or empty return)
+    async fn call(&self, token: Address, selector: [u8; 4]) -> Option<Bytes> {
+        let tx: TypedTransaction = TransactionRequest::new().to(token).data(selector.to_vec()).into();
+        self.provider.call(&tx, None).await.ok().filter(|data| !data.is_empty())
+    }
+
+    async fn guess_decimals(&self, token: Address) -> Option<u8> {
+        let latest = self.provider.get_block_number().await.ok()?.as_u64();
+        let filter = Filter::new()
+            .address(token)
+            .topic0(get_transfer_topic())
+            .from_block(latest.saturating_sub(DECIMALS_SAMPLE_BLOCKS))
+            .to_block(latest);
+        let mut amounts: Vec<U256> = self
+            .provider
+            .get_logs(&filter)
+            .await
+            .ok()?
+            .iter()
+            .filter(|log| log.data.len() >= 32)
+            .map(|log| U256::from_big_endian(&log.data[..32]))
+            .filter(|amount| !amount.is_zero())
+            .collect();
+        guess_decimals(&mut amounts)
+    }
+
+    /// Merge the cache into the file (newest lookup wins) and replace it atomically
+    async fn save(&self, path: &Path) -> Result<()> {
+        let mut entries = read_cache_file(path).unwrap_or_default();
+        for (address, entry) in self.cache.read().await.iter() {
+            if entries.get(address).is_none_or(|existing| existing.fetched_at < entry.fetched_at) {
+                entries.insert(*address, entry.clone());
+            }
+        }
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(&entries)?).await?;
+        tokio::fs::rename(&tmp, path).await?;
+        Ok(())
+    }
 }
 
@@ -0,0 +1,144 @@
+use ethers::types::Address;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::options::TrendingCallback;
+use crate::types::{SwapEvent, TradeType, TrendingConfig, TrendingMetrics};
+
+// Share of the score taken by volume, trade count and unique buyers
+const VOLUME_WEIGHT: f64 = 0.5;
+const TRADES_WEIGHT: f64 = 0.25;
+const BUYERS_WEIGHT: f64 = 0.25;
+
+struct Trade {
+    at: Instant,
+    usd: f64,
+    is_buy: bool,
+    buyer: Option<Address>,
+}
+
+struct TokenWindow {
+    symbol: String,
+    trades: VecDeque<Trade>,
+}
+
+/// Ranks tokens by short-window volume, trade count and unique buyers
+///
+/// Feed it every swap (e.g. from the swap firehose) and call `refresh`
+/// periodically to learn which tokens entered the top N.
+pub struct TrendingTracker {
+    config: TrendingConfig,
+    tokens: HashMap<Address, TokenWindow>,
+    top: HashSet<Address>,
+}
+
+impl TrendingTracker {
+    pub fn new(config: TrendingConfig) -> Self {
+        Self {
+            config,
+            tokens: HashMap::new(),
+            top: HashSet::new(),
+        }
+    }
+
+    /// Record a swap as of now
+    pub fn record(&mut self, swap: &SwapEvent) {
+        let is_buy = swap.trade_type == TradeType::Buy;
+        let window = self.tokens.entry(swap.token.address).or_insert_with(|| TokenWindow {
+            symbol: swap.token.symbol.clone(),
+            trades: VecDeque::new(),
+        });
+        window.trades.push_back(Trade {
+            at: Instant::now(),
+            usd: swap.usd_value.unwrap_or(0.0),
+            is_buy,
+            buyer: is_buy.then_some(swap.trader.unwrap_or(swap.recipient)),
+        });
+    }
+
+    /// Current top N, hottest first (drops trades that left the window)
+    pub fn ranking(&mut self) -> Vec<(Address, TrendingMetrics)> {
+        let window_secs = self.config.window.as_secs();
+        self.tokens.retain(|_, window| {
+            while window.trades.front().is_some_and(|t| t.at.elapsed() > self.config.window) {
+                window.trades.pop_front();
+            }
+            !window.trades.is_empty()
+        });
+
+        let mut ranked: Vec<(Address, TrendingMetrics)> = self
+            .tokens
+            .iter()
+            .filter(|(_, window)| window.trades.len() >= self.config.min_trades)
+            .map(|(address, window)| {
+                let buys = window.trades.iter().filter(|t| t.is_buy).count();
+                let metrics = TrendingMetrics {
+                    symbol: window.symbol.clone(),
+                    rank: 0,
+                    score: 0.0,
+                    volume_usd: window.trades.iter().map(|t| t.usd).sum(),
+                    trades: window.trades.len(),
+                    buys,
+                    sells: window.trades.len() - buys,
+                    unique_buyers: window.trades.iter().filter_map(|t| t.buyer).collect::<HashSet<_>>().len(),
+                    window_secs,
+                };
+                (*address, metrics)
+            })
+            .collect();
+
+        // Each metric is scaled against the leader in that metric
+        let max = |f: fn(&TrendingMetrics) -> f64| ranked.iter().map(|(_, m)| f(m)).fold(0.0, f64::max).max(f64::EPSILON);
+        let (max_volume, max_trades, max_buyers) =
+            (max(|m| m.volume_usd), max(|m| m.trades as f64), max(|m| m.unique_buyers as f64));
+        for (_, metrics) in &mut ranked {
+            metrics.score = VOLUME_WEIGHT * metrics.volume_usd / max_volume
+                + TRADES_WEIGHT * metrics.trades as f64 / max_trades
+                + BUYERS_WEIGHT * metrics.unique_buyers as f64 / max_buyers;
+        }
+
+        ranked.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+        ranked.truncate(self.config.top_n);
+        for (index, (_, metrics)) in ranked.iter_mut().enumerate() {
+            metrics.rank = index + 1;
+        }
+        ranked
+    }
+
+    /// Recompute the ranking and return the tokens that entered the top N since the last refresh
+    pub fn refresh(&mut self) -> Vec<(Address, TrendingMetrics)> {
+        let ranking = self.ranking();
+        let entered = ranking.iter().filter(|(address, _)| !self.top.contains(address)).cloned().collect();
+        self.top = ranking.into_iter().map(|(address, _)| address).collect();
+        entered
+    }
+}
+
+/// Refresh the ranking every `refresh` and report tokens entering the top N
+pub(crate) fn spawn_trending_reporter(
+    tracker: Arc<Mutex<TrendingTracker>>,
+    refresh: Duration,
+    callback: TrendingCallback,
+    cancel_token: CancellationToken,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(refresh);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [TRENDING] Trending reporter cancelled");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            let entered = tracker.lock().unwrap().refresh();
+            for (token, metrics) in entered {
+                log::info!("🔥 [TRENDING] {} ({:?}) entered the top list at #{}", metrics.symbol, token, metrics.rank);
+                callback(token, metrics);
+            }
+        }
+    });
+}
@@ -0,0 +1,164 @@
+use ethers::{providers::Middleware, types::Address};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::liquidity::pool_liquidity_usd;
+use crate::core::options::PoolMigrationCallback;
+use crate::core::price_oracle::PriceOracle;
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{PairInfo, PoolMigrationConfig, PoolMigrationEvent};
+
+struct Venue {
+    info: PairInfo,
+    liquidity_usd: f64,
+    peak_liquidity_usd: f64,
+}
+
+#[derive(Default)]
+struct TokenVenues {
+    pools: HashMap<Address, Venue>,
+    primary: Option<Address>,
+}
+
+/// Monitored pools of each token and which one is treated as primary (the deepest)
+#[derive(Clone, Default)]
+pub struct VenueTracker {
+    tokens: Arc<Mutex<HashMap<Address, TokenVenues>>>,
+}
+
+impl VenueTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a monitored pool
+    pub fn add(&self, pair_info: PairInfo) {
+        let mut tokens = self.tokens.lock().unwrap();
+        let venues = tokens.entry(pair_info.token).or_default();
+        venues.primary.get_or_insert(pair_info.pair_address);
+        venues.pools.entry(pair_info.pair_address).or_insert(Venue {
+            info: pair_info,
+            liquidity_usd: 0.0,
+            peak_liquidity_usd: 0.0,
+        });
+    }
+
+    /// Pool currently holding most of the token's liquidity
+    pub fn primary(&self, token: Address) -> Option<PairInfo> {
+        let tokens = self.tokens.lock().unwrap();
+        let venues = tokens.get(&token)?;
+        venues.pools.get(&venues.primary?).map(|venue| venue.info.clone())
+    }
+
+    /// Pools tracked for a token
+    pub fn pools(&self, token: Address) -> Vec<PairInfo> {
+        let tokens = self.tokens.lock().unwrap();
+        tokens
+            .get(&token)
+            .map(|venues| venues.pools.values().map(|venue| venue.info.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Record USD liquidity readings and move the primary to the deepest pool
+    ///
+    /// Returns a migration event when the old primary lost at least
+    /// `min_drop_pct` of its peak liquidity; a pool that merely outgrew the
+    /// primary takes over silently.
+    pub fn update(&self, token: Address, readings: &[(Address, f64)], min_drop_pct: f64) -> Option<PoolMigrationEvent> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let venues = tokens.get_mut(&token)?;
+        let first_reading = venues.pools.values().all(|venue| venue.peak_liquidity_usd == 0.0);
+        for (pool, liquidity_usd) in readings {
+            if let Some(venue) = venues.pools.get_mut(pool) {
+                venue.liquidity_usd = *liquidity_usd;
+                venue.peak_liquidity_usd = venue.peak_liquidity_usd.max(*liquidity_usd);
+            }
+        }
+
+        let deepest = venues
+            .pools
+            .values()
+            .max_by(|a, b| a.liquidity_usd.total_cmp(&b.liquidity_usd))
+            .filter(|venue| venue.liquidity_usd > 0.0)?;
+        let previous = venues.primary.replace(deepest.info.pair_address);
+        let from = venues.pools.get(&previous?).filter(|from| from.info.pair_address != deepest.info.pair_address)?;
+        if first_reading {
+            return None;
+        }
+
+        let drop_pct = if from.peak_liquidity_usd > 0.0 {
+            (1.0 - from.liquidity_usd / from.peak_liquidity_usd) * 100.0
+        } else {
+            0.0
+        };
+        log::info!(
+            "🔀 [VENUES] Primary pool of {:?} is now {} {:?} (was {} {:?}, down {:.0}% from peak)",
+            token,
+            deepest.info.version().as_str(),
+            deepest.info.pair_address,
+            from.info.version().as_str(),
+            from.info.pair_address,
+            drop_pct
+        );
+        (drop_pct >= min_drop_pct).then(|| PoolMigrationEvent {
+            token_address: token,
+            from_pair: from.info.pair_address,
+            from_pool: from.info.pool_metadata(),
+            to_pair: deepest.info.pair_address,
+            to_pool: deepest.info.pool_metadata(),
+            to_base_token_symbol: deepest.info.base_token_symbol.clone(),
+            from_peak_liquidity_usd: from.peak_liquidity_usd,
+            from_liquidity_usd: from.liquidity_usd,
+            to_liquidity_usd: deepest.liquidity_usd,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+}
+
+/// Periodically read the liquidity of the token's pools, keeping the primary pool current
+pub(crate) fn spawn_venue_watcher<M: Middleware + 'static>(
+    provider: Arc<M>,
+    tracker: VenueTracker,
+    token_address: Address,
+    config: PoolMigrationConfig,
+    callback: Option<PoolMigrationCallback>,
+    cancel_token: CancellationToken,
+) {
+    tokio::spawn(async move {
+        let token_cache = TokenInfoCache::new(provider.clone());
+        let price_oracle = PriceOracle::new(provider.clone());
+        let mut interval = tokio::time::interval(config.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    log::debug!("🛑 [VENUES] Pool liquidity watcher for {:?} cancelled", token_address);
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+
+            // Base-token balances cover V2 reserves and every V3 position alike
+            let mut readings = Vec::new();
+            for pool in tracker.pools(token_address) {
+                if let Some(usd) = pool_liquidity_usd(&provider, &price_oracle, &token_cache, pool.pair_address, pool.base_token).await {
+                    readings.push((pool.pair_address, usd));
+                }
+            }
+            if let Some(event) = tracker.update(token_address, &readings, config.min_drop_pct) {
+                log::warn!(
+                    "🔀 [VENUES] Liquidity of {:?} moved from {:?} to {:?} (${:.0} -> ${:.0})",
+                    token_address,
+                    event.from_pair,
+                    event.to_pair,
+                    event.from_peak_liquidity_usd,
+                    event.to_liquidity_usd
+                );
+                if let Some(ref callback) = callback {
+                    callback(event);
+                }
+            }
+        }
+    });
+}
@@ -1,71 +1,312 @@
 use colored::*;
 
 use crate::core::price_tracker::PriceTracker;
-use crate::types::{SwapEvent, TradeType};
+use crate::core::stats::TradeStats;
+use crate::display::sparkline::{sparkline, sparkline_ascii};
+use crate::types::{PriceStats, QuoteCurrency, SwapEvent, TradeType, VolumeStats};
+
+// Trades shown in the price sparkline
+const SPARKLINE_WIDTH: usize = 30;
+
+/// How `SwapFormatter` prints swaps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// Colored multi-line block per swap
+    #[default]
+    Pretty,
+    /// One JSON object per line: `{"type":"swap","swap":{..},"price_stats":{..},"volume":{..}}`
+    Json,
+}
+
+impl OutputMode {
+    /// `OUTPUT_FORMAT=json` selects `Json`; anything else (or unset) is `Pretty`
+    pub fn from_env() -> Self {
+        match std::env::var("OUTPUT_FORMAT") {
+            Ok(format) if format.eq_ignore_ascii_case("json") => OutputMode::Json,
+            _ => OutputMode::Pretty,
+        }
+    }
+}
+
+/// Color and emoji settings of the pretty output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    pub color: bool,
+    pub emoji: bool,
+}
+
+impl DisplayOptions {
+    /// Colors unless `NO_COLOR` is set, emoji unless `NO_EMOJI` is set (empty values are ignored)
+    pub fn from_env() -> Self {
+        let is_set = |name: &str| std::env::var(name).is_ok_and(|value| !value.is_empty());
+        Self {
+            color: !is_set("NO_COLOR"),
+            emoji: !is_set("NO_EMOJI"),
+        }
+    }
+
+    /// Plain ASCII text without ANSI escapes, for log aggregators, CI and legacy consoles
+    pub fn plain() -> Self {
+        Self {
+            color: false,
+            emoji: false,
+        }
+    }
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            color: true,
+            emoji: true,
+        }
+    }
+}
+
+/// Statistics `SwapFormatter` tracked for a swap, handed to `SwapDisplay::render`
+#[derive(Debug, Clone)]
+pub struct SwapContext {
+    /// Price stats in `currency`, including this swap
+    pub price_stats: PriceStats,
+    /// Quote currency the stats are tracked in (normalized currency when available, else the base token)
+    pub currency: String,
+    /// Rolling volume of the swapped token
+    pub volume: Option<VolumeStats>,
+    /// Prices of the latest trades in `currency`, oldest first (for sparklines)
+    pub recent_prices: Vec<f64>,
+}
+
+/// Layout of a swap on the console
+///
+/// `SwapFormatter` implements the default layouts; plug your own in with
+/// `SwapFormatter::with_layout` to keep its price and volume tracking.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::display::formatter::{SwapContext, SwapDisplay, SwapFormatter};
+/// use bsc_streamer::SwapEvent;
+///
+/// struct OneLine;
+///
+/// impl SwapDisplay for OneLine {
+///     fn render(&self, swap: &SwapEvent, context: &SwapContext) {
+///         println!(
+///             "{} {} {} @ {} ({:+.2}%)",
+///             swap.trade_type.as_str(),
+///             swap.token.amount,
+///             swap.token.symbol,
+///             swap.price.display,
+///             context.price_stats.price_change_percent.unwrap_or_default()
+///         );
+///     }
+/// }
+///
+/// let formatter = SwapFormatter::new().with_layout(OneLine);
+/// ```
+pub trait SwapDisplay: Send + Sync {
+    fn render(&self, swap: &SwapEvent, context: &SwapContext);
+}
 
 pub struct SwapFormatter {
     price_tracker: PriceTracker,
+    trade_stats: TradeStats,
+    mode: OutputMode,
+    display: DisplayOptions,
+    layout: Option<Box<dyn SwapDisplay>>,
 }
 
 impl SwapFormatter {
     pub fn new() -> Self {
+        Self::with_mode(OutputMode::Pretty)
+    }
+
+    pub fn with_mode(mode: OutputMode) -> Self {
         Self {
             price_tracker: PriceTracker::new(),
+            trade_stats: TradeStats::new(),
+            mode,
+            display: DisplayOptions::from_env(),
+            layout: None,
+        }
+    }
+
+    /// Render swaps with `layout` instead of the built-in pretty/JSON output
+    pub fn with_layout<D: SwapDisplay + 'static>(mut self, layout: D) -> Self {
+        self.layout = Some(Box::new(layout));
+        self
+    }
+
+    /// Override the `NO_COLOR`/`NO_EMOJI` settings read at construction
+    pub fn with_display(mut self, display: DisplayOptions) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Formatter printing one JSON object per swap
+    pub fn json() -> Self {
+        Self::with_mode(OutputMode::Json)
+    }
+
+    /// Formatter in the mode selected by `OUTPUT_FORMAT`
+    pub fn from_env() -> Self {
+        Self::with_mode(OutputMode::from_env())
+    }
+
+    pub fn mode(&self) -> OutputMode {
+        self.mode
+    }
+
+    fn paint(&self, text: ColoredString) -> ColoredString {
+        if self.display.color {
+            text
+        } else {
+            text.clear()
+        }
+    }
+
+    /// Track and print a swap
+    ///
+    /// Tracking is synchronous and never blocks the runtime, so this works on
+    /// current-thread and multi-thread runtimes alike.
+    pub async fn display(&self, swap: &SwapEvent) {
+        self.display_sync(swap);
+    }
+
+    /// `display` for synchronous callbacks (`on_swap`), usable with or without a runtime
+    pub fn display_sync(&self, swap: &SwapEvent) {
+        let context = self.track(swap);
+        match self.layout {
+            Some(ref layout) => layout.render(swap, &context),
+            None => self.render(swap, &context),
         }
     }
 
-    pub fn display(&self, swap: &SwapEvent) {
+    /// Record a swap in the price/volume trackers and return the resulting stats without printing
+    pub fn track(&self, swap: &SwapEvent) -> SwapContext {
+        // Track normalized prices when available so stats aren't split per base token
+        let (tracked_price, tracked_currency) = match swap.normalized_price {
+            Some(ref normalized) => (normalized.value, normalized.currency.as_str().to_string()),
+            None => (swap.price.value, swap.price.base_token.clone()),
+        };
+
         // Update price tracking
-        let price_stats = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(async {
-                self.price_tracker
-                    .update_price(
-                        &format!("{:?}", swap.token.address),
-                        &swap.price.base_token,
-                        swap.price.value,
-                    )
-                    .await
-            })
+        let token = format!("{:?}", swap.token.address);
+        let price_stats = self.price_tracker.record_trade(
+            &token,
+            &tracked_currency,
+            tracked_price,
+            swap.token.amount.parse().unwrap_or_default(),
+            crate::core::candles::swap_time(swap) as f64,
+        );
+
+        self.trade_stats.record(swap);
+
+        let history = self.price_tracker.price_history(&token, &tracked_currency, 0.0);
+        SwapContext {
+            price_stats,
+            volume: self.trade_stats.volume(swap.token.address),
+            recent_prices: history[history.len().saturating_sub(SPARKLINE_WIDTH)..]
+                .iter()
+                .map(|(_, price, _)| *price)
+                .collect(),
+            currency: tracked_currency,
+        }
+    }
+
+    fn print_json(&self, swap: &SwapEvent, context: &SwapContext) {
+        let line = serde_json::json!({
+            "type": "swap",
+            "swap": swap,
+            "price_stats": context.price_stats,
+            "volume": context.volume,
         });
+        println!("{}", line);
+    }
+}
+
+impl SwapDisplay for SwapFormatter {
+    fn render(&self, swap: &SwapEvent, context: &SwapContext) {
+        if self.mode == OutputMode::Json {
+            self.print_json(swap, context);
+            return;
+        }
+
+        let price_stats = &context.price_stats;
+        let tracked_currency = &context.currency;
 
         // Get emoji and trend
-        let emoji = match swap.trade_type {
-            TradeType::Buy => "🟢",
-            TradeType::Sell => "🔴",
+        let emoji = match (swap.trade_type, self.display.emoji) {
+            (TradeType::Buy, true) => "🟢",
+            (TradeType::Sell, true) => "🔴",
+            (TradeType::Buy, false) => "+",
+            (TradeType::Sell, false) => "-",
+        };
+        let trend = if self.display.emoji {
+            PriceTracker::get_trend_emoji(price_stats.price_change_percent)
+        } else {
+            PriceTracker::get_trend_text(price_stats.price_change_percent)
         };
-        let trend = PriceTracker::get_trend_emoji(price_stats.price_change_percent);
 
         // Display trade info
         println!(
             "{} {} {} {} [{}]",
             emoji,
             swap.trade_type.as_str(),
-            swap.token.symbol.bright_white().bold(),
+            self.paint(swap.token.symbol.bright_white().bold()),
             trend,
-            swap.platform.as_str().cyan()
+            self.paint(swap.platform.as_str().cyan())
         );
 
         println!(
             "   Amount: {} {}",
-            format!("{:.6}", swap.token.amount.parse::<f64>().unwrap_or(0.0)).bright_yellow(),
+            self.paint(format!("{:.6}", swap.token.amount.parse::<f64>().unwrap_or(0.0)).bright_yellow()),
             swap.token.symbol
         );
 
         println!(
             "   For: {} {}",
-            format!("{:.6}", swap.base_token.amount.parse::<f64>().unwrap_or(0.0)).bright_yellow(),
+            self.paint(format!("{:.6}", swap.base_token.amount.parse::<f64>().unwrap_or(0.0)).bright_yellow()),
             swap.base_token.symbol
         );
 
-        println!("   Price: {}", swap.price.display.bright_cyan());
+        println!("   Price: {}", self.paint(swap.price.display.bright_cyan()));
+
+        if let Some(ref normalized) = swap.normalized_price {
+            // Prices quoted in WBNB or a stablecoin are already in that currency
+            if swap.price.quote_token.and_then(QuoteCurrency::of_token) != Some(normalized.currency) {
+                let approx = if self.display.emoji { "≈" } else { "~" };
+                println!("   {} {:.12} {}", approx, normalized.value, normalized.currency.as_str());
+            }
+        }
+
+        if let (Some(price_usd), Some(usd_value)) = (swap.price_usd, swap.usd_value) {
+            println!(
+                "   USD: {} per token | Value: {}",
+                self.paint(format!("${:.10}", price_usd).bright_cyan()),
+                self.paint(format!("${:.2}", usd_value).bright_yellow())
+            );
+        }
+
+        if let Some(trader) = swap.trader {
+            let label = swap
+                .trader_label
+                .as_ref()
+                .map(|l| format!(" [{}]", l.name))
+                .unwrap_or_default();
+            match swap.route_via {
+                Some(router) => println!("   Trader: {:?}{} via {}", trader, label, router.as_str()),
+                None => println!("   Trader: {:?}{}", trader, label),
+            }
+        }
 
         // Display price change if available
         if let Some(change_percent) = price_stats.price_change_percent {
             let change_symbol = if change_percent >= 0.0 { "+" } else { "" };
-            let change_color = if change_percent >= 0.0 {
-                "🟢".green()
-            } else {
-                "🔴".red()
+            let change_color = match (change_percent >= 0.0, self.display.emoji) {
+                (true, true) => self.paint("🟢".green()),
+                (false, true) => self.paint("🔴".red()),
+                (true, false) => self.paint("up".green()),
+                (false, false) => self.paint("down".red()),
             };
 
             if let Some(change) = price_stats.price_change {
@@ -76,7 +317,7 @@ impl SwapFormatter {
                     change_percent,
                     change_symbol,
                     change,
-                    swap.price.base_token
+                    tracked_currency
                 );
             }
         }
@@ -97,6 +338,39 @@ impl SwapFormatter {
             );
         }
 
+        if context.recent_prices.len() > 1 {
+            let chart = if self.display.emoji {
+                sparkline(&context.recent_prices, SPARKLINE_WIDTH)
+            } else {
+                sparkline_ascii(&context.recent_prices, SPARKLINE_WIDTH)
+            };
+            println!("   Chart: {} (last {} trades)", self.paint(chart.bright_cyan()), context.recent_prices.len());
+        }
+
+        if let Some(ref volume) = context.volume {
+            let windows: Vec<String> = volume
+                .windows
+                .iter()
+                .map(|w| match w.volume_usd() {
+                    Some(usd) => format!("{} ${:.0} ({}B/{}S)", w.label(), usd, w.buys, w.sells),
+                    None => format!("{} {:.2} {} ({}B/{}S)", w.label(), w.buy_volume_tokens + w.sell_volume_tokens, swap.token.symbol, w.buys, w.sells),
+                })
+                .collect();
+            println!("   Volume: {}", windows.join(" | "));
+        }
+
+        for average in &price_stats.averages {
+            if let (Some(vwap), Some(twap)) = (average.vwap, average.twap) {
+                println!(
+                    "   VWAP {}m: {:.12} | TWAP {}m: {:.12}",
+                    average.window.as_secs() / 60,
+                    vwap,
+                    average.window.as_secs() / 60,
+                    twap
+                );
+            }
+        }
+
         // Display pair or bonding curve address
         if let Some(bc_addr) = swap.bonding_curve_address {
             println!("   Bonding Curve: {:?}", bc_addr);
@@ -104,6 +378,33 @@ impl SwapFormatter {
             println!("   Pair: {:?}", pair_addr);
         }
 
+        if let Some(ref pool) = swap.pool {
+            println!("   Pool: {} ({}% fee)", pool.version.as_str(), pool.fee_percent());
+        }
+
+        if let Some(tax) = swap.effective_tax_pct.filter(|tax| *tax > 0.0) {
+            println!("   Tax: {:.2}%", tax);
+        }
+
+        if let Some(ref cap) = swap.market_cap {
+            println!("   MCap: ${:.0} | FDV: ${:.0}", cap.market_cap_usd, cap.fdv_usd);
+        }
+
+        if let Some(impact) = swap.price_impact_pct() {
+            println!("   Price impact: {:.2}%", impact);
+        }
+
+        if let Some(ref depth) = swap.depth {
+            let change = depth
+                .price_change_pct()
+                .map(|pct| format!(" ({:+.2}%)", pct))
+                .unwrap_or_default();
+            println!(
+                "   Pool price after: {:.12} {}{} | 2% depth: {:.4} {}",
+                depth.price_after, swap.base_token.symbol, change, depth.depth_2pct, swap.base_token.symbol
+            );
+        }
+
         println!(
             "   Tx: https://bscscan.com/tx/{:?}",
             swap.transaction_hash
@@ -113,7 +414,13 @@ impl SwapFormatter {
             println!("   Time: {}", timestamp);
         }
 
-        println!("{}", "─".repeat(80).bright_black());
+        let rule = if self.display.emoji { "─" } else { "-" };
+        println!("{}", self.paint(rule.repeat(80).bright_black()));
     }
 }
 
+impl Default for SwapFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,2 +1,5 @@
 pub mod formatter;
+pub mod sparkline;
 
+#[cfg(feature = "tui")]
+pub mod tui;
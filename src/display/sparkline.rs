@@ -0,0 +1,35 @@
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+// For consoles without block elements (see `DisplayOptions::emoji`)
+const ASCII_BARS: [char; 8] = ['_', '.', ',', '-', '~', '=', '*', '#'];
+
+/// Sparkline of the last `width` values, e.g. "▁▂▄▃▆█"
+///
+/// A flat series renders as a row of mid-height bars; non-finite values are skipped.
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    render(values, width, &BARS)
+}
+
+/// `sparkline` drawn with ASCII characters only
+pub fn sparkline_ascii(values: &[f64], width: usize) -> String {
+    render(values, width, &ASCII_BARS)
+}
+
+fn render(values: &[f64], width: usize, bars: &[char; 8]) -> String {
+    let values: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    let values = &values[values.len().saturating_sub(width)..];
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(*v), max.max(*v)));
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|v| {
+            if range <= 0.0 {
+                return bars[bars.len() / 2];
+            }
+            let level = ((v - min) / range * (bars.len() - 1) as f64).round() as usize;
+            bars[level.min(bars.len() - 1)]
+        })
+        .collect()
+}
@@ -0,0 +1,329 @@
+//! Full-screen terminal dashboard (requires the `tui` feature)
+//!
+//! `Dashboard` is an event sink: register a clone on one or more streamers and
+//! call `run()` to draw a live token table and a scrolling trade tape until
+//! the user presses `q`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::types::Address;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, List, ListItem, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::core::candles::swap_time;
+use crate::display::sparkline::sparkline;
+use crate::sinks::EventSink;
+use crate::types::{MigrationEvent, Platform, StreamEvent, SwapEvent, TradeType};
+
+// Window of the change/volume columns
+const WINDOW_SECS: i64 = 300;
+// Trades kept on the tape
+const TAPE_LEN: usize = 200;
+// Trades drawn in the chart column
+const CHART_WIDTH: usize = 20;
+const REDRAW_INTERVAL: Duration = Duration::from_millis(250);
+
+struct Trade {
+    time: i64,
+    price: f64,
+    tokens: f64,
+    usd: Option<f64>,
+}
+
+struct TokenRow {
+    symbol: String,
+    platform: Platform,
+    currency: String,
+    price: f64,
+    price_usd: Option<f64>,
+    // Trades within WINDOW_SECS of the latest one
+    window: VecDeque<Trade>,
+    last_trade: Option<(TradeType, String, Option<String>)>,
+    migrated: bool,
+}
+
+impl TokenRow {
+    fn new(symbol: String, platform: Platform) -> Self {
+        Self {
+            symbol,
+            platform,
+            currency: String::new(),
+            price: 0.0,
+            price_usd: None,
+            window: VecDeque::new(),
+            last_trade: None,
+            migrated: false,
+        }
+    }
+
+    fn change_pct(&self) -> Option<f64> {
+        let first = self.window.front()?.price;
+        (first > 0.0).then(|| (self.price - first) / first * 100.0)
+    }
+
+    /// USD volume when every trade in the window has a valuation, else volume in tokens
+    fn volume(&self) -> String {
+        let usd: Option<f64> = self.window.iter().map(|t| t.usd).sum();
+        match usd {
+            Some(usd) => format!("${:.0}", usd),
+            None => format!("{:.2} {}", self.window.iter().map(|t| t.tokens).sum::<f64>(), self.symbol),
+        }
+    }
+}
+
+#[derive(Default)]
+struct DashboardState {
+    tokens: BTreeMap<Address, TokenRow>,
+    tape: VecDeque<Line<'static>>,
+    events: u64,
+}
+
+impl DashboardState {
+    fn record_swap(&mut self, swap: &SwapEvent) {
+        self.events += 1;
+        let row = self
+            .tokens
+            .entry(swap.token.address)
+            .or_insert_with(|| TokenRow::new(swap.token.symbol.clone(), swap.platform));
+
+        // Track the normalized price when there is one so pairs with different base tokens line up
+        let (price, currency) = match swap.normalized_price {
+            Some(ref normalized) => (normalized.value, normalized.currency.as_str().to_string()),
+            None => (swap.price.value, swap.price.base_token.clone()),
+        };
+        let time = swap_time(swap);
+        row.window.push_back(Trade {
+            time,
+            price,
+            tokens: swap.token.amount.parse().unwrap_or_default(),
+            usd: swap.usd_value,
+        });
+        while row.window.front().is_some_and(|t| t.time < time - WINDOW_SECS) {
+            row.window.pop_front();
+        }
+        row.platform = swap.platform;
+        row.currency = currency;
+        row.price = price;
+        row.price_usd = swap.price_usd.or(row.price_usd);
+        row.last_trade = Some((swap.trade_type, swap.token.amount.clone(), swap.timestamp.clone()));
+
+        let (color, side) = match swap.trade_type {
+            TradeType::Buy => (Color::Green, "BUY "),
+            TradeType::Sell => (Color::Red, "SELL"),
+        };
+        let usd = swap.usd_value.map(|usd| format!(" (${:.2})", usd)).unwrap_or_default();
+        let time = swap
+            .timestamp
+            .as_deref()
+            .and_then(|t| t.get(11..19))
+            .unwrap_or("--:--:--");
+        let line = Line::styled(
+            format!(
+                "{} {} {:>14} {:<8} for {} {}{} @ {}",
+                time, side, swap.token.amount, swap.token.symbol, swap.base_token.amount, swap.base_token.symbol, usd, swap.price.display
+            ),
+            Style::default().fg(color),
+        );
+        self.push_tape(line);
+    }
+
+    fn record_migration(&mut self, migration: &MigrationEvent) {
+        self.events += 1;
+        let row = self
+            .tokens
+            .entry(migration.token_address)
+            .or_insert_with(|| TokenRow::new(format!("{:?}", migration.token_address), migration.from_platform));
+        row.migrated = true;
+        row.platform = migration.to_platform;
+        let line = Line::styled(
+            format!(
+                "MIGRATION {} moved to {} ({} pair(s)) at block {}",
+                row.symbol,
+                migration.to_platform.as_str(),
+                migration.pair_count,
+                migration.block_number
+            ),
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+        );
+        self.push_tape(line);
+    }
+
+    fn push_tape(&mut self, line: Line<'static>) {
+        if self.tape.len() == TAPE_LEN {
+            self.tape.pop_back();
+        }
+        self.tape.push_front(line);
+    }
+}
+
+/// Live table of tokens (price, 5m change and volume, last trade, migration status) and a trade tape
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::display::tui::Dashboard;
+/// use bsc_streamer::StreamerBuilder;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let dashboard = Dashboard::new();
+/// let handle = StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+///     .await?
+///     .token_address("0x...")
+///     .auto_detect()
+///     .sink(dashboard.clone())
+///     .on_swap(|_| {})
+///     .start()
+///     .await?;
+///
+/// // Draws until `q`, Esc or Ctrl-C
+/// dashboard.run().await?;
+/// handle.stop();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Dashboard {
+    state: Arc<Mutex<DashboardState>>,
+}
+
+impl Dashboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_swap(&self, swap: &SwapEvent) {
+        self.state.lock().unwrap().record_swap(swap);
+    }
+
+    pub fn record_migration(&self, migration: &MigrationEvent) {
+        self.state.lock().unwrap().record_migration(migration);
+    }
+
+    /// Take over the terminal and draw until the user quits
+    ///
+    /// Anything else printing to the terminal (including logs) corrupts the
+    /// screen - send logs to a file or disable them while the dashboard runs.
+    pub async fn run(&self) -> Result<()> {
+        let dashboard = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut terminal = ratatui::init();
+            let result = dashboard.draw_loop(&mut terminal);
+            ratatui::restore();
+            result
+        })
+        .await?
+    }
+
+    fn draw_loop(&self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(REDRAW_INTERVAL)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.kind == KeyEventKind::Press && (matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) || ctrl_c) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let state = self.state.lock().unwrap();
+        let [header, table, tape] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(state.tokens.len() as u16 + 3),
+            Constraint::Min(3),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Line::styled(
+                format!(
+                    " BSC Streamer | {} token(s) | {} event(s) | q to quit",
+                    state.tokens.len(),
+                    state.events
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            header,
+        );
+
+        let rows = state.tokens.values().map(|row| {
+            let (change, change_style) = match row.change_pct() {
+                Some(pct) if pct >= 0.0 => (format!("{:+.2}%", pct), Style::default().fg(Color::Green)),
+                Some(pct) => (format!("{:+.2}%", pct), Style::default().fg(Color::Red)),
+                None => ("-".to_string(), Style::default()),
+            };
+            let last_trade = match row.last_trade {
+                Some((trade_type, ref amount, ref time)) => format!(
+                    "{} {} {}",
+                    time.as_deref().and_then(|t| t.get(11..19)).unwrap_or(""),
+                    trade_type.as_str(),
+                    amount
+                ),
+                None => "-".to_string(),
+            };
+            let status = if row.migrated { "migrated" } else { "" };
+            let price = match row.price_usd {
+                Some(usd) => format!("{:.10} {} (${:.8})", row.price, row.currency, usd),
+                None => format!("{:.10} {}", row.price, row.currency),
+            };
+            Row::new(vec![
+                Cell::from(row.symbol.clone()),
+                row.platform.as_str().into(),
+                price.into(),
+                Cell::from(change).style(change_style),
+                row.volume().into(),
+                sparkline(&row.window.iter().map(|t| t.price).collect::<Vec<_>>(), CHART_WIDTH).into(),
+                row.window.len().to_string().into(),
+                last_trade.into(),
+                status.into(),
+            ])
+        });
+        let widths = [
+            Constraint::Length(10),
+            Constraint::Length(24),
+            Constraint::Min(24),
+            Constraint::Length(9),
+            Constraint::Length(14),
+            Constraint::Length(CHART_WIDTH as u16),
+            Constraint::Length(9),
+            Constraint::Min(20),
+            Constraint::Length(9),
+        ];
+        let table_widget = Table::new(rows, widths)
+            .header(
+                Row::new(["Token", "Platform", "Price", "5m", "5m volume", "5m chart", "5m trades", "Last trade", "Status"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::bordered().title(" Tokens "));
+        frame.render_widget(table_widget, table);
+
+        let items: Vec<ListItem> = state.tape.iter().cloned().map(ListItem::new).collect();
+        frame.render_widget(List::new(items).block(Block::bordered().title(" Trades ")), tape);
+    }
+}
+
+#[async_trait]
+impl EventSink for Dashboard {
+    fn name(&self) -> &str {
+        "dashboard"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        match event {
+            StreamEvent::Swap(swap) => self.record_swap(swap),
+            StreamEvent::Migration(migration) => self.record_migration(migration),
+            StreamEvent::Candle(_) => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+//! Chain-wide streams that are not tied to one token
+
+use anyhow::{anyhow, Result};
+use ethers::providers::{Middleware, Provider, PubsubClient, Ws};
+use ethers::types::Address;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::filter::FirehoseFilter;
+use crate::core::health::HealthMonitor;
+use crate::core::new_pairs::spawn_new_pair_watcher;
+use crate::core::options::{NewPairCallback, SwapCallback, TrendingCallback};
+use crate::core::swap_firehose::spawn_swap_firehose;
+use crate::core::trending::{spawn_trending_reporter, TrendingTracker};
+use crate::types::{HealthReport, NewPairEvent, SwapEvent, TrendingConfig, TrendingMetrics};
+
+/// Builder for chain-wide streams (new pairs, filtered swaps, trending tokens), the entry point for discovery bots
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::Firehose;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let handle = Firehose::from_wss("wss://bsc.publicnode.com")
+///         .await?
+///         .on_new_pair(|pair| {
+///             println!("{} / {:?}: {:?}", pair.token_symbol, pair.base_token_symbol, pair.liquidity_usd);
+///         })
+///         .start()
+///         .await?;
+///
+///     tokio::signal::ctrl_c().await?;
+///     handle.stop();
+///     Ok(())
+/// }
+/// ```
+pub struct Firehose<M> {
+    provider: Arc<M>,
+    new_pair: Option<NewPairCallback>,
+    swaps: Option<(FirehoseFilter, SwapCallback)>,
+    trending: Option<(TrendingConfig, TrendingCallback)>,
+}
+
+impl Firehose<Provider<Ws>> {
+    /// Create a firehose with a WebSocket URL
+    pub async fn from_wss(wss_url: &str) -> Result<Self> {
+        let provider = Provider::<Ws>::connect(wss_url).await?;
+        Ok(Self::new(Arc::new(provider)))
+    }
+}
+
+impl<M> Firehose<M>
+where
+    M: Middleware + 'static,
+    M::Provider: PubsubClient,
+{
+    /// Create a firehose with an existing provider
+    pub fn new(provider: Arc<M>) -> Self {
+        Self {
+            provider,
+            new_pair: None,
+            swaps: None,
+            trending: None,
+        }
+    }
+
+    /// Call `callback` for every pair or pool created on the PancakeSwap V2 and V3 factories
+    ///
+    /// Events carry both tokens, the base side and the liquidity added in the
+    /// creation transaction (None when the pair was created empty).
+    pub fn on_new_pair<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(NewPairEvent) + Send + Sync + 'static,
+    {
+        self.new_pair = Some(Arc::new(callback));
+        self
+    }
+
+    /// Call `callback` for every swap passing `filter` on any PancakeSwap pool or the Four.meme bonding curve
+    ///
+    /// Pools are resolved on their first swap; pools of other factories and
+    /// pools without a base token are skipped. Keep the filter narrow (e.g. a
+    /// USD minimum) - BSC carries many swaps per second.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::core::filter::FirehoseFilter;
+    /// use bsc_streamer::{Firehose, TradeThreshold};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let filter = FirehoseFilter {
+    ///     min_trade: Some(TradeThreshold::Usd(10_000.0)),
+    ///     ..Default::default()
+    /// };
+    /// let handle = Firehose::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .on_swap(filter, |swap| println!("{} {:?}", swap.token.symbol, swap.usd_value))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_swap<F>(mut self, filter: FirehoseFilter, callback: F) -> Self
+    where
+        F: Fn(SwapEvent) + Send + Sync + 'static,
+    {
+        self.swaps = Some((filter, Arc::new(callback)));
+        self
+    }
+
+    /// Call `callback` when a token enters the top `config.top_n` by short-window volume, trade count and unique buyers
+    ///
+    /// Ranks the swaps the `on_swap` filter lets through (every swap when
+    /// `on_swap` is not used), sharing its subscription.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{Firehose, TrendingConfig};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let handle = Firehose::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .on_trending(TrendingConfig::default(), |token, metrics| {
+    ///         println!("#{} {} ({:?}): ${:.0} in {} trades", metrics.rank, metrics.symbol, token, metrics.volume_usd, metrics.trades);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_trending<T>(mut self, config: TrendingConfig, callback: T) -> Self
+    where
+        T: Fn(Address, TrendingMetrics) + Send + Sync + 'static,
+    {
+        self.trending = Some((config, Arc::new(callback)));
+        self
+    }
+
+    /// Start the subscriptions for the registered callbacks
+    pub async fn start(self) -> Result<FirehoseHandle> {
+        let cancel_token = CancellationToken::new();
+        let health = HealthMonitor::new(self.provider.clone());
+        let mut started = false;
+
+        if let Some(callback) = self.new_pair {
+            spawn_new_pair_watcher(self.provider.clone(), callback, health.clone(), cancel_token.clone());
+            started = true;
+        }
+
+        let tracker = self.trending.map(|(config, callback)| {
+            let tracker = Arc::new(Mutex::new(TrendingTracker::new(config)));
+            spawn_trending_reporter(tracker.clone(), config.refresh, callback, cancel_token.clone());
+            tracker
+        });
+        if self.swaps.is_some() || tracker.is_some() {
+            let (filter, on_swap) = match self.swaps {
+                Some((filter, callback)) => (filter, Some(callback)),
+                None => (FirehoseFilter::default(), None),
+            };
+            let callback: SwapCallback = Arc::new(move |swap| {
+                if let Some(tracker) = &tracker {
+                    tracker.lock().unwrap().record(&swap);
+                }
+                if let Some(on_swap) = &on_swap {
+                    on_swap(swap);
+                }
+            });
+            spawn_swap_firehose(self.provider.clone(), filter, callback, health.clone(), cancel_token.clone());
+            started = true;
+        }
+
+        if !started {
+            return Err(anyhow!("No firehose callback registered (call on_new_pair, on_swap or on_trending first)"));
+        }
+        Ok(FirehoseHandle { cancel_token, health })
+    }
+}
+
+/// Handle to a running `Firehose`
+#[derive(Clone)]
+pub struct FirehoseHandle {
+    cancel_token: CancellationToken,
+    health: HealthMonitor,
+}
+
+impl FirehoseHandle {
+    /// Provider connectivity and subscription liveness
+    pub async fn health(&self) -> HealthReport {
+        self.health.report(self.is_stopped()).await
+    }
+
+    /// Stop all subscriptions
+    pub fn stop(&self) {
+        self.cancel_token.cancel();
+    }
+
+    /// Whether `stop()` has been called
+    pub fn is_stopped(&self) -> bool {
+        self.cancel_token.is_cancelled()
+    }
+}
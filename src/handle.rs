@@ -0,0 +1,166 @@
+use ethers::providers::Middleware;
+use ethers::types::Address;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::core::health::HealthMonitor;
+use crate::core::labels::LabelRegistry;
+use crate::core::price_tracker::PriceTracker;
+use crate::core::reserves::ReserveTracker;
+use crate::core::stats::TradeStats;
+use crate::core::streamer::SwapStreamer;
+use crate::core::supply::SupplyCache;
+use crate::core::venues::VenueTracker;
+use crate::sinks::SinkSet;
+use crate::types::{HealthReport, MarketDepth, PairInfo, PriceImpact, PriceStats, Reserves, TokenSupply, TradeType, TraderStats, VolumeStats};
+
+/// Handle to a running streamer
+///
+/// Returned by `StreamerRunner::start()`. Cloning is cheap; all clones
+/// refer to the same running subscriptions.
+#[derive(Clone)]
+pub struct StreamerHandle {
+    cancel_token: CancellationToken,
+    reserves: ReserveTracker,
+    venues: VenueTracker,
+    labels: LabelRegistry,
+    stats: TradeStats,
+    supply: SupplyCache,
+    prices: PriceTracker,
+    health: HealthMonitor,
+    sinks: SinkSet,
+}
+
+impl StreamerHandle {
+    pub(crate) fn new<M: Middleware + 'static>(cancel_token: CancellationToken, labels: LabelRegistry, streamer: &SwapStreamer<M>) -> Self {
+        Self {
+            cancel_token,
+            reserves: streamer.reserves(),
+            venues: streamer.venues(),
+            labels,
+            stats: streamer.stats(),
+            supply: streamer.supply(),
+            prices: streamer.prices(),
+            health: streamer.health(),
+            sinks: streamer.sinks(),
+        }
+    }
+
+    /// Latest reserves of a monitored V2 pair (kept current by Sync events)
+    pub async fn current_reserves(&self, pair: Address) -> Option<Reserves> {
+        self.reserves.current_reserves(pair).await
+    }
+
+    /// Post-swap pool price of the monitored token on a V2 pair, in the pair's base token
+    pub async fn reserve_price(&self, pair: Address) -> Option<f64> {
+        self.reserves.reserve_price(pair).await
+    }
+
+    /// Marginal price and depth of a monitored V2 pair, computed from tracked reserves
+    pub async fn market_depth(&self, pair: Address) -> Option<MarketDepth> {
+        self.reserves.market_depth(pair).await
+    }
+
+    /// Expected slippage of a hypothetical trade on a monitored V2 pair, from tracked reserves
+    pub async fn price_impact(&self, pair: Address, trade_type: TradeType, size: f64) -> Option<PriceImpact> {
+        self.reserves.price_impact(pair, trade_type, size).await
+    }
+
+    /// Reserve state of all monitored V2 pairs
+    pub fn reserves(&self) -> &ReserveTracker {
+        &self.reserves
+    }
+
+    /// Monitored pool currently holding most of the token's liquidity
+    ///
+    /// Follows liquidity moving between pools (e.g. a V2 pair drained into a V3 pool).
+    pub fn primary_pair(&self, token: Address) -> Option<PairInfo> {
+        self.venues.primary(token)
+    }
+
+    /// Buy/sell volume and trade counts over the last 5m, 1h and 24h
+    pub fn volume(&self, token: Address) -> Option<VolumeStats> {
+        self.stats.volume(token)
+    }
+
+    /// Unique traders, buy:sell ratio and top-trader share over the last 5m, 1h and 24h
+    pub fn traders(&self, token: Address) -> Option<TraderStats> {
+        self.stats.traders(token)
+    }
+
+    /// Rolling trade statistics of the streamed token
+    pub fn stats(&self) -> &TradeStats {
+        &self.stats
+    }
+
+    /// Latest total/burned/circulating supply of a token
+    ///
+    /// Refreshed while pricing swaps, and on every burn or mint when `on_burn` is set.
+    pub async fn supply(&self, token: Address) -> Option<TokenSupply> {
+        self.supply.get(token).await
+    }
+
+    /// Current price, high/low, change and swap count of a token
+    ///
+    /// `base_token` is the normalized quote currency (e.g. "USD") when one is set,
+    /// otherwise the pair's base token symbol.
+    pub fn get_stats(&self, token: Address, base_token: &str) -> Option<PriceStats> {
+        self.prices.get_stats(&format!("{:?}", token), base_token)
+    }
+
+    /// Trades of a token since unix time `since` (seconds), as `(timestamp, price, volume)`
+    ///
+    /// Prices are in `base_token` as for `get_stats`; volume is in whole tokens.
+    pub fn price_history(&self, token: Address, base_token: &str, since: f64) -> Vec<(f64, f64, f64)> {
+        self.prices.price_history(&format!("{:?}", token), base_token, since)
+    }
+
+    /// Price statistics of every token/base pair seen so far, keyed by (token, base token)
+    pub fn all_stats(&self) -> HashMap<(String, String), PriceStats> {
+        self.prices.all_stats()
+    }
+
+    /// Restart the session high/low/%change of a token (e.g. from the migration callback)
+    ///
+    /// Named sessions (`prices().start_session`) are unaffected.
+    pub fn reset_price_stats(&self, token: Address) -> bool {
+        self.prices.reset(&format!("{:?}", token))
+    }
+
+    /// Price statistics tracker fed by the streamer - can be saved with `save_to_file`
+    pub fn prices(&self) -> &PriceTracker {
+        &self.prices
+    }
+
+    /// Address labels applied to swaps - labels added here take effect immediately
+    pub fn labels(&self) -> &LabelRegistry {
+        &self.labels
+    }
+
+    /// Provider connectivity, per-subscription liveness and last-event age
+    ///
+    /// Makes one `eth_blockNumber` call (timing out after 5s). Use
+    /// `is_healthy()` for liveness probes and `is_ready()` for readiness probes.
+    pub async fn health(&self) -> HealthReport {
+        self.health.report(self.is_stopped()).await
+    }
+
+    /// Stop all subscriptions started by this streamer
+    pub fn stop(&self) {
+        self.cancel_token.cancel();
+    }
+
+    /// Stop all subscriptions and wait up to `timeout` for sinks to deliver queued events and close
+    ///
+    /// Returns false if a sink did not finish in time.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.stop();
+        tokio::time::timeout(timeout, self.sinks.join()).await.is_ok()
+    }
+
+    /// Whether `stop()` has been called
+    pub fn is_stopped(&self) -> bool {
+        self.cancel_token.is_cancelled()
+    }
+}
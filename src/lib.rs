@@ -18,6 +18,7 @@
 //! ```rust,no_run
 //! use bsc_streamer::{StreamerBuilder, Platform};
 //! use ethers::providers::{Provider, Ws};
+//! use std::sync::Arc;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,7 +26,7 @@
 //!     let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
 //!     
 //!     // Build and start streamer with auto-detection
-//!     StreamerBuilder::new(provider)
+//!     StreamerBuilder::new(Arc::new(provider))
 //!         .token_address("0x...")
 //!         .auto_detect() // Automatically find where token is trading
 //!         .on_swap(|swap| {
@@ -41,24 +42,61 @@
 pub mod config;
 pub mod core;
 pub mod display;
+pub mod firehose;
+pub mod handle;
+pub mod logging;
 pub mod multi_token_streamer;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod settings;
+pub mod sinks;
+#[cfg(feature = "trader")]
+pub mod trader;
 pub mod types;
 
 use anyhow::{anyhow, Result};
 use ethers::providers::{Middleware, Provider, Ws};
+use ethers::types::{Address, Log};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
+pub use crate::core::backtest::Backtest;
+pub use crate::core::copy_trading::CopyTrader;
+pub use crate::core::labels::LabelRegistry;
+pub use crate::core::paper::PaperTrader;
+pub use firehose::{Firehose, FirehoseHandle};
+pub use handle::StreamerHandle;
 pub use multi_token_streamer::MultiTokenStreamer;
-pub use types::{MigrationEvent, Platform, SwapEvent, TradeType};
+pub use sinks::EventSink;
+pub use types::{
+    AddressLabel, BacktestReport, BlockEvent, BondingCurveInfo, BurnAlertConfig, BurnEvent, Candle, CandleInterval,
+    ContractVerification, CopyConfig, CopySignal, CrossDirection, DevActionEvent, DevActionKind, DrainAlertConfig,
+    DrainReason, EVENT_SCHEMA_VERSION, EquityPoint, EventLatency, HealthReport, HolderSnapshot, HolderStats,
+    HolderStatsConfig, HoneypotReport, ImplementationChangedEvent, Indicator, IndicatorCross, IndicatorValue,
+    LabelKind, LatencyStats, LatencySummary, LiquidityDrainedEvent, LiquidityEvent, LiquidityEventKind,
+    LpLockStatus, MarketCap, MigrationEvent, NewPairEvent, OwnershipChangeEvent, PairDetails, PairInfo,
+    PairSelection, PaperConfig, PaperFill, PaperPortfolio, PaperPosition, PendingSwapEvent, PendingSwapResolution,
+    PendingSwapStatus, Platform, PoolMetadata, PoolMigrationConfig, PoolMigrationEvent, PoolVersion, PriceAlert,
+    PriceAlertRule, PriceImpact, PriceSession, PrimaryPair, ProviderHealth, ProxyInfo, ProxyKind, QuoteCurrency,
+    Reserves, RiskCategory, RiskFlag, RiskReport, StreamEvent, SubscriptionHealth, SubscriptionState, SwapEvent,
+    TaxEstimate, TokenCandidate, TokenLaunch, TokenProfile, TokenSupply, TradeExecution, TradeQuote, TradeThreshold,
+    TradeType, TraderStats, TraderWindow, TrendingConfig, TrendingMetrics, VolumeStats, VolumeWindow,
+};
 
+use crate::core::options::StreamOptions;
 use crate::core::streamer::SwapStreamer;
 
 /// Builder for configuring and starting a token swap event streamer
 pub struct StreamerBuilder<M> {
     provider: Arc<M>,
-    token_address: Option<String>,
+    token_addresses: Vec<String>,
+    pair_address: Option<String>,
+    bonding_curve_address: Option<String>,
     platform: Option<Platform>,
     auto_detect: bool,
+    options: StreamOptions,
 }
 
 impl StreamerBuilder<Provider<Ws>> {
@@ -99,15 +137,99 @@ impl<M: Middleware + 'static> StreamerBuilder<M> {
     pub fn new(provider: Arc<M>) -> Self {
         Self {
             provider,
-            token_address: None,
+            token_addresses: Vec::new(),
+            pair_address: None,
+            bonding_curve_address: None,
             platform: None,
             auto_detect: false,
+            options: StreamOptions::default(),
         }
     }
 
     /// Set the token address to monitor
     pub fn token_address(mut self, address: &str) -> Self {
-        self.token_address = Some(address.to_string());
+        self.token_addresses = vec![address.to_string()];
+        self
+    }
+
+    /// Set several token addresses to monitor with the same callbacks
+    ///
+    /// The tokens share one handle, sink workers and statistics (keyed by
+    /// token). Tokens that cannot be located are logged and skipped; `start`
+    /// only fails if none can be streamed. For adding and removing tokens
+    /// while running, use `MultiTokenStreamer`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_addresses(&["0x...", "0x..."])
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("{} {}", swap.token.symbol, swap.trade_type.as_str()))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn token_addresses<S: AsRef<str>>(mut self, addresses: &[S]) -> Self {
+        self.token_addresses = addresses.iter().map(|address| address.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Monitor a known PancakeSwap V2 pair or V3 pool directly
+    ///
+    /// Skips pair discovery and the liquidity filter, and only this pool is
+    /// monitored. The token address is optional: without it, the side of the
+    /// pool that is not a base token is streamed.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .pair_address("0x...")
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pair_address(mut self, address: &str) -> Self {
+        self.pair_address = Some(address.to_string());
+        self.platform = Some(Platform::PancakeSwap);
+        self.auto_detect = false;
+        self
+    }
+
+    /// Use this bonding curve contract for the token(s) instead of the default Four.meme curve
+    ///
+    /// For tokens from other launchpads with the same Transfer-based curve, or
+    /// from newer Four.meme deployments. Detection, curve trades and graduation
+    /// all use the given contract.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .bonding_curve_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bonding_curve_address(mut self, address: &str) -> Self {
+        self.bonding_curve_address = Some(address.to_string());
         self
     }
 
@@ -144,6 +266,245 @@ impl<M: Middleware + 'static> StreamerBuilder<M> {
         self
     }
 
+    /// Monitor the Four.meme bonding curve and DEX pairs at the same time
+    ///
+    /// Auto-detection streams from one venue: DEX pairs if any exist, otherwise
+    /// the bonding curve. Around graduation both can trade for a while (and
+    /// pairs are sometimes created before it); this mode subscribes to curve
+    /// trades and to every existing and new DEX pair together, so no trades are
+    /// missed. Swaps keep their `platform` to tell the venues apart.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .monitor_all()
+    ///     .on_swap(|swap| println!("{}: {}", swap.platform.as_str(), swap.price.value))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn monitor_all(mut self) -> Self {
+        self.options.monitor_all = true;
+        self.auto_detect = true;
+        self.platform = None;
+        self
+    }
+
+    /// Set the currency all swap prices are normalized to (defaults to BNB)
+    ///
+    /// Tokens trading against several base tokens (WBNB, USDT, ...) then report
+    /// comparable prices in `SwapEvent::normalized_price`.
+    pub fn quote_currency(mut self, currency: QuoteCurrency) -> Self {
+        self.options.quote_currency = currency;
+        self
+    }
+
+    /// Number of trades kept per token for `StreamerHandle::price_history` (default 1000)
+    pub fn price_history_limit(mut self, limit: usize) -> Self {
+        self.options.price_history_limit = Some(limit);
+        self
+    }
+
+    /// Persist token metadata (name, symbol, decimals, supply) to `path`, refetching entries older than `ttl`
+    ///
+    /// Saves the name/symbol/decimals calls for known tokens on every restart.
+    /// Streamers and processes may share the file.
+    pub fn token_cache_file(mut self, path: impl Into<std::path::PathBuf>, ttl: std::time::Duration) -> Self {
+        self.options.token_cache_file = Some((path.into(), ttl));
+        self
+    }
+
+    /// Replay swaps of the token's DEX pairs from `block` before going live
+    ///
+    /// Swaps from `block` up to the head are delivered through the usual
+    /// callbacks, hooks and sinks, oldest first per pair, then the live stream
+    /// continues without gaps or duplicates. Bonding curve trades and pairs
+    /// created while streaming are not backfilled.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .start_block(45_000_000)
+    ///     .on_swap(|swap| println!("Swap in block {}", swap.block_number))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn start_block(mut self, block: u64) -> Self {
+        self.options.start_block = Some(block);
+        self
+    }
+
+    /// Only deliver swaps and migrations once `blocks` blocks are built on top of theirs
+    ///
+    /// Trades latency (about 3 seconds per block on BSC) for safety: events
+    /// are re-checked against the canonical block on release and dropped if
+    /// their transaction was reorged out. Stats, candles, alerts and sinks all
+    /// see confirmed events only. 0 (the default) delivers immediately.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .confirmations(15)
+    ///     .on_swap(|swap| println!("Confirmed swap in block {}", swap.block_number))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn confirmations(mut self, blocks: u64) -> Self {
+        self.options.confirmations = blocks;
+        self
+    }
+
+    /// Parse up to `n` swaps of a pair (or bonding curve) at once (default 8)
+    ///
+    /// Each parse can take several RPC round-trips, so parsing one log at a
+    /// time falls behind during volume spikes. Swaps are still delivered in
+    /// the order their logs arrived. 1 parses one at a time.
+    pub fn parse_concurrency(mut self, n: usize) -> Self {
+        self.options.parse_concurrency = Some(n);
+        self
+    }
+
+    /// Only monitor DEX pairs with at least this much liquidity in USD (default $5000)
+    ///
+    /// Liquidity is checked on DexScreener; pairs it does not list are only used
+    /// when no listed pair qualifies.
+    pub fn min_liquidity(mut self, usd: f64) -> Self {
+        self.options.min_liquidity_usd = Some(usd);
+        self
+    }
+
+    /// Compute indicators over streamed prices
+    ///
+    /// Values are included in `PriceStats::indicators` (see `StreamerHandle::get_stats`).
+    /// Periods are counted in trades; an SMA longer than `price_history_limit` never fills.
+    pub fn indicators(mut self, indicators: &[Indicator]) -> Self {
+        self.options.indicators.extend_from_slice(indicators);
+        self
+    }
+
+    /// Send every swap (after the filter), migration and candle (with `on_candle`) to a sink
+    ///
+    /// Sinks run in their own tasks; call several times to register more than one.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{EventSink, StreamEvent, StreamerBuilder};
+    ///
+    /// struct Printer;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl EventSink for Printer {
+    ///     fn name(&self) -> &str {
+    ///         "printer"
+    ///     }
+    ///
+    ///     async fn handle(&self, event: &StreamEvent) -> anyhow::Result<()> {
+    ///         println!("{}", serde_json::to_string(event)?);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .sink(Printer)
+    ///     .on_swap(|_| {})
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sink<S: EventSink>(mut self, sink: S) -> Self {
+        self.options.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Label an address (shown on swaps as sender/recipient/trader label)
+    pub fn label_address(self, address: Address, name: &str, kind: LabelKind) -> Self {
+        self.options.labels.insert(address, name, kind);
+        self
+    }
+
+    /// Replace the default curated label set
+    pub fn labels(mut self, labels: LabelRegistry) -> Self {
+        self.options.labels = labels;
+        self
+    }
+
+    /// Only deliver swaps at or above a minimum size
+    pub fn min_trade_size(mut self, threshold: TradeThreshold) -> Self {
+        self.options.filter.min_trade = Some(threshold);
+        self
+    }
+
+    /// Only deliver buys or only sells
+    pub fn trade_type(mut self, trade_type: TradeType) -> Self {
+        self.options.filter.trade_type = Some(trade_type);
+        self
+    }
+
+    /// Only deliver swaps on these pair (or bonding curve) addresses
+    pub fn filter_pairs(mut self, pairs: &[Address]) -> Self {
+        self.options.filter.pairs.extend_from_slice(pairs);
+        self
+    }
+
+    /// Only deliver swaps involving these wallets (trader, sender or recipient)
+    pub fn allow_senders(mut self, wallets: &[Address]) -> Self {
+        self.options.filter.allow_senders.extend_from_slice(wallets);
+        self
+    }
+
+    /// Drop swaps involving these wallets (trader, sender or recipient)
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{StreamerBuilder, TradeThreshold, TradeType};
+    /// use ethers::types::Address;
+    ///
+    /// # async fn example(mev_bot: Address) -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .trade_type(TradeType::Buy)
+    ///     .min_trade_size(TradeThreshold::BaseToken(0.5))
+    ///     .deny_senders(&[mev_bot])
+    ///     .on_swap(|swap| println!("Buy: {:?}", swap))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deny_senders(mut self, wallets: &[Address]) -> Self {
+        self.options.filter.deny_senders.extend_from_slice(wallets);
+        self
+    }
+
     /// Start the streamer with a callback for swap events
     ///
     /// # Example
@@ -232,87 +593,771 @@ where
         }
     }
 
-    /// Start streaming swap events
-    pub async fn start(self) -> Result<()> {
-        let token_address = self
-            .builder
-            .token_address
-            .ok_or_else(|| anyhow!("Token address is required"))?;
-
-        let mut streamer = SwapStreamer::new(self.builder.provider);
-
-        if self.builder.auto_detect {
-            // Auto-detect mode: Let streamer figure out where token is
-            streamer.start_with_migration_callback(
-                &token_address, 
-                self.swap_callback,
-                self.migration_callback,
-            ).await?;
-        } else if let Some(platform) = self.builder.platform {
-            // Manual platform mode
-            match platform {
-                Platform::FourMemeBondingCurve => {
-                    // Start bonding curve monitoring with migration detection
-                    streamer.start_with_migration_callback(
-                        &token_address,
-                        self.swap_callback,
-                        self.migration_callback,
-                    ).await?;
-                }
-                Platform::PancakeSwap => {
-                    // Start DEX monitoring only
-                    streamer.start_with_migration_callback(
-                        &token_address,
-                        self.swap_callback,
-                        self.migration_callback,
-                    ).await?;
-                }
-            }
-        } else {
-            return Err(anyhow!("Must either enable auto_detect() or specify platform()"));
-        }
-
-        Ok(())
+    /// Set a callback for liquidity changes on the monitored DEX pairs
+    ///
+    /// Receives V2 Mint/Burn and V3 Mint/Burn/Collect events with the amounts
+    /// involved and the pool reserves after the change.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_liquidity(|lp| {
+    ///         println!("{} {} {} + {} {}",
+    ///             lp.kind.as_str(),
+    ///             lp.token.amount,
+    ///             lp.token.symbol,
+    ///             lp.base_token.amount,
+    ///             lp.base_token.symbol
+    ///         );
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_liquidity<L>(mut self, callback: L) -> Self
+    where
+        L: Fn(LiquidityEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.liquidity = Some(Arc::new(callback));
+        self
     }
-}
 
-/// Find where a token is currently trading
-///
-/// Returns information about where the token can be found (bonding curve, DEX pairs, etc.)
-///
-/// # Example
-/// ```rust,no_run
-/// use bsc_streamer::find_token_location;
-/// use ethers::providers::{Provider, Ws};
-///
-/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
-/// let location = find_token_location(&provider, "0x...").await?;
-/// println!("Token found on: {:?}", location);
-/// # Ok(())
+    /// Set a rug-pull alert for the monitored DEX pairs
+    ///
+    /// Fires when a single Burn removes more than `config.max_removal_pct` of the
+    /// pool, or when the base token reserve drops below `config.min_base_reserve`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{DrainAlertConfig, StreamerBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_liquidity_drained(
+    ///         DrainAlertConfig { max_removal_pct: 80.0, min_base_reserve: Some(1.0) },
+    ///         |alert| println!("🚨 {} on {:?}", alert.reason.as_str(), alert.pair_address),
+    ///     )
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_liquidity_drained<D>(mut self, config: DrainAlertConfig, callback: D) -> Self
+    where
+        D: Fn(LiquidityDrainedEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.liquidity_drained = Some((config, Arc::new(callback)));
+        self
+    }
+
+    /// Track the token's holders and report stats periodically
+    ///
+    /// Holder counts are approximate: balances are rebuilt from Transfer events
+    /// over `config.lookback_blocks` of history plus live transfers.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{HolderStatsConfig, StreamerBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_holder_stats(HolderStatsConfig::default(), |stats| {
+    ///         println!("👥 {} holders (+{:.1}/min)", stats.holder_count, stats.new_holders_per_min);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_holder_stats<S>(mut self, config: HolderStatsConfig, callback: S) -> Self
+    where
+        S: Fn(HolderStats) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.holder_stats = Some((config, Arc::new(callback)));
+        self
+    }
+
+    /// Watch the token creator's wallet (Four.meme launches)
+    ///
+    /// The creator is resolved from the Four.meme TokenCreate event; the callback
+    /// fires when that wallet sells, transfers tokens, or adds/removes liquidity.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_dev_action(|action| {
+    ///         println!("🚨 {} {} tokens", action.kind.as_str(), action.amount);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_dev_action<D>(mut self, callback: D) -> Self
+    where
+        D: Fn(DevActionEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.dev_action = Some(Arc::new(callback));
+        self
+    }
+
+    /// Alert on price conditions
+    ///
+    /// Rules are evaluated by a `PriceTracker` on every swap, using the normalized
+    /// price (see `quote_currency`) when available.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{PriceAlertRule, StreamerBuilder};
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|_| {})
+    ///     .on_price_alert(
+    ///         &[
+    ///             PriceAlertRule::CrossesAbove(0.0001),
+    ///             PriceAlertRule::MoveWithin { pct: 20.0, window: Duration::from_secs(300) },
+    ///             PriceAlertRule::NewHigh,
+    ///         ],
+    ///         |alert| println!("🔔 {:?}: {} ({:+.2}%)", alert.rule, alert.price, alert.change_pct),
+    ///     )
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_price_alert<P>(mut self, rules: &[PriceAlertRule], callback: P) -> Self
+    where
+        P: Fn(PriceAlert) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.price_alert = Some((rules.to_vec(), Arc::new(callback)));
+        self
+    }
+
+    /// Alert when the price crosses a moving average
+    ///
+    /// Fires when a trade moves the price from one side of an SMA/EMA to the other;
+    /// RSI indicators are computed but never cross.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{Indicator, StreamerBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|_| {})
+    ///     .on_indicator_cross(&[Indicator::Ema(20)], |cross| {
+    ///         println!("📐 Price crossed {} {} at {}", cross.direction.as_str(), cross.indicator.label(), cross.price);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_indicator_cross<I>(mut self, indicators: &[Indicator], callback: I) -> Self
+    where
+        I: Fn(IndicatorCross) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.indicator_cross = Some((indicators.to_vec(), Arc::new(callback)));
+        self
+    }
+
+    /// Alert on large burns and keep the token's circulating supply live
+    ///
+    /// Transfers to the dead or zero address of at least `config.min_supply_pct`
+    /// percent of total supply fire the callback; the updated supply is available
+    /// from `StreamerHandle::supply`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{BurnAlertConfig, StreamerBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|_| {})
+    ///     .on_burn(BurnAlertConfig { min_supply_pct: 1.0 }, |burn| {
+    ///         println!("🔥 {:.2}% of supply burned", burn.pct_of_supply);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_burn<B>(mut self, config: BurnAlertConfig, callback: B) -> Self
+    where
+        B: Fn(BurnEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.burn = Some((config, Arc::new(callback)));
+        self
+    }
+
+    /// Aggregate swaps into OHLCV candles per pair
+    ///
+    /// One candle per market (DEX pair or bonding curve) and interval is emitted
+    /// when the interval closes; intervals without trades produce no candle.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{CandleInterval, StreamerBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|_| {})
+    ///     .on_candle(&[CandleInterval::OneMinute, CandleInterval::FiveMinutes], |candle| {
+    ///         println!(
+    ///             "[{}] O {} H {} L {} C {} V {}",
+    ///             candle.interval.as_str(), candle.open, candle.high, candle.low, candle.close, candle.volume_base
+    ///         );
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_candle<C>(mut self, intervals: &[CandleInterval], callback: C) -> Self
+    where
+        C: Fn(Candle) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.candles = Some((intervals.to_vec(), Arc::new(callback)));
+        self
+    }
+
+    /// Watch for ownership changes on the token contract
+    ///
+    /// Fires on every OwnershipTransferred event, including renounces to the
+    /// zero/dead address (`event.renounced`).
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_ownership_change(|change| {
+    ///         if change.renounced {
+    ///             println!("👑 Ownership renounced in {:?}", change.transaction_hash);
+    ///         }
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_ownership_change<O>(mut self, callback: O) -> Self
+    where
+        O: Fn(OwnershipChangeEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.ownership_change = Some(Arc::new(callback));
+        self
+    }
+
+    /// Watch for upgrades of a proxy token
+    ///
+    /// The token is checked for EIP-1967 transparent, UUPS and beacon proxies;
+    /// the callback fires when the implementation changes while streaming,
+    /// a common step before a rug. Non-upgradeable tokens are not watched.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_implementation_changed(|upgrade| {
+    ///         println!("🧬 Token upgraded to {:?}", upgrade.new_implementation);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_implementation_changed<I>(mut self, callback: I) -> Self
+    where
+        I: Fn(ImplementationChangedEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.implementation_changed = Some(Arc::new(callback));
+        self
+    }
+
+    /// Get notified when liquidity moves from the token's primary pool to another of its pools
+    ///
+    /// Pool liquidity is read every `config.interval`; the event fires when a
+    /// different pool becomes the deepest and the old primary lost at least
+    /// `config.min_drop_pct` of its peak (the common V2 to V3 move after launch).
+    /// `StreamerHandle::primary_pair` follows the move either way.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{PoolMigrationConfig, StreamerBuilder};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_pool_migration(PoolMigrationConfig::default(), |event| {
+    ///         println!("🔀 Liquidity moved {:?} -> {:?}", event.from_pair, event.to_pair);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_pool_migration<P>(mut self, config: PoolMigrationConfig, callback: P) -> Self
+    where
+        P: Fn(PoolMigrationEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.pool_migration = Some((config, Arc::new(callback)));
+        self
+    }
+
+    /// Get notified of DEX pairs created for the token while streaming
+    ///
+    /// New V2 pairs and V3 pools (e.g. a second base token or another fee tier)
+    /// are always picked up and monitored; this callback only reports them.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_new_pair(|pair| {
+    ///         println!("🆕 New {:?} pool {:?} vs {:?}", pair.pool.version, pair.pair_address, pair.base_token_symbol);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_new_pair<N>(mut self, callback: N) -> Self
+    where
+        N: Fn(NewPairEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.new_pair = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called on every new chain head, over the streamer's own connection
+    ///
+    /// Reports the head's number, timestamp, base fee and gas, plus how many
+    /// swaps of the monitored tokens were seen in the block before it (that
+    /// block's logs have arrived by the time the next head does).
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_block(|block| {
+    ///         println!("⛓️ Block {} ({} swaps in the previous one)", block.block_number, block.previous_block_swaps);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_block<B>(mut self, callback: B) -> Self
+    where
+        B: Fn(BlockEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.block = Some(Arc::new(callback));
+        self
+    }
+
+    /// Receive every undecoded log of the streamer's subscriptions
+    ///
+    /// Covers the DEX pair subscriptions (Swap, Sync and, with liquidity hooks,
+    /// Mint/Burn/Collect), the bonding curve Transfers and the factory
+    /// PairCreated/PoolCreated watchers, before any decoding or filtering. Use
+    /// it to decode events the crate does not know about on top of its
+    /// subscriptions.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_raw_log(|log| println!("{:?} topic0 {:?}", log.address, log.topics.first()))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_raw_log<R>(mut self, callback: R) -> Self
+    where
+        R: Fn(Log) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.raw_log = Some(Arc::new(callback));
+        self
+    }
+
+    /// Stream swaps of the token from the mempool, before they are mined
+    ///
+    /// Decodes PancakeSwap V2 router (and fork) swap calls and V3 `exactInputSingle`
+    /// from pending transactions. Requires a node exposing `newPendingTransactions`
+    /// or `txpool_content` - most public endpoints do not.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://your-node:8546")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_pending_swap(|pending| {
+    ///         println!("⏳ Pending {} via {}", pending.trade_type.as_str(), pending.method);
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_pending_swap<P>(mut self, callback: P) -> Self
+    where
+        P: Fn(PendingSwapEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.pending_swap = Some(Arc::new(callback));
+        self
+    }
+
+    /// Track the lifecycle of pending swaps seen in the mempool
+    ///
+    /// Each pending swap is resolved exactly once, linked by transaction hash:
+    /// confirmed (with the streamed `SwapEvent` when it traded on a monitored
+    /// pool), reverted, replaced by another transaction with the same nonce, or
+    /// dropped. Has the same node requirements as `on_pending_swap`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://your-node:8546")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_pending_resolved(|resolution| {
+    ///         println!(
+    ///             "{:?} {} after {}ms",
+    ///             resolution.pending.transaction_hash,
+    ///             resolution.status.as_str(),
+    ///             resolution.pending_ms
+    ///         );
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_pending_resolved<P>(mut self, callback: P) -> Self
+    where
+        P: Fn(PendingSwapResolution) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.pending_resolved = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set a separate callback for large trades
+    ///
+    /// Fires for every swap at or above `threshold`, in addition to `on_swap`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::{StreamerBuilder, TradeThreshold};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("Swap: {:?}", swap))
+    ///     .on_whale_trade(TradeThreshold::Usd(10_000.0), |swap| {
+    ///         println!("🐋 {} ${:.0}", swap.trade_type.as_str(), swap.usd_value.unwrap_or_default());
+    ///     })
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_whale_trade<W>(mut self, threshold: TradeThreshold, callback: W) -> Self
+    where
+        W: Fn(SwapEvent) + Send + Sync + 'static,
+    {
+        self.builder.options.hooks.whale_trade = Some((threshold, Arc::new(callback)));
+        self
+    }
+
+    /// Start streaming swap events
+    ///
+    /// Subscriptions run in background tasks; the returned handle exposes live
+    /// state (e.g. pair reserves) and can stop them.
+    pub async fn start(mut self) -> Result<StreamerHandle> {
+        use crate::core::pair_finder::PairFinder;
+        use std::str::FromStr;
+
+        let mut tokens = self
+            .builder
+            .token_addresses
+            .iter()
+            .map(|token| Address::from_str(token).map_err(|_| anyhow!("Invalid token address: {}", token)))
+            .collect::<Result<Vec<_>>>()?;
+        tokens.dedup();
+
+        let pair = match self.builder.pair_address {
+            Some(_) if tokens.len() > 1 => return Err(anyhow!("pair_address() can only be combined with a single token")),
+            Some(ref pair_address) => {
+                let finder = PairFinder::new(self.builder.provider.clone());
+                let pair = finder.pair_info(Address::from_str(pair_address)?, tokens.first().copied()).await?;
+                tokens = vec![pair.token];
+                Some(pair)
+            }
+            None => None,
+        };
+        if tokens.is_empty() {
+            return Err(anyhow!("Token address is required"));
+        }
+        if let Some(ref curve) = self.builder.bonding_curve_address {
+            let curve = Address::from_str(curve).map_err(|_| anyhow!("Invalid bonding curve address: {}", curve))?;
+            self.builder.options.bonding_curves.extend(tokens.iter().map(|token| (*token, curve)));
+        }
+
+        let labels = self.builder.options.labels.clone();
+        let mut streamer = SwapStreamer::with_options(self.builder.provider, self.builder.options);
+        if let Some(pair) = pair {
+            streamer = streamer.with_pair(pair);
+        }
+        let cancel_token = CancellationToken::new();
+        let handle = StreamerHandle::new(cancel_token.clone(), labels, &streamer);
+
+        if self.builder.auto_detect {
+            // Auto-detect mode: Let streamer figure out where token is
+            streamer.start_tokens(
+                &tokens,
+                self.swap_callback,
+                self.migration_callback,
+                cancel_token,
+            ).await?;
+        } else if let Some(platform) = self.builder.platform {
+            // Manual platform mode
+            match platform {
+                Platform::FourMemeBondingCurve => {
+                    // Start bonding curve monitoring with migration detection
+                    streamer.start_tokens(
+                        &tokens,
+                        self.swap_callback,
+                        self.migration_callback,
+                        cancel_token,
+                    ).await?;
+                }
+                Platform::PancakeSwap => {
+                    // Start DEX monitoring only
+                    streamer.start_tokens(
+                        &tokens,
+                        self.swap_callback,
+                        self.migration_callback,
+                        cancel_token,
+                    ).await?;
+                }
+            }
+        } else {
+            return Err(anyhow!("Must either enable auto_detect() or specify platform()"));
+        }
+
+        Ok(handle)
+    }
+}
+
+/// Find where a token is currently trading
+///
+/// Returns information about where the token can be found (bonding curve, DEX pairs, etc.)
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::find_token_location;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let location = find_token_location(Arc::new(provider), "0x...").await?;
+/// println!("Token found on: {:?}", location);
+/// # Ok(())
 /// # }
 /// ```
 pub async fn find_token_location<M: Middleware + Clone + 'static>(
     provider: Arc<M>,
     token_address: &str,
 ) -> Result<TokenLocation> {
+    use crate::core::bonding_curve::BondingCurveReader;
+    use crate::core::explorer::BscScanClient;
+    use crate::core::honeypot::HoneypotChecker;
+    use crate::core::lp_lock::LpLockChecker;
+    use crate::core::risk::RiskScanner;
     use crate::core::pair_finder::PairFinder;
-    use ethers::types::Address;
+    use crate::core::swap_parser::SwapParser;
+    use crate::core::token_info::TokenInfoCache;
     use std::str::FromStr;
 
     let token_address = Address::from_str(token_address)?;
+    let metadata = match TokenInfoCache::new(provider.clone()).with_profiles().get_token_info(token_address).await {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            log::debug!("⚠️  Metadata lookup failed for {:?}: {}", token_address, e);
+            None
+        }
+    };
 
     // Check bonding curve first
     let streamer = SwapStreamer::new(provider.clone());
     let on_bonding_curve = streamer.check_bonding_curve_public(&token_address).await?;
+    let bonding_curve = if on_bonding_curve {
+        match BondingCurveReader::new(provider.clone()).read(token_address).await {
+            Ok(info) => info,
+            Err(e) => {
+                log::debug!("⚠️  Bonding curve read failed for {:?}: {}", token_address, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
 
     // Check for DEX pairs
-    let pair_finder = PairFinder::new(provider);
+    let pair_finder = PairFinder::new(provider.clone());
     let pairs = pair_finder.find_pairs(token_address).await.unwrap_or_default();
 
+    // Sellability of the most liquid pair
+    let honeypot = match pairs.first() {
+        Some(pair) => match HoneypotChecker::new(provider.clone()).check(pair).await {
+            Ok(report) => Some(report),
+            Err(e) => {
+                log::debug!("⚠️  Honeypot check failed for pair {:?}: {}", pair.pair_address, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let risk = match RiskScanner::new(provider.clone()).scan(token_address).await {
+        Ok(report) => Some(report),
+        Err(e) => {
+            log::debug!("⚠️  Risk scan failed for {:?}: {}", token_address, e);
+            None
+        }
+    };
+
+    // Contract verification, only when a BscScan API key is configured
+    let contract = match BscScanClient::from_env() {
+        Some(client) => match client.contract_info(token_address).await {
+            Ok(info) => Some(info),
+            Err(e) => {
+                log::debug!("⚠️  BscScan lookup failed for {:?}: {}", token_address, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let parser = SwapParser::new(provider.clone());
+    let mut pair_details = Vec::with_capacity(pairs.len());
+    for pair in &pairs {
+        pair_details.push(parser.pair_details(pair).await);
+    }
+    pair_details.sort_by(|a, b| b.liquidity_usd.unwrap_or(0.0).total_cmp(&a.liquidity_usd.unwrap_or(0.0)));
+
+    // Routing recommendation from liquidity and recent activity
+    let selection = PairSelection::default();
+    match pair_finder.recent_swaps(&pairs, selection.activity_blocks).await {
+        Ok(counts) => {
+            for pair in &mut pair_details {
+                pair.recent_swaps = counts.get(&pair.info.pair_address).copied();
+            }
+        }
+        Err(e) => log::debug!("⚠️  Could not count recent swaps for {:?}: {}", token_address, e),
+    }
+    let primary_pair = selection.select(&pair_details);
+
+    // LP burn/lock status of V2 pairs (V3 liquidity is held as NFT positions)
+    let lp_checker = LpLockChecker::new(provider);
+    let mut lp_locks = Vec::new();
+    for pair in pairs.iter().filter(|p| !p.is_v3) {
+        match lp_checker.check(pair.pair_address).await {
+            Ok(status) => lp_locks.push(status),
+            Err(e) => log::debug!("⚠️  Could not check LP lock for pair {:?}: {}", pair.pair_address, e),
+        }
+    }
+
     Ok(TokenLocation {
+        metadata,
         on_bonding_curve,
+        bonding_curve,
         dex_pairs: pairs.len(),
+        pairs: pair_details,
+        primary_pair,
         platforms: if on_bonding_curve {
             vec![Platform::FourMemeBondingCurve]
         } else if !pairs.is_empty() {
@@ -320,17 +1365,378 @@ pub async fn find_token_location<M: Middleware + Clone + 'static>(
         } else {
             vec![]
         },
+        lp_locks,
+        honeypot,
+        risk,
+        contract,
     })
 }
 
+/// Check whether a token can be sold on its most liquid DEX pair
+///
+/// Simulates the buy and sell token transfers with eth_call (the sell uses a
+/// state override, so the RPC node must support them) and looks at recent
+/// trades on the pool.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::check_honeypot;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let report = check_honeypot(Arc::new(provider), "0x...").await?;
+/// if report.is_honeypot {
+///     println!("🍯 Honeypot: {}", report.reason.unwrap_or_default());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn check_honeypot<M: Middleware + 'static>(provider: Arc<M>, token_address: &str) -> Result<HoneypotReport> {
+    use crate::core::honeypot::HoneypotChecker;
+    use crate::core::pair_finder::PairFinder;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    let pairs = PairFinder::new(provider.clone()).find_pairs(token_address).await?;
+    let pair = pairs
+        .first()
+        .ok_or_else(|| anyhow!("No DEX pairs found for {:?}", token_address))?;
+
+    HoneypotChecker::new(provider).check(pair).await
+}
+
+/// Estimate a token's buy and sell tax on its most liquid DEX pair
+///
+/// Taxes come from a simulated buy and sell through the PancakeSwap V2 router
+/// (needs eth_call state overrides). When that isn't possible they are inferred from
+/// the token Transfer logs of recent trades on the pool; tokens without recent trades
+/// then return no estimate.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::estimate_taxes;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let taxes = estimate_taxes(Arc::new(provider), "0x...").await?;
+/// println!("Buy tax: {:?}% | Sell tax: {:?}%", taxes.buy_tax_pct, taxes.sell_tax_pct);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn estimate_taxes<M: Middleware + 'static>(provider: Arc<M>, token_address: &str) -> Result<TaxEstimate> {
+    use crate::core::pair_finder::PairFinder;
+    use crate::core::tax::TaxEstimator;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    let pairs = PairFinder::new(provider.clone()).find_pairs(token_address).await?;
+    let pair = pairs
+        .first()
+        .ok_or_else(|| anyhow!("No DEX pairs found for {:?}", token_address))?;
+
+    TaxEstimator::new(provider).estimate(pair).await
+}
+
+/// Quote buying a token with `bnb_amount` BNB at current depth
+///
+/// Queries the PancakeSwap V2 router (`getAmountsOut`) and V3 QuoterV2 against
+/// every discovered pair and returns the best output.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::quote_buy;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let quote = quote_buy(Arc::new(provider), "0x...", 0.5).await?;
+/// println!("0.5 BNB buys {} tokens at {} BNB each", quote.amount_out, quote.price_bnb);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn quote_buy<M: Middleware + 'static>(provider: Arc<M>, token_address: &str, bnb_amount: f64) -> Result<TradeQuote> {
+    use crate::core::pair_finder::PairFinder;
+    use crate::core::quoter::Quoter;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    let pairs = PairFinder::new(provider.clone()).find_pairs(token_address).await?;
+    Quoter::new(provider)?.quote_buy(&pairs, bnb_amount).await
+}
+
+/// Quote selling `token_amount` tokens for BNB at current depth
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::quote_sell;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let quote = quote_sell(Arc::new(provider), "0x...", 1_000_000.0).await?;
+/// println!("Selling returns {} BNB", quote.amount_out);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn quote_sell<M: Middleware + 'static>(provider: Arc<M>, token_address: &str, token_amount: f64) -> Result<TradeQuote> {
+    use crate::core::pair_finder::PairFinder;
+    use crate::core::quoter::Quoter;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    let pairs = PairFinder::new(provider.clone()).find_pairs(token_address).await?;
+    Quoter::new(provider)?.quote_sell(&pairs, token_amount).await
+}
+
+/// Estimate the slippage of a trade of `size` on the token's most liquid V2 pair
+///
+/// `size` is the input amount: base token (e.g. WBNB) for buys, the token itself
+/// for sells. Computed from the pair's current reserves with the constant product
+/// formula; a running streamer can get the same from `StreamerHandle::price_impact`
+/// without any RPC calls.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::{estimate_price_impact, TradeType};
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let impact = estimate_price_impact(Arc::new(provider), "0x...", TradeType::Buy, 1.0).await?;
+/// println!("1 BNB buy moves the price {:.2}%", impact.impact_pct);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn estimate_price_impact<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: &str,
+    trade_type: TradeType,
+    size: f64,
+) -> Result<PriceImpact> {
+    use crate::core::pair_finder::PairFinder;
+    use crate::core::reserves::ReserveTracker;
+    use crate::core::token_info::TokenInfoCache;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    let pairs = PairFinder::new(provider.clone()).find_pairs(token_address).await?;
+    let pair = pairs
+        .iter()
+        .find(|p| !p.is_v3)
+        .ok_or_else(|| anyhow!("No V2 pair found for {:?}", token_address))?;
+
+    let reserves = ReserveTracker::new();
+    reserves.seed(provider.clone(), &TokenInfoCache::new(provider), pair).await?;
+    reserves
+        .price_impact(pair.pair_address, trade_type, size)
+        .await
+        .ok_or_else(|| anyhow!("Pair {:?} has no usable reserves", pair.pair_address))
+}
+
+/// Market cap and FDV of a token at its current USD price
+///
+/// Circulating supply excludes tokens held by the dead and zero addresses. The
+/// price comes from the reserves of the most liquid V2 pair, falling back to a
+/// router quote for tokens that only trade on V3.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::market_cap;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let cap = market_cap(Arc::new(provider), "0x...").await?;
+/// println!("MC ${:.0} | FDV ${:.0}", cap.market_cap_usd, cap.fdv_usd);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn market_cap<M: Middleware + 'static>(provider: Arc<M>, token_address: &str) -> Result<MarketCap> {
+    use crate::core::pair_finder::PairFinder;
+    use crate::core::price_oracle::PriceOracle;
+    use crate::core::quoter::Quoter;
+    use crate::core::reserves::ReserveTracker;
+    use crate::core::supply::SupplyTracker;
+    use crate::core::token_info::TokenInfoCache;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    let pairs = PairFinder::new(provider.clone()).find_pairs(token_address).await?;
+    let oracle = PriceOracle::new(provider.clone());
+
+    let price_usd = match pairs.iter().find(|p| !p.is_v3 && p.route.is_none()) {
+        Some(pair) => {
+            let reserves = ReserveTracker::new();
+            reserves.seed(provider.clone(), &TokenInfoCache::new(provider.clone()), pair).await?;
+            let price = reserves.reserve_price(pair.pair_address).await;
+            let base_usd = oracle.usd_price(pair.base_token).await;
+            price.zip(base_usd).map(|(price, base_usd)| price * base_usd)
+        }
+        None => {
+            let quote = Quoter::new(provider.clone())?.quote_sell(&pairs, 1.0).await?;
+            oracle.bnb_usd().await.map(|bnb_usd| quote.price_bnb * bnb_usd)
+        }
+    }
+    .ok_or_else(|| anyhow!("No USD price available for {:?}", token_address))?;
+
+    SupplyTracker::new(provider)
+        .market_cap(token_address, price_usd)
+        .await
+        .ok_or_else(|| anyhow!("Failed to read supply of {:?}", token_address))
+}
+
+/// Scan a token's bytecode for dangerous owner functions
+///
+/// Flags mint, blacklist, pause, tax-change and upgrade selectors (following
+/// EIP-1967/EIP-1167 proxies to their implementation) and scores them 0-100.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::scan_token_risk;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let risk = scan_token_risk(Arc::new(provider), "0x...").await?;
+/// if risk.score >= 50 {
+///     println!("⚠️  Risky token ({}/100)", risk.score);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn scan_token_risk<M: Middleware + 'static>(provider: Arc<M>, token_address: &str) -> Result<RiskReport> {
+    use crate::core::risk::RiskScanner;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    RiskScanner::new(provider).scan(token_address).await
+}
+
+/// Report the share of supply held by the top `top_n` wallets
+///
+/// DEX pairs (including ones below the streaming liquidity threshold), the
+/// Four.meme bonding curve and burn addresses are excluded from the ranking. Holders are discovered from recent Transfer history.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::holder_snapshot;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let snapshot = holder_snapshot(Arc::new(provider), "0x...", 10).await?;
+/// println!("Top 10 hold {:.2}%", snapshot.top_holders_pct);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn holder_snapshot<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: &str,
+    top_n: usize,
+) -> Result<HolderSnapshot> {
+    use crate::config::get_bonding_curve_address;
+    use crate::core::holders;
+    use crate::core::pair_finder::PairFinder;
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+
+    let pairs = PairFinder::new(provider.clone()).find_all_pairs(token_address).await.unwrap_or_default();
+    let mut excluded: Vec<Address> = pairs.iter().map(|p| p.pair_address).collect();
+    excluded.push(get_bonding_curve_address());
+
+    holders::snapshot(provider, token_address, top_n, excluded).await
+}
+
+/// Fetch a token's historical swaps (bonding curve and DEX pairs) between two blocks, oldest first
+///
+/// `to_block` defaults to the latest block. Logs are fetched in chunks of 5000
+/// blocks, so long ranges take many requests on public RPCs.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::backfill_swaps;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// let swaps = backfill_swaps(Arc::new(provider), "0x...", 45_000_000, None).await?;
+/// println!("{} swaps", swaps.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn backfill_swaps<M: Middleware + 'static>(
+    provider: Arc<M>,
+    token_address: &str,
+    from_block: u64,
+    to_block: Option<u64>,
+) -> Result<Vec<SwapEvent>> {
+    use std::str::FromStr;
+
+    let token_address = Address::from_str(token_address)?;
+    crate::core::backfill::fetch_swaps(provider, token_address, from_block, to_block).await
+}
+
+/// Find tokens by ticker (or name) among recently launched and listed tokens
+///
+/// Searches the last ~day of PancakeSwap pair creations and Four.meme launches
+/// (see `core::search::search_tokens` for a custom window). Exact symbol
+/// matches come first, then the most liquid, so the top candidate is usually
+/// the one meant - but tickers are not unique, so check the address.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::find_token_by_symbol;
+/// use ethers::providers::{Provider, Ws};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+/// for candidate in find_token_by_symbol(Arc::new(provider), "PEPE").await? {
+///     println!("{} {:?} ${:?}", candidate.symbol, candidate.address, candidate.liquidity_usd);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn find_token_by_symbol<M: Middleware + 'static>(provider: Arc<M>, symbol: &str) -> Result<Vec<TokenCandidate>> {
+    crate::core::search::search_tokens(provider, symbol, crate::core::search::SEARCH_LOOKBACK_BLOCKS).await
+}
+
 /// Information about where a token is currently trading
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct TokenLocation {
+    /// Name, symbol, supply, logo and links of the token
+    pub metadata: Option<crate::core::token_info::TokenMetadata>,
     /// Whether the token is on Four.meme bonding curve
     pub on_bonding_curve: bool,
+    /// Curve progress, price, funds raised and creator while on the bonding curve
+    pub bonding_curve: Option<BondingCurveInfo>,
     /// Number of DEX pairs found
     pub dex_pairs: usize,
+    /// DEX pairs found, most liquid first, with their current price and liquidity
+    pub pairs: Vec<PairDetails>,
+    /// Pair to route orders through, picked by liquidity and recent activity (see `PairSelection`)
+    pub primary_pair: Option<PrimaryPair>,
     /// Platforms where the token is available
     pub platforms: Vec<Platform>,
+    /// LP burn/lock status of each V2 pair
+    pub lp_locks: Vec<LpLockStatus>,
+    /// Sellability check on the most liquid pair
+    pub honeypot: Option<HoneypotReport>,
+    /// Bytecode risk scan of the token contract
+    pub risk: Option<RiskReport>,
+    /// Verification status, creator and creation date (requires `BSCSCAN_API_KEY`)
+    pub contract: Option<ContractVerification>,
 }
 
@@ -0,0 +1,118 @@
+//! Rotating log files for long-running deployments
+
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Log file writer that starts a new file every UTC day or once the current one reaches `max_bytes`
+///
+/// Files are named `<dir>/<prefix>-<UTC time>.log`; only the newest `keep`
+/// files are kept. Wrap it in a `Mutex` to use it as a `tracing_subscriber` writer.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::logging::RotatingFile;
+/// use std::sync::Mutex;
+///
+/// # fn example() -> anyhow::Result<()> {
+/// let file = RotatingFile::new("logs", "bsc_streamer")?.keep(7);
+/// tracing_subscriber::fmt().json().with_writer(Mutex::new(file)).init();
+/// # Ok(())
+/// # }
+/// ```
+pub struct RotatingFile {
+    dir: PathBuf,
+    prefix: String,
+    max_bytes: u64,
+    keep: usize,
+    current: Option<(File, NaiveDate, u64)>,
+}
+
+impl RotatingFile {
+    /// Write into `dir` (created if missing); rotates daily or at 100 MiB and keeps 14 files by default
+    pub fn new(dir: impl AsRef<Path>, prefix: &str) -> Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            max_bytes: 100 * 1024 * 1024,
+            keep: 14,
+            current: None,
+        })
+    }
+
+    /// Start a new file once the current one reaches `bytes`
+    pub fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Number of files kept, including the current one
+    pub fn keep(mut self, count: usize) -> Self {
+        self.keep = count.max(1);
+        self
+    }
+
+    fn open(&mut self) -> io::Result<()> {
+        let stamp = Utc::now().format("%Y%m%dT%H%M%S");
+        let mut path = self.dir.join(format!("{}-{}.log", self.prefix, stamp));
+        let mut n = 1;
+        while path.exists() {
+            path = self.dir.join(format!("{}-{}-{}.log", self.prefix, stamp, n));
+            n += 1;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        self.current = Some((file, Utc::now().date_naive(), 0));
+        self.prune();
+        Ok(())
+    }
+
+    /// Delete the oldest files beyond `keep` (names sort by time)
+    fn prune(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&format!("{}-", self.prefix)) && name.ends_with(".log"))
+            })
+            .collect();
+        files.sort();
+        for path in &files[..files.len().saturating_sub(self.keep)] {
+            if let Err(e) = std::fs::remove_file(path) {
+                eprintln!("Failed to remove old log file {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let expired = self
+            .current
+            .as_ref()
+            .is_some_and(|(_, day, bytes)| *bytes >= self.max_bytes || *day != Utc::now().date_naive());
+        if expired || self.current.is_none() {
+            self.open()?;
+        }
+        let Some((file, _, bytes)) = self.current.as_mut() else {
+            return Err(io::Error::other("log file is not open"));
+        };
+        let written = file.write(buf)?;
+        *bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.current.as_mut() {
+            Some((file, _, _)) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,41 +1,853 @@
-use anyhow::Result;
-use bsc_streamer::{display::formatter::SwapFormatter, StreamerBuilder};
+use anyhow::{anyhow, bail, Result};
+use bsc_streamer::config::get_base_tokens;
+use bsc_streamer::core::creator::recent_launches;
+use bsc_streamer::core::filter::FirehoseFilter;
+use bsc_streamer::core::search::{search_tokens, SEARCH_LOOKBACK_BLOCKS};
+use bsc_streamer::display::formatter::{DisplayOptions, OutputMode, SwapFormatter};
+use bsc_streamer::logging::RotatingFile;
+use bsc_streamer::settings::Settings;
+use bsc_streamer::sinks::{FileFormat, FileSink, JsonLinesSink};
+use bsc_streamer::{
+    backfill_swaps, find_token_location, EventSink, Firehose, MigrationEvent, MultiTokenStreamer, Platform, PoolVersion, StreamEvent,
+    StreamerBuilder, TradeThreshold, TradeType, TrendingConfig,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use dotenv::dotenv;
-use std::env;
+use ethers::providers::{Provider, Ws};
+use ethers::types::Address;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Real-time BSC token swap streamer with Four.meme bonding curve support
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// TOML or YAML config file (environment variables and flags take precedence)
+    #[arg(long, short, env = "BSC_STREAMER_CONFIG", global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// BSC WebSocket endpoint
+    #[arg(long, env = "BSC_WSS_URL", global = true, hide_env_values = true)]
+    wss_url: Option<String>,
+
+    /// pretty, json (swaps with stats), jsonl (one event per line) or tui (needs `--features tui`)
+    #[arg(long, short, env = "OUTPUT_FORMAT", global = true, value_enum, ignore_case = true)]
+    output: Option<Output>,
+
+    /// Run unattended: JSON logs to rotating files in --log-dir, clean shutdown on SIGTERM/SIGINT
+    #[arg(long, env = "BSC_STREAMER_DAEMON", global = true)]
+    daemon: bool,
+
+    /// Log directory in daemon mode
+    #[arg(long, env = "LOG_DIR", global = true, default_value = "logs", value_name = "DIR")]
+    log_dir: PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Stream a token's swaps live (the default, with TOKEN_ADDRESS)
+    Watch(WatchArgs),
+    /// Show where a token trades, with honeypot, risk and LP lock checks
+    Discover {
+        /// Token address
+        token: String,
+    },
+    /// Find tokens by ticker or name among recent pair creations and Four.meme launches
+    Search {
+        /// Symbol (exact, case-insensitive) or part of the name
+        query: String,
+        /// How many blocks back to look
+        #[arg(long, default_value_t = SEARCH_LOOKBACK_BLOCKS)]
+        blocks: u64,
+    },
+    /// Print a token's historical swaps
+    Backfill(HistoryArgs),
+    /// List recent Four.meme launches
+    Launches {
+        /// How many blocks back to look
+        #[arg(long, default_value_t = 1_200)]
+        blocks: u64,
+    },
+    /// Stream every new PancakeSwap V2 pair and V3 pool, chain-wide
+    Pairs {
+        /// Skip pairs with less initial liquidity (USD; pairs without a priced base token are skipped too)
+        #[arg(long)]
+        min_liquidity: Option<f64>,
+    },
+    /// Stream swaps on any token, chain-wide, that pass the given filters
+    Swaps(SwapsArgs),
+    /// Report tokens entering the top list by short-window volume, trades and unique buyers
+    Trending {
+        /// Size of the trending list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Window in seconds
+        #[arg(long, default_value_t = 300)]
+        window: u64,
+        /// Trades needed within the window to be ranked
+        #[arg(long, default_value_t = 5)]
+        min_trades: usize,
+    },
+    /// Write a token's historical swaps to CSV or Parquet files
+    Export {
+        #[command(flatten)]
+        history: HistoryArgs,
+        /// Directory the files are written to
+        #[arg(long, default_value = "export")]
+        dir: PathBuf,
+        #[arg(long, value_enum, default_value = "csv")]
+        format: ExportFormat,
+    },
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Token address(es); TOKEN_ADDRESS (or the config's tokens) is used when no token is given
+    #[arg(value_delimiter = ',')]
+    tokens: Vec<String>,
+
+    /// Another token to watch (repeatable)
+    #[arg(long = "token", short = 't', value_name = "TOKEN")]
+    extra_tokens: Vec<String>,
+
+    /// File with one token address per line (`#` starts a comment)
+    #[arg(long, value_name = "PATH")]
+    token_file: Option<PathBuf>,
+
+    /// Where to watch the token (detected automatically by default; single token only)
+    #[arg(long, value_enum, default_value = "auto")]
+    platform: PlatformArg,
+
+    /// Ignore DEX pairs with less liquidity than this (USD)
+    #[arg(long, value_name = "USD")]
+    min_liquidity: Option<f64>,
+
+    /// Replay DEX swaps from this block before streaming live
+    #[arg(long, value_name = "BLOCK")]
+    start_block: Option<u64>,
+
+    /// Monitor only this pair/pool, skipping discovery (the token address becomes optional)
+    #[arg(long, value_name = "ADDRESS")]
+    pair: Option<String>,
+
+    /// Bonding curve contract of the token(s), for other launchpads or newer Four.meme deployments
+    #[arg(long, value_name = "ADDRESS")]
+    bonding_curve: Option<String>,
+
+    /// Only print swaps and migrations once this many blocks are built on top of theirs
+    #[arg(long, value_name = "BLOCKS", default_value_t = 0)]
+    confirmations: u64,
+
+    /// Swaps parsed at once per pair during volume spikes (still printed in order)
+    #[arg(long, value_name = "N")]
+    parse_concurrency: Option<usize>,
+}
+
+impl WatchArgs {
+    /// Positional, `--token` and file addresses, without duplicates
+    fn all_tokens(&self) -> Result<Vec<String>> {
+        let mut tokens = self.tokens.clone();
+        tokens.extend(self.extra_tokens.iter().cloned());
+        if let Some(ref path) = self.token_file {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("Failed to read token file {}: {}", path.display(), e))?;
+            tokens.extend(
+                contents
+                    .lines()
+                    .map(|line| line.split('#').next().unwrap_or_default().trim())
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string),
+            );
+        }
+
+        let mut unique: Vec<String> = Vec::new();
+        for token in tokens.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !unique.iter().any(|u| u.eq_ignore_ascii_case(token)) {
+                unique.push(token.to_string());
+            }
+        }
+        Ok(unique)
+    }
+}
+
+#[derive(Args)]
+struct HistoryArgs {
+    /// Token address
+    token: String,
+    /// First block to fetch
+    #[arg(long)]
+    from_block: u64,
+    /// Defaults to the latest block
+    #[arg(long)]
+    to_block: Option<u64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    Pretty,
+    Json,
+    Jsonl,
+    Tui,
+}
+
+#[derive(Args)]
+struct SwapsArgs {
+    /// Minimum trade value in USD
+    #[arg(long)]
+    min_usd: Option<f64>,
+    /// Only swaps priced in these base tokens (symbol or address; repeatable)
+    #[arg(long = "base", value_name = "TOKEN")]
+    base_tokens: Vec<String>,
+    /// Only PancakeSwap pools or only the Four.meme bonding curve
+    #[arg(long, value_enum, default_value = "auto")]
+    platform: PlatformArg,
+    /// Only buys or only sells
+    #[arg(long, value_enum)]
+    side: Option<SideArg>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SideArg {
+    Buy,
+    Sell,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PlatformArg {
+    Auto,
+    FourMeme,
+    Pancakeswap,
+    /// Bonding curve and DEX pairs at the same time
+    All,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+// How long sinks get to deliver queued events after a shutdown signal
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Exit code when the stream stopped but sinks did not flush in time
+const EXIT_UNFLUSHED: u8 = 2;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging (suppress ethers WebSocket errors)
-    tracing_subscriber::fmt()
-        .with_env_filter("bsc_streamer=info,ethers=warn,ethers_providers::rpc::transports::ws=off")
-        .init();
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            log::warn!("⚠️ [DAEMON] Sinks did not flush within {:?}", SHUTDOWN_TIMEOUT);
+            ExitCode::from(EXIT_UNFLUSHED)
+        }
+        Err(e) => {
+            log::error!("❌ [DAEMON] Exiting on error: {:#}", e);
+            eprintln!("Error: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-    // Load environment variables
+/// Run the CLI; Ok(false) means the stream stopped cleanly but sinks did not flush in time
+async fn run() -> Result<bool> {
+    // Load environment variables (before parsing, so they can fill in flags)
     dotenv().ok();
+    let cli = Cli::parse();
+    let settings = match cli.config {
+        Some(ref path) => Settings::load(path)?,
+        None => Settings::from_env()?,
+    };
+    let output = match (cli.output, settings.output.as_deref()) {
+        (Some(output), _) => output,
+        (None, Some(output)) => Output::from_str(output, true).map_err(|e| anyhow!("Invalid output in config: {}", e))?,
+        (None, None) => Output::Pretty,
+    };
 
-    let wss_url = env::var("BSC_WSS_URL").expect("BSC_WSS_URL must be set in .env file");
-    let token_address = env::var("TOKEN_ADDRESS").expect("TOKEN_ADDRESS must be set in .env file");
+    // Initialize logging (suppress ethers WebSocket errors); keep stdout clean for JSON output
+    let log_filter = "bsc_streamer=info,ethers=warn,ethers_providers::rpc::transports::ws=off";
+    if cli.daemon && output == Output::Tui {
+        bail!("--output tui needs a terminal and cannot run with --daemon");
+    }
+    match output {
+        // Daemons log structured JSON to files, leaving stdout to the stream itself
+        _ if cli.daemon => tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(log_filter)
+            .with_writer(Mutex::new(RotatingFile::new(&cli.log_dir, "bsc_streamer")?))
+            .init(),
+        // Anything written to the terminal would tear the dashboard
+        Output::Tui => tracing_subscriber::fmt()
+            .with_env_filter(log_filter)
+            .with_writer(std::io::sink)
+            .init(),
+        Output::Json | Output::Jsonl => tracing_subscriber::fmt()
+            .with_env_filter(log_filter)
+            .with_writer(std::io::stderr)
+            .init(),
+        Output::Pretty => tracing_subscriber::fmt().with_env_filter(log_filter).init(),
+    }
 
-    println!("\n🦀 BSC Token Streamer");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+    let Some(wss_url) = cli.wss_url.as_deref().or(settings.wss_url.as_deref()) else {
+        bail!("Set BSC_WSS_URL (in .env, the environment or the config file) or pass --wss-url");
+    };
 
-    // Create formatter for displaying swaps
-    let formatter = SwapFormatter::new();
+    match cli.command {
+        Some(Command::Watch(args)) => watch(wss_url, args, output, &settings).await,
+        None => {
+            // Without a subcommand, watch TOKEN_ADDRESS (or the config's tokens) like earlier versions did
+            if settings.tokens.is_empty() {
+                bail!("Pass a subcommand (see --help) or set TOKEN_ADDRESS to watch a token");
+            }
+            let args = WatchArgs {
+                tokens: Vec::new(),
+                extra_tokens: Vec::new(),
+                token_file: None,
+                platform: PlatformArg::Auto,
+                min_liquidity: None,
+                start_block: None,
+                pair: None,
+                bonding_curve: None,
+                confirmations: 0,
+                parse_concurrency: None,
+            };
+            watch(wss_url, args, output, &settings).await
+        }
+        Some(Command::Discover { token }) => discover(wss_url, &token, output).await.map(|()| true),
+        Some(Command::Search { query, blocks }) => search(wss_url, &query, blocks, output).await.map(|()| true),
+        Some(Command::Backfill(args)) => backfill(wss_url, args, output).await.map(|()| true),
+        Some(Command::Launches { blocks }) => launches(wss_url, blocks, output).await.map(|()| true),
+        Some(Command::Pairs { min_liquidity }) => pairs(wss_url, min_liquidity, output).await,
+        Some(Command::Swaps(args)) => swaps(wss_url, args, output).await,
+        Some(Command::Trending { top, window, min_trades }) => {
+            let config = TrendingConfig {
+                window: Duration::from_secs(window),
+                top_n: top,
+                min_trades,
+                ..Default::default()
+            };
+            trending(wss_url, config, output).await
+        }
+        Some(Command::Export { history, dir, format }) => export(wss_url, history, dir, format).await.map(|()| true),
+    }
+}
+
+/// Wait for Ctrl+C (SIGINT) or, on Unix, SIGTERM; returns the signal's name
+async fn shutdown_signal() -> Result<&'static str> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut sigterm = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result.map(|()| "SIGINT").map_err(Into::into),
+            _ = sigterm.recv() => Ok("SIGTERM"),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await?;
+        Ok("SIGINT")
+    }
+}
 
-    // Build and start streamer with auto-detection
-    StreamerBuilder::from_wss(&wss_url)
-        .await?
-        .token_address(&token_address)
-        .auto_detect() // Automatically detect platform and handle migration
-        .on_swap(move |swap| {
-            formatter.display(&swap);
+async fn connect(wss_url: &str) -> Result<Arc<Provider<Ws>>> {
+    Ok(Arc::new(Provider::<Ws>::connect(wss_url).await?))
+}
+
+fn formatter(output: Output) -> SwapFormatter {
+    // NO_COLOR / NO_EMOJI keep the formatted view readable in CI and log aggregators
+    let mode = if output == Output::Json { OutputMode::Json } else { OutputMode::Pretty };
+    SwapFormatter::with_mode(mode).with_display(DisplayOptions::from_env())
+}
+
+fn print_banner(output: Output, display: DisplayOptions) {
+    if output == Output::Pretty && display.emoji {
+        println!("\n🦀 BSC Token Streamer");
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+    } else if output == Output::Pretty {
+        println!("\nBSC Token Streamer");
+        println!("{}\n", "=".repeat(60));
+    }
+}
+
+async fn watch(wss_url: &str, args: WatchArgs, output: Output, settings: &Settings) -> Result<bool> {
+    let mut tokens = args.all_tokens()?;
+    if tokens.is_empty() {
+        tokens = settings.tokens.clone();
+    }
+    match (tokens.as_slice(), &args.pair) {
+        ([], None) => bail!("No token to watch: pass an address, --token, --token-file or --pair"),
+        ([], Some(_)) => watch_token(wss_url, None, &args, output, settings).await,
+        ([token], _) => watch_token(wss_url, Some(token), &args, output, settings).await,
+        (_, Some(_)) => bail!("--pair only applies to a single token"),
+        _ => watch_tokens(wss_url, &tokens, &args, output, settings).await,
+    }
+}
+
+async fn watch_token(wss_url: &str, token: Option<&str>, args: &WatchArgs, output: Output, settings: &Settings) -> Result<bool> {
+    let display = DisplayOptions::from_env();
+
+    let mut builder = StreamerBuilder::from_wss(wss_url).await?;
+    if let Some(token) = token {
+        builder = builder.token_address(token);
+    }
+    let mut builder = settings.apply(builder)?;
+    builder = match args.platform {
+        PlatformArg::Auto => builder.auto_detect(), // Automatically detect platform and handle migration
+        PlatformArg::FourMeme => builder.platform(Platform::FourMemeBondingCurve),
+        PlatformArg::Pancakeswap => builder.platform(Platform::PancakeSwap),
+        PlatformArg::All => builder.monitor_all(),
+    };
+    if let Some(ref pair) = args.pair {
+        builder = builder.pair_address(pair);
+    }
+    if let Some(ref curve) = args.bonding_curve {
+        builder = builder.bonding_curve_address(curve);
+    }
+    if args.confirmations > 0 {
+        builder = builder.confirmations(args.confirmations);
+    }
+    if let Some(n) = args.parse_concurrency {
+        builder = builder.parse_concurrency(n);
+    }
+    if let Some(usd) = args.min_liquidity {
+        builder = builder.min_liquidity(usd);
+    }
+    if let Some(block) = args.start_block {
+        builder = builder.start_block(block);
+    }
+
+    let handle = match output {
+        #[cfg(feature = "tui")]
+        Output::Tui => {
+            let dashboard = bsc_streamer::display::tui::Dashboard::new();
+            let handle = builder.sink(dashboard.clone()).on_swap(|_| {}).start().await?;
+            dashboard.run().await?;
+            return Ok(handle.shutdown(SHUTDOWN_TIMEOUT).await);
+        }
+        #[cfg(not(feature = "tui"))]
+        Output::Tui => bail!("--output tui needs a build with `--features tui`"),
+        Output::Jsonl => builder.sink(JsonLinesSink::stdout()).on_swap(|_| {}).start().await?,
+        Output::Json | Output::Pretty => {
+            print_banner(output, display);
+
+            // Create formatter for displaying swaps
+            let formatter = formatter(output);
+            builder
+                .on_swap(move |swap| {
+                    formatter.display_sync(&swap);
+                })
+                .start()
+                .await?
+        }
+    };
+
+    // Keep running until asked to stop, then let sinks flush
+    let signal = shutdown_signal().await?;
+    log::info!("🛑 [DAEMON] {} received, shutting down", signal);
+    if output == Output::Pretty {
+        println!("\n{}Shutting down...", if display.emoji { "👋 " } else { "" });
+    }
+    Ok(handle.shutdown(SHUTDOWN_TIMEOUT).await)
+}
+
+/// Watch several tokens with one MultiTokenStreamer (always auto-detected)
+async fn watch_tokens(wss_url: &str, tokens: &[String], args: &WatchArgs, output: Output, settings: &Settings) -> Result<bool> {
+    if !matches!(args.platform, PlatformArg::Auto) {
+        bail!("--platform only applies to a single token; several tokens are always auto-detected");
+    }
+    let display = DisplayOptions::from_env();
+
+    let mut options = settings.stream_options()?;
+    if args.min_liquidity.is_some() {
+        options.min_liquidity_usd = args.min_liquidity;
+    }
+    if args.start_block.is_some() {
+        options.start_block = args.start_block;
+    }
+    if let Some(ref curve) = args.bonding_curve {
+        let curve: Address = curve.parse().map_err(|_| anyhow!("Invalid bonding curve address: {}", curve))?;
+        for token in tokens {
+            options.bonding_curves.insert(token.parse().map_err(|_| anyhow!("Invalid token address: {}", token))?, curve);
+        }
+    }
+    options.confirmations = args.confirmations;
+    options.parse_concurrency = args.parse_concurrency;
+    let streamer = MultiTokenStreamer::with_options(connect(wss_url).await?, options);
+
+    #[cfg(feature = "tui")]
+    if output == Output::Tui {
+        let dashboard = bsc_streamer::display::tui::Dashboard::new();
+        for token in tokens {
+            let (swaps, migrations) = (dashboard.clone(), dashboard.clone());
+            streamer
+                .add_token(
+                    token,
+                    move |swap| swaps.record_swap(&swap),
+                    Some(move |migration: MigrationEvent| migrations.record_migration(&migration)),
+                )
+                .await?;
+        }
+        dashboard.run().await?;
+        return Ok(streamer.shutdown(SHUTDOWN_TIMEOUT).await);
+    }
+    #[cfg(not(feature = "tui"))]
+    if output == Output::Tui {
+        bail!("--output tui needs a build with `--features tui`");
+    }
+
+    print_banner(output, display);
+    let formatter = Arc::new(formatter(output));
+    for token in tokens {
+        let formatter = formatter.clone();
+        streamer
+            .add_token(
+                token,
+                move |swap| match output {
+                    Output::Jsonl => print_jsonl(&StreamEvent::Swap(Box::new(swap))),
+                    _ => formatter.display_sync(&swap),
+                },
+                Some(move |migration: MigrationEvent| {
+                    if output == Output::Jsonl {
+                        print_jsonl(&StreamEvent::Migration(Box::new(migration)));
+                    }
+                }),
+            )
+            .await?;
+    }
+    log::info!("👀 [CLI] Watching {} tokens", tokens.len());
+
+    // Keep running until asked to stop, then let sinks flush
+    let signal = shutdown_signal().await?;
+    log::info!("🛑 [DAEMON] {} received, shutting down", signal);
+    if output == Output::Pretty {
+        println!("\n{}Shutting down...", if display.emoji { "👋 " } else { "" });
+    }
+    Ok(streamer.shutdown(SHUTDOWN_TIMEOUT).await)
+}
+
+fn print_jsonl(event: &StreamEvent) {
+    match serde_json::to_string(event) {
+        Ok(line) => println!("{}", line),
+        Err(e) => log::warn!("⚠️ [CLI] Failed to serialize {} event: {}", event.as_str(), e),
+    }
+}
+
+async fn discover(wss_url: &str, token: &str, output: Output) -> Result<()> {
+    let location = find_token_location(connect(wss_url).await?, token).await?;
+    if output != Output::Pretty {
+        println!("{}", serde_json::to_string(&location)?);
+        return Ok(());
+    }
+
+    println!("Token {}", token);
+    if let Some(ref metadata) = location.metadata {
+        println!("  {} ({}), {} decimals", metadata.name, metadata.symbol, metadata.decimals);
+        if !metadata.metadata_reliable {
+            println!("  ⚠️  Non-standard ERC20 metadata: name, symbol or decimals may be wrong");
+        }
+        if let Some(ref proxy) = metadata.proxy {
+            println!("  🧬 {} proxy, implementation {:?}", proxy.kind.as_str(), proxy.implementation);
+        }
+        if let Some(supply) = metadata.total_supply {
+            println!("  Total supply: {}", ethers::utils::format_units(supply, metadata.decimals as u32)?);
+        }
+        if let Some(ref profile) = metadata.profile {
+            let links = [("Logo", &profile.logo_url), ("Website", &profile.website), ("Twitter", &profile.twitter), ("Telegram", &profile.telegram)];
+            for (label, url) in links.iter().filter_map(|(label, url)| url.as_ref().map(|url| (label, url))) {
+                println!("  {}: {}", label, url);
+            }
+        }
+    }
+    println!("  On bonding curve: {}", location.on_bonding_curve);
+    if let Some(ref curve) = location.bonding_curve {
+        let price = match curve.price_usd {
+            Some(usd) => format!("${:.10}", usd),
+            None => format!("{:.10} {}", curve.price, curve.quote_symbol),
+        };
+        println!(
+            "    Progress {:.2}% ({:.4} / {:.4} {} raised), price {}",
+            curve.progress_pct, curve.funds_raised, curve.funds_target, curve.quote_symbol, price
+        );
+        if let Some(creator) = curve.creator {
+            println!("    Creator {:?}", creator);
+        }
+    }
+    println!("  DEX pairs: {}", location.dex_pairs);
+    for pair in &location.pairs {
+        let version = if pair.info.is_v3 { "V3" } else { "V2" };
+        let price = match (pair.price, pair.price_usd) {
+            (_, Some(usd)) => format!("${:.8}", usd),
+            (Some(price), None) => format!("{:.8} {}", price, pair.info.base_token_symbol),
+            (None, None) => "-".to_string(),
+        };
+        let liquidity = pair.liquidity_usd.map(|usd| format!("${:.0}", usd)).unwrap_or_else(|| "-".to_string());
+        let swaps = pair.recent_swaps.map(|n| n.to_string()).unwrap_or_else(|| "-".to_string());
+        println!(
+            "    {} {:?} vs {} ({:.2}% fee): price {}, liquidity {}, {} recent swaps",
+            version,
+            pair.info.pair_address,
+            pair.info.base_token_symbol,
+            pair.info.fee_tier as f64 / 10_000.0,
+            price,
+            liquidity,
+            swaps
+        );
+    }
+    if let Some(ref primary) = location.primary_pair {
+        println!(
+            "  Primary pair {:?} vs {} (score {:.2}: {:.0}% of liquidity, {:.0}% of swaps in {} blocks)",
+            primary.pair_address,
+            primary.base_token_symbol,
+            primary.score,
+            primary.liquidity_share * 100.0,
+            primary.activity_share * 100.0,
+            primary.criteria.activity_blocks
+        );
+    }
+    if location.platforms.is_empty() {
+        println!("  Not found on any supported platform");
+    }
+    for platform in &location.platforms {
+        println!("  Trading on {}", platform.as_str());
+    }
+    if let Some(ref honeypot) = location.honeypot {
+        match honeypot.reason {
+            Some(ref reason) => println!("  Honeypot risk: {}", reason),
+            None => match honeypot.round_trip_loss_pct {
+                Some(loss) => println!("  Sellable ({:.1}% round-trip loss, {} buys / {} sells recently)", loss, honeypot.recent_buys, honeypot.recent_sells),
+                None => println!("  Sellable ({} buys / {} sells recently)", honeypot.recent_buys, honeypot.recent_sells),
+            },
+        }
+    }
+    if let Some(ref risk) = location.risk {
+        println!("  Risk score: {}/100", risk.score);
+        for flag in &risk.flags {
+            println!("    - {}: {}", flag.category.as_str(), flag.signature);
+        }
+    }
+    for lock in &location.lp_locks {
+        println!(
+            "  LP {:?}: {:.2}% burned, {:.2}% locked",
+            lock.pair_address, lock.burned_pct, lock.locked_pct
+        );
+    }
+    Ok(())
+}
+
+async fn search(wss_url: &str, query: &str, blocks: u64, output: Output) -> Result<()> {
+    let candidates = search_tokens(connect(wss_url).await?, query, blocks).await?;
+    for candidate in &candidates {
+        if output == Output::Pretty {
+            let liquidity = candidate.liquidity_usd.map(|usd| format!("${:.0}", usd)).unwrap_or_else(|| "-".to_string());
+            println!(
+                "{:<12} {:<24} {:?} liquidity {:<10} {} pair(s){}",
+                candidate.symbol,
+                candidate.name,
+                candidate.address,
+                liquidity,
+                candidate.pairs.len(),
+                if candidate.four_meme { ", Four.meme launch" } else { "" }
+            );
+        } else {
+            println!("{}", serde_json::to_string(candidate)?);
+        }
+    }
+    if output == Output::Pretty {
+        println!("{} match(es) for \"{}\" in the last {} blocks", candidates.len(), query, blocks);
+    }
+    Ok(())
+}
+
+async fn backfill(wss_url: &str, args: HistoryArgs, output: Output) -> Result<()> {
+    let swaps = backfill_swaps(connect(wss_url).await?, &args.token, args.from_block, args.to_block).await?;
+    match output {
+        Output::Jsonl => {
+            let sink = JsonLinesSink::stdout();
+            for swap in swaps {
+                sink.handle(&StreamEvent::Swap(Box::new(swap))).await?;
+            }
+            sink.close().await?;
+        }
+        Output::Tui => bail!("--output tui is only supported by watch"),
+        Output::Json | Output::Pretty => {
+            // Replaying through the formatter also builds up its price and volume stats
+            let formatter = formatter(output);
+            for swap in &swaps {
+                formatter.display(swap).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn launches(wss_url: &str, blocks: u64, output: Output) -> Result<()> {
+    let launches = recent_launches(connect(wss_url).await?, blocks).await?;
+    for launch in &launches {
+        if output == Output::Pretty {
+            let time = chrono::DateTime::from_timestamp(launch.launch_time as i64, 0)
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            println!("{} {:<12} {:<24} {:?} by {:?}", time, launch.symbol, launch.name, launch.token_address, launch.creator);
+        } else {
+            println!("{}", serde_json::to_string(launch)?);
+        }
+    }
+    if output == Output::Pretty {
+        println!("{} launch(es) in the last {} blocks", launches.len(), blocks);
+    }
+    Ok(())
+}
+
+/// Print new pairs until a shutdown signal
+async fn pairs(wss_url: &str, min_liquidity: Option<f64>, output: Output) -> Result<bool> {
+    if output == Output::Tui {
+        bail!("--output tui is only supported by watch");
+    }
+    let handle = Firehose::new(connect(wss_url).await?)
+        .on_new_pair(move |pair| {
+            if min_liquidity.is_some_and(|min| pair.liquidity_usd.is_none_or(|usd| usd < min)) {
+                return;
+            }
+            if output != Output::Pretty {
+                match serde_json::to_string(&pair) {
+                    Ok(line) => println!("{}", line),
+                    Err(e) => log::warn!("⚠️ [CLI] Failed to serialize new pair: {}", e),
+                }
+                return;
+            }
+            let liquidity = match (pair.base_liquidity, pair.liquidity_usd) {
+                (_, Some(usd)) => format!("${:.0}", usd),
+                (Some(base), None) => format!("{:.4} {}", base, pair.base_token_symbol.as_deref().unwrap_or("")),
+                (None, None) => "no liquidity yet".to_string(),
+            };
+            println!(
+                "{} {:<3} {:<12} / {:<6} {:?} ({})",
+                chrono::Utc::now().format("%H:%M:%S"),
+                if pair.pool.version == PoolVersion::V3 { "V3" } else { "V2" },
+                pair.token_symbol,
+                pair.base_token_symbol.as_deref().unwrap_or("?"),
+                pair.pair_address,
+                liquidity
+            );
+        })
+        .start()
+        .await?;
+
+    let signal = shutdown_signal().await?;
+    log::info!("🛑 [DAEMON] {} received, shutting down", signal);
+    handle.stop();
+    Ok(true)
+}
+
+/// Print chain-wide swaps passing the filters until a shutdown signal
+async fn swaps(wss_url: &str, args: SwapsArgs, output: Output) -> Result<bool> {
+    if output == Output::Tui {
+        bail!("--output tui is only supported by watch");
+    }
+    let base_tokens = get_base_tokens();
+    let filter = FirehoseFilter {
+        min_trade: args.min_usd.map(TradeThreshold::Usd),
+        trade_type: args.side.map(|side| match side {
+            SideArg::Buy => TradeType::Buy,
+            SideArg::Sell => TradeType::Sell,
+        }),
+        base_tokens: args
+            .base_tokens
+            .iter()
+            .map(|token| match base_tokens.iter().find(|(symbol, _)| symbol.eq_ignore_ascii_case(token)) {
+                Some((_, address)) => Ok(*address),
+                None => token.parse().map_err(|_| anyhow!("Unknown base token {} (use a symbol from the config or an address)", token)),
+            })
+            .collect::<Result<_>>()?,
+        platforms: match args.platform {
+            PlatformArg::Auto | PlatformArg::All => Vec::new(),
+            PlatformArg::FourMeme => vec![Platform::FourMemeBondingCurve],
+            PlatformArg::Pancakeswap => vec![Platform::PancakeSwap],
+        },
+        versions: Vec::new(),
+    };
+
+    print_banner(output, DisplayOptions::from_env());
+    let formatter = formatter(output);
+    let handle = Firehose::new(connect(wss_url).await?)
+        .on_swap(filter, move |swap| match output {
+            Output::Jsonl => print_jsonl(&StreamEvent::Swap(Box::new(swap))),
+            _ => formatter.display_sync(&swap),
         })
         .start()
         .await?;
 
-    // Keep running
-    tokio::signal::ctrl_c().await?;
-    println!("\n👋 Shutting down...");
+    let signal = shutdown_signal().await?;
+    log::info!("🛑 [DAEMON] {} received, shutting down", signal);
+    handle.stop();
+    Ok(true)
+}
+
+/// Print tokens entering the trending list until a shutdown signal
+async fn trending(wss_url: &str, config: TrendingConfig, output: Output) -> Result<bool> {
+    if output == Output::Tui {
+        bail!("--output tui is only supported by watch");
+    }
+    let handle = Firehose::new(connect(wss_url).await?)
+        .on_trending(config, move |token, metrics| {
+            if output == Output::Pretty {
+                println!(
+                    "{} #{:<3} {:<12} {:?}  ${:<12.0} {} trades ({} buys / {} sells), {} buyers",
+                    chrono::Utc::now().format("%H:%M:%S"),
+                    metrics.rank,
+                    metrics.symbol,
+                    token,
+                    metrics.volume_usd,
+                    metrics.trades,
+                    metrics.buys,
+                    metrics.sells,
+                    metrics.unique_buyers
+                );
+                return;
+            }
+            match serde_json::to_value(&metrics) {
+                Ok(mut value) => {
+                    value["token_address"] = serde_json::json!(token);
+                    println!("{}", value);
+                }
+                Err(e) => log::warn!("⚠️ [CLI] Failed to serialize trending metrics: {}", e),
+            }
+        })
+        .start()
+        .await?;
+    if output == Output::Pretty {
+        println!("Ranking swaps over the last {}s; tokens are printed when they enter the top {}", config.window.as_secs(), config.top_n);
+    }
+
+    let signal = shutdown_signal().await?;
+    log::info!("🛑 [DAEMON] {} received, shutting down", signal);
+    handle.stop();
+    Ok(true)
+}
+
+async fn export(wss_url: &str, args: HistoryArgs, dir: PathBuf, format: ExportFormat) -> Result<()> {
+    let format = match format {
+        ExportFormat::Csv => FileFormat::Csv,
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => FileFormat::Parquet,
+        #[cfg(not(feature = "parquet"))]
+        ExportFormat::Parquet => bail!("--format parquet needs a build with `--features parquet`"),
+    };
+    let swaps = backfill_swaps(connect(wss_url).await?, &args.token, args.from_block, args.to_block).await?;
 
+    // One file regardless of size or how long the export takes
+    let sink = FileSink::new(&dir, format)?.rotate_at_bytes(u64::MAX).rotate_every(std::time::Duration::MAX);
+    let count = swaps.len();
+    for swap in swaps {
+        sink.handle(&StreamEvent::Swap(Box::new(swap))).await?;
+    }
+    sink.close().await?;
+    log::info!("💾 [EXPORT] Wrote {} swap(s) to {}", count, dir.display());
     Ok(())
 }
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 
+use crate::core::options::StreamOptions;
 use crate::core::streamer::SwapStreamer;
 use crate::types::{MigrationEvent, SwapEvent};
 
@@ -21,6 +22,7 @@ pub struct TokenInfo {
 pub struct MultiTokenStreamer<M> {
     provider: Arc<M>,
     tokens: Arc<RwLock<HashMap<Address, CancellationToken>>>,
+    options: StreamOptions,
 }
 
 impl<M> MultiTokenStreamer<M>
@@ -29,9 +31,17 @@ where
 {
     /// Create a new multi-token streamer
     pub fn new(provider: Arc<M>) -> Self {
+        Self::with_options(provider, StreamOptions::default())
+    }
+
+    /// Create a multi-token streamer applying `options` (filter, quote currency, liquidity threshold, ...) to every token
+    ///
+    /// Each token runs its own streamer, so sinks in `options` get one worker per token.
+    pub fn with_options(provider: Arc<M>, options: StreamOptions) -> Self {
         Self {
             provider,
             tokens: Arc::new(RwLock::new(HashMap::new())),
+            options,
         }
     }
 
@@ -44,14 +54,17 @@ where
     ///
     /// # Example
     /// ```rust,no_run
-    /// # use bsc_streamer::MultiTokenStreamer;
+    /// # use bsc_streamer::{MigrationEvent, MultiTokenStreamer};
+    /// # use ethers::providers::{Provider, Ws};
+    /// # use std::sync::Arc;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let provider = Arc::new(Provider::<Ws>::connect("wss://bsc.publicnode.com").await?);
     /// let streamer = MultiTokenStreamer::new(provider);
     /// 
     /// streamer.add_token(
     ///     "0x...",
     ///     |swap| println!("Swap: {:?}", swap),
-    ///     Some(|migration| println!("Migration: {:?}", migration))
+    ///     Some(|migration: MigrationEvent| println!("Migration: {:?}", migration))
     /// ).await?;
     /// # Ok(())
     /// # }
@@ -62,12 +75,50 @@ where
         swap_callback: F,
         migration_callback: Option<G>,
     ) -> Result<()>
+    where
+        F: Fn(SwapEvent) + Send + Sync + 'static,
+        G: Fn(MigrationEvent) + Send + Sync + 'static,
+        M::Provider: ethers::providers::PubsubClient,
+    {
+        self.add_token_with_bonding_curve(token_address, None, swap_callback, migration_callback).await
+    }
+
+    /// Add a token trading on a different bonding curve contract than the default Four.meme one
+    ///
+    /// `bonding_curve_address` of `None` behaves like `add_token`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use bsc_streamer::{MigrationEvent, MultiTokenStreamer};
+    /// # use ethers::providers::{Provider, Ws};
+    /// # use std::sync::Arc;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let provider = Arc::new(Provider::<Ws>::connect("wss://bsc.publicnode.com").await?);
+    /// let streamer = MultiTokenStreamer::new(provider);
+    ///
+    /// streamer.add_token_with_bonding_curve(
+    ///     "0x...",
+    ///     Some("0x..."),
+    ///     |swap| println!("Swap: {:?}", swap),
+    ///     None::<fn(MigrationEvent)>,
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_token_with_bonding_curve<F, G>(
+        &self,
+        token_address: &str,
+        bonding_curve_address: Option<&str>,
+        swap_callback: F,
+        migration_callback: Option<G>,
+    ) -> Result<()>
     where
         F: Fn(SwapEvent) + Send + Sync + 'static,
         G: Fn(MigrationEvent) + Send + Sync + 'static,
         M::Provider: ethers::providers::PubsubClient,
     {
         let address = Address::from_str(token_address)?;
+        let bonding_curve = bonding_curve_address.map(Address::from_str).transpose()?;
 
         // Check if already monitoring
         {
@@ -90,9 +141,13 @@ where
         let provider_clone = self.provider.clone();
         let cancel_token_clone = cancel_token.clone();
         let tokens_clone = self.tokens.clone();
+        let mut options = self.options.clone();
+        if let Some(bonding_curve) = bonding_curve {
+            options.bonding_curves.insert(address, bonding_curve);
+        }
 
         tokio::spawn(async move {
-            let mut streamer = SwapStreamer::new(provider_clone);
+            let mut streamer = SwapStreamer::with_options(provider_clone, options);
             // Format address as hex string with 0x prefix
             let address_str = format!("{:#x}", address);
 
@@ -132,7 +187,10 @@ where
     /// # Example
     /// ```rust,no_run
     /// # use bsc_streamer::MultiTokenStreamer;
+    /// # use ethers::providers::{Provider, Ws};
+    /// # use std::sync::Arc;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let provider = Arc::new(Provider::<Ws>::connect("wss://bsc.publicnode.com").await?);
     /// # let streamer = MultiTokenStreamer::new(provider);
     /// streamer.remove_token("0x...").await?;
     /// # Ok(())
@@ -168,7 +226,10 @@ where
     /// # Example
     /// ```rust,no_run
     /// # use bsc_streamer::MultiTokenStreamer;
+    /// # use ethers::providers::{Provider, Ws};
+    /// # use std::sync::Arc;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let provider = Arc::new(Provider::<Ws>::connect("wss://bsc.publicnode.com").await?);
     /// # let streamer = MultiTokenStreamer::new(provider);
     /// let tokens = streamer.list_tokens().await;
     /// println!("Monitoring {} tokens", tokens.len());
@@ -200,6 +261,14 @@ where
             token.cancel();
         }
     }
+
+    /// Stop all tokens and wait up to `timeout` for sinks to deliver queued events and close
+    ///
+    /// Returns false if a sink did not finish in time.
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> bool {
+        self.stop_all().await;
+        tokio::time::timeout(timeout, self.options.sinks.join()).await.is_ok()
+    }
 }
 
 impl<M> Clone for MultiTokenStreamer<M> {
@@ -207,6 +276,7 @@ impl<M> Clone for MultiTokenStreamer<M> {
         Self {
             provider: self.provider.clone(),
             tokens: self.tokens.clone(),
+            options: self.options.clone(),
         }
     }
 }
@@ -0,0 +1,154 @@
+//! Protobuf messages mirroring the stream events (requires the `proto` feature)
+//!
+//! Generated from `proto/bsc_streamer.proto`. Convert events with `From` and
+//! encode them with `prost::Message`; the gRPC service (feature `grpc`) sends
+//! the same messages.
+//!
+//! # Example
+//! ```rust,no_run
+//! use bsc_streamer::proto;
+//! use bsc_streamer::StreamerBuilder;
+//! use prost::Message;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+//!     .await?
+//!     .token_address("0x...")
+//!     .auto_detect()
+//!     .on_swap(|swap| {
+//!         let bytes = proto::Swap::from(&swap).encode_to_vec();
+//!         println!("{} bytes", bytes.len());
+//!     })
+//!     .start()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::candles::swap_time;
+use crate::types::{self, MigrationEvent, StreamEvent, SwapEvent, TokenInfo};
+
+#[allow(clippy::large_enum_variant)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/bsc_streamer.v1.rs"));
+}
+
+pub use generated::*;
+
+fn hex(value: impl std::fmt::Debug) -> String {
+    format!("{:?}", value)
+}
+
+impl From<&StreamEvent> for Event {
+    fn from(event: &StreamEvent) -> Self {
+        let event = match event {
+            StreamEvent::Swap(swap) => event::Event::Swap(swap.as_ref().into()),
+            StreamEvent::Migration(migration) => event::Event::Migration(migration.as_ref().into()),
+            StreamEvent::Candle(candle) => event::Event::Candle(candle.into()),
+        };
+        Event { event: Some(event) }
+    }
+}
+
+impl From<&TokenInfo> for Token {
+    fn from(info: &TokenInfo) -> Self {
+        Token {
+            address: hex(info.address),
+            symbol: info.symbol.clone(),
+            amount: info.amount.clone(),
+            decimals: info.decimals as u32,
+            amount_raw: format!("{:#x}", info.amount_raw),
+        }
+    }
+}
+
+impl From<&SwapEvent> for Swap {
+    fn from(swap: &SwapEvent) -> Self {
+        Swap {
+            transaction_hash: hex(swap.transaction_hash),
+            block_number: swap.block_number,
+            transaction_index: swap.transaction_index,
+            log_index: swap.log_index,
+            time: swap_time(swap),
+            platform: swap.platform.as_str().to_string(),
+            trade_type: match swap.trade_type {
+                types::TradeType::Buy => TradeType::Buy,
+                types::TradeType::Sell => TradeType::Sell,
+            } as i32,
+            token: Some((&swap.token).into()),
+            base_token: Some((&swap.base_token).into()),
+            price: swap.price.value,
+            price_usd: swap.price_usd,
+            usd_value: swap.usd_value,
+            sender: hex(swap.sender),
+            recipient: hex(swap.recipient),
+            trader: swap.trader.map(hex),
+            pair_address: swap.pair_address.map(hex),
+            bonding_curve_address: swap.bonding_curve_address.map(hex),
+            schema_version: swap.schema_version,
+            timestamp: swap.timestamp.clone(),
+            price_base_token: swap.price.base_token.clone(),
+            route_via: swap.route_via.map(|router| router.as_str().to_string()),
+            sender_label: swap.sender_label.as_ref().map(|label| label.name.clone()),
+            recipient_label: swap.recipient_label.as_ref().map(|label| label.name.clone()),
+            trader_label: swap.trader_label.as_ref().map(|label| label.name.clone()),
+            pool: swap.pool.as_ref().map(|pool| Pool {
+                version: pool.version.as_str().to_string(),
+                fee_tier: pool.fee_tier,
+                tick_spacing: pool.tick_spacing,
+            }),
+            effective_tax_pct: swap.effective_tax_pct,
+            market_cap_usd: swap.market_cap.as_ref().map(|cap| cap.market_cap_usd),
+            fdv_usd: swap.market_cap.as_ref().map(|cap| cap.fdv_usd),
+            gas_used: swap.gas.as_ref().map(|gas| format!("{:#x}", gas.gas_used)),
+            fee_bnb: swap.gas.as_ref().map(|gas| gas.fee_bnb.clone()),
+            received_at_ms: swap.latency.map(|latency| latency.received_at_ms),
+            block_to_receive_ms: swap.latency.and_then(|latency| latency.block_to_receive_ms),
+            block_to_dispatch_ms: swap.latency.and_then(|latency| latency.block_to_dispatch_ms),
+        }
+    }
+}
+
+impl From<&MigrationEvent> for Migration {
+    fn from(migration: &MigrationEvent) -> Self {
+        Migration {
+            token_address: hex(migration.token_address),
+            from_platform: migration.from_platform.as_str().to_string(),
+            to_platform: migration.to_platform.as_str().to_string(),
+            transaction_hash: hex(migration.transaction_hash),
+            block_number: migration.block_number,
+            timestamp: migration.timestamp.clone(),
+            pair_addresses: migration.pair_addresses.iter().map(hex).collect(),
+            schema_version: migration.schema_version,
+            pair_count: migration.pair_count as u64,
+            lp_burned_pct: migration.lp_lock.as_ref().map(|lock| lock.burned_pct),
+            lp_locked_pct: migration.lp_lock.as_ref().map(|lock| lock.locked_pct),
+            is_honeypot: migration.honeypot.as_ref().map(|report| report.is_honeypot),
+            risk_score: migration.risk.as_ref().map(|report| report.score as u32),
+            is_proxy: migration.risk.as_ref().map(|report| report.is_proxy),
+        }
+    }
+}
+
+impl From<&types::Candle> for Candle {
+    fn from(candle: &types::Candle) -> Self {
+        Candle {
+            market: hex(candle.market),
+            token_address: hex(candle.token_address),
+            base_token: candle.base_token.clone(),
+            interval: candle.interval.as_str().to_string(),
+            open_time: candle.open_time,
+            close_time: candle.close_time,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume_token: candle.volume_token,
+            volume_base: candle.volume_base,
+            volume_usd: candle.volume_usd,
+            trades: candle.trades,
+            buys: candle.buys,
+            sells: candle.sells,
+        }
+    }
+}
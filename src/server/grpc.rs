@@ -0,0 +1,80 @@
+use anyhow::Result;
+use futures::stream::{self, Stream};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Request, Response, Status};
+
+use super::{EventHub, TokenFilter};
+pub use crate::proto;
+use crate::types::StreamEvent;
+
+use proto::event_stream_server::{EventStream, EventStreamServer};
+
+/// gRPC `EventStream` service streaming what an `EventHub` receives
+pub struct GrpcService {
+    hub: EventHub,
+}
+
+impl GrpcService {
+    pub fn new(hub: EventHub) -> Self {
+        Self { hub }
+    }
+
+    /// Service for mounting into an existing tonic server
+    pub fn into_server(self) -> EventStreamServer<Self> {
+        EventStreamServer::new(self)
+    }
+
+    /// Serve the service alone on `addr`
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        log::info!("🌐 [GRPC] Listening on {}", addr);
+        tonic::transport::Server::builder()
+            .add_service(self.into_server())
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+}
+
+type EventResult = Result<proto::Event, Status>;
+
+#[tonic::async_trait]
+impl EventStream for GrpcService {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = EventResult> + Send>>;
+
+    async fn subscribe(&self, request: Request<proto::TokenFilter>) -> Result<Response<Self::SubscribeStream>, Status> {
+        let request = request.into_inner();
+        let filter =
+            TokenFilter::parse(Some(&request.tokens.join(","))).map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let kinds = request.kinds;
+
+        let events = stream::unfold(self.hub.subscribe(), move |mut events| {
+            let (filter, kinds) = (filter.clone(), kinds.clone());
+            async move {
+                loop {
+                    let event = match events.recv().await {
+                        Ok(event) if filter.matches(&event) && (kinds.is_empty() || kinds.contains(&(kind(&event) as i32))) => {
+                            proto::Event::from(event.as_ref())
+                        }
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(skipped)) => proto::Event {
+                            event: Some(proto::event::Event::Lagged(skipped)),
+                        },
+                        Err(RecvError::Closed) => return None,
+                    };
+                    return Some((Ok(event), events));
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(events)))
+    }
+}
+
+fn kind(event: &StreamEvent) -> proto::EventKind {
+    match event {
+        StreamEvent::Swap(_) => proto::EventKind::Swap,
+        StreamEvent::Migration(_) => proto::EventKind::Migration,
+        StreamEvent::Candle(_) => proto::EventKind::Candle,
+    }
+}
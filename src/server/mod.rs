@@ -0,0 +1,193 @@
+//! HTTP server re-broadcasting stream events (requires the `server` feature)
+//!
+//! An `EventHub` is registered as a sink on one or more streamers; an
+//! `EventServer` serves what the hub receives to any number of clients, so
+//! they never need their own BSC connection.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use axum::routing::get;
+use axum::Router;
+use ethers::types::Address;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::broadcast;
+
+use crate::handle::StreamerHandle;
+use crate::sinks::EventSink;
+use crate::types::StreamEvent;
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod rest;
+pub mod sse;
+pub mod ws;
+
+/// Fan-out point between streamers and server clients
+///
+/// Register a clone on every streamer with `StreamerBuilder::sink`. Clients
+/// that fall more than `capacity` events behind skip the missed events.
+/// The hub also keeps per-token activity and the last `RECENT_EVENTS` events
+/// for the REST API.
+#[derive(Clone)]
+pub struct EventHub {
+    tx: broadcast::Sender<Arc<StreamEvent>>,
+    activity: Arc<Mutex<rest::Activity>>,
+}
+
+/// Events kept for `GET /api/events/recent`
+pub const RECENT_EVENTS: usize = 500;
+
+impl EventHub {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self {
+            tx,
+            activity: Arc::new(Mutex::new(rest::Activity::new(RECENT_EVENTS))),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<StreamEvent>> {
+        self.tx.subscribe()
+    }
+
+    /// Number of connected clients
+    pub fn subscribers(&self) -> usize {
+        self.tx.receiver_count()
+    }
+
+    /// Activity of every token the hub has seen, ordered by address
+    pub fn tokens(&self) -> Vec<rest::TokenActivity> {
+        self.activity().tokens().cloned().collect()
+    }
+
+    pub(crate) fn activity(&self) -> MutexGuard<'_, rest::Activity> {
+        self.activity.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new(4096)
+    }
+}
+
+#[async_trait]
+impl EventSink for EventHub {
+    fn name(&self) -> &str {
+        "hub"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let event = Arc::new(event.clone());
+        self.activity().record(&event);
+        // No receivers is not an error - clients come and go
+        let _ = self.tx.send(event);
+        Ok(())
+    }
+}
+
+/// Set of tokens a client is interested in; empty means every token
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TokenFilter {
+    tokens: HashSet<Address>,
+}
+
+impl TokenFilter {
+    /// Parse a comma-separated address list (`?token=0x..,0x..`)
+    pub(crate) fn parse(tokens: Option<&str>) -> Result<Self> {
+        let tokens = tokens
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(|token| Address::from_str(token).map_err(|_| anyhow!("Invalid token address: {}", token)))
+            .collect::<Result<_>>()?;
+        Ok(Self { tokens })
+    }
+
+    pub(crate) fn matches(&self, event: &StreamEvent) -> bool {
+        self.tokens.is_empty() || self.tokens.contains(&event.token_address())
+    }
+
+    pub(crate) fn add(&mut self, tokens: &[Address]) {
+        self.tokens.extend(tokens);
+    }
+
+    pub(crate) fn remove(&mut self, tokens: &[Address]) {
+        for token in tokens {
+            self.tokens.remove(token);
+        }
+    }
+}
+
+/// HTTP server for an `EventHub`
+///
+/// Routes:
+/// - `GET /ws?token=0x..,0x..` - WebSocket with one JSON event per text message
+/// - `GET /events?token=0x..,0x..` - Server-Sent Events stream of the same JSON
+/// - `GET /api/status` - uptime, event count, connected clients, running streamers
+/// - `GET /api/tokens` - tokens seen, with their platforms, pairs and swap counts
+/// - `GET /api/tokens/{token}` - one token, with price statistics from attached handles
+/// - `GET /api/stats` - price statistics of every token/base pair of attached handles
+/// - `GET /api/events/recent?token=0x..&limit=50` - latest events, oldest first
+/// - `GET /healthz`, `GET /readyz` - 200/503 probes over the attached handles' `health()`
+pub struct EventServer {
+    hub: EventHub,
+    handles: Vec<StreamerHandle>,
+}
+
+impl EventServer {
+    pub fn new(hub: EventHub) -> Self {
+        Self {
+            hub,
+            handles: Vec::new(),
+        }
+    }
+
+    /// Serve price statistics, running state and health of a streamer on the `/api` and probe routes
+    pub fn with_handle(mut self, handle: StreamerHandle) -> Self {
+        self.handles.push(handle);
+        self
+    }
+
+    /// Routes of this server, for mounting into an existing axum app
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/ws", get(ws::ws_handler))
+            .route("/events", get(sse::sse_handler))
+            .with_state(self.hub.clone())
+            .merge(rest::router(self.hub.clone(), self.handles.clone()))
+    }
+
+    /// Listen on `addr` until the task is dropped or the listener fails
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::server::{EventHub, EventServer};
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let hub = EventHub::default();
+    /// tokio::spawn(EventServer::new(hub.clone()).serve("0.0.0.0:8080".parse()?));
+    ///
+    /// StreamerBuilder::from_wss("wss://bsc.publicnode.com")
+    ///     .await?
+    ///     .token_address("0x...")
+    ///     .auto_detect()
+    ///     .sink(hub)
+    ///     .on_swap(|_| {})
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        log::info!("🌐 [SERVER] Listening on {}", addr);
+        axum::serve(listener, self.router()).await?;
+        Ok(())
+    }
+}
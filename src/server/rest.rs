@@ -0,0 +1,283 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use ethers::types::Address;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::{EventHub, TokenFilter};
+use crate::handle::StreamerHandle;
+use crate::types::{HealthReport, Platform, PriceStats, StreamEvent};
+
+// Events returned by /api/events/recent when no limit is given
+const DEFAULT_RECENT_LIMIT: usize = 50;
+
+/// What the hub has seen of one token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenActivity {
+    pub address: Address,
+    /// None until the first swap of the token
+    pub symbol: Option<String>,
+    pub platforms: Vec<Platform>,
+    pub pairs: Vec<Address>,
+    pub bonding_curve: Option<Address>,
+    pub swaps: u64,
+    pub migrated: bool,
+    pub last_block: Option<u64>,
+    pub last_seen: Option<String>,
+}
+
+impl TokenActivity {
+    fn new(address: Address) -> Self {
+        Self {
+            address,
+            symbol: None,
+            platforms: Vec::new(),
+            pairs: Vec::new(),
+            bonding_curve: None,
+            swaps: 0,
+            migrated: false,
+            last_block: None,
+            last_seen: None,
+        }
+    }
+
+    fn add_platform(&mut self, platform: Platform) {
+        if !self.platforms.contains(&platform) {
+            self.platforms.push(platform);
+        }
+    }
+
+    fn add_pair(&mut self, pair: Address) {
+        if !self.pairs.contains(&pair) {
+            self.pairs.push(pair);
+        }
+    }
+}
+
+/// Per-token activity and the most recent events, kept by the hub for the REST API
+pub(crate) struct Activity {
+    started_at: Instant,
+    events_received: u64,
+    tokens: BTreeMap<Address, TokenActivity>,
+    recent: VecDeque<Arc<StreamEvent>>,
+    recent_capacity: usize,
+}
+
+impl Activity {
+    pub(crate) fn new(recent_capacity: usize) -> Self {
+        Self {
+            started_at: Instant::now(),
+            events_received: 0,
+            tokens: BTreeMap::new(),
+            recent: VecDeque::new(),
+            recent_capacity,
+        }
+    }
+
+    pub(crate) fn record(&mut self, event: &Arc<StreamEvent>) {
+        self.events_received += 1;
+
+        let token = self
+            .tokens
+            .entry(event.token_address())
+            .or_insert_with(|| TokenActivity::new(event.token_address()));
+        match event.as_ref() {
+            StreamEvent::Swap(swap) => {
+                token.symbol = Some(swap.token.symbol.clone());
+                token.add_platform(swap.platform);
+                if let Some(pair) = swap.pair_address {
+                    token.add_pair(pair);
+                }
+                if swap.bonding_curve_address.is_some() {
+                    token.bonding_curve = swap.bonding_curve_address;
+                }
+                token.swaps += 1;
+                token.last_block = Some(swap.block_number);
+                token.last_seen = swap.timestamp.clone();
+            }
+            StreamEvent::Migration(migration) => {
+                token.migrated = true;
+                token.add_platform(migration.to_platform);
+                for pair in &migration.pair_addresses {
+                    token.add_pair(*pair);
+                }
+                token.last_block = Some(migration.block_number);
+            }
+            StreamEvent::Candle(_) => {}
+        }
+
+        if self.recent_capacity > 0 {
+            if self.recent.len() == self.recent_capacity {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(event.clone());
+        }
+    }
+
+    pub(crate) fn tokens(&self) -> impl Iterator<Item = &TokenActivity> {
+        self.tokens.values()
+    }
+
+    /// Up to `limit` most recent events matching `filter`, oldest first
+    pub(crate) fn recent(&self, filter: &TokenFilter, limit: usize) -> Vec<Arc<StreamEvent>> {
+        let mut events: Vec<_> = self
+            .recent
+            .iter()
+            .rev()
+            .filter(|event| filter.matches(event))
+            .take(limit)
+            .cloned()
+            .collect();
+        events.reverse();
+        events
+    }
+}
+
+/// Overall state of the server and attached streamers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerStatus {
+    pub uptime_secs: u64,
+    pub events_received: u64,
+    pub tokens: usize,
+    /// Connected WebSocket/SSE/gRPC clients
+    pub clients: usize,
+    pub streamers: usize,
+    pub streamers_running: usize,
+}
+
+/// Price statistics of one token/base pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenStats {
+    pub token: String,
+    pub base_token: String,
+    pub stats: PriceStats,
+}
+
+/// Activity and price statistics of one token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenDetail {
+    #[serde(flatten)]
+    pub activity: TokenActivity,
+    pub stats: Vec<TokenStats>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecentQuery {
+    token: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    hub: EventHub,
+    handles: Arc<Vec<StreamerHandle>>,
+}
+
+impl ApiState {
+    fn stats(&self, token: Option<Address>) -> Vec<TokenStats> {
+        let token = token.map(|token| format!("{:?}", token));
+        let mut stats: Vec<_> = self
+            .handles
+            .iter()
+            .flat_map(|handle| handle.all_stats())
+            .filter(|((stats_token, _), _)| token.as_ref().is_none_or(|token| token == stats_token))
+            .map(|((token, base_token), stats)| TokenStats { token, base_token, stats })
+            .collect();
+        stats.sort_by(|a, b| (&a.token, &a.base_token).cmp(&(&b.token, &b.base_token)));
+        stats
+    }
+}
+
+/// Routes under `/api`, see `EventServer`
+pub(crate) fn router(hub: EventHub, handles: Vec<StreamerHandle>) -> Router {
+    Router::new()
+        .route("/api/status", get(status))
+        .route("/api/tokens", get(tokens))
+        .route("/api/tokens/:token", get(token))
+        .route("/api/stats", get(stats))
+        .route("/api/events/recent", get(recent_events))
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(ApiState {
+            hub,
+            handles: Arc::new(handles),
+        })
+}
+
+async fn status(State(state): State<ApiState>) -> Json<ServerStatus> {
+    let (uptime_secs, events_received, tokens) = {
+        let activity = state.hub.activity();
+        (activity.started_at.elapsed().as_secs(), activity.events_received, activity.tokens.len())
+    };
+    Json(ServerStatus {
+        uptime_secs,
+        events_received,
+        tokens,
+        clients: state.hub.subscribers(),
+        streamers: state.handles.len(),
+        streamers_running: state.handles.iter().filter(|handle| !handle.is_stopped()).count(),
+    })
+}
+
+async fn tokens(State(state): State<ApiState>) -> Json<Vec<TokenActivity>> {
+    Json(state.hub.tokens())
+}
+
+async fn token(Path(token): Path<String>, State(state): State<ApiState>) -> Response {
+    let Ok(address) = Address::from_str(&token) else {
+        return (StatusCode::BAD_REQUEST, format!("Invalid token address: {}", token)).into_response();
+    };
+
+    let activity = state.hub.activity().tokens.get(&address).cloned();
+    let stats = state.stats(Some(address));
+    if activity.is_none() && stats.is_empty() {
+        return (StatusCode::NOT_FOUND, format!("Token {:?} has not been seen", address)).into_response();
+    }
+
+    Json(TokenDetail {
+        activity: activity.unwrap_or_else(|| TokenActivity::new(address)),
+        stats,
+    })
+    .into_response()
+}
+
+async fn stats(State(state): State<ApiState>) -> Json<Vec<TokenStats>> {
+    Json(state.stats(None))
+}
+
+async fn recent_events(Query(query): Query<RecentQuery>, State(state): State<ApiState>) -> Response {
+    match TokenFilter::parse(query.token.as_deref()) {
+        Ok(filter) => {
+            let limit = query.limit.unwrap_or(DEFAULT_RECENT_LIMIT);
+            let events = state.hub.activity().recent(&filter, limit);
+            Json(events.iter().map(AsRef::as_ref).collect::<Vec<&StreamEvent>>()).into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+async fn healthz(State(state): State<ApiState>) -> Response {
+    probe(&state, HealthReport::is_healthy).await
+}
+
+async fn readyz(State(state): State<ApiState>) -> Response {
+    probe(&state, HealthReport::is_ready).await
+}
+
+/// 200 when every attached streamer passes `check`, 503 otherwise; the body lists each report
+async fn probe(state: &ApiState, check: fn(&HealthReport) -> bool) -> Response {
+    let reports = join_all(state.handles.iter().map(StreamerHandle::health)).await;
+    let status = if reports.iter().all(check) {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(reports)).into_response()
+}
@@ -0,0 +1,43 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures::stream::{self, Stream};
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::ws::EventQuery;
+use super::{EventHub, TokenFilter};
+
+/// `GET /events?token=0x..` - Server-Sent Events, one JSON event per message
+///
+/// The SSE event name is the event type (`swap`, `migration`, `candle`), so
+/// browsers can `addEventListener("swap", ...)`; `curl -N` shows the raw stream.
+pub(crate) async fn sse_handler(Query(query): Query<EventQuery>, State(hub): State<EventHub>) -> Response {
+    match TokenFilter::parse(query.token.as_deref()) {
+        Ok(filter) => Sse::new(event_stream(hub, filter))
+            .keep_alive(KeepAlive::default())
+            .into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+fn event_stream(hub: EventHub, filter: TokenFilter) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold((hub.subscribe(), filter), |(mut events, filter)| async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) if filter.matches(&event) => match Event::default().event(event.as_str()).json_data(&*event) {
+                    Ok(event) => event,
+                    Err(_) => continue,
+                },
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("⚠️ [SSE] Client fell behind, skipped {} event(s)", skipped);
+                    Event::default().event("lagged").data(skipped.to_string())
+                }
+                Err(RecvError::Closed) => return None,
+            };
+            return Some((Ok(event), (events, filter)));
+        }
+    })
+}
@@ -0,0 +1,76 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use ethers::types::Address;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{EventHub, TokenFilter};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct EventQuery {
+    /// Comma-separated token addresses; all tokens when absent
+    pub token: Option<String>,
+}
+
+/// Filter changes a client can send as text messages, e.g. `{"subscribe": ["0x..."]}`
+///
+/// Removing every token from the filter goes back to receiving all tokens.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ControlMessage {
+    Subscribe(Vec<Address>),
+    Unsubscribe(Vec<Address>),
+}
+
+pub(crate) async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Query(query): Query<EventQuery>,
+    State(hub): State<EventHub>,
+) -> Response {
+    match TokenFilter::parse(query.token.as_deref()) {
+        Ok(filter) => ws.on_upgrade(move |socket| handle_socket(socket, hub, filter)),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+async fn handle_socket(mut socket: WebSocket, hub: EventHub, mut filter: TokenFilter) {
+    let mut events = hub.subscribe();
+    log::debug!("🔌 [WS] Client connected ({} total)", hub.subscribers());
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    if !filter.matches(&event) {
+                        continue;
+                    }
+                    let Ok(text) = serde_json::to_string(&*event) else { continue };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("⚠️ [WS] Client fell behind, skipped {} event(s)", skipped);
+                    let notice = serde_json::json!({ "type": "lagged", "skipped": skipped }).to_string();
+                    if socket.send(Message::Text(notice)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ControlMessage>(&text) {
+                    Ok(ControlMessage::Subscribe(tokens)) => filter.add(&tokens),
+                    Ok(ControlMessage::Unsubscribe(tokens)) => filter.remove(&tokens),
+                    Err(e) => log::debug!("🔌 [WS] Ignoring client message: {}", e),
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    log::debug!("🔌 [WS] Client disconnected");
+}
@@ -0,0 +1,380 @@
+//! Configuration file support (TOML or YAML) with environment variable overrides
+//!
+//! ```toml
+//! wss_url = "wss://bsc.publicnode.com"
+//! tokens = ["0x...", "0x..."]
+//! output = "pretty"
+//!
+//! [[base_tokens]]
+//! symbol = "CAKE"
+//! address = "0x0E09FaBB73Bd3Ade0a17ECC321fD13a19e81cE82"
+//!
+//! [thresholds]
+//! min_liquidity_usd = 10000
+//! min_trade_usd = 50
+//!
+//! [alerts]
+//! classes = ["whale", "migration"]
+//! whale_min_usd = 25000
+//!
+//! [token_cache]
+//! path = "tokens.json"
+//! ttl_hours = 168
+//!
+//! [sinks]
+//! jsonl = "events.jsonl"
+//! discord = "https://discord.com/api/webhooks/..."
+//! file = { dir = "data", format = "csv" }
+//! webhook = { url = "https://example.com/hook", secret = "..." }
+//! ```
+//!
+//! These environment variables override the file: `BSC_WSS_URL`,
+//! `TOKEN_ADDRESS` (comma-separated), `OUTPUT_FORMAT`, `MIN_LIQUIDITY_USD`,
+//! `MIN_TRADE_USD`, `MIN_TRADE_BASE`, `TOKEN_CACHE_FILE`, `WEBHOOK_URL`,
+//! `WEBHOOK_SECRET`, `DISCORD_WEBHOOK_URL` and `SLACK_WEBHOOK_URL`.
+
+use anyhow::{anyhow, Result};
+use ethers::providers::Middleware;
+use ethers::types::Address;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::add_base_token;
+use crate::core::options::StreamOptions;
+use crate::sinks::{
+    ClickHouseSink, DiscordSink, EventClass, EventSink, FileFormat, FileSink, JsonLinesSink, SlackSink, WebhookSink,
+};
+use crate::types::TradeThreshold;
+use crate::StreamerBuilder;
+
+/// Deployment settings loaded from a config file and the environment
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// BSC WebSocket endpoint
+    pub wss_url: Option<String>,
+    /// Tokens to watch
+    pub tokens: Vec<String>,
+    /// Output of the binary: pretty, json, jsonl or tui
+    pub output: Option<String>,
+    /// Searched for pairs in addition to the built-in base tokens
+    pub base_tokens: Vec<BaseTokenSettings>,
+    pub thresholds: Thresholds,
+    pub alerts: AlertSettings,
+    /// Token metadata persisted across restarts
+    pub token_cache: Option<TokenCacheSettings>,
+    pub sinks: SinkSettings,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaseTokenSettings {
+    pub symbol: String,
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    /// Ignore DEX pairs with less liquidity (USD)
+    pub min_liquidity_usd: Option<f64>,
+    /// Drop swaps worth less (USD)
+    pub min_trade_usd: Option<f64>,
+    /// Drop swaps of less base token (used when `min_trade_usd` is not set)
+    pub min_trade_base: Option<f64>,
+}
+
+/// Event classes posted by the Discord and Slack sinks
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertSettings {
+    /// buy, sell, whale, migration or candle (sink defaults when empty)
+    pub classes: Vec<String>,
+    /// Minimum USD value of a `whale` trade
+    pub whale_min_usd: f64,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            classes: Vec::new(),
+            whale_min_usd: 10_000.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenCacheSettings {
+    pub path: PathBuf,
+    /// Entries older than this are refetched
+    #[serde(default = "default_token_cache_ttl_hours")]
+    pub ttl_hours: u64,
+}
+
+fn default_token_cache_ttl_hours() -> u64 {
+    168
+}
+
+impl TokenCacheSettings {
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_hours * 3600)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SinkSettings {
+    /// JSON lines file, or `-` for stdout
+    pub jsonl: Option<String>,
+    pub file: Option<FileSinkSettings>,
+    pub webhook: Option<WebhookSettings>,
+    /// Discord webhook URL
+    pub discord: Option<String>,
+    /// Slack incoming webhook URL
+    pub slack: Option<String>,
+    pub clickhouse: Option<ClickHouseSettings>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileSinkSettings {
+    pub dir: PathBuf,
+    /// csv or parquet (parquet needs the `parquet` feature)
+    #[serde(default = "default_file_format")]
+    pub format: String,
+}
+
+fn default_file_format() -> String {
+    "csv".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSettings {
+    pub url: String,
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClickHouseSettings {
+    pub url: String,
+    pub database: Option<String>,
+    pub user: Option<String>,
+    #[serde(default)]
+    pub password: String,
+}
+
+impl Settings {
+    /// Read a `.toml`, `.yaml` or `.yml` file, then apply environment overrides
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let settings: Settings = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&contents).map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e))?,
+            Some("yaml" | "yml") => {
+                serde_yaml::from_str(&contents).map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e))?
+            }
+            _ => return Err(anyhow!("Config file {} must end in .toml, .yaml or .yml", path.display())),
+        };
+        settings.with_env_overrides()
+    }
+
+    /// Settings from the environment only
+    pub fn from_env() -> Result<Self> {
+        Settings::default().with_env_overrides()
+    }
+
+    /// Replace values with the ones set in the environment (empty variables are ignored)
+    pub fn with_env_overrides(mut self) -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+        let number = |name: &str| -> Result<Option<f64>> {
+            var(name)
+                .map(|value| value.trim().parse().map_err(|_| anyhow!("{} must be a number, got {:?}", name, value)))
+                .transpose()
+        };
+
+        if let Some(url) = var("BSC_WSS_URL") {
+            self.wss_url = Some(url);
+        }
+        if let Some(tokens) = var("TOKEN_ADDRESS") {
+            self.tokens = tokens.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect();
+        }
+        if let Some(output) = var("OUTPUT_FORMAT") {
+            self.output = Some(output);
+        }
+        if let Some(usd) = number("MIN_LIQUIDITY_USD")? {
+            self.thresholds.min_liquidity_usd = Some(usd);
+        }
+        if let Some(usd) = number("MIN_TRADE_USD")? {
+            self.thresholds.min_trade_usd = Some(usd);
+        }
+        if let Some(amount) = number("MIN_TRADE_BASE")? {
+            self.thresholds.min_trade_base = Some(amount);
+        }
+        if let Some(path) = var("TOKEN_CACHE_FILE") {
+            let ttl_hours = self.token_cache.take().map_or_else(default_token_cache_ttl_hours, |cache| cache.ttl_hours);
+            self.token_cache = Some(TokenCacheSettings {
+                path: PathBuf::from(path),
+                ttl_hours,
+            });
+        }
+        if let Some(url) = var("WEBHOOK_URL") {
+            let secret = self.sinks.webhook.take().and_then(|webhook| webhook.secret);
+            self.sinks.webhook = Some(WebhookSettings { url, secret });
+        }
+        if let (Some(secret), Some(webhook)) = (var("WEBHOOK_SECRET"), self.sinks.webhook.as_mut()) {
+            webhook.secret = Some(secret);
+        }
+        if let Some(url) = var("DISCORD_WEBHOOK_URL") {
+            self.sinks.discord = Some(url);
+        }
+        if let Some(url) = var("SLACK_WEBHOOK_URL") {
+            self.sinks.slack = Some(url);
+        }
+        Ok(self)
+    }
+
+    /// Alert classes in posting order (whale trades are checked before buys and sells)
+    pub fn alert_classes(&self) -> Result<Vec<EventClass>> {
+        let mut classes = self
+            .alerts
+            .classes
+            .iter()
+            .map(|class| match class.to_ascii_lowercase().as_str() {
+                "buy" => Ok(EventClass::Buy),
+                "sell" => Ok(EventClass::Sell),
+                "whale" => Ok(EventClass::Whale { min_usd: self.alerts.whale_min_usd }),
+                "migration" => Ok(EventClass::Migration),
+                "candle" => Ok(EventClass::Candle),
+                other => Err(anyhow!("Unknown alert class {:?} (buy, sell, whale, migration, candle)", other)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        classes.sort_by_key(|class| !matches!(class, EventClass::Whale { .. }));
+        Ok(classes)
+    }
+
+    /// Minimum trade size from the thresholds, USD first
+    pub fn min_trade(&self) -> Option<TradeThreshold> {
+        match (self.thresholds.min_trade_usd, self.thresholds.min_trade_base) {
+            (Some(usd), _) => Some(TradeThreshold::Usd(usd)),
+            (None, Some(amount)) => Some(TradeThreshold::BaseToken(amount)),
+            (None, None) => None,
+        }
+    }
+
+    /// Build the configured sinks
+    pub fn sinks(&self) -> Result<Vec<Arc<dyn EventSink>>> {
+        let mut sinks: Vec<Arc<dyn EventSink>> = Vec::new();
+        let classes = self.alert_classes()?;
+
+        match self.sinks.jsonl.as_deref() {
+            Some("-") => sinks.push(Arc::new(JsonLinesSink::stdout())),
+            Some(path) => sinks.push(Arc::new(JsonLinesSink::file(path)?)),
+            None => {}
+        }
+        if let Some(ref file) = self.sinks.file {
+            let format = match file.format.to_ascii_lowercase().as_str() {
+                "csv" => FileFormat::Csv,
+                #[cfg(feature = "parquet")]
+                "parquet" => FileFormat::Parquet,
+                other => return Err(anyhow!("Unsupported file sink format {:?}", other)),
+            };
+            sinks.push(Arc::new(FileSink::new(&file.dir, format)?));
+        }
+        if let Some(ref webhook) = self.sinks.webhook {
+            let mut sink = WebhookSink::new(&webhook.url);
+            if let Some(ref secret) = webhook.secret {
+                sink = sink.with_secret(secret);
+            }
+            sinks.push(Arc::new(sink));
+        }
+        if let Some(ref url) = self.sinks.discord {
+            let mut sink = DiscordSink::new(url);
+            if !classes.is_empty() {
+                sink = sink.with_classes(&classes);
+            }
+            sinks.push(Arc::new(sink));
+        }
+        if let Some(ref url) = self.sinks.slack {
+            let mut sink = SlackSink::new(url);
+            if !classes.is_empty() {
+                sink = sink.with_classes(&classes);
+            }
+            sinks.push(Arc::new(sink));
+        }
+        if let Some(ref clickhouse) = self.sinks.clickhouse {
+            let mut sink = ClickHouseSink::new(&clickhouse.url);
+            if let Some(ref database) = clickhouse.database {
+                sink = sink.with_database(database);
+            }
+            if let Some(ref user) = clickhouse.user {
+                sink = sink.with_credentials(user, &clickhouse.password);
+            }
+            sinks.push(Arc::new(sink));
+        }
+        Ok(sinks)
+    }
+
+    /// Make the configured base tokens available to pair discovery (process-wide)
+    pub fn register_base_tokens(&self) {
+        for token in &self.base_tokens {
+            add_base_token(&token.symbol, token.address);
+        }
+    }
+
+    /// Options for a `MultiTokenStreamer`; also registers the base tokens
+    pub fn stream_options(&self) -> Result<StreamOptions> {
+        self.register_base_tokens();
+        let mut options = StreamOptions {
+            min_liquidity_usd: self.thresholds.min_liquidity_usd,
+            token_cache_file: self.token_cache.as_ref().map(|cache| (cache.path.clone(), cache.ttl())),
+            ..Default::default()
+        };
+        options.filter.min_trade = self.min_trade();
+        for sink in self.sinks()? {
+            options.sinks.push(sink);
+        }
+        Ok(options)
+    }
+
+    /// Apply thresholds and sinks to a builder; also registers the base tokens
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use bsc_streamer::settings::Settings;
+    /// use bsc_streamer::StreamerBuilder;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let settings = Settings::load("bsc_streamer.toml")?;
+    /// let builder = StreamerBuilder::from_wss(settings.wss_url.as_deref().unwrap_or("wss://bsc.publicnode.com")).await?;
+    /// settings
+    ///     .apply(builder)?
+    ///     .token_address(&settings.tokens[0])
+    ///     .auto_detect()
+    ///     .on_swap(|swap| println!("{:?}", swap))
+    ///     .start()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply<M: Middleware + 'static>(&self, mut builder: StreamerBuilder<M>) -> Result<StreamerBuilder<M>> {
+        self.register_base_tokens();
+        if let Some(usd) = self.thresholds.min_liquidity_usd {
+            builder = builder.min_liquidity(usd);
+        }
+        if let Some(threshold) = self.min_trade() {
+            builder = builder.min_trade_size(threshold);
+        }
+        if let Some(ref cache) = self.token_cache {
+            builder = builder.token_cache_file(&cache.path, cache.ttl());
+        }
+        for sink in self.sinks()? {
+            builder = builder.sink(sink);
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,235 @@
+//! Event classes shared by the chat notification sinks
+//!
+//! Chat sinks post only the classes they are configured with; the first class
+//! that matches an event decides how it is presented and its severity (which
+//! Slack routes on).
+//!
+//! Message templates (`render_template`) replace `{name}` placeholders:
+//! - swaps: `{class}`, `{side}`, `{symbol}`, `{amount}`, `{base_amount}`, `{base_symbol}`,
+//!   `{price}`, `{usd}`, `{token}`, `{trader}`, `{platform}`, `{block}`, `{tx}`, `{tx_url}`
+//! - migrations: `{class}`, `{token}`, `{from}`, `{to}`, `{pairs}`, `{block}`, `{tx}`, `{tx_url}`
+//! - candles: `{class}`, `{token}`, `{interval}`, `{open}`, `{high}`, `{low}`, `{close}`,
+//!   `{base_symbol}`, `{trades}`
+//!
+//! `{price}` already ends with its quote symbol. Unknown placeholders are left as they are.
+
+use ethers::types::{Address, H256};
+
+use crate::types::{StreamEvent, SwapEvent, TradeType};
+
+/// Kind of event a notification sink posts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventClass {
+    Buy,
+    Sell,
+    /// Swaps (either side) worth at least `min_usd`; needs USD valuation on swaps
+    Whale { min_usd: f64 },
+    Migration,
+    Candle,
+}
+
+impl EventClass {
+    pub fn matches(&self, event: &StreamEvent) -> bool {
+        match (self, event) {
+            (EventClass::Buy, StreamEvent::Swap(swap)) => swap.trade_type == TradeType::Buy,
+            (EventClass::Sell, StreamEvent::Swap(swap)) => swap.trade_type == TradeType::Sell,
+            (EventClass::Whale { min_usd }, StreamEvent::Swap(swap)) => swap.usd_value.is_some_and(|usd| usd >= *min_usd),
+            (EventClass::Migration, StreamEvent::Migration(_)) => true,
+            (EventClass::Candle, StreamEvent::Candle(_)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventClass::Buy => "buy",
+            EventClass::Sell => "sell",
+            EventClass::Whale { .. } => "whale",
+            EventClass::Migration => "migration",
+            EventClass::Candle => "candle",
+        }
+    }
+
+    /// Default severity: migrations are critical, whale trades a warning
+    pub fn severity(&self) -> Severity {
+        match self {
+            EventClass::Migration => Severity::Critical,
+            EventClass::Whale { .. } => Severity::Warning,
+            EventClass::Buy | EventClass::Sell | EventClass::Candle => Severity::Info,
+        }
+    }
+}
+
+/// How urgent a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Ordered list of event classes; an event belongs to the first class it matches
+///
+/// Put `Whale` before `Buy`/`Sell` so large trades are presented as whale trades.
+/// Each class has a severity (`EventClass::severity` unless overridden).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRules {
+    classes: Vec<EventClass>,
+    severities: Vec<(EventClass, Severity)>,
+}
+
+impl AlertRules {
+    pub fn new(classes: &[EventClass]) -> Self {
+        Self {
+            classes: classes.to_vec(),
+            severities: Vec::new(),
+        }
+    }
+
+    /// Class of the event, or None if the sink should skip it
+    pub fn classify(&self, event: &StreamEvent) -> Option<EventClass> {
+        self.classes.iter().find(|class| class.matches(event)).copied()
+    }
+
+    pub fn classes(&self) -> &[EventClass] {
+        &self.classes
+    }
+
+    /// Replace the classes, keeping severity overrides
+    pub fn set_classes(&mut self, classes: &[EventClass]) {
+        self.classes = classes.to_vec();
+    }
+
+    /// Add a class if it is missing; whale trades go first so they win over buys and sells
+    pub fn add_class(&mut self, class: EventClass) {
+        if self.classes.contains(&class) {
+            return;
+        }
+        match class {
+            EventClass::Whale { .. } => self.classes.insert(0, class),
+            _ => self.classes.push(class),
+        }
+    }
+
+    /// Override the severity of events of `class`
+    pub fn set_severity(&mut self, class: EventClass, severity: Severity) {
+        self.severities.retain(|(overridden, _)| *overridden != class);
+        self.severities.push((class, severity));
+    }
+
+    pub fn severity(&self, class: EventClass) -> Severity {
+        self.severities
+            .iter()
+            .find(|(overridden, _)| *overridden == class)
+            .map_or(class.severity(), |(_, severity)| *severity)
+    }
+}
+
+impl Default for AlertRules {
+    /// Buys, sells and migrations
+    fn default() -> Self {
+        Self::new(&[EventClass::Buy, EventClass::Sell, EventClass::Migration])
+    }
+}
+
+pub(crate) fn tx_url(hash: H256) -> String {
+    format!("https://bscscan.com/tx/{:?}", hash)
+}
+
+pub(crate) fn token_url(address: Address) -> String {
+    format!("https://bscscan.com/token/{:?}", address)
+}
+
+/// "$12,345" for whole amounts, "$0.42" below $100
+pub(crate) fn format_usd(value: f64) -> String {
+    if value.abs() < 100.0 {
+        return format!("${:.2}", value);
+    }
+    let digits = format!("{:.0}", value.abs());
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}${}", if value < 0.0 { "-" } else { "" }, grouped)
+}
+
+/// Title line such as "🐋 BUY 1,000 CAKE" - shared by the chat sinks
+pub(crate) fn swap_title(class: EventClass, swap: &SwapEvent) -> String {
+    let icon = match (class, swap.trade_type) {
+        (EventClass::Whale { .. }, _) => "🐋",
+        (_, TradeType::Buy) => "🟢",
+        (_, TradeType::Sell) => "🔴",
+    };
+    format!(
+        "{} {} {} {}",
+        icon,
+        swap.trade_type.as_str(),
+        swap.token.amount,
+        swap.token.symbol
+    )
+}
+
+/// Placeholder values of an event, see the module docs
+fn template_values(class: EventClass, event: &StreamEvent) -> Vec<(&'static str, String)> {
+    let mut values = vec![("class", class.as_str().to_string())];
+    match event {
+        StreamEvent::Swap(swap) => values.extend([
+            ("side", swap.trade_type.as_str().to_string()),
+            ("symbol", swap.token.symbol.clone()),
+            ("amount", swap.token.amount.clone()),
+            ("base_amount", swap.base_token.amount.clone()),
+            ("base_symbol", swap.base_token.symbol.clone()),
+            ("price", swap.price.display.clone()),
+            ("usd", swap.usd_value.map(format_usd).unwrap_or_else(|| "-".to_string())),
+            ("token", format!("{:?}", swap.token.address)),
+            ("trader", format!("{:?}", swap.trader.unwrap_or(swap.recipient))),
+            ("platform", swap.platform.as_str().to_string()),
+            ("block", swap.block_number.to_string()),
+            ("tx", format!("{:?}", swap.transaction_hash)),
+            ("tx_url", tx_url(swap.transaction_hash)),
+        ]),
+        StreamEvent::Migration(migration) => values.extend([
+            ("token", format!("{:?}", migration.token_address)),
+            ("from", migration.from_platform.as_str().to_string()),
+            ("to", migration.to_platform.as_str().to_string()),
+            (
+                "pairs",
+                migration.pair_addresses.iter().map(|pair| format!("{:?}", pair)).collect::<Vec<_>>().join(", "),
+            ),
+            ("block", migration.block_number.to_string()),
+            ("tx", format!("{:?}", migration.transaction_hash)),
+            ("tx_url", tx_url(migration.transaction_hash)),
+        ]),
+        StreamEvent::Candle(candle) => values.extend([
+            ("token", format!("{:?}", candle.token_address)),
+            ("interval", candle.interval.as_str().to_string()),
+            ("open", format!("{:.12}", candle.open)),
+            ("high", format!("{:.12}", candle.high)),
+            ("low", format!("{:.12}", candle.low)),
+            ("close", format!("{:.12}", candle.close)),
+            ("base_symbol", candle.base_token.clone()),
+            ("trades", candle.trades.to_string()),
+        ]),
+    }
+    values
+}
+
+/// Fill the `{name}` placeholders of `template` with the values of `event`
+pub fn render_template(template: &str, class: EventClass, event: &StreamEvent) -> String {
+    template_values(class, event)
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
@@ -0,0 +1,239 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use super::EventSink;
+use crate::core::candles::swap_time;
+use crate::types::{Candle, MigrationEvent, StreamEvent, SwapEvent};
+
+// Insert attempts per batch before it is dropped
+const INSERT_ATTEMPTS: u32 = 3;
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS {db}.swaps (
+        tx_hash String, log_index UInt64, block_number UInt64, time DateTime,
+        platform LowCardinality(String), trade_type LowCardinality(String),
+        token_address String, token_symbol String, token_amount Float64,
+        base_token_address String, base_token_symbol LowCardinality(String), base_amount Float64,
+        price Float64, price_usd Nullable(Float64), usd_value Nullable(Float64),
+        trader Nullable(String), market Nullable(String)
+    ) ENGINE = ReplacingMergeTree PARTITION BY toYYYYMM(time) ORDER BY (token_address, block_number, log_index, tx_hash)",
+    "CREATE TABLE IF NOT EXISTS {db}.migrations (
+        tx_hash String, token_address String, block_number UInt64, time DateTime,
+        from_platform LowCardinality(String), to_platform LowCardinality(String), pair_addresses Array(String)
+    ) ENGINE = ReplacingMergeTree ORDER BY (token_address, tx_hash)",
+    "CREATE TABLE IF NOT EXISTS {db}.candles (
+        market String, interval LowCardinality(String), open_time DateTime, close_time DateTime,
+        token_address String, base_token LowCardinality(String),
+        open Float64, high Float64, low Float64, close Float64,
+        volume_token Float64, volume_base Float64, volume_usd Nullable(Float64),
+        trades UInt32, buys UInt32, sells UInt32
+    ) ENGINE = ReplacingMergeTree PARTITION BY toYYYYMM(open_time) ORDER BY (token_address, interval, market, open_time)",
+];
+
+/// Buffers events and bulk-inserts them into ClickHouse over HTTP (`JSONEachRow`)
+///
+/// Tables are created with `ensure_schema`. ReplacingMergeTree engines
+/// collapse re-delivered events on merge.
+pub struct ClickHouseSink {
+    client: reqwest::Client,
+    url: String,
+    database: String,
+    credentials: Option<(String, String)>,
+    batch_size: usize,
+    flush_interval: Duration,
+    buffer: Mutex<Vec<StreamEvent>>,
+}
+
+impl ClickHouseSink {
+    /// Sink for the HTTP interface at `url` (e.g. `http://localhost:8123`), database `default`
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            url: url.trim_end_matches('/').to_string(),
+            database: "default".to_string(),
+            credentials: None,
+            batch_size: 10_000,
+            flush_interval: Duration::from_secs(5),
+            buffer: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn with_database(mut self, database: &str) -> Self {
+        self.database = database.to_string();
+        self
+    }
+
+    pub fn with_credentials(mut self, user: &str, password: &str) -> Self {
+        self.credentials = Some((user.to_string(), password.to_string()));
+        self
+    }
+
+    /// Insert once `size` events are buffered, or every `interval` (default 10000 / 5s)
+    pub fn with_batching(mut self, size: usize, interval: Duration) -> Self {
+        self.batch_size = size.max(1);
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Create the `swaps`, `migrations` and `candles` tables if they do not exist
+    pub async fn ensure_schema(&self) -> Result<()> {
+        for statement in SCHEMA {
+            self.execute(&statement.replace("{db}", &self.database), String::new()).await?;
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, query: &str, body: String) -> Result<()> {
+        let mut request = self.client.post(&self.url).query(&[("query", query)]).body(body);
+        if let Some((ref user, ref password)) = self.credentials {
+            request = request.header("X-ClickHouse-User", user).header("X-ClickHouse-Key", password);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ClickHouse returned {}: {}", status, message.trim()));
+        }
+        Ok(())
+    }
+
+    async fn insert(&self, table: &str, rows: &[Value]) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let query = format!("INSERT INTO {}.{} FORMAT JSONEachRow", self.database, table);
+        let body = rows.iter().map(|row| row.to_string()).collect::<Vec<_>>().join("\n");
+
+        let mut attempt = 1;
+        loop {
+            match self.execute(&query, body.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= INSERT_ATTEMPTS => {
+                    return Err(e.context(format!("dropping {} {} row(s)", rows.len(), table)));
+                }
+                Err(e) => {
+                    log::debug!("🔁 [CLICKHOUSE] Insert into {} failed ({}), retrying", table, e);
+                    tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn write(&self, events: Vec<StreamEvent>) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let (mut swaps, mut migrations, mut candles) = (Vec::new(), Vec::new(), Vec::new());
+        for event in &events {
+            match event {
+                StreamEvent::Swap(swap) => swaps.push(swap_row(swap)),
+                StreamEvent::Migration(migration) => migrations.push(migration_row(migration)),
+                StreamEvent::Candle(candle) => candles.push(candle_row(candle)),
+            }
+        }
+        self.insert("swaps", &swaps).await?;
+        self.insert("migrations", &migrations).await?;
+        self.insert("candles", &candles).await?;
+
+        log::debug!("💾 [CLICKHOUSE] Inserted {} event(s)", events.len());
+        Ok(())
+    }
+}
+
+fn swap_row(swap: &SwapEvent) -> Value {
+    json!({
+        "tx_hash": format!("{:?}", swap.transaction_hash),
+        "log_index": swap.log_index,
+        "block_number": swap.block_number,
+        "time": swap_time(swap),
+        "platform": swap.platform.as_str(),
+        "trade_type": swap.trade_type.as_str(),
+        "token_address": format!("{:?}", swap.token.address),
+        "token_symbol": swap.token.symbol,
+        "token_amount": swap.token.amount.parse::<f64>().unwrap_or_default(),
+        "base_token_address": format!("{:?}", swap.base_token.address),
+        "base_token_symbol": swap.base_token.symbol,
+        "base_amount": swap.base_token.amount.parse::<f64>().unwrap_or_default(),
+        "price": swap.price.value,
+        "price_usd": swap.price_usd,
+        "usd_value": swap.usd_value,
+        "trader": swap.trader.map(|trader| format!("{:?}", trader)),
+        "market": swap.pair_address.or(swap.bonding_curve_address).map(|market| format!("{:?}", market)),
+    })
+}
+
+fn migration_row(migration: &MigrationEvent) -> Value {
+    let time = migration
+        .timestamp
+        .as_deref()
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.timestamp())
+        .unwrap_or_else(|| chrono::Utc::now().timestamp());
+    json!({
+        "tx_hash": format!("{:?}", migration.transaction_hash),
+        "token_address": format!("{:?}", migration.token_address),
+        "block_number": migration.block_number,
+        "time": time,
+        "from_platform": migration.from_platform.as_str(),
+        "to_platform": migration.to_platform.as_str(),
+        "pair_addresses": migration.pair_addresses.iter().map(|pair| format!("{:?}", pair)).collect::<Vec<_>>(),
+    })
+}
+
+fn candle_row(candle: &Candle) -> Value {
+    json!({
+        "market": format!("{:?}", candle.market),
+        "interval": candle.interval.as_str(),
+        "open_time": candle.open_time,
+        "close_time": candle.close_time,
+        "token_address": format!("{:?}", candle.token_address),
+        "base_token": candle.base_token,
+        "open": candle.open,
+        "high": candle.high,
+        "low": candle.low,
+        "close": candle.close,
+        "volume_token": candle.volume_token,
+        "volume_base": candle.volume_base,
+        "volume_usd": candle.volume_usd,
+        "trades": candle.trades,
+        "buys": candle.buys,
+        "sells": candle.sells,
+    })
+}
+
+#[async_trait]
+impl EventSink for ClickHouseSink {
+    fn name(&self) -> &str {
+        "clickhouse"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let ready = {
+            let mut buffer = self.buffer.lock().await;
+            buffer.push(event.clone());
+            if buffer.len() >= self.batch_size {
+                std::mem::take(&mut *buffer)
+            } else {
+                Vec::new()
+            }
+        };
+        self.write(ready).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.buffer.lock().await);
+        self.write(pending).await
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        Some(self.flush_interval)
+    }
+}
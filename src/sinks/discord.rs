@@ -0,0 +1,199 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::alerts::{format_usd, swap_title, token_url, tx_url, AlertRules, EventClass};
+use super::EventSink;
+use crate::types::{Candle, MigrationEvent, StreamEvent, SwapEvent, TradeType};
+
+const COLOR_BUY: u32 = 0x2ecc71;
+const COLOR_SELL: u32 = 0xe74c3c;
+const COLOR_WHALE: u32 = 0x3498db;
+const COLOR_MIGRATION: u32 = 0x9b59b6;
+const COLOR_CANDLE: u32 = 0x95a5a6;
+
+/// Posts events to a Discord channel webhook as rich embeds
+///
+/// Only events matching the configured classes are posted (buys, sells and
+/// migrations by default). Rate-limited requests (429) are retried after the
+/// delay Discord asks for.
+pub struct DiscordSink {
+    client: reqwest::Client,
+    url: String,
+    username: Option<String>,
+    avatar_url: Option<String>,
+    rules: AlertRules,
+    max_retries: u32,
+}
+
+impl DiscordSink {
+    /// `url` is the channel webhook URL (`https://discord.com/api/webhooks/<id>/<token>`)
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url: url.to_string(),
+            username: None,
+            avatar_url: None,
+            rules: AlertRules::default(),
+            max_retries: 3,
+        }
+    }
+
+    /// Post only these event classes, e.g. `[Whale { min_usd: 10_000.0 }, Migration]`
+    pub fn with_classes(mut self, classes: &[EventClass]) -> Self {
+        self.rules = AlertRules::new(classes);
+        self
+    }
+
+    /// Override the webhook's display name
+    pub fn with_username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_string());
+        self
+    }
+
+    /// Override the webhook's avatar
+    pub fn with_avatar(mut self, avatar_url: &str) -> Self {
+        self.avatar_url = Some(avatar_url.to_string());
+        self
+    }
+
+    fn message(&self, embed: Value) -> Value {
+        let mut message = json!({ "embeds": [embed] });
+        if let Some(ref username) = self.username {
+            message["username"] = json!(username);
+        }
+        if let Some(ref avatar_url) = self.avatar_url {
+            message["avatar_url"] = json!(avatar_url);
+        }
+        message
+    }
+
+    async fn post(&self, message: &Value) -> Result<()> {
+        let mut attempt = 0;
+        loop {
+            let response = self
+                .client
+                .post(&self.url)
+                .json(message)
+                .send()
+                .await
+                .map_err(|e| anyhow!("Discord request failed: {}", e))?;
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            if status != reqwest::StatusCode::TOO_MANY_REQUESTS || attempt >= self.max_retries {
+                let body = response.text().await.unwrap_or_default();
+                return Err(anyhow!("Discord returned status {}: {}", status, body));
+            }
+
+            // Rate limit body: {"retry_after": <seconds>, ...}
+            let retry_after = response
+                .json::<Value>()
+                .await
+                .ok()
+                .and_then(|body| body["retry_after"].as_f64())
+                .unwrap_or(1.0);
+            attempt += 1;
+            log::debug!("🔁 [DISCORD] Rate limited - retry {}/{} in {:.1}s", attempt, self.max_retries, retry_after);
+            tokio::time::sleep(Duration::from_secs_f64(retry_after.clamp(0.0, 60.0))).await;
+        }
+    }
+}
+
+fn swap_embed(class: EventClass, swap: &SwapEvent) -> Value {
+    let color = match (class, swap.trade_type) {
+        (EventClass::Whale { .. }, _) => COLOR_WHALE,
+        (_, TradeType::Buy) => COLOR_BUY,
+        (_, TradeType::Sell) => COLOR_SELL,
+    };
+    let mut fields = vec![
+        json!({ "name": "Token", "value": format!("[{}]({})", swap.token.symbol, token_url(swap.token.address)), "inline": true }),
+        json!({ "name": "Side", "value": swap.trade_type.as_str(), "inline": true }),
+        json!({ "name": "Size", "value": format!("{} {}", swap.token.amount, swap.token.symbol), "inline": true }),
+        json!({ "name": "Paid", "value": format!("{} {}", swap.base_token.amount, swap.base_token.symbol), "inline": true }),
+        json!({ "name": "Price", "value": swap.price.display, "inline": true }),
+    ];
+    if let Some(usd) = swap.usd_value {
+        fields.push(json!({ "name": "Value", "value": format_usd(usd), "inline": true }));
+    }
+    if let Some(ref cap) = swap.market_cap {
+        fields.push(json!({ "name": "MCap", "value": format_usd(cap.market_cap_usd), "inline": true }));
+    }
+    let trader = swap.trader.unwrap_or(swap.recipient);
+    let trader = match swap.trader_label {
+        Some(ref label) => format!("{} ({:?})", label.name, trader),
+        None => format!("{:?}", trader),
+    };
+    fields.push(json!({ "name": "Trader", "value": trader, "inline": false }));
+
+    let mut embed = json!({
+        "title": swap_title(class, swap),
+        "url": tx_url(swap.transaction_hash),
+        "color": color,
+        "fields": fields,
+        "footer": { "text": format!("{} · block {}", swap.platform.as_str(), swap.block_number) },
+    });
+    if let Some(ref timestamp) = swap.timestamp {
+        embed["timestamp"] = json!(timestamp);
+    }
+    embed
+}
+
+fn migration_embed(migration: &MigrationEvent) -> Value {
+    let pairs = migration
+        .pair_addresses
+        .iter()
+        .map(|pair| format!("{:?}", pair))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut embed = json!({
+        "title": format!("🎉 {:?} migrated to {}", migration.token_address, migration.to_platform.as_str()),
+        "url": tx_url(migration.transaction_hash),
+        "color": COLOR_MIGRATION,
+        "fields": [
+            { "name": "Token", "value": format!("[{:?}]({})", migration.token_address, token_url(migration.token_address)), "inline": false },
+            { "name": "From", "value": migration.from_platform.as_str(), "inline": true },
+            { "name": "Pairs", "value": if pairs.is_empty() { "-".to_string() } else { pairs }, "inline": false },
+        ],
+        "footer": { "text": format!("block {}", migration.block_number) },
+    });
+    if let Some(ref timestamp) = migration.timestamp {
+        embed["timestamp"] = json!(timestamp);
+    }
+    embed
+}
+
+fn candle_embed(candle: &Candle) -> Value {
+    json!({
+        "title": format!("🕯️ {} candle {:?}", candle.interval.as_str(), candle.token_address),
+        "color": COLOR_CANDLE,
+        "description": format!(
+            "O {:.12} H {:.12} L {:.12} C {:.12} {}\n{} trades ({} buys / {} sells)",
+            candle.open, candle.high, candle.low, candle.close, candle.base_token, candle.trades, candle.buys, candle.sells
+        ),
+    })
+}
+
+#[async_trait]
+impl EventSink for DiscordSink {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let Some(class) = self.rules.classify(event) else {
+            return Ok(());
+        };
+        let embed = match event {
+            StreamEvent::Swap(swap) => swap_embed(class, swap),
+            StreamEvent::Migration(migration) => migration_embed(migration),
+            StreamEvent::Candle(candle) => candle_embed(candle),
+        };
+        self.post(&self.message(embed)).await
+    }
+}
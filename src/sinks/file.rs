@@ -0,0 +1,456 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::EventSink;
+use crate::core::candles::swap_time;
+use crate::types::{Candle, MigrationEvent, StreamEvent, SwapEvent};
+
+#[cfg(feature = "parquet")]
+use parquet::arrow::ArrowWriter;
+
+// Rows buffered before a Parquet record batch is written
+#[cfg(feature = "parquet")]
+const PARQUET_BATCH_ROWS: usize = 8192;
+
+/// Output format of a `FileSink`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    /// Snappy-compressed Parquet (requires the `parquet` feature)
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            #[cfg(feature = "parquet")]
+            FileFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ColumnType {
+    Text,
+    Int,
+    Float,
+}
+
+#[derive(Debug, Clone)]
+enum Cell {
+    Text(Option<String>),
+    Int(Option<i64>),
+    Float(Option<f64>),
+}
+
+impl Cell {
+    fn to_csv(&self) -> String {
+        match self {
+            Cell::Text(value) => value.clone().unwrap_or_default(),
+            Cell::Int(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+            Cell::Float(value) => value.map(|v| v.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+struct Table {
+    name: &'static str,
+    columns: &'static [(&'static str, ColumnType)],
+}
+
+const SWAPS: Table = Table {
+    name: "swaps",
+    columns: &[
+        ("tx_hash", ColumnType::Text),
+        ("log_index", ColumnType::Int),
+        ("block_number", ColumnType::Int),
+        ("time", ColumnType::Int),
+        ("platform", ColumnType::Text),
+        ("trade_type", ColumnType::Text),
+        ("token_address", ColumnType::Text),
+        ("token_symbol", ColumnType::Text),
+        ("token_amount", ColumnType::Float),
+        ("base_token_address", ColumnType::Text),
+        ("base_token_symbol", ColumnType::Text),
+        ("base_amount", ColumnType::Float),
+        ("price", ColumnType::Float),
+        ("price_usd", ColumnType::Float),
+        ("usd_value", ColumnType::Float),
+        ("trader", ColumnType::Text),
+        ("market", ColumnType::Text),
+    ],
+};
+
+const MIGRATIONS: Table = Table {
+    name: "migrations",
+    columns: &[
+        ("tx_hash", ColumnType::Text),
+        ("token_address", ColumnType::Text),
+        ("block_number", ColumnType::Int),
+        ("timestamp", ColumnType::Text),
+        ("from_platform", ColumnType::Text),
+        ("to_platform", ColumnType::Text),
+        ("pair_addresses", ColumnType::Text),
+    ],
+};
+
+const CANDLES: Table = Table {
+    name: "candles",
+    columns: &[
+        ("market", ColumnType::Text),
+        ("interval", ColumnType::Text),
+        ("open_time", ColumnType::Int),
+        ("close_time", ColumnType::Int),
+        ("token_address", ColumnType::Text),
+        ("base_token", ColumnType::Text),
+        ("open", ColumnType::Float),
+        ("high", ColumnType::Float),
+        ("low", ColumnType::Float),
+        ("close", ColumnType::Float),
+        ("volume_token", ColumnType::Float),
+        ("volume_base", ColumnType::Float),
+        ("volume_usd", ColumnType::Float),
+        ("trades", ColumnType::Int),
+        ("buys", ColumnType::Int),
+        ("sells", ColumnType::Int),
+    ],
+};
+
+fn text(value: impl ToString) -> Cell {
+    Cell::Text(Some(value.to_string()))
+}
+
+fn hex(value: impl std::fmt::Debug) -> Cell {
+    Cell::Text(Some(format!("{:?}", value)))
+}
+
+fn swap_row(swap: &SwapEvent) -> Vec<Cell> {
+    vec![
+        hex(swap.transaction_hash),
+        Cell::Int(Some(swap.log_index as i64)),
+        Cell::Int(Some(swap.block_number as i64)),
+        Cell::Int(Some(swap_time(swap))),
+        text(swap.platform.as_str()),
+        text(swap.trade_type.as_str()),
+        hex(swap.token.address),
+        text(&swap.token.symbol),
+        Cell::Float(swap.token.amount.parse().ok()),
+        hex(swap.base_token.address),
+        text(&swap.base_token.symbol),
+        Cell::Float(swap.base_token.amount.parse().ok()),
+        Cell::Float(Some(swap.price.value)),
+        Cell::Float(swap.price_usd),
+        Cell::Float(swap.usd_value),
+        Cell::Text(swap.trader.map(|trader| format!("{:?}", trader))),
+        Cell::Text(swap.pair_address.or(swap.bonding_curve_address).map(|market| format!("{:?}", market))),
+    ]
+}
+
+fn migration_row(migration: &MigrationEvent) -> Vec<Cell> {
+    vec![
+        hex(migration.transaction_hash),
+        hex(migration.token_address),
+        Cell::Int(Some(migration.block_number as i64)),
+        Cell::Text(migration.timestamp.clone()),
+        text(migration.from_platform.as_str()),
+        text(migration.to_platform.as_str()),
+        text(
+            migration
+                .pair_addresses
+                .iter()
+                .map(|pair| format!("{:?}", pair))
+                .collect::<Vec<_>>()
+                .join(";"),
+        ),
+    ]
+}
+
+fn candle_row(candle: &Candle) -> Vec<Cell> {
+    vec![
+        hex(candle.market),
+        text(candle.interval.as_str()),
+        Cell::Int(Some(candle.open_time)),
+        Cell::Int(Some(candle.close_time)),
+        hex(candle.token_address),
+        text(&candle.base_token),
+        Cell::Float(Some(candle.open)),
+        Cell::Float(Some(candle.high)),
+        Cell::Float(Some(candle.low)),
+        Cell::Float(Some(candle.close)),
+        Cell::Float(Some(candle.volume_token)),
+        Cell::Float(Some(candle.volume_base)),
+        Cell::Float(candle.volume_usd),
+        Cell::Int(Some(candle.trades as i64)),
+        Cell::Int(Some(candle.buys as i64)),
+        Cell::Int(Some(candle.sells as i64)),
+    ]
+}
+
+enum Writer {
+    Csv(csv::Writer<File>),
+    #[cfg(feature = "parquet")]
+    Parquet {
+        writer: ArrowWriter<File>,
+        rows: Vec<Vec<Cell>>,
+    },
+}
+
+struct OpenFile {
+    path: PathBuf,
+    opened_at: Instant,
+    // Bytes written (CSV: estimated from row sizes)
+    bytes: u64,
+    writer: Writer,
+}
+
+impl OpenFile {
+    fn create(dir: &Path, table: &Table, format: FileFormat) -> Result<Self> {
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S");
+        let mut path = dir.join(format!("{}-{}.{}", table.name, stamp, format.extension()));
+        let mut n = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}-{}.{}", table.name, stamp, n, format.extension()));
+            n += 1;
+        }
+        let file = File::create(&path)?;
+
+        let writer = match format {
+            FileFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(file);
+                writer.write_record(table.columns.iter().map(|(name, _)| *name))?;
+                Writer::Csv(writer)
+            }
+            #[cfg(feature = "parquet")]
+            FileFormat::Parquet => {
+                let properties = parquet::file::properties::WriterProperties::builder()
+                    .set_compression(parquet::basic::Compression::SNAPPY)
+                    .build();
+                Writer::Parquet {
+                    writer: ArrowWriter::try_new(file, parquet_schema(table), Some(properties))?,
+                    rows: Vec::new(),
+                }
+            }
+        };
+
+        log::info!("📁 [FILE] Writing {}", path.display());
+        Ok(Self {
+            path,
+            opened_at: Instant::now(),
+            bytes: 0,
+            writer,
+        })
+    }
+
+    #[cfg_attr(not(feature = "parquet"), allow(unused_variables))]
+    fn write(&mut self, table: &Table, row: Vec<Cell>) -> Result<()> {
+        match &mut self.writer {
+            Writer::Csv(writer) => {
+                let record: Vec<String> = row.iter().map(Cell::to_csv).collect();
+                self.bytes += record.iter().map(|field| field.len() as u64 + 1).sum::<u64>();
+                writer.write_record(&record)?;
+            }
+            #[cfg(feature = "parquet")]
+            Writer::Parquet { writer, rows } => {
+                rows.push(row);
+                if rows.len() >= PARQUET_BATCH_ROWS {
+                    writer.write(&record_batch(table, std::mem::take(rows))?)?;
+                }
+                self.bytes = (writer.bytes_written() + writer.in_progress_size()) as u64;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "parquet"), allow(unused_variables))]
+    fn flush(&mut self, table: &Table) -> Result<()> {
+        match &mut self.writer {
+            Writer::Csv(writer) => writer.flush()?,
+            #[cfg(feature = "parquet")]
+            Writer::Parquet { writer, rows } => {
+                if !rows.is_empty() {
+                    writer.write(&record_batch(table, std::mem::take(rows))?)?;
+                }
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(mut self, table: &Table) -> Result<()> {
+        self.flush(table)?;
+        #[cfg(feature = "parquet")]
+        if let Writer::Parquet { writer, .. } = self.writer {
+            writer.close()?;
+        }
+        log::debug!("📁 [FILE] Closed {}", self.path.display());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_schema(table: &Table) -> std::sync::Arc<arrow_schema::Schema> {
+    use arrow_schema::{DataType, Field, Schema};
+
+    let fields: Vec<Field> = table
+        .columns
+        .iter()
+        .map(|(name, kind)| {
+            let data_type = match kind {
+                ColumnType::Text => DataType::Utf8,
+                ColumnType::Int => DataType::Int64,
+                ColumnType::Float => DataType::Float64,
+            };
+            Field::new(*name, data_type, true)
+        })
+        .collect();
+    std::sync::Arc::new(Schema::new(fields))
+}
+
+#[cfg(feature = "parquet")]
+fn record_batch(table: &Table, rows: Vec<Vec<Cell>>) -> Result<arrow_array::RecordBatch> {
+    use arrow_array::{ArrayRef, Float64Array, Int64Array, StringArray};
+    use std::sync::Arc;
+
+    let columns: Vec<ArrayRef> = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, (_, kind))| -> ArrayRef {
+            let cells = rows.iter().map(|row| &row[i]);
+            match kind {
+                ColumnType::Text => Arc::new(StringArray::from_iter(cells.map(|cell| match cell {
+                    Cell::Text(value) => value.clone(),
+                    _ => None,
+                }))),
+                ColumnType::Int => Arc::new(Int64Array::from_iter(cells.map(|cell| match cell {
+                    Cell::Int(value) => *value,
+                    _ => None,
+                }))),
+                ColumnType::Float => Arc::new(Float64Array::from_iter(cells.map(|cell| match cell {
+                    Cell::Float(value) => *value,
+                    _ => None,
+                }))),
+            }
+        })
+        .collect();
+    Ok(arrow_array::RecordBatch::try_new(parquet_schema(table), columns)?)
+}
+
+#[derive(Default)]
+struct OpenFiles {
+    swaps: Option<OpenFile>,
+    migrations: Option<OpenFile>,
+    candles: Option<OpenFile>,
+}
+
+impl OpenFiles {
+    fn slots(&mut self) -> [(&mut Option<OpenFile>, &'static Table); 3] {
+        [
+            (&mut self.swaps, &SWAPS),
+            (&mut self.migrations, &MIGRATIONS),
+            (&mut self.candles, &CANDLES),
+        ]
+    }
+}
+
+/// Appends events to rotating CSV or Parquet files, one file series per event kind
+///
+/// Files are named `<dir>/<swaps|migrations|candles>-<UTC time>.<ext>` and
+/// rotated when they reach `max_bytes` or `max_age`. Parquet files are only
+/// readable once closed (on rotation or when the streamer stops).
+pub struct FileSink {
+    dir: PathBuf,
+    format: FileFormat,
+    max_bytes: u64,
+    max_age: Duration,
+    files: Mutex<OpenFiles>,
+}
+
+impl FileSink {
+    /// Write into `dir` (created if missing); rotates hourly or at 256 MiB by default
+    pub fn new(dir: impl AsRef<Path>, format: FileFormat) -> Result<Self> {
+        std::fs::create_dir_all(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            format,
+            max_bytes: 256 * 1024 * 1024,
+            max_age: Duration::from_secs(3600),
+            files: Mutex::new(OpenFiles::default()),
+        })
+    }
+
+    /// Start a new file once the current one reaches `bytes`
+    pub fn rotate_at_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// Start a new file once the current one is `age` old
+    pub fn rotate_every(mut self, age: Duration) -> Self {
+        self.max_age = age;
+        self
+    }
+
+    fn append(&self, slot: &mut Option<OpenFile>, table: &Table, row: Vec<Cell>) -> Result<()> {
+        let expired = slot
+            .as_ref()
+            .is_some_and(|file| file.bytes >= self.max_bytes || file.opened_at.elapsed() >= self.max_age);
+        if expired {
+            if let Some(file) = slot.take() {
+                file.finish(table)?;
+            }
+        }
+        let file = match slot {
+            Some(file) => file,
+            None => slot.insert(OpenFile::create(&self.dir, table, self.format)?),
+        };
+        file.write(table, row)
+    }
+}
+
+#[async_trait]
+impl EventSink for FileSink {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let mut files = self.files.lock().unwrap();
+        let files = &mut *files;
+        match event {
+            StreamEvent::Swap(swap) => self.append(&mut files.swaps, &SWAPS, swap_row(swap)),
+            StreamEvent::Migration(migration) => self.append(&mut files.migrations, &MIGRATIONS, migration_row(migration)),
+            StreamEvent::Candle(candle) => self.append(&mut files.candles, &CANDLES, candle_row(candle)),
+        }
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let mut files = self.files.lock().unwrap();
+        for (slot, table) in files.slots() {
+            if let Some(file) = slot {
+                file.flush(table)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        let mut files = self.files.lock().unwrap();
+        for (slot, table) in files.slots() {
+            if let Some(file) = slot.take() {
+                file.finish(table)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(5))
+    }
+}
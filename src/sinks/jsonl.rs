@@ -0,0 +1,75 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::EventSink;
+use crate::types::StreamEvent;
+
+/// Writes each event as one JSON object per line (NDJSON) to stdout or a file
+///
+/// Objects carry a `type` field (`swap`, `migration`, `candle`) next to the
+/// event's own fields, e.g. `jq 'select(.type == "swap") | .price.value'`.
+pub struct JsonLinesSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+    // Flush after every line so downstream tools see events immediately
+    line_buffered: bool,
+}
+
+impl JsonLinesSink {
+    /// Line-buffered output on stdout
+    pub fn stdout() -> Self {
+        Self {
+            writer: Mutex::new(Box::new(std::io::stdout())),
+            line_buffered: true,
+        }
+    }
+
+    /// Append to `path` (created if missing), flushed every second
+    pub fn file(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Mutex::new(Box::new(BufWriter::new(file))),
+            line_buffered: false,
+        })
+    }
+
+    /// Any writer (socket, pipe, in-memory buffer, ...), flushed every second
+    pub fn from_writer<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+            line_buffered: false,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for JsonLinesSink {
+    fn name(&self) -> &str {
+        "jsonl"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        let mut writer = self.writer.lock().unwrap();
+        writer.write_all(&line)?;
+        if self.line_buffered {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.writer.lock().unwrap().flush()?;
+        Ok(())
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        (!self.line_buffered).then_some(Duration::from_secs(1))
+    }
+}
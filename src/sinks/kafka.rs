@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+use rdkafka::util::Timeout;
+use std::time::Duration;
+
+use super::EventSink;
+use crate::types::StreamEvent;
+
+const DEFAULT_SWAP_TOPIC: &str = "bsc.swaps";
+const DEFAULT_MIGRATION_TOPIC: &str = "bsc.migrations";
+const DEFAULT_CANDLE_TOPIC: &str = "bsc.candles";
+
+/// Publishes events as JSON to Kafka, keyed by token address
+///
+/// Keying by token keeps each token's events ordered within its partition.
+pub struct KafkaSink {
+    producer: FutureProducer,
+    swap_topic: String,
+    migration_topic: String,
+    candle_topic: String,
+    timeout: Duration,
+}
+
+impl KafkaSink {
+    /// Producer for a comma-separated broker list, with idempotent delivery enabled
+    pub fn new(brokers: &str) -> Result<Self> {
+        let mut config = ClientConfig::new();
+        config
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .set("compression.type", "lz4");
+        Self::from_config(&config)
+    }
+
+    /// Producer from a full librdkafka configuration (SASL, TLS, ...)
+    pub fn from_config(config: &ClientConfig) -> Result<Self> {
+        Ok(Self {
+            producer: config.create()?,
+            swap_topic: DEFAULT_SWAP_TOPIC.to_string(),
+            migration_topic: DEFAULT_MIGRATION_TOPIC.to_string(),
+            candle_topic: DEFAULT_CANDLE_TOPIC.to_string(),
+            timeout: Duration::from_secs(5),
+        })
+    }
+
+    /// Topics for swaps and migrations (default `bsc.swaps` / `bsc.migrations`)
+    pub fn with_topics(mut self, swap_topic: &str, migration_topic: &str) -> Self {
+        self.swap_topic = swap_topic.to_string();
+        self.migration_topic = migration_topic.to_string();
+        self
+    }
+
+    /// Topic for closed candles (default `bsc.candles`)
+    pub fn with_candle_topic(mut self, topic: &str) -> Self {
+        self.candle_topic = topic.to_string();
+        self
+    }
+
+    /// How long a send may wait for queue space before failing
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let topic = match event {
+            StreamEvent::Swap(_) => &self.swap_topic,
+            StreamEvent::Migration(_) => &self.migration_topic,
+            StreamEvent::Candle(_) => &self.candle_topic,
+        };
+        let key = format!("{:?}", event.token_address());
+        let payload = serde_json::to_vec(event)?;
+
+        self.producer
+            .send(FutureRecord::to(topic).key(&key).payload(&payload), Timeout::After(self.timeout))
+            .await
+            .map_err(|(e, _)| anyhow!("Failed to publish to {}: {}", topic, e))?;
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let producer = self.producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || producer.flush(Timeout::After(timeout))).await??;
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+//! Pluggable outputs for stream events
+//!
+//! Sinks registered on the builder receive every swap (after the filter),
+//! migration and closed candle (when `on_candle` is set) of the streamer. Each sink runs in its own task behind a bounded
+//! queue, so a slow or failing sink never blocks the stream or other sinks.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::types::StreamEvent;
+
+pub mod alerts;
+pub mod clickhouse;
+pub mod discord;
+#[cfg(feature = "email")]
+pub mod email;
+pub mod file;
+pub mod jsonl;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "nats")]
+pub mod nats;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "redis")]
+pub mod redis;
+pub mod slack;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod webhook;
+
+pub use alerts::{render_template, AlertRules, EventClass, Severity};
+pub use clickhouse::ClickHouseSink;
+pub use discord::DiscordSink;
+#[cfg(feature = "email")]
+pub use email::EmailSink;
+pub use file::{FileFormat, FileSink};
+pub use jsonl::JsonLinesSink;
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+#[cfg(feature = "mqtt")]
+pub use mqtt::MqttSink;
+#[cfg(feature = "nats")]
+pub use nats::NatsSink;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresSink;
+#[cfg(feature = "redis")]
+pub use self::redis::{RedisMode, RedisSink};
+pub use slack::SlackSink;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteSink;
+pub use webhook::WebhookSink;
+
+// Events queued per sink before new ones are dropped
+const SINK_QUEUE_SIZE: usize = 1024;
+
+/// Destination for stream events (database, queue, webhook, ...)
+#[async_trait]
+pub trait EventSink: Send + Sync + 'static {
+    /// Name used in logs
+    fn name(&self) -> &str;
+
+    /// Handle one event; errors are logged and the event is skipped
+    async fn handle(&self, event: &StreamEvent) -> Result<()>;
+
+    /// Write out buffered events; called every `flush_interval`
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once after the last event when the streamer stops (defaults to `flush`)
+    async fn close(&self) -> Result<()> {
+        self.flush().await
+    }
+
+    /// How often `flush` is called while streaming (None: only on stop)
+    fn flush_interval(&self) -> Option<Duration> {
+        None
+    }
+}
+
+// Lets sinks built at runtime (e.g. from `Settings`) be passed to `StreamerBuilder::sink`
+#[async_trait]
+impl EventSink for Arc<dyn EventSink> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        self.as_ref().handle(event).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.as_ref().flush().await
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.as_ref().close().await
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        self.as_ref().flush_interval()
+    }
+}
+
+/// Sinks registered on a streamer
+#[derive(Clone, Default)]
+pub struct SinkSet {
+    sinks: Vec<Arc<dyn EventSink>>,
+    // Running workers, shared by clones so a handle can wait for them
+    workers: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl SinkSet {
+    pub fn push(&mut self, sink: Arc<dyn EventSink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Start one worker per sink; workers drain their queue and flush on cancellation
+    pub(crate) fn spawn(&self, cancel_token: &CancellationToken) -> SinkDispatcher {
+        let mut workers = self.workers.lock().unwrap();
+        let senders = self
+            .sinks
+            .iter()
+            .map(|sink| {
+                let (tx, rx) = mpsc::channel(SINK_QUEUE_SIZE);
+                workers.push(spawn_sink_worker(sink.clone(), rx, cancel_token.clone()));
+                (sink.name().to_string(), tx)
+            })
+            .collect();
+        SinkDispatcher { senders: Arc::new(senders) }
+    }
+
+    /// Wait for the workers to deliver their queued events and close (after cancellation)
+    pub(crate) async fn join(&self) {
+        let workers = std::mem::take(&mut *self.workers.lock().unwrap());
+        for worker in workers {
+            let _ = worker.await;
+        }
+    }
+}
+
+impl fmt::Debug for SinkSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.sinks.iter().map(|sink| sink.name())).finish()
+    }
+}
+
+/// Sending side of the running sink workers
+#[derive(Clone)]
+pub(crate) struct SinkDispatcher {
+    senders: Arc<Vec<(String, mpsc::Sender<StreamEvent>)>>,
+}
+
+impl SinkDispatcher {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Queue an event for every sink without waiting
+    pub(crate) fn send(&self, event: StreamEvent) {
+        for (name, tx) in self.senders.iter() {
+            match tx.try_send(event.clone()) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    log::warn!("⚠️ [SINK] {} is falling behind, dropping {} event", name, event.as_str());
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => {
+                    log::debug!("🔌 [SINK] {} has stopped, dropping {} event", name, event.as_str());
+                }
+            }
+        }
+    }
+}
+
+fn spawn_sink_worker(sink: Arc<dyn EventSink>, mut rx: mpsc::Receiver<StreamEvent>, cancel_token: CancellationToken) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        log::debug!("🚰 [SINK] {} started", sink.name());
+        let flush_interval = sink.flush_interval();
+        let mut flush_timer = tokio::time::interval(flush_interval.unwrap_or(Duration::from_secs(3600)));
+        flush_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        flush_timer.tick().await;
+        loop {
+            tokio::select! {
+                _ = cancel_token.cancelled() => break,
+                _ = flush_timer.tick(), if flush_interval.is_some() => {
+                    if let Err(e) = sink.flush().await {
+                        log::warn!("⚠️ [SINK] {} failed to flush: {}", sink.name(), e);
+                    }
+                }
+                event = rx.recv() => match event {
+                    Some(event) => handle_event(sink.as_ref(), &event).await,
+                    None => break,
+                },
+            }
+        }
+
+        // Deliver whatever was queued before the stop
+        rx.close();
+        while let Some(event) = rx.recv().await {
+            handle_event(sink.as_ref(), &event).await;
+        }
+        if let Err(e) = sink.close().await {
+            log::warn!("⚠️ [SINK] {} failed to close: {}", sink.name(), e);
+        }
+        log::debug!("🛑 [SINK] {} stopped", sink.name());
+    })
+}
+
+async fn handle_event(sink: &dyn EventSink, event: &StreamEvent) {
+    if let Err(e) = sink.handle(event).await {
+        log::warn!("⚠️ [SINK] {} failed to handle {} event: {}", sink.name(), event.as_str(), e);
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use super::EventSink;
+use crate::types::StreamEvent;
+
+pub use rumqttc::QoS;
+
+/// Publishes events as JSON to MQTT topics `<prefix>/<kind>s/<token>` (e.g. `bsc/swaps/0x...`)
+///
+/// Subscribe to `bsc/swaps/#` for all tokens. Migrations can be retained so
+/// clients connecting later still see the latest one.
+pub struct MqttSink {
+    client: AsyncClient,
+    prefix: String,
+    qos: QoS,
+    retain_migrations: bool,
+    // Stops the connection event loop when the sink is dropped
+    shutdown: CancellationToken,
+}
+
+impl MqttSink {
+    /// Connect to `host:port`; reconnects automatically while the sink is alive
+    pub async fn connect(host: &str, port: u16, client_id: &str) -> Result<Self> {
+        let mut options = MqttOptions::new(client_id, host, port);
+        options.set_keep_alive(Duration::from_secs(30));
+        Self::from_options(options).await
+    }
+
+    /// Connect with full MQTT options (credentials, TLS, ...)
+    pub async fn from_options(options: MqttOptions) -> Result<Self> {
+        let (client, mut event_loop) = AsyncClient::new(options, 256);
+        let shutdown = CancellationToken::new();
+
+        let stop = shutdown.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = stop.cancelled() => break,
+                    event = event_loop.poll() => match event {
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => log::info!("📡 [MQTT] Connected to broker"),
+                        Ok(_) => {}
+                        Err(e) => {
+                            log::warn!("⚠️ [MQTT] Connection error: {} - reconnecting", e);
+                            tokio::time::sleep(Duration::from_secs(2)).await;
+                        }
+                    },
+                }
+            }
+        });
+
+        Ok(Self {
+            client,
+            prefix: "bsc".to_string(),
+            qos: QoS::AtLeastOnce,
+            retain_migrations: false,
+            shutdown,
+        })
+    }
+
+    /// Topic prefix (default `bsc`)
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Delivery guarantee for published events (default at-least-once)
+    pub fn with_qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    /// Publish migrations with the retain flag
+    pub fn retain_migrations(mut self, retain: bool) -> Self {
+        self.retain_migrations = retain;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for MqttSink {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let topic = format!("{}/{}s/{:?}", self.prefix, event.as_str(), event.token_address());
+        let retain = self.retain_migrations && matches!(event, StreamEvent::Migration(_));
+        self.client
+            .publish(topic, self.qos, retain, serde_json::to_vec(event)?)
+            .await?;
+        Ok(())
+    }
+}
+
+impl Drop for MqttSink {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
@@ -0,0 +1,109 @@
+use anyhow::{anyhow, Result};
+use async_nats::jetstream;
+use async_trait::async_trait;
+use std::time::Duration;
+
+use super::EventSink;
+use crate::types::StreamEvent;
+
+// Publish attempts per event in JetStream mode before giving up
+const JETSTREAM_ATTEMPTS: u32 = 3;
+
+enum Publisher {
+    Core(async_nats::Client),
+    JetStream(jetstream::Context),
+}
+
+/// Publishes events as JSON to NATS subjects `<prefix>.<kind>s.<token>` (e.g. `bsc.swaps.0x...`)
+///
+/// Core NATS is fire-and-forget; JetStream mode waits for the server ack of
+/// every event and retries, giving at-least-once delivery.
+pub struct NatsSink {
+    publisher: Publisher,
+    prefix: String,
+}
+
+impl NatsSink {
+    /// Publish on core NATS (no persistence)
+    pub async fn connect(url: &str) -> Result<Self> {
+        Ok(Self {
+            publisher: Publisher::Core(async_nats::connect(url).await?),
+            prefix: "bsc".to_string(),
+        })
+    }
+
+    /// Publish to JetStream, creating `stream` (capturing `bsc.>`) if it does not exist
+    pub async fn connect_jetstream(url: &str, stream: &str) -> Result<Self> {
+        Self::connect_jetstream_with_prefix(url, stream, "bsc").await
+    }
+
+    /// JetStream publisher using a custom subject prefix
+    pub async fn connect_jetstream_with_prefix(url: &str, stream: &str, prefix: &str) -> Result<Self> {
+        let context = jetstream::new(async_nats::connect(url).await?);
+        context
+            .get_or_create_stream(jetstream::stream::Config {
+                name: stream.to_string(),
+                subjects: vec![format!("{}.>", prefix)],
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to create JetStream stream {}: {}", stream, e))?;
+
+        Ok(Self {
+            publisher: Publisher::JetStream(context),
+            prefix: prefix.to_string(),
+        })
+    }
+
+    /// Subject prefix for core NATS (default `bsc`)
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let subject = format!("{}.{}s.{:?}", self.prefix, event.as_str(), event.token_address());
+        let payload = serde_json::to_vec(event)?;
+
+        match &self.publisher {
+            Publisher::Core(client) => {
+                client.publish(subject, payload.into()).await?;
+            }
+            Publisher::JetStream(context) => {
+                let mut attempt = 1;
+                loop {
+                    let result = match context.publish(subject.clone(), payload.clone().into()).await {
+                        Ok(ack) => ack.await.map(|_| ()).map_err(|e| anyhow!("{}", e)),
+                        Err(e) => Err(anyhow!("{}", e)),
+                    };
+                    match result {
+                        Ok(()) => break,
+                        Err(e) if attempt >= JETSTREAM_ATTEMPTS => {
+                            return Err(e.context(format!("JetStream publish to {} failed {} times", subject, attempt)));
+                        }
+                        Err(e) => {
+                            log::debug!("🔁 [NATS] Publish to {} failed ({}), retrying", subject, e);
+                            tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+                            attempt += 1;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        if let Publisher::Core(client) = &self.publisher {
+            client.flush().await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::streams::StreamMaxlen;
+use redis::AsyncCommands;
+
+use super::EventSink;
+use crate::types::StreamEvent;
+
+/// How events are written to Redis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedisMode {
+    /// PUBLISH to `<prefix>:<kind>s:<token>` (fire-and-forget fan-out; use PSUBSCRIBE for all tokens)
+    Publish,
+    /// XADD to `<prefix>:<kind>s` with `token` and `data` fields, optionally trimmed to about `max_len` entries
+    Stream { max_len: Option<usize> },
+}
+
+/// Publishes events as JSON to Redis pub/sub channels or streams
+pub struct RedisSink {
+    connection: ConnectionManager,
+    mode: RedisMode,
+    prefix: String,
+}
+
+impl RedisSink {
+    /// Connect to `url` (e.g. `redis://127.0.0.1/`); the connection reconnects automatically
+    pub async fn connect(url: &str, mode: RedisMode) -> Result<Self> {
+        let client = redis::Client::open(url)?;
+        Ok(Self {
+            connection: ConnectionManager::new(client).await?,
+            mode,
+            prefix: "bsc".to_string(),
+        })
+    }
+
+    /// Channel/stream key prefix (default `bsc`)
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl EventSink for RedisSink {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let payload = serde_json::to_string(event)?;
+        let token = format!("{:?}", event.token_address());
+        let mut connection = self.connection.clone();
+
+        match self.mode {
+            RedisMode::Publish => {
+                let channel = format!("{}:{}s:{}", self.prefix, event.as_str(), token);
+                connection.publish::<_, _, ()>(channel, payload).await?;
+            }
+            RedisMode::Stream { max_len } => {
+                let key = format!("{}:{}s", self.prefix, event.as_str());
+                let fields = [("token", token), ("data", payload)];
+                match max_len {
+                    Some(max_len) => {
+                        connection
+                            .xadd_maxlen::<_, _, _, _, ()>(key, StreamMaxlen::Approx(max_len), "*", &fields)
+                            .await?
+                    }
+                    None => connection.xadd::<_, _, _, _, ()>(key, "*", &fields).await?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
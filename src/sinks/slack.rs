@@ -0,0 +1,151 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::alerts::{render_template, AlertRules, EventClass, Severity};
+use super::EventSink;
+use crate::types::StreamEvent;
+
+// Slack mrkdwn: <url|text> is a link
+const DEFAULT_SWAP_TEMPLATE: &str =
+    "*{side}* {amount} {symbol} for {base_amount} {base_symbol} ({usd}) @ {price} · <{tx_url}|tx>";
+const DEFAULT_WHALE_TEMPLATE: &str =
+    ":whale: *{side}* {amount} {symbol} for {base_amount} {base_symbol} (*{usd}*) by `{trader}` · <{tx_url}|tx>";
+const DEFAULT_MIGRATION_TEMPLATE: &str =
+    ":tada: *Migration* `{token}` moved from {from} to {to} at block {block} · pairs: {pairs} · <{tx_url}|tx>";
+const DEFAULT_CANDLE_TEMPLATE: &str = "{interval} candle `{token}`: O {open} H {high} L {low} C {close} {base_symbol} ({trades} trades)";
+
+/// Posts events to Slack incoming webhooks
+///
+/// Events are classified with the same `AlertRules` as the Discord and email sinks.
+/// Incoming webhooks are bound to a channel, so events are routed by severity
+/// (migrations critical, whale trades warning, the rest info) or, taking
+/// precedence, by class. Each class is formatted with its own template, see
+/// `sinks::alerts`.
+///
+/// # Example
+/// ```rust,no_run
+/// use bsc_streamer::sinks::{EventClass, Severity, SlackSink};
+///
+/// let slack = SlackSink::new("https://hooks.slack.com/services/T000/B000/feed")
+///     .with_classes(&[EventClass::Whale { min_usd: 25_000.0 }, EventClass::Migration])
+///     .route_severity(Severity::Critical, "https://hooks.slack.com/services/T000/B000/alerts")
+///     .route_severity(Severity::Warning, "https://hooks.slack.com/services/T000/B000/trades")
+///     .with_template(EventClass::Migration, "<!here> {token} graduated to {to}: {tx_url}");
+/// ```
+pub struct SlackSink {
+    client: reqwest::Client,
+    default_url: String,
+    rules: AlertRules,
+    routes: Vec<(EventClass, String)>,
+    severity_routes: Vec<(Severity, String)>,
+    templates: HashMap<&'static str, String>,
+}
+
+impl SlackSink {
+    /// Events without a class or severity route go to `url`
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            default_url: url.to_string(),
+            rules: AlertRules::default(),
+            routes: Vec::new(),
+            severity_routes: Vec::new(),
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Post only these event classes (buys, sells and migrations by default)
+    pub fn with_classes(mut self, classes: &[EventClass]) -> Self {
+        self.rules.set_classes(classes);
+        self
+    }
+
+    /// Send events of `class` to another webhook, posting the class if it is not yet configured
+    ///
+    /// Routed whale trades are checked before buys and sells.
+    pub fn route(mut self, class: EventClass, url: &str) -> Self {
+        self.rules.add_class(class);
+        self.routes.retain(|(routed, _)| *routed != class);
+        self.routes.push((class, url.to_string()));
+        self
+    }
+
+    /// Send events of `severity` to another webhook
+    pub fn route_severity(mut self, severity: Severity, url: &str) -> Self {
+        self.severity_routes.retain(|(routed, _)| *routed != severity);
+        self.severity_routes.push((severity, url.to_string()));
+        self
+    }
+
+    /// Change the severity events of `class` are routed with
+    pub fn with_severity(mut self, class: EventClass, severity: Severity) -> Self {
+        self.rules.set_severity(class, severity);
+        self
+    }
+
+    /// Message text for events of `class` (Slack mrkdwn with `{placeholder}`s)
+    pub fn with_template(mut self, class: EventClass, template: &str) -> Self {
+        self.templates.insert(class.as_str(), template.to_string());
+        self
+    }
+
+    fn url(&self, class: EventClass) -> &str {
+        let severity = self.rules.severity(class);
+        self.routes
+            .iter()
+            .find(|(routed, _)| *routed == class)
+            .map(|(_, url)| url)
+            .or_else(|| {
+                self.severity_routes
+                    .iter()
+                    .find(|(routed, _)| *routed == severity)
+                    .map(|(_, url)| url)
+            })
+            .unwrap_or(&self.default_url)
+    }
+
+    fn template(&self, class: EventClass) -> &str {
+        if let Some(template) = self.templates.get(class.as_str()) {
+            return template;
+        }
+        match class {
+            EventClass::Buy | EventClass::Sell => DEFAULT_SWAP_TEMPLATE,
+            EventClass::Whale { .. } => DEFAULT_WHALE_TEMPLATE,
+            EventClass::Migration => DEFAULT_MIGRATION_TEMPLATE,
+            EventClass::Candle => DEFAULT_CANDLE_TEMPLATE,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for SlackSink {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let Some(class) = self.rules.classify(event) else {
+            return Ok(());
+        };
+        let text = render_template(self.template(class), class, event);
+        let response = self
+            .client
+            .post(self.url(class))
+            .json(&json!({ "text": text }))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Slack request failed: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Slack returned status {}: {}", status, body));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::types::Address;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::{QueryBuilder, Row, Sqlite};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use super::EventSink;
+use crate::core::candles::swap_time;
+use crate::types::StreamEvent;
+
+static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations/sqlite");
+
+// Rows per INSERT statement (SQLite allows at most 32766 bind parameters)
+const ROWS_PER_STATEMENT: usize = 1000;
+
+/// Durable event history in a local SQLite file, with simple queries
+///
+/// Events are buffered and written in one transaction per batch; re-delivered
+/// events replace the stored copy.
+pub struct SqliteSink {
+    pool: SqlitePool,
+    batch_size: usize,
+    flush_interval: Duration,
+    buffer: Mutex<Vec<StreamEvent>>,
+}
+
+impl SqliteSink {
+    /// Open (or create) the database at `path` and apply the schema
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new().max_connections(4).connect_with(options).await?;
+        MIGRATOR.run(&pool).await?;
+
+        Ok(Self {
+            pool,
+            batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            buffer: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Write once `size` events are buffered, or every `interval` (default 100 / 1s)
+    pub fn with_batching(mut self, size: usize, interval: Duration) -> Self {
+        self.batch_size = size.max(1);
+        self.flush_interval = interval;
+        self
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Stored events with `from <= time < to` (unix seconds), oldest first
+    ///
+    /// Buffered events not yet written are not included.
+    pub async fn events_between(&self, from: i64, to: i64, token: Option<Address>) -> Result<Vec<StreamEvent>> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT data FROM events WHERE time >= ");
+        query.push_bind(from).push(" AND time < ").push_bind(to);
+        if let Some(token) = token {
+            query.push(" AND token_address = ").push_bind(format!("{:?}", token));
+        }
+        query.push(" ORDER BY time, id");
+        let rows = query.build().fetch_all(&self.pool).await?;
+        decode_rows(rows)
+    }
+
+    /// Latest `n` stored events, oldest first
+    pub async fn last_n_events(&self, n: usize, token: Option<Address>) -> Result<Vec<StreamEvent>> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT data FROM events");
+        if let Some(token) = token {
+            query.push(" WHERE token_address = ").push_bind(format!("{:?}", token));
+        }
+        query.push(" ORDER BY time DESC, id DESC LIMIT ").push_bind(n as i64);
+        let rows = query.build().fetch_all(&self.pool).await?;
+        let mut events = decode_rows(rows)?;
+        events.reverse();
+        Ok(events)
+    }
+
+    async fn write(&self, events: Vec<StreamEvent>) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let rows = events
+            .iter()
+            .map(|event| Ok((event_key(event), event_time(event), serde_json::to_string(event)?, event)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut tx = self.pool.begin().await?;
+        for chunk in rows.chunks(ROWS_PER_STATEMENT) {
+            let mut query = QueryBuilder::<Sqlite>::new("INSERT INTO events (event_key, kind, token_address, time, data) ");
+            query.push_values(chunk, |mut row, (key, time, data, event)| {
+                row.push_bind(key.clone())
+                    .push_bind(event.as_str())
+                    .push_bind(format!("{:?}", event.token_address()))
+                    .push_bind(*time)
+                    .push_bind(data.clone());
+            });
+            query.push(" ON CONFLICT (event_key) DO UPDATE SET time = excluded.time, data = excluded.data");
+            query.build().execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+
+        log::debug!("💾 [SQLITE] Wrote {} event(s)", events.len());
+        Ok(())
+    }
+}
+
+fn decode_rows(rows: Vec<sqlx::sqlite::SqliteRow>) -> Result<Vec<StreamEvent>> {
+    rows.iter()
+        .map(|row| Ok(serde_json::from_str(row.try_get::<&str, _>("data")?)?))
+        .collect()
+}
+
+fn event_key(event: &StreamEvent) -> String {
+    match event {
+        StreamEvent::Swap(swap) => format!("swap:{:?}:{}", swap.transaction_hash, swap.log_index),
+        StreamEvent::Migration(migration) => format!("migration:{:?}", migration.transaction_hash),
+        StreamEvent::Candle(candle) => format!("candle:{:?}:{}:{}", candle.market, candle.interval.as_str(), candle.open_time),
+    }
+}
+
+fn event_time(event: &StreamEvent) -> i64 {
+    match event {
+        StreamEvent::Swap(swap) => swap_time(swap),
+        StreamEvent::Migration(migration) => migration
+            .timestamp
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .map(|dt| dt.timestamp())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        StreamEvent::Candle(candle) => candle.open_time,
+    }
+}
+
+#[async_trait]
+impl EventSink for SqliteSink {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let ready = {
+            let mut buffer = self.buffer.lock().await;
+            buffer.push(event.clone());
+            if buffer.len() >= self.batch_size {
+                std::mem::take(&mut *buffer)
+            } else {
+                Vec::new()
+            }
+        };
+        self.write(ready).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.buffer.lock().await);
+        self.write(pending).await
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        Some(self.flush_interval)
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::EventSink;
+use crate::types::StreamEvent;
+
+// Header carrying the hex HMAC-SHA256 of the request body
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+/// POSTs events as JSON to a URL
+///
+/// With a batch size of 1 (default) each event is sent as a JSON object;
+/// larger batches are sent as a JSON array once full, or every `batch_delay`.
+/// Failed requests (network errors, 429 and 5xx) are retried with exponential
+/// backoff; other 4xx responses are not retried.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    secret: Option<Vec<u8>>,
+    batch_size: usize,
+    batch_delay: Duration,
+    max_retries: u32,
+    initial_backoff: Duration,
+    batch: Mutex<Vec<StreamEvent>>,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url: url.to_string(),
+            secret: None,
+            batch_size: 1,
+            batch_delay: Duration::from_secs(5),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            batch: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sign bodies with HMAC-SHA256 (hex, `sha256=` prefixed) in the `X-Signature-256` header
+    pub fn with_secret(mut self, secret: &str) -> Self {
+        self.secret = Some(secret.as_bytes().to_vec());
+        self
+    }
+
+    /// Send events in JSON arrays of up to `size`, flushing partial batches every `delay`
+    pub fn with_batching(mut self, size: usize, delay: Duration) -> Self {
+        self.batch_size = size.max(1);
+        self.batch_delay = delay;
+        self
+    }
+
+    /// Retry failed requests up to `max_retries` times, doubling `initial_backoff` each time
+    pub fn with_retries(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    fn sign(&self, body: &[u8]) -> Option<String> {
+        let secret = self.secret.as_ref()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).ok()?;
+        mac.update(body);
+        Some(format!("sha256={}", ethers::utils::hex::encode(mac.finalize().into_bytes())))
+    }
+
+    async fn post(&self, body: Vec<u8>) -> Result<()> {
+        let signature = self.sign(&body);
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+
+        loop {
+            let mut request = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(ref signature) = signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        return Err(anyhow!("Webhook rejected events with status {}", status));
+                    }
+                    anyhow!("Webhook returned status {}", status)
+                }
+                Err(e) => anyhow!("Webhook request failed: {}", e),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error.context(format!("giving up after {} retries", attempt)));
+            }
+            attempt += 1;
+            log::debug!("🔁 [WEBHOOK] {} - retry {}/{} in {:?}", error, attempt, self.max_retries, backoff);
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    async fn send_batch(&self, events: Vec<StreamEvent>) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let body = if self.batch_size == 1 && events.len() == 1 {
+            serde_json::to_vec(&events[0])?
+        } else {
+            serde_json::to_vec(&events)?
+        };
+        self.post(body).await
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn handle(&self, event: &StreamEvent) -> Result<()> {
+        let ready = {
+            let mut batch = self.batch.lock().unwrap();
+            batch.push(event.clone());
+            if batch.len() >= self.batch_size {
+                std::mem::take(&mut *batch)
+            } else {
+                Vec::new()
+            }
+        };
+        self.send_batch(ready).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.batch.lock().unwrap());
+        self.send_batch(pending).await
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        (self.batch_size > 1).then_some(self.batch_delay)
+    }
+}
@@ -0,0 +1,354 @@
+//! Buy and sell tokens through the venue they trade on (requires the `trader` feature)
+//!
+//! `Trader` wraps a signing client (usually `SignerMiddleware`) and routes each
+//! order like the streamer does: tokens still on a Four.meme bonding curve are
+//! traded on the curve, graduated tokens through the best PancakeSwap pair.
+//! Orders are priced first and sent with a minimum output derived from the
+//! slippage tolerance.
+//!
+//! # Example
+//! ```rust,no_run
+//! use bsc_streamer::trader::Trader;
+//! use bsc_streamer::{StreamerBuilder, TradeType};
+//! use ethers::middleware::SignerMiddleware;
+//! use ethers::providers::{Provider, Ws};
+//! use ethers::signers::{LocalWallet, Signer};
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = Provider::<Ws>::connect("wss://bsc.publicnode.com").await?;
+//! let wallet: LocalWallet = std::env::var("PRIVATE_KEY")?.parse::<LocalWallet>()?.with_chain_id(56u64);
+//! let trader = Arc::new(Trader::new(Arc::new(SignerMiddleware::new(provider.clone(), wallet))));
+//!
+//! StreamerBuilder::new(Arc::new(provider))
+//!     .token_address("0x...")
+//!     .auto_detect()
+//!     .on_swap(move |swap| {
+//!         // Callbacks are synchronous: spawn the order
+//!         if swap.trade_type == TradeType::Buy && swap.usd_value.unwrap_or_default() > 10_000.0 {
+//!             let trader = trader.clone();
+//!             tokio::spawn(async move {
+//!                 match trader.buy(swap.token.address, 0.1, 5.0).await {
+//!                     Ok(trade) => println!("Bought in {:?}", trade.transaction_hash),
+//!                     Err(e) => eprintln!("Buy failed: {}", e),
+//!                 }
+//!             });
+//!         }
+//!     })
+//!     .start()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{anyhow, bail, Result};
+use ethers::{
+    abi::{Abi, Token},
+    contract::{Contract, ContractCall},
+    providers::Middleware,
+    types::{Address, Bytes, TransactionReceipt, U256},
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{get_fourmeme_helper_address, get_v2_router_address, get_v3_router_address, get_wbnb_address};
+use crate::core::bonding_curve::{HelperTokenInfo, HELPER_ABI};
+use crate::core::pair_finder::PairFinder;
+use crate::core::quoter::{from_raw_amount, to_raw_amount, Quoter};
+use crate::core::token_info::TokenInfoCache;
+use crate::types::{PairInfo, Platform, TradeExecution, TradeQuote, TradeType};
+
+const ROUTER_V2_ABI: &str = r#"[
+    {"inputs":[{"name":"amountOutMin","type":"uint256"},{"name":"path","type":"address[]"},{"name":"to","type":"address"},{"name":"deadline","type":"uint256"}],"name":"swapExactETHForTokensSupportingFeeOnTransferTokens","outputs":[],"stateMutability":"payable","type":"function"},
+    {"inputs":[{"name":"amountIn","type":"uint256"},{"name":"amountOutMin","type":"uint256"},{"name":"path","type":"address[]"},{"name":"to","type":"address"},{"name":"deadline","type":"uint256"}],"name":"swapExactTokensForETHSupportingFeeOnTransferTokens","outputs":[],"stateMutability":"nonpayable","type":"function"}
+]"#;
+
+const ROUTER_V3_ABI: &str = r#"[
+    {"inputs":[{"components":[{"name":"tokenIn","type":"address"},{"name":"tokenOut","type":"address"},{"name":"fee","type":"uint24"},{"name":"recipient","type":"address"},{"name":"deadline","type":"uint256"},{"name":"amountIn","type":"uint256"},{"name":"amountOutMinimum","type":"uint256"},{"name":"sqrtPriceLimitX96","type":"uint160"}],"name":"params","type":"tuple"}],"name":"exactInputSingle","outputs":[{"name":"amountOut","type":"uint256"}],"stateMutability":"payable","type":"function"},
+    {"inputs":[{"name":"amountMinimum","type":"uint256"},{"name":"recipient","type":"address"}],"name":"unwrapWETH9","outputs":[],"stateMutability":"payable","type":"function"},
+    {"inputs":[{"name":"data","type":"bytes[]"}],"name":"multicall","outputs":[{"name":"results","type":"bytes[]"}],"stateMutability":"payable","type":"function"}
+]"#;
+
+// Four.meme TokenManagerHelper3 pre-trade estimates
+const CURVE_HELPER_ABI: &str = r#"[
+    {"inputs":[{"name":"token","type":"address"},{"name":"amount","type":"uint256"},{"name":"funds","type":"uint256"}],"name":"tryBuy","outputs":[{"name":"tokenManager","type":"address"},{"name":"quote","type":"address"},{"name":"estimatedAmount","type":"uint256"},{"name":"estimatedCost","type":"uint256"},{"name":"estimatedFee","type":"uint256"},{"name":"amountMsgValue","type":"uint256"},{"name":"amountApproval","type":"uint256"},{"name":"amountFunds","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"token","type":"address"},{"name":"amount","type":"uint256"}],"name":"trySell","outputs":[{"name":"tokenManager","type":"address"},{"name":"quote","type":"address"},{"name":"funds","type":"uint256"},{"name":"fee","type":"uint256"}],"stateMutability":"view","type":"function"}
+]"#;
+
+// Four.meme TokenManager2 trading functions
+const TOKEN_MANAGER_ABI: &str = r#"[
+    {"inputs":[{"name":"token","type":"address"},{"name":"funds","type":"uint256"},{"name":"minAmount","type":"uint256"}],"name":"buyTokenAMAP","outputs":[],"stateMutability":"payable","type":"function"},
+    {"inputs":[{"name":"origin","type":"uint256"},{"name":"token","type":"address"},{"name":"amount","type":"uint256"},{"name":"minFunds","type":"uint256"}],"name":"sellToken","outputs":[],"stateMutability":"nonpayable","type":"function"}
+]"#;
+
+const ERC20_ABI: &str = r#"[
+    {"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"name":"allowance","outputs":[{"name":"","type":"uint256"}],"stateMutability":"view","type":"function"},
+    {"inputs":[{"name":"spender","type":"address"},{"name":"amount","type":"uint256"}],"name":"approve","outputs":[{"name":"","type":"bool"}],"stateMutability":"nonpayable","type":"function"}
+]"#;
+
+type TryBuy = (Address, Address, U256, U256, U256, U256, U256, U256);
+
+/// Sends buy and sell orders from the client's default sender
+pub struct Trader<M> {
+    client: Arc<M>,
+    pair_finder: PairFinder<M>,
+    deadline: Duration,
+}
+
+impl<M: Middleware + 'static> Trader<M> {
+    /// `client` must sign for its default sender (e.g. `SignerMiddleware`)
+    pub fn new(client: Arc<M>) -> Self {
+        Self {
+            pair_finder: PairFinder::new(client.clone()),
+            client,
+            deadline: Duration::from_secs(60),
+        }
+    }
+
+    /// How long DEX orders stay valid after being sent (default 60s)
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Only route through DEX pairs with at least this much liquidity in USD
+    pub fn with_min_liquidity(mut self, usd: f64) -> Self {
+        self.pair_finder = self.pair_finder.with_min_liquidity(usd);
+        self
+    }
+
+    /// Spend `bnb_amount` BNB on the token, accepting up to `slippage_pct` less than quoted
+    pub async fn buy(&self, token: Address, bnb_amount: f64, slippage_pct: f64) -> Result<TradeExecution> {
+        let slippage_bps = slippage_bps(slippage_pct)?;
+        if self.on_curve(token).await? {
+            return self.buy_on_curve(token, to_raw_amount(bnb_amount, 18)?, slippage_bps).await;
+        }
+
+        let pairs = self.pair_finder.find_pairs(token).await?;
+        let quote = Quoter::new(self.client.clone())?.quote_buy(&pairs, bnb_amount).await?;
+        let min_out = apply_slippage(quote.amount_out_raw, slippage_bps);
+        let me = self.sender()?;
+
+        let receipt = match pair_of(&pairs, &quote)? {
+            pair if pair.is_v3 => {
+                let router = contract(&self.client, get_v3_router_address(), ROUTER_V3_ABI)?;
+                let params = exact_input_single(pair, get_wbnb_address(), token, me, self.deadline_ts(), quote.amount_in_raw, min_out);
+                let call = router.method::<_, U256>("exactInputSingle", (params,))?.value(quote.amount_in_raw);
+                send(call, "Buy").await?
+            }
+            _ => {
+                let router = contract(&self.client, get_v2_router_address(), ROUTER_V2_ABI)?;
+                let call = router
+                    .method::<_, ()>(
+                        "swapExactETHForTokensSupportingFeeOnTransferTokens",
+                        (min_out, quote.path.clone(), me, self.deadline_ts()),
+                    )?
+                    .value(quote.amount_in_raw);
+                send(call, "Buy").await?
+            }
+        };
+        let decimals = self.decimals(token).await?;
+        Ok(execution(&quote, TradeType::Buy, receipt, from_raw_amount(min_out, decimals)))
+    }
+
+    /// Sell `token_amount` tokens for BNB, accepting up to `slippage_pct` less than quoted
+    ///
+    /// The router or token manager is approved first if its allowance is too low.
+    pub async fn sell(&self, token: Address, token_amount: f64, slippage_pct: f64) -> Result<TradeExecution> {
+        let slippage_bps = slippage_bps(slippage_pct)?;
+        let amount = to_raw_amount(token_amount, self.decimals(token).await?)?;
+        if self.on_curve(token).await? {
+            return self.sell_on_curve(token, amount, slippage_bps).await;
+        }
+
+        let pairs = self.pair_finder.find_pairs(token).await?;
+        let quote = Quoter::new(self.client.clone())?.quote_sell(&pairs, token_amount).await?;
+        let min_out = apply_slippage(quote.amount_out_raw, slippage_bps);
+        let me = self.sender()?;
+
+        let receipt = match pair_of(&pairs, &quote)? {
+            pair if pair.is_v3 => {
+                let router = contract(&self.client, get_v3_router_address(), ROUTER_V3_ABI)?;
+                self.approve(token, router.address(), amount).await?;
+                // Swap to WBNB held by the router, then unwrap it to the sender
+                let params = exact_input_single(pair, token, get_wbnb_address(), router.address(), self.deadline_ts(), amount, min_out);
+                let swap = router.method::<_, U256>("exactInputSingle", (params,))?.calldata();
+                let unwrap = router.method::<_, ()>("unwrapWETH9", (min_out, me))?.calldata();
+                let (Some(swap), Some(unwrap)) = (swap, unwrap) else {
+                    bail!("Failed to encode the swap");
+                };
+                send(router.method::<_, Vec<Bytes>>("multicall", (vec![swap, unwrap],))?, "Sell").await?
+            }
+            _ => {
+                let router = contract(&self.client, get_v2_router_address(), ROUTER_V2_ABI)?;
+                self.approve(token, router.address(), amount).await?;
+                let call = router.method::<_, ()>(
+                    "swapExactTokensForETHSupportingFeeOnTransferTokens",
+                    (amount, min_out, quote.path.clone(), me, self.deadline_ts()),
+                )?;
+                send(call, "Sell").await?
+            }
+        };
+        Ok(execution(&quote, TradeType::Sell, receipt, from_raw_amount(min_out, 18)))
+    }
+
+    /// Whether the token is still trading on its Four.meme bonding curve
+    async fn on_curve(&self, token: Address) -> Result<bool> {
+        let helper = contract(&self.client, get_fourmeme_helper_address(), HELPER_ABI)?;
+        let info: Result<HelperTokenInfo, _> = helper.method("getTokenInfo", token)?.call().await;
+        Ok(match info {
+            Ok((version, token_manager, .., liquidity_added)) => !version.is_zero() && !token_manager.is_zero() && !liquidity_added,
+            // Tokens the helper does not know revert
+            Err(_) => false,
+        })
+    }
+
+    async fn buy_on_curve(&self, token: Address, funds: U256, slippage_bps: u64) -> Result<TradeExecution> {
+        let helper = contract(&self.client, get_fourmeme_helper_address(), CURVE_HELPER_ABI)?;
+        let (token_manager, quote_token, estimated_amount, _, _, msg_value, _, amount_funds): TryBuy =
+            helper.method("tryBuy", (token, U256::zero(), funds))?.call().await?;
+        if !quote_token.is_zero() {
+            bail!("{:?} is quoted in {:?} on the curve; only BNB-quoted curves can be bought", token, quote_token);
+        }
+
+        let min_amount = apply_slippage(estimated_amount, slippage_bps);
+        let manager = contract(&self.client, token_manager, TOKEN_MANAGER_ABI)?;
+        let call = manager.method::<_, ()>("buyTokenAMAP", (token, amount_funds, min_amount))?.value(msg_value);
+        let receipt = send(call, "Bonding curve buy").await?;
+
+        let decimals = self.decimals(token).await?;
+        Ok(TradeExecution {
+            token_address: token,
+            trade_type: TradeType::Buy,
+            platform: Platform::FourMemeBondingCurve,
+            pair_address: None,
+            transaction_hash: receipt.transaction_hash,
+            block_number: receipt.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+            amount_in: from_raw_amount(funds, 18),
+            expected_out: from_raw_amount(estimated_amount, decimals),
+            min_out: from_raw_amount(min_amount, decimals),
+            gas_used: receipt.gas_used,
+        })
+    }
+
+    async fn sell_on_curve(&self, token: Address, amount: U256, slippage_bps: u64) -> Result<TradeExecution> {
+        let helper = contract(&self.client, get_fourmeme_helper_address(), CURVE_HELPER_ABI)?;
+        let (token_manager, quote_token, funds, fee): (Address, Address, U256, U256) =
+            helper.method("trySell", (token, amount))?.call().await?;
+        if !quote_token.is_zero() {
+            bail!("{:?} is quoted in {:?} on the curve; only BNB-quoted curves can be sold", token, quote_token);
+        }
+
+        let expected = funds.saturating_sub(fee);
+        let min_funds = apply_slippage(expected, slippage_bps);
+        self.approve(token, token_manager, amount).await?;
+        let manager = contract(&self.client, token_manager, TOKEN_MANAGER_ABI)?;
+        let call = manager.method::<_, ()>("sellToken", (U256::zero(), token, amount, min_funds))?;
+        let receipt = send(call, "Bonding curve sell").await?;
+
+        Ok(TradeExecution {
+            token_address: token,
+            trade_type: TradeType::Sell,
+            platform: Platform::FourMemeBondingCurve,
+            pair_address: None,
+            transaction_hash: receipt.transaction_hash,
+            block_number: receipt.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+            amount_in: from_raw_amount(amount, self.decimals(token).await?),
+            expected_out: from_raw_amount(expected, 18),
+            min_out: from_raw_amount(min_funds, 18),
+            gas_used: receipt.gas_used,
+        })
+    }
+
+    /// Approve `spender` for the token unless it may already spend `amount`
+    async fn approve(&self, token: Address, spender: Address, amount: U256) -> Result<()> {
+        let erc20 = contract(&self.client, token, ERC20_ABI)?;
+        let allowance: U256 = erc20.method("allowance", (self.sender()?, spender))?.call().await?;
+        if allowance >= amount {
+            return Ok(());
+        }
+        log::info!("🔓 [TRADER] Approving {:?} to spend {:?}", spender, token);
+        send(erc20.method::<_, bool>("approve", (spender, U256::MAX))?, "Approval").await?;
+        Ok(())
+    }
+
+    async fn decimals(&self, token: Address) -> Result<u8> {
+        Ok(TokenInfoCache::new(self.client.clone()).get_token_info(token).await?.decimals)
+    }
+
+    fn sender(&self) -> Result<Address> {
+        self.client
+            .default_sender()
+            .ok_or_else(|| anyhow!("The trader's client has no default sender - use a SignerMiddleware"))
+    }
+
+    fn deadline_ts(&self) -> U256 {
+        U256::from((chrono::Utc::now().timestamp() as u64) + self.deadline.as_secs())
+    }
+}
+
+fn contract<M: Middleware>(client: &Arc<M>, address: Address, abi: &str) -> Result<Contract<M>> {
+    let abi: Abi = serde_json::from_str(abi)?;
+    Ok(Contract::new(address, abi, client.clone()))
+}
+
+fn exact_input_single(pair: &PairInfo, token_in: Address, token_out: Address, recipient: Address, deadline: U256, amount_in: U256, min_out: U256) -> Token {
+    Token::Tuple(vec![
+        Token::Address(token_in),
+        Token::Address(token_out),
+        Token::Uint(U256::from(pair.fee_tier)),
+        Token::Address(recipient),
+        Token::Uint(deadline),
+        Token::Uint(amount_in),
+        Token::Uint(min_out),
+        Token::Uint(U256::zero()),
+    ])
+}
+
+fn pair_of<'a>(pairs: &'a [PairInfo], quote: &TradeQuote) -> Result<&'a PairInfo> {
+    pairs
+        .iter()
+        .find(|p| p.pair_address == quote.pair_address)
+        .ok_or_else(|| anyhow!("Quoted pair {:?} not found", quote.pair_address))
+}
+
+fn slippage_bps(slippage_pct: f64) -> Result<u64> {
+    if !(0.0..100.0).contains(&slippage_pct) {
+        bail!("Slippage must be between 0 and 100%, got {}", slippage_pct);
+    }
+    Ok((slippage_pct * 100.0).round() as u64)
+}
+
+fn apply_slippage(amount: U256, slippage_bps: u64) -> U256 {
+    amount * U256::from(10_000 - slippage_bps) / U256::from(10_000)
+}
+
+/// Send a transaction and wait for a successful receipt
+async fn send<M: Middleware + 'static, D: ethers::abi::Detokenize>(call: ContractCall<M, D>, what: &str) -> Result<TransactionReceipt> {
+    let pending = call.send().await.map_err(|e| anyhow!("{} failed to send: {}", what, e))?;
+    let hash = *pending;
+    log::info!("📤 [TRADER] {} sent: {:?}", what, hash);
+    let receipt = pending
+        .await
+        .map_err(|e| anyhow!("{} {:?} failed: {}", what, hash, e))?
+        .ok_or_else(|| anyhow!("{} {:?} was dropped", what, hash))?;
+    if receipt.status != Some(1u64.into()) {
+        bail!("{} {:?} reverted", what, hash);
+    }
+    Ok(receipt)
+}
+
+fn execution(quote: &TradeQuote, trade_type: TradeType, receipt: TransactionReceipt, min_out: f64) -> TradeExecution {
+    TradeExecution {
+        token_address: quote.token_address,
+        trade_type,
+        platform: Platform::PancakeSwap,
+        pair_address: Some(quote.pair_address),
+        transaction_hash: receipt.transaction_hash,
+        block_number: receipt.block_number.map(|b| b.as_u64()).unwrap_or_default(),
+        amount_in: quote.amount_in,
+        expected_out: quote.amount_out,
+        min_out,
+        gas_used: receipt.gas_used,
+    }
+}
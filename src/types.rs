@@ -1,20 +1,73 @@
-use ethers::types::{Address, H256};
+use ethers::types::{Address, H256, U256};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Version of the serialized `SwapEvent` / `MigrationEvent` JSON schema
+///
+/// Within a version fields are only added, never renamed, removed or
+/// re-encoded; any such change bumps the version. See "Event JSON schema" in
+/// the README.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapEvent {
+    /// `EVENT_SCHEMA_VERSION` the event was produced with (0 if serialized before versioning)
+    #[serde(default)]
+    pub schema_version: u32,
     pub transaction_hash: H256,
     pub block_number: u64,
+    /// Position of the transaction within the block
+    pub transaction_index: u64,
+    /// Position of the log within the block (total order of swaps within a block)
+    pub log_index: u64,
     pub timestamp: Option<String>,
     pub platform: Platform,
     pub trade_type: TradeType,
     pub token: TokenInfo,
     pub base_token: TokenInfo,
     pub price: PriceInfo,
+    /// USD price per token
+    pub price_usd: Option<f64>,
+    /// USD value of the whole trade
+    pub usd_value: Option<f64>,
+    /// Price converted to the configured quote currency, comparable across base tokens
+    pub normalized_price: Option<NormalizedPrice>,
     pub sender: Address,
     pub recipient: Address,
+    /// Originating wallet (tx.from) - `sender` is often the router contract
+    pub trader: Option<Address>,
+    /// Router or aggregator the swap was routed through, if recognised
+    pub route_via: Option<KnownRouter>,
+    /// Known-address labels for sender / recipient / trader
+    pub sender_label: Option<AddressLabel>,
+    pub recipient_label: Option<AddressLabel>,
+    pub trader_label: Option<AddressLabel>,
     pub pair_address: Option<Address>,
     pub bonding_curve_address: Option<Address>,
+    pub pool: Option<PoolMetadata>,
+    pub v3_state: Option<V3SwapState>,
+    /// Transfer tax taken on this trade (percent), inferred from the receipt's Transfer logs
+    pub effective_tax_pct: Option<f64>,
+    /// Reserve-derived pool price and depth around the trade (V2 only)
+    pub depth: Option<MarketDepth>,
+    /// Market cap and FDV at this trade's USD price
+    pub market_cap: Option<MarketCap>,
+    pub gas: Option<GasInfo>,
+    /// How far behind the chain the swap was received and delivered (live swaps only)
+    pub latency: Option<EventLatency>,
+}
+
+impl SwapEvent {
+    /// Slippage this trade paid against the pool's pre-trade marginal price (percent, V2 only)
+    pub fn price_impact_pct(&self) -> Option<f64> {
+        let before = self.depth.as_ref()?.price_before?;
+        let execution = self.price.raw.to_f64(self.token.decimals, self.base_token.decimals);
+        if before > 0.0 && execution > 0.0 {
+            Some((execution - before).abs() / before * 100.0)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +85,124 @@ impl Platform {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolVersion {
+    V2,
+    V3,
+}
+
+impl PoolVersion {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PoolVersion::V2 => "V2",
+            PoolVersion::V3 => "V3",
+        }
+    }
+}
+
+/// DEX pool metadata attached to swaps so pools of the same pair can be told apart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolMetadata {
+    pub version: PoolVersion,
+    /// Swap fee in hundredths of a bip (2500 = 0.25%)
+    pub fee_tier: u32,
+    /// Tick spacing (V3 only)
+    pub tick_spacing: Option<i32>,
+}
+
+impl PoolMetadata {
+    /// Swap fee as a percentage (e.g. 0.25)
+    pub fn fee_percent(&self) -> f64 {
+        self.fee_tier as f64 / 10_000.0
+    }
+}
+
+/// Post-swap pool state carried by a V3 Swap event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct V3SwapState {
+    pub sqrt_price_x96: U256,
+    /// In-range liquidity after the swap
+    pub liquidity: u128,
+    pub tick: i32,
+    pub protocol_fees_token0: u128,
+    pub protocol_fees_token1: u128,
+}
+
+/// Gas usage of the transaction containing the swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GasInfo {
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+    /// Total transaction fee in BNB (gas_used * effective_gas_price)
+    pub fee_bnb: String,
+}
+
+/// Timing of a live swap from its block to the callbacks
+///
+/// Block timestamps have one-second resolution, so the block-relative
+/// latencies are only accurate to about a second.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EventLatency {
+    /// Unix milliseconds the log arrived from the RPC subscription
+    pub received_at_ms: u64,
+    /// Unix milliseconds the swap was handed to the hooks, sinks and swap callback
+    pub dispatched_at_ms: Option<u64>,
+    /// Block timestamp to log receipt: the RPC provider's delay
+    pub block_to_receive_ms: Option<i64>,
+    /// Log receipt to dispatch: parsing, enrichment and any confirmation hold
+    pub receive_to_dispatch_ms: Option<u64>,
+    /// Block timestamp to dispatch: how far behind the chain the callback runs
+    pub block_to_dispatch_ms: Option<i64>,
+}
+
+/// Well-known BSC routers and aggregators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KnownRouter {
+    PancakeSwapV2Router,
+    PancakeSwapV3Router,
+    PancakeSwapSmartRouter,
+    PancakeSwapUniversalRouter,
+    OneInch,
+    OkxDex,
+    KyberSwap,
+    ParaSwap,
+    ZeroEx,
+    MetaMaskSwaps,
+}
+
+impl KnownRouter {
+    /// Look up a router by contract address
+    pub fn from_address(address: Address) -> Option<KnownRouter> {
+        crate::config::get_known_router(address)
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            KnownRouter::PancakeSwapV2Router => "PancakeSwap Router V2",
+            KnownRouter::PancakeSwapV3Router => "PancakeSwap Router V3",
+            KnownRouter::PancakeSwapSmartRouter => "PancakeSwap Smart Router",
+            KnownRouter::PancakeSwapUniversalRouter => "PancakeSwap Universal Router",
+            KnownRouter::OneInch => "1inch",
+            KnownRouter::OkxDex => "OKX DEX",
+            KnownRouter::KyberSwap => "KyberSwap",
+            KnownRouter::ParaSwap => "ParaSwap",
+            KnownRouter::ZeroEx => "0x",
+            KnownRouter::MetaMaskSwaps => "MetaMask Swaps",
+        }
+    }
+
+    /// Whether this is a third-party aggregator rather than a PancakeSwap router
+    pub fn is_aggregator(&self) -> bool {
+        !matches!(
+            self,
+            KnownRouter::PancakeSwapV2Router
+                | KnownRouter::PancakeSwapV3Router
+                | KnownRouter::PancakeSwapSmartRouter
+                | KnownRouter::PancakeSwapUniversalRouter
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TradeType {
     Buy,
@@ -52,6 +223,8 @@ pub struct TokenInfo {
     pub address: Address,
     pub symbol: String,
     pub amount: String,
+    /// Unscaled on-chain amount (`amount` is this formatted with `decimals`)
+    pub amount_raw: U256,
     pub decimals: u8,
 }
 
@@ -60,64 +233,1723 @@ pub struct PriceInfo {
     pub value: f64,
     pub display: String,
     pub base_token: String,
+    /// Address of the token the price is quoted in (None in events recorded before it was added)
+    pub quote_token: Option<Address>,
+    /// Exact execution price in the swap's own base-token units (before any routing hop)
+    pub raw: RationalPrice,
+}
+
+/// Common currency all pair prices can be normalized to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QuoteCurrency {
+    #[default]
+    Bnb,
+    Usd,
+}
+
+impl QuoteCurrency {
+    pub fn as_str(&self) -> &str {
+        match self {
+            QuoteCurrency::Bnb => "BNB",
+            QuoteCurrency::Usd => "USD",
+        }
+    }
+
+    /// Currency a token already is: WBNB for BNB, the USD stablecoins for USD
+    pub fn of_token(token: Address) -> Option<Self> {
+        if token == crate::config::get_wbnb_address() {
+            Some(QuoteCurrency::Bnb)
+        } else if crate::config::get_usd_stablecoins().contains(&token) {
+            Some(QuoteCurrency::Usd)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedPrice {
+    pub value: f64,
+    pub currency: QuoteCurrency,
 }
 
-#[derive(Debug, Clone)]
+/// Exact price as a ratio of raw amounts: `numerator / denominator` base-token wei per token wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RationalPrice {
+    /// Raw base-token amount
+    pub numerator: U256,
+    /// Raw token amount
+    pub denominator: U256,
+}
+
+impl RationalPrice {
+    /// Price per whole token, scaled by both tokens' decimals
+    pub fn to_f64(&self, token_decimals: u8, base_decimals: u8) -> f64 {
+        if self.denominator.is_zero() {
+            return 0.0;
+        }
+        let numerator: f64 = self.numerator.to_string().parse().unwrap_or(0.0);
+        let denominator: f64 = self.denominator.to_string().parse().unwrap_or(0.0);
+        (numerator / denominator) * 10f64.powi(token_decimals as i32 - base_decimals as i32)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PairInfo {
     pub pair_address: Address,
     pub token: Address,
     pub base_token: Address,
     pub base_token_symbol: String,
     pub is_v3: bool,  // true for V3, false for V2
+    pub fee_tier: u32,  // hundredths of a bip (V2 pairs are fixed at 2500 = 0.25%)
+    pub tick_spacing: Option<i32>,  // V3 only
+    pub route: Option<QuoteRoute>,  // Some when base_token is a non-base counter-token
 }
 
+impl PairInfo {
+    pub fn version(&self) -> PoolVersion {
+        if self.is_v3 {
+            PoolVersion::V3
+        } else {
+            PoolVersion::V2
+        }
+    }
+
+    pub fn pool_metadata(&self) -> PoolMetadata {
+        PoolMetadata {
+            version: self.version(),
+            fee_tier: self.fee_tier,
+            tick_spacing: self.tick_spacing,
+        }
+    }
+}
+
+/// DEX pair found by `find_token_location`, with its current state
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BondingCurveInfo {
-    pub address: Address,
-    pub token: Address,
+pub struct PairDetails {
+    #[serde(flatten)]
+    pub info: PairInfo,
+    /// Value of both sides of the pool (twice its base-token balance)
+    pub liquidity_usd: Option<f64>,
+    /// Spot price of one token in `info.base_token`
+    pub price: Option<f64>,
+    pub price_usd: Option<f64>,
+    /// Swaps over the last `PairSelection::activity_blocks` blocks (None when logs could not be read)
+    pub recent_swaps: Option<usize>,
 }
 
-#[derive(Debug, Clone)]
-pub struct PriceStats {
-    pub current_price: f64,
+/// How `find_token_location` picks its primary pair
+///
+/// Each pair scores its share of the token's total liquidity and of its
+/// recent swaps, weighted by `liquidity_weight` and `activity_weight`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PairSelection {
+    pub liquidity_weight: f64,
+    pub activity_weight: f64,
+    /// Recent swaps are counted over this many blocks
+    pub activity_blocks: u64,
+}
+
+impl Default for PairSelection {
+    fn default() -> Self {
+        Self {
+            liquidity_weight: 0.7,
+            activity_weight: 0.3,
+            activity_blocks: 1_200,
+        }
+    }
+}
+
+impl PairSelection {
+    /// Highest-scoring pair, ties going to the deeper pool
+    pub fn select(&self, pairs: &[PairDetails]) -> Option<PrimaryPair> {
+        let total_liquidity: f64 = pairs.iter().filter_map(|p| p.liquidity_usd).sum();
+        let total_swaps: usize = pairs.iter().filter_map(|p| p.recent_swaps).sum();
+        let share = |part: f64, total: f64| if total > 0.0 { part / total } else { 0.0 };
+
+        pairs
+            .iter()
+            .map(|pair| {
+                let liquidity_share = share(pair.liquidity_usd.unwrap_or(0.0), total_liquidity);
+                let activity_share = share(pair.recent_swaps.unwrap_or(0) as f64, total_swaps as f64);
+                PrimaryPair {
+                    pair_address: pair.info.pair_address,
+                    base_token_symbol: pair.info.base_token_symbol.clone(),
+                    is_v3: pair.info.is_v3,
+                    fee_tier: pair.info.fee_tier,
+                    score: self.liquidity_weight * liquidity_share + self.activity_weight * activity_share,
+                    liquidity_share,
+                    activity_share,
+                    criteria: *self,
+                }
+            })
+            .zip(pairs)
+            .max_by(|(a, pa), (b, pb)| {
+                a.score
+                    .total_cmp(&b.score)
+                    .then(pa.liquidity_usd.unwrap_or(0.0).total_cmp(&pb.liquidity_usd.unwrap_or(0.0)))
+            })
+            .map(|(primary, _)| primary)
+    }
+}
+
+/// Pair recommended for routing orders, with why it was picked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrimaryPair {
+    pub pair_address: Address,
+    pub base_token_symbol: String,
+    pub is_v3: bool,
+    pub fee_tier: u32,
+    /// Weighted sum of the two shares below (0-1)
+    pub score: f64,
+    /// Share of the token's liquidity across all found pairs (0-1)
+    pub liquidity_share: f64,
+    /// Share of the token's recent swaps across all found pairs (0-1)
+    pub activity_share: f64,
+    pub criteria: PairSelection,
+}
+
+/// Routing hop used to price a pair whose counter-token is not a base token
+///
+/// The swap is priced in the counter-token first, then converted into
+/// `base_token_symbol` using the counter-token's own spot price on `hop_pair`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteRoute {
+    /// Pair/pool quoting the counter-token against a base token
+    pub hop_pair: Address,
+    /// Whether the hop is a V3 pool
+    pub hop_is_v3: bool,
+    /// Base token the final price is expressed in
+    pub base_token: Address,
+    pub base_token_symbol: String,
+}
+
+/// Router/quoter quote for a hypothetical BNB <-> token order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeQuote {
+    pub token_address: Address,
+    /// Pair the quote was taken against (best output among discovered pairs)
+    pub pair_address: Address,
+    pub version: PoolVersion,
+    pub trade_type: TradeType,
+    /// Token path from input to output
+    pub path: Vec<Address>,
+    /// BNB for buys, tokens for sells (whole units)
+    pub amount_in: f64,
+    pub amount_in_raw: U256,
+    /// Tokens for buys, BNB for sells (whole units)
+    pub amount_out: f64,
+    pub amount_out_raw: U256,
+    /// Effective execution price in BNB per token
+    pub price_bnb: f64,
+}
+
+/// Trade sent by `trader::Trader`, once mined
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeExecution {
+    pub token_address: Address,
+    pub trade_type: TradeType,
+    pub platform: Platform,
+    /// DEX pair traded through (None on the bonding curve)
+    pub pair_address: Option<Address>,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    /// BNB for buys, tokens for sells (whole units)
+    pub amount_in: f64,
+    /// Quoted output (whole units)
+    pub expected_out: f64,
+    /// Least output the transaction accepted after slippage (whole units)
+    pub min_out: f64,
+    pub gas_used: Option<U256>,
+}
+
+/// Fill model of `PaperTrader`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PaperConfig {
+    /// Virtual BNB balance to start with
+    pub starting_bnb: f64,
+    /// Time between placing an order and its fill; swaps in between move the price first
+    pub latency: Duration,
+    /// Extra adverse slippage applied to every fill (percent), on top of the pool's price impact
+    pub slippage_pct: f64,
+    /// Reject fills worse than the quote at order time by more than this (percent), like a min-out revert
+    pub max_slippage_pct: Option<f64>,
+    /// Gas charged per filled order, in BNB
+    pub gas_fee_bnb: f64,
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            starting_bnb: 10.0,
+            latency: Duration::from_secs(3),
+            slippage_pct: 0.5,
+            max_slippage_pct: Some(10.0),
+            gas_fee_bnb: 0.0005,
+        }
+    }
+}
+
+/// Outcome of a simulated order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperFill {
+    pub order_id: u64,
+    pub token_address: Address,
+    pub trade_type: TradeType,
+    /// BNB for buys, tokens for sells
+    pub amount_in: f64,
+    /// Tokens for buys, BNB for sells (0 when rejected)
+    pub amount_out: f64,
+    /// Average price quoted when the order was placed (BNB per token)
+    pub quoted_price: f64,
+    /// Average fill price, including pool fee and slippage (BNB per token)
+    pub fill_price: f64,
+    /// Stream time (unix seconds) the order was placed and filled at
+    pub submitted_at: f64,
+    pub filled_at: f64,
+    /// Why the order did not fill (funds are returned)
+    pub rejected: Option<String>,
+}
+
+/// Simulated holding of one token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperPosition {
+    pub token_address: Address,
+    pub amount: f64,
+    /// BNB spent on the tokens still held (average cost basis)
+    pub cost_bnb: f64,
+    pub realized_pnl_bnb: f64,
+    /// Last streamed price (BNB per token)
     pub last_price: Option<f64>,
-    pub price_change: Option<f64>,
-    pub price_change_percent: Option<f64>,
+}
+
+impl PaperPosition {
+    /// Mark-to-market profit of the tokens still held
+    pub fn unrealized_pnl_bnb(&self) -> f64 {
+        self.last_price.map(|price| self.amount * price - self.cost_bnb).unwrap_or_default()
+    }
+}
+
+/// Snapshot of a `PaperTrader`'s virtual portfolio
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperPortfolio {
+    /// BNB not tied up in positions or pending buys
+    pub cash_bnb: f64,
+    /// Cash plus positions at their last price
+    pub equity_bnb: f64,
+    pub realized_pnl_bnb: f64,
+    pub unrealized_pnl_bnb: f64,
+    pub gas_spent_bnb: f64,
+    pub positions: Vec<PaperPosition>,
+    pub pending_orders: usize,
+}
+
+/// Equity of a backtest's paper portfolio at a point in time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EquityPoint {
+    /// Unix seconds of the swap that moved it
+    pub timestamp: i64,
+    pub equity_bnb: f64,
+}
+
+/// Result of replaying swaps through a strategy with `Backtest`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    /// Swaps replayed
+    pub swaps: usize,
+    pub starting_bnb: f64,
+    /// Portfolio at the end, with orders still pending filled at the last prices
+    pub portfolio: PaperPortfolio,
+    /// Final equity minus the starting BNB
+    pub pnl_bnb: f64,
+    pub return_pct: f64,
+    /// Largest fall of equity from a previous peak
+    pub max_drawdown_bnb: f64,
+    pub max_drawdown_pct: f64,
+    /// Every filled or rejected order, in fill order
+    pub fills: Vec<PaperFill>,
+    /// Equity after each swap that changed it
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+/// Guardrails and sizing of `CopyTrader`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyConfig {
+    /// Wallets to mirror, matched like `SwapFilter::allow_senders` (trader, sender or recipient)
+    pub wallets: Vec<Address>,
+    /// Share of the leader's BNB size to mirror on buys (0.1 = 10%)
+    pub scale: f64,
+    /// Largest single mirrored buy, in BNB
+    pub max_trade_bnb: Option<f64>,
+    /// Most BNB put into one token across mirrored buys
+    pub max_position_bnb: f64,
+    /// Ignore mirrored buys smaller than this, in BNB
+    pub min_trade_bnb: f64,
+    /// Skip tokens whose bytecode risk score (0-100) is above this; tokens that can't be scanned are skipped too
+    pub max_risk_score: Option<u8>,
+    /// Sell alongside the leader, the same share of the position as the leader sold of what it was seen buying
+    pub mirror_sells: bool,
+    /// Slippage tolerance of executed mirror trades (percent)
+    pub slippage_pct: f64,
+}
+
+impl Default for CopyConfig {
+    fn default() -> Self {
+        Self {
+            wallets: Vec::new(),
+            scale: 0.1,
+            max_trade_bnb: None,
+            max_position_bnb: 1.0,
+            min_trade_bnb: 0.001,
+            max_risk_score: Some(30),
+            mirror_sells: true,
+            slippage_pct: 5.0,
+        }
+    }
+}
+
+/// A watched wallet's swap, sized into a mirror trade that passed every guardrail
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopySignal {
+    /// Watched wallet that traded
+    pub wallet: Address,
+    pub token_address: Address,
+    pub token_symbol: String,
+    pub trade_type: TradeType,
+    pub platform: Platform,
+    /// Pair or bonding curve the leader traded on
+    pub pool_address: Option<Address>,
+    /// The leader's transaction
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    /// BNB value of the leader's trade
+    pub leader_bnb: f64,
+    /// BNB price per token of the leader's trade
+    pub price_bnb: f64,
+    /// BNB to spend (buys) or cost basis being exited (sells)
+    pub size_bnb: f64,
+    /// Tokens to sell (sells only), estimated from the mirrored buys' prices
+    pub token_amount: Option<f64>,
+    pub risk_score: Option<u8>,
+    /// Mirror trade sent for this signal, when `CopyTrader` executes trades
+    pub execution: Option<TradeExecution>,
+}
+
+/// Live V2 pair reserves, as of the last Sync event
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Reserves {
+    pub reserve0: U256,
+    pub reserve1: U256,
+    /// Block of the Sync event (or the initial getReserves read)
+    pub block_number: u64,
+}
+
+/// Marginal pool price and liquidity depth computed locally from tracked reserves
+///
+/// Prices are in the pair's own base token, before any quote routing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketDepth {
+    /// Marginal price before the trade (None if no earlier reserve state was seen)
+    pub price_before: Option<f64>,
+    /// Marginal price after the trade
+    pub price_after: f64,
+    /// Token reserve after the trade, in whole tokens
+    pub token_reserve: f64,
+    /// Base token reserve after the trade, in whole tokens
+    pub base_reserve: f64,
+    /// Base token amount a buy needs to move the price up by 2%
+    pub depth_2pct: f64,
+}
+
+impl MarketDepth {
+    /// Percentage move of the marginal price caused by the trade
+    pub fn price_change_pct(&self) -> Option<f64> {
+        let before = self.price_before?;
+        if before > 0.0 {
+            Some((self.price_after - before) / before * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Token supply split into burned and circulating
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSupply {
+    pub token_address: Address,
+    pub decimals: u8,
+    /// totalSupply(), in whole tokens
+    pub total_supply: f64,
+    /// Held by the dead and zero addresses
+    pub burned: f64,
+    pub circulating_supply: f64,
+    pub total_supply_raw: U256,
+    pub burned_raw: U256,
+}
+
+/// USD valuation of a token's supply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketCap {
+    pub price_usd: f64,
+    /// Price x circulating supply (excluding burned tokens)
+    pub market_cap_usd: f64,
+    /// Price x total supply
+    pub fdv_usd: f64,
+    pub circulating_supply: f64,
+    pub total_supply: f64,
+}
+
+/// Threshold for the large-burn callback
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BurnAlertConfig {
+    /// Fire for burns of at least this share of total supply (percent)
+    pub min_supply_pct: f64,
+}
+
+impl Default for BurnAlertConfig {
+    fn default() -> Self {
+        Self { min_supply_pct: 0.1 }
+    }
+}
+
+/// Tokens sent to the dead or zero address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurnEvent {
+    pub token_address: Address,
+    pub from: Address,
+    pub burn_address: Address,
+    /// Tokens burned, in whole tokens
+    pub amount: f64,
+    pub amount_raw: U256,
+    /// Share of total supply burned (percent)
+    pub pct_of_supply: f64,
+    /// Circulating supply after the burn
+    pub circulating_supply: f64,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+}
+
+/// Expected outcome of a hypothetical trade on a V2 pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceImpact {
+    pub pair_address: Address,
+    pub trade_type: TradeType,
+    /// Base token for buys, monitored token for sells (whole units)
+    pub amount_in: f64,
+    /// Monitored token for buys, base token for sells (whole units)
+    pub amount_out: f64,
+    /// Marginal price before the trade, in the pair's base token
+    pub spot_price: f64,
+    /// Average price paid/received, including the pool fee
+    pub execution_price: f64,
+    /// Marginal price after the trade
+    pub price_after: f64,
+    /// Slippage of the execution price against the spot price (percent)
+    pub impact_pct: f64,
+}
+
+/// Candle bar length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CandleInterval {
+    OneSecond,
+    FifteenSeconds,
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl CandleInterval {
+    pub fn as_secs(&self) -> u64 {
+        match self {
+            CandleInterval::OneSecond => 1,
+            CandleInterval::FifteenSeconds => 15,
+            CandleInterval::OneMinute => 60,
+            CandleInterval::FiveMinutes => 300,
+            CandleInterval::OneHour => 3600,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            CandleInterval::OneSecond => "1s",
+            CandleInterval::FifteenSeconds => "15s",
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::OneHour => "1h",
+        }
+    }
+}
+
+/// OHLCV bar of one market (DEX pair or bonding curve)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    /// Pair or bonding curve address
+    pub market: Address,
+    pub token_address: Address,
+    /// Symbol prices are quoted in (`SwapEvent::price.base_token`)
+    pub base_token: String,
+    pub interval: CandleInterval,
+    /// Interval start/end (unix seconds, end exclusive)
+    pub open_time: i64,
+    pub close_time: i64,
+    pub open: f64,
     pub high: f64,
     pub low: f64,
-    pub first_price: f64,
-    pub swap_count: usize,
+    pub close: f64,
+    /// Volume in whole tokens
+    pub volume_token: f64,
+    /// Volume in the pair's base token
+    pub volume_base: f64,
+    /// Volume in USD, from swaps with a USD valuation
+    pub volume_usd: Option<f64>,
+    pub trades: u32,
+    pub buys: u32,
+    pub sells: u32,
 }
 
-/// Event emitted when a token migrates from bonding curve to DEX
+/// Kind of liquidity change on a DEX pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiquidityEventKind {
+    /// Liquidity added (V2 Mint / V3 Mint)
+    Mint,
+    /// Liquidity removed (V2 Burn / V3 Burn)
+    Burn,
+    /// Tokens owed to a V3 position withdrawn (V3 Collect)
+    Collect,
+}
+
+impl LiquidityEventKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LiquidityEventKind::Mint => "ADD",
+            LiquidityEventKind::Burn => "REMOVE",
+            LiquidityEventKind::Collect => "COLLECT",
+        }
+    }
+}
+
+/// Liquidity added to or removed from a monitored pair
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MigrationEvent {
-    /// Token address that migrated
-    pub token_address: Address,
-    /// Source platform (usually FourMemeBondingCurve)
-    pub from_platform: Platform,
-    /// Destination platform (usually PancakeSwap)
-    pub to_platform: Platform,
-    /// Transaction hash of the migration (PairCreated transaction)
+pub struct LiquidityEvent {
     pub transaction_hash: H256,
-    /// Block number when migration occurred
     pub block_number: u64,
-    /// Timestamp of migration
+    pub log_index: u64,
     pub timestamp: Option<String>,
-    /// New DEX pair addresses created
-    pub pair_addresses: Vec<Address>,
-    /// Number of pairs found on DEX
-    pub pair_count: usize,
+    pub kind: LiquidityEventKind,
+    pub pair_address: Address,
+    pub pool: PoolMetadata,
+    /// Amount of the monitored token added/removed
+    pub token: TokenInfo,
+    /// Amount of the base token added/removed
+    pub base_token: TokenInfo,
+    /// Account that initiated the change (V2 sender / V3 position owner)
+    pub owner: Address,
+    /// Receiver of withdrawn tokens (V2 Burn `to` / V3 Collect recipient)
+    pub recipient: Option<Address>,
+    /// V3 position range and liquidity delta
+    pub tick_lower: Option<i32>,
+    pub tick_upper: Option<i32>,
+    pub liquidity: Option<u128>,
+    /// Pool reserves of the monitored token after the change, in whole tokens
+    pub token_reserve: Option<f64>,
+    /// Pool reserves of the base token after the change, in whole tokens
+    pub base_reserve: Option<f64>,
 }
 
-impl MigrationEvent {
-    pub fn as_message(&self) -> String {
-        format!(
-            "🎉 MIGRATION DETECTED! Token migrated from {} to {} at block {}. Found {} DEX pair(s).",
-            self.from_platform.as_str(),
-            self.to_platform.as_str(),
-            self.block_number,
-            self.pair_count
-        )
+/// Thresholds for the liquidity-drained (rug pull) alert
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DrainAlertConfig {
+    /// Fire when a single removal takes more than this share of the pool (percent)
+    pub max_removal_pct: f64,
+    /// Fire when the pool's base token reserve falls below this amount (whole tokens)
+    pub min_base_reserve: Option<f64>,
+}
+
+impl Default for DrainAlertConfig {
+    fn default() -> Self {
+        Self {
+            max_removal_pct: 50.0,
+            min_base_reserve: None,
+        }
+    }
+}
+
+/// Why a liquidity-drained alert fired
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DrainReason {
+    /// A single Burn removed more than `max_removal_pct` of the pool
+    LargeRemoval,
+    /// Base token reserve dropped below `min_base_reserve`
+    BelowFloor,
+}
+
+impl DrainReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DrainReason::LargeRemoval => "Large liquidity removal",
+            DrainReason::BelowFloor => "Reserves below floor",
+        }
+    }
+}
+
+/// Alert raised when a monitored pool loses most of its liquidity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityDrainedEvent {
+    pub reason: DrainReason,
+    pub pair_address: Address,
+    pub token_address: Address,
+    pub base_token_symbol: String,
+    pub block_number: u64,
+    pub transaction_hash: Option<H256>,
+    /// Share of the pool's base reserve removed by the triggering Burn (percent)
+    pub removed_pct: Option<f64>,
+    /// Base token reserve left in the pool, in whole tokens
+    pub base_reserve: f64,
+    /// The triggering Burn, if the alert came from a liquidity removal
+    pub liquidity: Option<LiquidityEvent>,
+}
+
+/// Settings for detecting liquidity moving between a token's pools (e.g. V2 to V3)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PoolMigrationConfig {
+    /// How often pool liquidity is read
+    pub interval: Duration,
+    /// Share of its peak liquidity the old primary pool must have lost (percent)
+    pub min_drop_pct: f64,
+}
+
+impl Default for PoolMigrationConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            min_drop_pct: 50.0,
+        }
+    }
+}
+
+/// Liquidity moved from the token's primary pool to another of its pools
+///
+/// Typically a V2 pair drained into a new V3 pool after launch. From this
+/// point `to_pair` is treated as the primary pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolMigrationEvent {
+    pub token_address: Address,
+    pub from_pair: Address,
+    pub from_pool: PoolMetadata,
+    pub to_pair: Address,
+    pub to_pool: PoolMetadata,
+    pub to_base_token_symbol: String,
+    /// USD liquidity of the old pool at its peak and now
+    pub from_peak_liquidity_usd: f64,
+    pub from_liquidity_usd: f64,
+    pub to_liquidity_usd: f64,
+    /// Detection time (RFC3339)
+    pub timestamp: String,
+}
+
+/// LP tokens of a pair held by a locker contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpLockHolding {
+    pub locker: String,
+    pub address: Address,
+    pub amount: U256,
+    /// Share of the LP supply (percent)
+    pub pct: f64,
+}
+
+/// How much of a V2 pair's LP supply is burned or locked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpLockStatus {
+    pub pair_address: Address,
+    pub total_supply: U256,
+    /// Share of the LP supply sent to burn addresses (percent)
+    pub burned_pct: f64,
+    /// Share of the LP supply held by known lockers (percent)
+    pub locked_pct: f64,
+    pub lockers: Vec<LpLockHolding>,
+}
+
+impl LpLockStatus {
+    /// Share of the LP supply that cannot be pulled by the deployer right now (percent)
+    pub fn secured_pct(&self) -> f64 {
+        (self.burned_pct + self.locked_pct).min(100.0)
+    }
+
+    /// Whether at least `min_pct` of the LP supply is burned or locked
+    pub fn is_secured(&self, min_pct: f64) -> bool {
+        self.secured_pct() >= min_pct
+    }
+}
+
+/// Settings for holder tracking and the `on_holder_stats` callback
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HolderStatsConfig {
+    /// How often stats are reported
+    pub interval: Duration,
+    /// Blocks of Transfer history replayed at startup to seed balances
+    pub lookback_blocks: u64,
+    /// Window the new-holder rate is measured over
+    pub rate_window: Duration,
+}
+
+impl Default for HolderStatsConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            lookback_blocks: 28_800,
+            rate_window: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Approximate holder statistics derived from Transfer events
+///
+/// Balances are reconstructed from the replayed history plus live transfers,
+/// so wallets that only held before the lookback window are not counted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderStats {
+    pub token_address: Address,
+    /// Addresses with a positive balance
+    pub holder_count: usize,
+    /// Addresses that became holders within the rate window
+    pub new_holders: usize,
+    /// New holders per minute over the rate window
+    pub new_holders_per_min: f64,
+    /// Transfers processed since tracking started (including replayed history)
+    pub transfers_seen: u64,
+    pub timestamp: String,
+}
+
+/// A wallet's balance in a holder snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderBalance {
+    pub address: Address,
+    pub balance: U256,
+    /// Share of total supply (percent)
+    pub pct: f64,
+}
+
+/// Top-holder concentration of a token at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderSnapshot {
+    pub token_address: Address,
+    pub block_number: u64,
+    pub total_supply: U256,
+    /// Largest wallets, descending, excluding pairs/bonding curve/burn addresses
+    pub top_holders: Vec<HolderBalance>,
+    /// Combined share of total supply held by `top_holders` (percent)
+    pub top_holders_pct: f64,
+    /// Addresses left out of the ranking (pools, bonding curve, burn addresses)
+    pub excluded: Vec<Address>,
+}
+
+/// Four.meme launch details from the TokenCreate event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenLaunch {
+    pub token_address: Address,
+    /// Wallet that launched the token
+    pub creator: Address,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: U256,
+    /// Unix timestamp trading opens
+    pub launch_time: u64,
+    pub block_number: u64,
+    pub transaction_hash: H256,
+}
+
+/// PancakeSwap V2 pair or V3 pool created by a factory (new-pair firehose)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewPairEvent {
+    pub pair_address: Address,
+    pub pool: PoolMetadata,
+    pub token0: Address,
+    pub token1: Address,
+    /// Non-base side of the pair (token0 when neither side is a base token)
+    pub token: Address,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub token_decimals: u8,
+    /// Base side of the pair, if either token is a base token
+    pub base_token: Option<Address>,
+    pub base_token_symbol: Option<String>,
+    /// Liquidity added in the creation transaction, in whole tokens (None when the pair was created empty)
+    pub token_liquidity: Option<f64>,
+    pub base_liquidity: Option<f64>,
+    /// Value of both sides of the initial liquidity, when the base token has a USD price
+    pub liquidity_usd: Option<f64>,
+    /// Sender of the creation transaction
+    pub creator: Option<Address>,
+    pub block_number: u64,
+    pub log_index: u64,
+    pub transaction_hash: H256,
+}
+
+impl NewPairEvent {
+    /// Pair info for monitoring the pair (None when neither side is a base token)
+    pub fn pair_info(&self) -> Option<PairInfo> {
+        Some(PairInfo {
+            pair_address: self.pair_address,
+            token: self.token,
+            base_token: self.base_token?,
+            base_token_symbol: self.base_token_symbol.clone()?,
+            is_v3: self.pool.version == PoolVersion::V3,
+            fee_tier: self.pool.fee_tier,
+            tick_spacing: self.pool.tick_spacing,
+            route: None,
+        })
+    }
+}
+
+/// Settings for the trending token detector (`Firehose::on_trending`)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TrendingConfig {
+    /// Window volume, trades and buyers are measured over
+    pub window: Duration,
+    /// Size of the trending list; tokens are reported when they enter it
+    pub top_n: usize,
+    /// Trades a token needs within the window to be ranked at all
+    pub min_trades: usize,
+    /// How often the ranking is recomputed
+    pub refresh: Duration,
+}
+
+impl Default for TrendingConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(300),
+            top_n: 10,
+            min_trades: 5,
+            refresh: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Short-window activity of a trending token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendingMetrics {
+    pub symbol: String,
+    /// Position in the trending list (1 = hottest)
+    pub rank: usize,
+    /// Weighted blend of volume, trade count and unique buyers, relative to the leader (0-1)
+    pub score: f64,
+    /// USD volume within the window (trades without a USD value count as zero)
+    pub volume_usd: f64,
+    pub trades: usize,
+    pub buys: usize,
+    pub sells: usize,
+    pub unique_buyers: usize,
+    pub window_secs: u64,
+}
+
+/// Token matching a symbol or name search (`find_token_by_symbol`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCandidate {
+    pub address: Address,
+    pub name: String,
+    pub symbol: String,
+    /// Whether the symbol matched exactly (otherwise the name contains the query)
+    pub exact_symbol: bool,
+    /// PancakeSwap pairs and pools against a base token created within the search window
+    pub pairs: Vec<Address>,
+    /// Combined USD liquidity of `pairs`
+    pub liquidity_usd: Option<f64>,
+    /// Launched on Four.meme within the search window
+    pub four_meme: bool,
+    /// Earliest pair creation or launch seen
+    pub first_seen_block: u64,
+}
+
+/// What the token creator did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DevActionKind {
+    Sell,
+    /// Tokens sent to another wallet
+    Transfer,
+    AddLiquidity,
+    RemoveLiquidity,
+}
+
+impl DevActionKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DevActionKind::Sell => "DEV SELL",
+            DevActionKind::Transfer => "DEV TRANSFER",
+            DevActionKind::AddLiquidity => "DEV ADD LIQUIDITY",
+            DevActionKind::RemoveLiquidity => "DEV REMOVE LIQUIDITY",
+        }
+    }
+}
+
+/// Activity of the token creator's wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevActionEvent {
+    pub kind: DevActionKind,
+    pub token_address: Address,
+    pub creator: Address,
+    /// Token amount moved, in whole tokens
+    pub amount: String,
+    pub amount_raw: U256,
+    /// Other side of the token transfer
+    pub counterparty: Address,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+}
+
+/// OwnershipTransferred event on the monitored token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipChangeEvent {
+    pub token_address: Address,
+    pub previous_owner: Address,
+    pub new_owner: Address,
+    /// Ownership moved to the zero or dead address
+    pub renounced: bool,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+}
+
+/// Router swap of the monitored token seen in the mempool, before confirmation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSwapEvent {
+    pub transaction_hash: H256,
+    pub from: Address,
+    /// Contract the transaction calls
+    pub router: Address,
+    pub route_via: Option<KnownRouter>,
+    /// Decoded router method (e.g. `swapExactETHForTokens`)
+    pub method: String,
+    pub trade_type: TradeType,
+    pub token_address: Address,
+    /// Swap path from input token to output token
+    pub path: Vec<Address>,
+    /// Exact input amount, or the maximum input for exact-output swaps (raw units)
+    pub amount_in: U256,
+    /// Minimum output amount, or the exact output for exact-output swaps (raw units)
+    pub amount_out: U256,
+    /// Whether the input amount is exact (output is then a minimum)
+    pub exact_input: bool,
+    /// BNB sent with the transaction (wei)
+    pub value: U256,
+    pub gas_price: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub nonce: U256,
+    /// When the transaction was seen (RFC3339)
+    pub detected_at: String,
+}
+
+/// Final state of a pending swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PendingSwapStatus {
+    /// Mined successfully
+    Confirmed,
+    /// Mined but reverted
+    Reverted,
+    /// Sender's nonce was used by another transaction (speed-up / cancel)
+    Replaced,
+    /// Left the mempool without being mined
+    Dropped,
+}
+
+impl PendingSwapStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PendingSwapStatus::Confirmed => "CONFIRMED",
+            PendingSwapStatus::Reverted => "REVERTED",
+            PendingSwapStatus::Replaced => "REPLACED",
+            PendingSwapStatus::Dropped => "DROPPED",
+        }
+    }
+}
+
+/// Outcome of a pending swap, linked by transaction hash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSwapResolution {
+    pub pending: PendingSwapEvent,
+    pub status: PendingSwapStatus,
+    /// The confirmed swap, when it was streamed from a monitored pool
+    pub swap: Option<SwapEvent>,
+    pub block_number: Option<u64>,
+    /// Time between the pending transaction being seen and its resolution
+    pub pending_ms: u64,
+}
+
+/// Minimum trade size, used by trade filters and the whale trade callback
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TradeThreshold {
+    /// Trade value in USD (swaps without a USD valuation never match)
+    Usd(f64),
+    /// Amount of the pair's base token (WBNB, USDT, ...)
+    BaseToken(f64),
+}
+
+impl TradeThreshold {
+    /// Whether a swap is at least this large
+    pub fn is_met(&self, swap: &SwapEvent) -> bool {
+        match self {
+            TradeThreshold::Usd(min) => swap.usd_value.is_some_and(|value| value >= *min),
+            TradeThreshold::BaseToken(min) => swap
+                .base_token
+                .amount
+                .parse::<f64>()
+                .is_ok_and(|amount| amount >= *min),
+        }
+    }
+}
+
+/// Category of a labelled address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelKind {
+    Router,
+    Aggregator,
+    /// Centralised exchange wallet
+    Exchange,
+    MevBot,
+    /// Protocol contract (factory, launchpad, locker, ...)
+    Protocol,
+    Burn,
+    /// User-defined label
+    Custom,
+}
+
+impl LabelKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LabelKind::Router => "Router",
+            LabelKind::Aggregator => "Aggregator",
+            LabelKind::Exchange => "Exchange",
+            LabelKind::MevBot => "MEV Bot",
+            LabelKind::Protocol => "Protocol",
+            LabelKind::Burn => "Burn",
+            LabelKind::Custom => "Custom",
+        }
+    }
+}
+
+/// Human-readable label for a known address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressLabel {
+    pub name: String,
+    pub kind: LabelKind,
+}
+
+/// Result of a honeypot check on a token's pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoneypotReport {
+    pub token_address: Address,
+    pub pair_address: Address,
+    pub is_honeypot: bool,
+    /// Why the token was flagged
+    pub reason: Option<String>,
+    /// Whether a simulated buy through the V2 router succeeded
+    pub buy_simulated: bool,
+    /// Whether the bought tokens could be sold back through the router;
+    /// None if the round trip could not be simulated
+    pub sell_simulated: Option<bool>,
+    /// BNB lost on the simulated buy and sell (percent), fees and price impact included
+    pub round_trip_loss_pct: Option<f64>,
+    /// Buys/sells seen on the pool over recent blocks
+    pub recent_buys: usize,
+    pub recent_sells: usize,
+}
+
+/// Class of dangerous owner capability found in a token contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RiskCategory {
+    Mint,
+    Blacklist,
+    Pause,
+    TaxChange,
+    Proxy,
+}
+
+impl RiskCategory {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RiskCategory::Mint => "Mint",
+            RiskCategory::Blacklist => "Blacklist",
+            RiskCategory::Pause => "Pause",
+            RiskCategory::TaxChange => "Tax Change",
+            RiskCategory::Proxy => "Upgradeable Proxy",
+        }
+    }
+
+    /// Contribution to the 0-100 risk score
+    pub fn weight(&self) -> u32 {
+        match self {
+            RiskCategory::Mint => 30,
+            RiskCategory::Blacklist => 25,
+            RiskCategory::Pause => 20,
+            RiskCategory::TaxChange => 15,
+            RiskCategory::Proxy => 30,
+        }
+    }
+}
+
+/// A dangerous function selector found in the bytecode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFlag {
+    pub category: RiskCategory,
+    /// 4-byte selector (empty for proxies detected by storage slot)
+    pub selector: String,
+    pub signature: String,
+}
+
+/// Bytecode risk scan of a token contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskReport {
+    pub token_address: Address,
+    pub is_proxy: bool,
+    /// Implementation contract, if the token is a proxy
+    pub implementation: Option<Address>,
+    pub flags: Vec<RiskFlag>,
+    /// 0 (no findings) to 100
+    pub score: u8,
+}
+
+/// How a proxy token delegates to its implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProxyKind {
+    /// EIP-1967 proxy with an admin slot (upgraded by the proxy admin)
+    Transparent,
+    /// EIP-1967 proxy without an admin (upgraded through the implementation)
+    Uups,
+    /// EIP-1967 beacon proxy (upgraded through the beacon)
+    Beacon,
+    /// EIP-1167 clone (not upgradeable)
+    Minimal,
+}
+
+impl ProxyKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ProxyKind::Transparent => "Transparent",
+            ProxyKind::Uups => "UUPS",
+            ProxyKind::Beacon => "Beacon",
+            ProxyKind::Minimal => "EIP-1167",
+        }
+    }
+
+    pub fn is_upgradeable(&self) -> bool {
+        !matches!(self, ProxyKind::Minimal)
+    }
+}
+
+/// Implementation behind a proxy token (the token address itself is the proxy)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyInfo {
+    pub kind: ProxyKind,
+    pub implementation: Address,
+    /// EIP-1967 admin slot, set on transparent proxies
+    pub admin: Option<Address>,
+    pub beacon: Option<Address>,
+}
+
+/// Proxy token upgraded to a new implementation while streaming
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImplementationChangedEvent {
+    pub token_address: Address,
+    pub previous_implementation: Address,
+    pub new_implementation: Address,
+    /// Beacon the upgrade went through, for beacon proxies
+    pub beacon: Option<Address>,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+}
+
+/// New chain head, reported by `on_block`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockEvent {
+    pub block_number: u64,
+    pub block_hash: Option<H256>,
+    /// Unix seconds
+    pub timestamp: u64,
+    pub base_fee_per_gas: Option<U256>,
+    pub gas_used: U256,
+    pub gas_limit: U256,
+    /// Swaps of the monitored tokens seen in the previous block (`block_number - 1`)
+    ///
+    /// Logs of a block can arrive after its head notification, so the count of
+    /// a block is complete once the next head arrives.
+    pub previous_block_swaps: u64,
+}
+
+impl RiskReport {
+    pub fn has(&self, category: RiskCategory) -> bool {
+        self.flags.iter().any(|f| f.category == category)
+    }
+}
+
+/// Buy/sell tax of a token, inferred from recent trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxEstimate {
+    pub token_address: Address,
+    pub pair_address: Address,
+    /// Tax on a simulated buy, or the median over recent buys (percent)
+    pub buy_tax_pct: Option<f64>,
+    /// Tax on a simulated sell, or the median over recent sells (percent)
+    pub sell_tax_pct: Option<f64>,
+    /// Whether the taxes come from a simulated round trip rather than past receipts
+    pub simulated: bool,
+    /// Receipts sampled when falling back to trade history
+    pub buys_sampled: usize,
+    pub sells_sampled: usize,
+}
+
+/// Logo and project links of a token, from TrustWallet assets or the Four.meme API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenProfile {
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Contract verification details from BscScan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractVerification {
+    pub address: Address,
+    /// Whether source code is published and verified
+    pub verified: bool,
+    pub contract_name: Option<String>,
+    pub compiler_version: Option<String>,
+    pub is_proxy: bool,
+    /// Deployer address
+    pub creator: Option<Address>,
+    pub creation_tx: Option<H256>,
+    /// Deployment time (RFC3339)
+    pub created_at: Option<String>,
+}
+
+/// State of a token on the Four.meme bonding curve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BondingCurveInfo {
+    /// TokenManager contract the token trades on
+    pub address: Address,
+    pub token: Address,
+    /// Token the curve is priced in (None = BNB)
+    pub quote_token: Option<Address>,
+    pub quote_symbol: String,
+    /// Share of the fundraising target raised so far (0-100), migration to PancakeSwap happens at 100
+    pub progress_pct: f64,
+    /// Last curve price, in quote token per token
+    pub price: f64,
+    pub price_usd: Option<f64>,
+    /// Quote raised so far (BNB for BNB-quoted curves)
+    pub funds_raised: f64,
+    /// Quote that completes the curve
+    pub funds_target: f64,
+    /// Tokens still for sale on the curve
+    pub tokens_remaining: f64,
+    /// Unix timestamp trading opened
+    pub launch_time: u64,
+    /// Whether the curve completed and liquidity was added to PancakeSwap
+    pub liquidity_added: bool,
+    /// Wallet that launched the token (None when the launch is older than the log lookback)
+    pub creator: Option<Address>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceStats {
+    pub current_price: f64,
+    pub last_price: Option<f64>,
+    pub price_change: Option<f64>,
+    pub price_change_percent: Option<f64>,
+    pub high: f64,
+    pub low: f64,
+    pub first_price: f64,
+    pub swap_count: usize,
+    /// VWAP/TWAP per configured window
+    pub averages: Vec<AveragePrice>,
+    /// Alert rules triggered by this update
+    pub alerts: Vec<PriceAlert>,
+    /// Value of each configured indicator (None until enough trades were seen)
+    pub indicators: Vec<IndicatorValue>,
+    /// Price crossings of SMA/EMA indicators caused by this update
+    pub crosses: Vec<IndicatorCross>,
+    /// Named sessions running on the tracker
+    pub sessions: Vec<PriceSession>,
+}
+
+impl PriceStats {
+    /// Averages over a specific window, if it is tracked
+    pub fn average(&self, window: std::time::Duration) -> Option<&AveragePrice> {
+        self.averages.iter().find(|a| a.window == window)
+    }
+
+    /// Stats of a named session, if it is running
+    pub fn session(&self, name: &str) -> Option<&PriceSession> {
+        self.sessions.iter().find(|s| s.name == name)
+    }
+
+    /// Current value of an indicator, if it is configured and warmed up
+    pub fn indicator(&self, indicator: Indicator) -> Option<f64> {
+        self.indicators.iter().find(|i| i.indicator == indicator).and_then(|i| i.value)
+    }
+}
+
+/// Trade activity of a token over one rolling window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeWindow {
+    pub window: std::time::Duration,
+    pub buys: u32,
+    pub sells: u32,
+    /// Volume in whole tokens
+    pub buy_volume_tokens: f64,
+    pub sell_volume_tokens: f64,
+    /// Volume in USD, from swaps with a USD valuation
+    pub buy_volume_usd: Option<f64>,
+    pub sell_volume_usd: Option<f64>,
+}
+
+impl VolumeWindow {
+    pub fn trades(&self) -> u32 {
+        self.buys + self.sells
+    }
+
+    pub fn volume_usd(&self) -> Option<f64> {
+        match (self.buy_volume_usd, self.sell_volume_usd) {
+            (None, None) => None,
+            (buy, sell) => Some(buy.unwrap_or_default() + sell.unwrap_or_default()),
+        }
+    }
+
+    /// Short label such as "5m", "1h" or "24h"
+    pub fn label(&self) -> String {
+        let secs = self.window.as_secs();
+        if secs >= 3600 && secs.is_multiple_of(3600) {
+            format!("{}h", secs / 3600)
+        } else if secs >= 60 && secs.is_multiple_of(60) {
+            format!("{}m", secs / 60)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+}
+
+/// Rolling trade activity of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeStats {
+    pub token_address: Address,
+    pub windows: Vec<VolumeWindow>,
+}
+
+/// Trader participation of a token over one rolling window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraderWindow {
+    pub window: std::time::Duration,
+    pub unique_traders: u32,
+    pub unique_buyers: u32,
+    pub unique_sellers: u32,
+    /// Buys per sell (None when there were no sells)
+    pub buy_sell_ratio: Option<f64>,
+    /// Share of token volume traded by the single most active wallet (percent)
+    pub top_trader_volume_pct: Option<f64>,
+}
+
+/// Rolling trader participation of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraderStats {
+    pub token_address: Address,
+    pub windows: Vec<TraderWindow>,
+}
+
+/// Condition evaluated by the price tracker on every price update
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PriceAlertRule {
+    /// Price moves from below to at/above this level
+    CrossesAbove(f64),
+    /// Price moves from above to at/below this level
+    CrossesBelow(f64),
+    /// Price moves at least `pct` percent (either direction) within `window`
+    MoveWithin { pct: f64, window: std::time::Duration },
+    /// Price exceeds the session high
+    NewHigh,
+    /// Price drops under the session low
+    NewLow,
+}
+
+/// A triggered price alert rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceAlert {
+    /// Token key the price was tracked under
+    pub token: String,
+    /// Currency the price is in
+    pub currency: String,
+    pub rule: PriceAlertRule,
+    pub price: f64,
+    /// Price the move is measured from (previous price, session extreme, or window extreme)
+    pub reference_price: f64,
+    pub change_pct: f64,
+    /// Unix time of the triggering trade (seconds)
+    pub time: f64,
+}
+
+/// Volume- and time-weighted average price over a trailing window
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AveragePrice {
+    pub window: std::time::Duration,
+    /// None when no trade with volume fell in the window
+    pub vwap: Option<f64>,
+    /// None when no price is known for the window
+    pub twap: Option<f64>,
+}
+
+/// High/low/change of a token since a named session started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSession {
+    pub name: String,
+    /// Unix time the session (re)started (seconds)
+    pub started_at: f64,
+    /// Restarts automatically at UTC midnight
+    pub daily: bool,
+    pub first_price: f64,
+    pub high: f64,
+    pub low: f64,
+    pub last_price: f64,
+    pub change_pct: f64,
+    pub swap_count: u64,
+}
+
+/// Technical indicator computed over trade prices (periods are counted in trades)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Indicator {
+    /// Simple moving average
+    Sma(usize),
+    /// Exponential moving average
+    Ema(usize),
+    /// Relative strength index (Wilder smoothing), 0-100
+    Rsi(usize),
+}
+
+impl Indicator {
+    pub fn period(&self) -> usize {
+        match *self {
+            Indicator::Sma(n) | Indicator::Ema(n) | Indicator::Rsi(n) => n,
+        }
+    }
+
+    /// Short name, e.g. "EMA-20"
+    pub fn label(&self) -> String {
+        match self {
+            Indicator::Sma(n) => format!("SMA-{}", n),
+            Indicator::Ema(n) => format!("EMA-{}", n),
+            Indicator::Rsi(n) => format!("RSI-{}", n),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IndicatorValue {
+    pub indicator: Indicator,
+    pub value: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrossDirection {
+    /// Price moved from at/below the indicator to above it
+    Above,
+    /// Price moved from at/above the indicator to below it
+    Below,
+}
+
+impl CrossDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossDirection::Above => "ABOVE",
+            CrossDirection::Below => "BELOW",
+        }
+    }
+}
+
+/// Price crossing a moving-average indicator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorCross {
+    /// Token key the price was tracked under
+    pub token: String,
+    /// Currency the price is in
+    pub currency: String,
+    pub indicator: Indicator,
+    pub direction: CrossDirection,
+    pub price: f64,
+    /// Indicator value after this trade
+    pub value: f64,
+    /// Unix time of the triggering trade (seconds)
+    pub time: f64,
+}
+
+/// Any event delivered to an `EventSink`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    Swap(Box<SwapEvent>),
+    Migration(Box<MigrationEvent>),
+    /// Closed candle (only when `on_candle` is set)
+    Candle(Candle),
+}
+
+impl StreamEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamEvent::Swap(_) => "swap",
+            StreamEvent::Migration(_) => "migration",
+            StreamEvent::Candle(_) => "candle",
+        }
+    }
+
+    /// Token the event belongs to
+    pub fn token_address(&self) -> Address {
+        match self {
+            StreamEvent::Swap(swap) => swap.token.address,
+            StreamEvent::Migration(migration) => migration.token_address,
+            StreamEvent::Candle(candle) => candle.token_address,
+        }
+    }
+}
+
+/// Event emitted when a token migrates from bonding curve to DEX
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    /// `EVENT_SCHEMA_VERSION` the event was produced with (0 if serialized before versioning)
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Token address that migrated
+    pub token_address: Address,
+    /// Source platform (usually FourMemeBondingCurve)
+    pub from_platform: Platform,
+    /// Destination platform (usually PancakeSwap)
+    pub to_platform: Platform,
+    /// Transaction hash of the migration (PairCreated transaction)
+    pub transaction_hash: H256,
+    /// Block number when migration occurred
+    pub block_number: u64,
+    /// Timestamp of migration
+    pub timestamp: Option<String>,
+    /// New DEX pair addresses created
+    pub pair_addresses: Vec<Address>,
+    /// Number of pairs found on DEX
+    pub pair_count: usize,
+    /// LP burn/lock status of the graduation (V2) pair
+    pub lp_lock: Option<LpLockStatus>,
+    /// Sellability check on the graduation pair
+    pub honeypot: Option<HoneypotReport>,
+    /// Bytecode risk scan of the token contract
+    pub risk: Option<RiskReport>,
+}
+
+impl MigrationEvent {
+    pub fn as_message(&self) -> String {
+        format!(
+            "🎉 MIGRATION DETECTED! Token migrated from {} to {} at block {}. Found {} DEX pair(s).",
+            self.from_platform.as_str(),
+            self.to_platform.as_str(),
+            self.block_number,
+            self.pair_count
+        )
+    }
+}
+
+/// State of one log subscription of a streamer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionState {
+    /// Subscription requested, not yet confirmed by the node
+    Connecting,
+    Live,
+    /// Cancelled through the handle or after a migration
+    Stopped,
+    /// The node closed the stream
+    Ended,
+    /// The subscription could not be created
+    Failed,
+}
+
+impl SubscriptionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriptionState::Connecting => "connecting",
+            SubscriptionState::Live => "live",
+            SubscriptionState::Stopped => "stopped",
+            SubscriptionState::Ended => "ended",
+            SubscriptionState::Failed => "failed",
+        }
+    }
+}
+
+/// Liveness of one log subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionHealth {
+    /// What the subscription watches, e.g. "V2 WBNB pair 0x.."
+    pub name: String,
+    pub state: SubscriptionState,
+    /// Logs received since the subscription started
+    pub events: u64,
+    /// Seconds since the last log (None before the first one)
+    pub last_event_age_secs: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Result of a provider round-trip (`eth_blockNumber`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    pub connected: bool,
+    pub block_number: Option<u64>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Distribution of one latency over recent swaps, in milliseconds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub mean_ms: f64,
+    pub p50_ms: i64,
+    pub p95_ms: i64,
+    pub max_ms: i64,
+}
+
+/// `EventLatency` aggregated over the most recent live swaps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyStats {
+    /// Swaps the summaries cover
+    pub samples: usize,
+    pub block_to_receive: Option<LatencySummary>,
+    pub receive_to_dispatch: Option<LatencySummary>,
+    pub block_to_dispatch: Option<LatencySummary>,
+}
+
+/// Health of a running streamer, from `StreamerHandle::health()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub provider: ProviderHealth,
+    pub subscriptions: Vec<SubscriptionHealth>,
+    /// Seconds since the last log on any subscription
+    pub last_event_age_secs: Option<f64>,
+    pub uptime_secs: u64,
+    pub stopped: bool,
+    /// Delivery latency of recent live swaps (None before the first one)
+    pub latency: Option<LatencyStats>,
+}
+
+impl HealthReport {
+    /// Provider reachable and no subscription ended or failed (liveness probe)
+    pub fn is_healthy(&self) -> bool {
+        self.provider.connected
+            && !self.stopped
+            && self
+                .subscriptions
+                .iter()
+                .all(|s| !matches!(s.state, SubscriptionState::Ended | SubscriptionState::Failed))
+    }
+
+    /// Healthy and at least one subscription is live (readiness probe)
+    pub fn is_ready(&self) -> bool {
+        self.is_healthy() && self.subscriptions.iter().any(|s| s.state == SubscriptionState::Live)
+    }
+
+    /// No log for longer than `max_age` on any subscription (false before the first log)
+    ///
+    /// Quiet tokens can go minutes between trades - pick `max_age` per token.
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.last_event_age_secs.is_some_and(|age| age > max_age.as_secs_f64())
     }
 }
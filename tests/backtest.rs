@@ -0,0 +1,99 @@
+//! Backtest runner over fixture swaps and recorded JSON Lines
+
+mod common;
+
+use bsc_streamer::{Backtest, StreamEvent};
+use common::{config, swap, SWAP};
+use std::cell::RefCell;
+
+#[test]
+fn drawdown_is_measured_from_the_running_peak() {
+    let swaps = vec![
+        swap(0, 25_000_000.0, 100.0),
+        swap(3, 25_000_000.0, 100.0),
+        // Price doubles, falls back to the start, then recovers half the way
+        swap(10, 12_500_000.0, 200.0),
+        swap(20, 25_000_000.0, 100.0),
+        swap(30, 20_000_000.0, 120.0),
+    ];
+    let report = Backtest::new(config()).run(swaps, |swap, paper| {
+        if swap.log_index == 0 {
+            paper.buy(swap.token.address, 1.0).unwrap();
+        }
+    });
+
+    let tokens = 25_000_000.0 * 0.9975 / (100.0 + 0.9975);
+    let equity = |price: f64| 9.0 + tokens * price;
+    let expected = [10.0, equity(4e-6), equity(16e-6), equity(4e-6), equity(6e-6)];
+    let curve: Vec<f64> = report.equity_curve.iter().map(|point| point.equity_bnb).collect();
+    assert_eq!(curve.len(), expected.len());
+    for (actual, expected) in curve.iter().zip(expected) {
+        assert!((actual - expected).abs() < 1e-9, "equity {} != {}", actual, expected);
+    }
+
+    // Peak at 2x, trough back at the entry price; the dip right after the fill is smaller
+    let peak = equity(16e-6);
+    let fall = peak - equity(4e-6);
+    assert!((report.max_drawdown_bnb - fall).abs() < 1e-9, "drawdown {}", report.max_drawdown_bnb);
+    assert!((report.max_drawdown_pct - fall / peak * 100.0).abs() < 1e-9, "drawdown {}%", report.max_drawdown_pct);
+    assert!((report.pnl_bnb - (equity(6e-6) - 10.0)).abs() < 1e-9);
+    assert!((report.return_pct - report.pnl_bnb * 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn strategy_sees_swaps_in_replay_order_after_due_fills() {
+    let seen = RefCell::new(Vec::new());
+    let swaps = vec![
+        swap(0, 25_000_000.0, 100.0),
+        swap(2, 25_000_000.0, 100.0),
+        swap(3, 25_000_000.0, 100.0),
+        swap(7, 25_000_000.0, 100.0),
+    ];
+    let report = Backtest::new(config()).run(swaps, |swap, paper| {
+        let held = paper.position(swap.token.address).is_some();
+        seen.borrow_mut().push((swap.log_index, held));
+        if swap.log_index == 0 {
+            paper.buy(swap.token.address, 1.0).unwrap();
+        }
+    });
+
+    // The buy is due at the swap 3 s later and fills before the strategy sees it
+    assert_eq!(seen.into_inner(), vec![(0, false), (2, false), (3, true), (7, true)]);
+    assert_eq!(report.swaps, 4);
+    assert_eq!(report.fills.len(), 1);
+    assert_eq!(report.fills[0].filled_at - report.fills[0].submitted_at, 3.0);
+    let timestamps: Vec<i64> = report.equity_curve.iter().map(|point| point.timestamp).collect();
+    assert!(timestamps.windows(2).all(|pair| pair[0] < pair[1]), "curve out of order: {:?}", timestamps);
+}
+
+#[test]
+fn orders_left_pending_fill_at_the_last_prices() {
+    let swaps = vec![swap(0, 25_000_000.0, 100.0), swap(1, 25_000_000.0, 100.0)];
+    let report = Backtest::new(config()).run(swaps, |swap, paper| {
+        if swap.log_index == 1 {
+            paper.buy(swap.token.address, 1.0).unwrap();
+        }
+    });
+    assert_eq!(report.fills.len(), 1);
+    assert_eq!(report.portfolio.pending_orders, 0);
+    assert_eq!(report.portfolio.positions.len(), 1);
+}
+
+#[test]
+fn loads_recorded_events_in_chain_order() {
+    let path = std::env::temp_dir().join(format!("bsc_streamer_backtest_{}.jsonl", std::process::id()));
+    let mut recording = String::new();
+    for second in [5, 2] {
+        let event = StreamEvent::Swap(Box::new(swap(second, 25_000_000.0, 100.0)));
+        recording.push_str(&serde_json::to_string(&event).unwrap());
+        recording.push('\n');
+    }
+    // Bare (pretty-printed) fixture objects are accepted too
+    recording.push_str(SWAP);
+    std::fs::write(&path, recording).unwrap();
+
+    let swaps = Backtest::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(swaps.len(), 3);
+    assert_eq!(swaps.iter().map(|s| s.log_index).collect::<Vec<_>>(), vec![2, 5, 87]);
+}
@@ -0,0 +1,42 @@
+//! Fixture builders shared by the integration tests
+
+// Each test crate uses only some of the builders
+#![allow(dead_code)]
+
+use bsc_streamer::{PaperConfig, SwapEvent, TradeType};
+use std::time::Duration;
+
+pub const SWAP: &str = include_str!("../fixtures/swap_event_v1.json");
+
+/// Fixture swap at `second` past 12:34:00 (log index `second`) with the pool at the given reserves
+pub fn swap(second: u32, token_reserve: f64, bnb_reserve: f64) -> SwapEvent {
+    let mut swap: SwapEvent = serde_json::from_str(SWAP).unwrap();
+    swap.timestamp = Some(format!("2025-01-15T12:34:{:02}+00:00", second));
+    swap.log_index = second as u64;
+    let depth = swap.depth.as_mut().unwrap();
+    depth.token_reserve = token_reserve;
+    depth.base_reserve = bnb_reserve;
+    let price = bnb_reserve / token_reserve;
+    swap.price.value = price;
+    swap.normalized_price.as_mut().unwrap().value = price;
+    swap
+}
+
+/// Fixture swap at the fixture price (0.000004 BNB) trading `tokens` on `trade_type`
+pub fn trade(trade_type: TradeType, tokens: f64) -> SwapEvent {
+    let mut swap = swap(0, 25_000_000.0, 100.0);
+    swap.trade_type = trade_type;
+    swap.token.amount = tokens.to_string();
+    swap
+}
+
+/// 10 BNB, 3 s latency, no slippage, tolerance or gas
+pub fn config() -> PaperConfig {
+    PaperConfig {
+        starting_bnb: 10.0,
+        latency: Duration::from_secs(3),
+        slippage_pct: 0.0,
+        max_slippage_pct: None,
+        gas_fee_bnb: 0.0,
+    }
+}
@@ -0,0 +1,84 @@
+//! Sizing and guardrails of the copy-trading helper, driven by fixture swaps
+
+mod common;
+
+use bsc_streamer::{CopyConfig, CopyTrader, TradeType};
+use common::trade;
+use ethers::providers::{MockProvider, Provider};
+use ethers::types::Address;
+use std::sync::Arc;
+
+fn copier(config: CopyConfig) -> CopyTrader<Provider<MockProvider>> {
+    let leader = trade(TradeType::Buy, 0.0).trader.unwrap();
+    CopyTrader::new(
+        Arc::new(Provider::new(MockProvider::new())),
+        CopyConfig {
+            wallets: vec![leader],
+            max_risk_score: None,
+            ..config
+        },
+    )
+}
+
+#[tokio::test]
+async fn buys_are_scaled_and_capped_per_token() {
+    let copier = copier(CopyConfig {
+        scale: 0.5,
+        max_position_bnb: 1.5,
+        ..CopyConfig::default()
+    });
+    let buy = trade(TradeType::Buy, 500_000.0); // 2 BNB
+    let token = buy.token.address;
+
+    let first = copier.on_swap(&buy).await.unwrap().unwrap();
+    assert!((first.leader_bnb - 2.0).abs() < 1e-9);
+    assert!((first.size_bnb - 1.0).abs() < 1e-9);
+
+    // Only 0.5 BNB left under the position limit, then nothing
+    let second = copier.on_swap(&buy).await.unwrap().unwrap();
+    assert!((second.size_bnb - 0.5).abs() < 1e-9);
+    assert!(copier.on_swap(&buy).await.unwrap().is_none());
+    assert!((copier.exposure_bnb(token) - 1.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn sells_exit_the_leaders_share() {
+    let copier = copier(CopyConfig {
+        scale: 0.1,
+        ..CopyConfig::default()
+    });
+    let buy = trade(TradeType::Buy, 1_000_000.0); // 4 BNB, copied with 0.4
+    let token = buy.token.address;
+    copier.on_swap(&buy).await.unwrap().unwrap();
+
+    // Leader sells a quarter of what it bought
+    let sell = copier.on_swap(&trade(TradeType::Sell, 250_000.0)).await.unwrap().unwrap();
+    assert!((sell.token_amount.unwrap() - 25_000.0).abs() < 1e-6);
+    assert!((sell.size_bnb - 0.1).abs() < 1e-9);
+
+    // More than it was seen buying: exit everything left
+    let sell = copier.on_swap(&trade(TradeType::Sell, 2_000_000.0)).await.unwrap().unwrap();
+    assert!((sell.token_amount.unwrap() - 75_000.0).abs() < 1e-6);
+    assert!(copier.position_tokens(token).abs() < 1e-6);
+    assert!(copier.on_swap(&trade(TradeType::Sell, 1.0)).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn other_wallets_and_small_trades_are_ignored() {
+    let copier = copier(CopyConfig {
+        min_trade_bnb: 0.01,
+        mirror_sells: false,
+        ..CopyConfig::default()
+    });
+
+    let mut stranger = trade(TradeType::Buy, 500_000.0);
+    stranger.trader = Some(Address::repeat_byte(0x11));
+    stranger.recipient = Address::repeat_byte(0x11);
+    assert!(copier.on_swap(&stranger).await.unwrap().is_none());
+
+    // 0.04 BNB * 10% = 0.004 BNB, under the minimum
+    assert!(copier.on_swap(&trade(TradeType::Buy, 10_000.0)).await.unwrap().is_none());
+
+    copier.on_swap(&trade(TradeType::Buy, 500_000.0)).await.unwrap().unwrap();
+    assert!(copier.on_swap(&trade(TradeType::Sell, 500_000.0)).await.unwrap().is_none());
+}
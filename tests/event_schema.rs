@@ -0,0 +1,127 @@
+//! Compatibility tests for the serialized event schema
+//!
+//! The fixtures are events as published with schema version 1. Fields may be
+//! added to the structs, but every fixture field must keep its name and
+//! encoding until `EVENT_SCHEMA_VERSION` is bumped.
+
+use bsc_streamer::{MigrationEvent, Platform, StreamEvent, SwapEvent, TradeType, EVENT_SCHEMA_VERSION};
+use serde_json::Value;
+
+const SWAP_V1: &str = include_str!("fixtures/swap_event_v1.json");
+const MIGRATION_V1: &str = include_str!("fixtures/migration_event_v1.json");
+
+/// Every field of `expected` is present in `actual` with the same value
+fn assert_superset(expected: &Value, actual: &Value, path: &str) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, value) in expected {
+                let field = format!("{}.{}", path, key);
+                let Some(actual) = actual.get(key) else {
+                    panic!("{} is missing from the serialized event", field);
+                };
+                assert_superset(value, actual, &field);
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            assert_eq!(expected.len(), actual.len(), "{} changed length", path);
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                assert_superset(expected, actual, &format!("{}[{}]", path, i));
+            }
+        }
+        _ => assert_eq!(expected, actual, "{} changed", path),
+    }
+}
+
+fn is_hex(value: &Value, len: usize) -> bool {
+    value
+        .as_str()
+        .and_then(|s| s.strip_prefix("0x"))
+        .is_some_and(|hex| hex.len() == len && hex.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()))
+}
+
+#[test]
+fn schema_version_is_one() {
+    assert_eq!(EVENT_SCHEMA_VERSION, 1, "bump the fixtures together with the schema version");
+}
+
+#[test]
+fn swap_v1_round_trips() {
+    let fixture: Value = serde_json::from_str(SWAP_V1).unwrap();
+    let swap: SwapEvent = serde_json::from_str(SWAP_V1).unwrap();
+    assert_eq!(swap.schema_version, 1);
+    assert_eq!(swap.platform, Platform::PancakeSwap);
+    assert_eq!(swap.trade_type, TradeType::Buy);
+    assert_eq!(swap.token.decimals, 18);
+    assert_eq!(swap.block_number, 45_123_456);
+
+    assert_superset(&fixture, &serde_json::to_value(&swap).unwrap(), "swap");
+}
+
+#[test]
+fn migration_v1_round_trips() {
+    let fixture: Value = serde_json::from_str(MIGRATION_V1).unwrap();
+    let migration: MigrationEvent = serde_json::from_str(MIGRATION_V1).unwrap();
+    assert_eq!(migration.schema_version, 1);
+    assert_eq!(migration.from_platform, Platform::FourMemeBondingCurve);
+    assert_eq!(migration.pair_addresses.len(), migration.pair_count);
+
+    assert_superset(&fixture, &serde_json::to_value(&migration).unwrap(), "migration");
+}
+
+#[test]
+fn addresses_hashes_and_amounts_are_hex_strings() {
+    let swap: SwapEvent = serde_json::from_str(SWAP_V1).unwrap();
+    let value = serde_json::to_value(&swap).unwrap();
+    for address in ["sender", "recipient", "trader", "pair_address"] {
+        assert!(is_hex(&value[address], 40), "{} is not a lowercase hex address", address);
+    }
+    assert!(is_hex(&value["token"]["address"], 40));
+    assert!(is_hex(&value["transaction_hash"], 64));
+    assert!(value["token"]["amount_raw"].as_str().is_some_and(|s| s.starts_with("0x")));
+    assert!(value["gas"]["gas_used"].as_str().is_some_and(|s| s.starts_with("0x")));
+}
+
+#[test]
+fn checksummed_addresses_are_accepted() {
+    let checksummed = SWAP_V1.replace(
+        "0x0ed7e52944161450477ee417de9cd3a859b14fd0",
+        "0x0eD7e52944161450477ee417DE9Cd3a859b14fD0",
+    );
+    let swap: SwapEvent = serde_json::from_str(&checksummed).unwrap();
+    let value = serde_json::to_value(&swap).unwrap();
+    assert_eq!(value["pair_address"], "0x0ed7e52944161450477ee417de9cd3a859b14fd0");
+}
+
+#[test]
+fn unknown_fields_are_ignored() {
+    let mut fixture: Value = serde_json::from_str(SWAP_V1).unwrap();
+    fixture["added_in_a_later_release"] = Value::from(42);
+    let swap: SwapEvent = serde_json::from_value(fixture).unwrap();
+    assert_eq!(swap.schema_version, 1);
+}
+
+#[test]
+fn unversioned_events_read_as_version_zero() {
+    let mut fixture: Value = serde_json::from_str(MIGRATION_V1).unwrap();
+    fixture.as_object_mut().unwrap().remove("schema_version");
+    let migration: MigrationEvent = serde_json::from_value(fixture).unwrap();
+    assert_eq!(migration.schema_version, 0);
+}
+
+#[test]
+fn stream_events_are_tagged_by_type() {
+    let mut fixture: Value = serde_json::from_str(SWAP_V1).unwrap();
+    fixture["type"] = Value::from("swap");
+    let event: StreamEvent = serde_json::from_value(fixture.clone()).unwrap();
+    assert!(matches!(event, StreamEvent::Swap(_)));
+
+    let value = serde_json::to_value(&event).unwrap();
+    assert_eq!(value["type"], "swap");
+    assert_eq!(value["schema_version"], 1);
+    assert_superset(&fixture, &value, "stream_event");
+
+    let mut fixture: Value = serde_json::from_str(MIGRATION_V1).unwrap();
+    fixture["type"] = Value::from("migration");
+    let event: StreamEvent = serde_json::from_value(fixture).unwrap();
+    assert!(matches!(event, StreamEvent::Migration(_)));
+}
@@ -0,0 +1,85 @@
+//! Fill model of the paper-trading engine, driven by fixture swaps
+
+mod common;
+
+use bsc_streamer::{PaperConfig, PaperTrader};
+use common::swap;
+
+fn config() -> PaperConfig {
+    PaperConfig {
+        max_slippage_pct: Some(5.0),
+        ..common::config()
+    }
+}
+
+#[test]
+fn buy_fills_after_latency_along_the_pool_curve() {
+    let paper = PaperTrader::new(config());
+    let first = swap(0, 25_000_000.0, 100.0);
+    let token = first.token.address;
+    paper.on_swap(&first);
+    paper.buy(token, 1.0).unwrap();
+    assert_eq!(paper.cash(), 9.0);
+
+    // Still within the latency
+    assert!(paper.on_swap(&swap(2, 25_000_000.0, 100.0)).is_empty());
+
+    let fills = paper.on_swap(&swap(3, 25_000_000.0, 100.0));
+    assert_eq!(fills.len(), 1);
+    let fill = &fills[0];
+    assert!(fill.rejected.is_none());
+    let expected = 25_000_000.0 * 0.9975 / (100.0 + 0.9975);
+    assert!((fill.amount_out - expected).abs() < 1e-6);
+    assert!((fill.fill_price - fill.quoted_price).abs() < 1e-18);
+    assert!((paper.position(token).unwrap().amount - expected).abs() < 1e-6);
+}
+
+#[test]
+fn fills_past_the_tolerance_are_rejected_and_refunded() {
+    let paper = PaperTrader::new(config());
+    let first = swap(0, 25_000_000.0, 100.0);
+    let token = first.token.address;
+    paper.on_swap(&first);
+    paper.buy(token, 1.0).unwrap();
+
+    // Price up ~21% during the latency
+    paper.on_swap(&swap(1, 22_727_272.0, 110.0));
+    let fills = paper.on_swap(&swap(4, 22_727_272.0, 110.0));
+    assert!(fills[0].rejected.is_some());
+    assert_eq!(fills[0].amount_out, 0.0);
+    assert_eq!(paper.cash(), 10.0);
+    assert!(paper.position(token).is_none());
+}
+
+#[test]
+fn round_trip_realizes_profit() {
+    let paper = PaperTrader::new(config());
+    let first = swap(0, 25_000_000.0, 100.0);
+    let token = first.token.address;
+    paper.on_swap(&first);
+    paper.buy(token, 1.0).unwrap();
+    paper.on_swap(&swap(3, 25_000_000.0, 100.0));
+
+    // Price doubles, then the whole position is sold
+    paper.on_swap(&swap(10, 12_500_000.0, 200.0));
+    paper.sell_all(token).unwrap();
+    let fills = paper.on_swap(&swap(13, 12_500_000.0, 200.0));
+    assert!(fills[0].rejected.is_none());
+
+    let portfolio = paper.portfolio();
+    assert_eq!(portfolio.pending_orders, 0);
+    assert!(portfolio.realized_pnl_bnb > 0.9, "realized {}", portfolio.realized_pnl_bnb);
+    assert!((portfolio.cash_bnb - (9.0 + fills[0].amount_out)).abs() < 1e-9);
+    assert!(paper.position(token).is_none());
+}
+
+#[test]
+fn orders_need_funds_and_a_price() {
+    let paper = PaperTrader::new(config());
+    let first = swap(0, 25_000_000.0, 100.0);
+    let token = first.token.address;
+    assert!(paper.buy(token, 1.0).is_err());
+    paper.on_swap(&first);
+    assert!(paper.buy(token, 11.0).is_err());
+    assert!(paper.sell(token, 1.0).is_err());
+}